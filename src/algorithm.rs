@@ -1,63 +1,334 @@
+use alloc::{ vec, vec::Vec };
 use qmath::prelude::*;
 use qmath::vec2::QVec2;
 use qmath::dir::QDir;
 use crate::prelude::*;
+use crate::shape::line::get_closest_point_on_chain;
+use crate::spatial::QKdTree;
+
+/// Upper bound of simplex refinements in `gjk`.
+pub const GJK_MAX_ITERATIONS: usize = 64;
+
+/// Upper bound of polytope expansions in `epa`.
+pub const EPA_MAX_ITERATIONS: usize = 64;
+
+/// Golden section steps refining the farthest sample in `directed_hausdorff_distance`.
+const HAUSDORFF_REFINEMENTS: usize = 32;
+
+/// Distance of the super triangle in `delaunay_triangulation` from the bbox center, in
+/// multiples of the longer bbox side.
+const DELAUNAY_SUPER_TRIANGLE_REACH: i64 = 100;
 
 /// Get the simplex point of minkowski difference at giving direction.
-pub fn _get_simplex_point_in_direction(shape_a: &QPolygon, shape_b: &QPolygon, dir: QDir) -> QPoint {
-    let point_a = shape_a.get_farest_point_in_direction(dir);
-    let point_b = shape_b.get_farest_point_in_direction(-dir);
+pub fn _get_simplex_point_in_direction(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap, dir: QDir) -> QPoint {
+    let point_a = shape_a.support(dir);
+    let point_b = shape_b.support(-dir);
     QPoint::new(point_a.pos().saturating_sub(point_b.pos()))
 }
 
+/// Support point of the minkowski difference along an unnormalized direction.
+fn support_difference(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap, dir: QVec2) -> QVec2 {
+    _get_simplex_point_in_direction(shape_a, shape_b, QDir::new_from_vec(dir)).pos()
+}
+
+/// Perpendicular of `edge` on the side of `towards`.
+fn perpendicular_towards(edge: QVec2, towards: QVec2) -> QVec2 {
+    let perpendicular = QVec2::new(-edge.y, edge.x);
+    if perpendicular.dot(towards) >= Q64::ZERO { perpendicular } else { -perpendicular }
+}
+
+/// How a GJK run ended, see `gjk_with_diagnostics`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum QGjkResult {
+    /// The simplex encloses the origin or passes through it, the shapes overlap or touch.
+    Intersecting,
+    /// A separating axis was found, `distance` is the gap along it, a lower bound of the
+    /// distance between the shapes.
+    Separated { distance: Q64 },
+    /// Neither was decided within the iteration cap, `last_simplex` holds the points of the
+    /// minkowski difference it ended with. `gjk` does not count this as intersecting.
+    MaxIterations { last_simplex: Vec<QVec2> },
+}
+
+/// Run GJK and return the final simplex if the origin is inside the minkowski difference.
+///
+/// The simplex is a triangle around the origin, or fewer points when the origin lies on it.
+fn gjk_simplex(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap) -> Option<Vec<QVec2>> {
+    let (result, simplex, _) = run_gjk(shape_a, shape_b, GJK_MAX_ITERATIONS);
+    match result {
+        QGjkResult::Intersecting => Some(simplex),
+        _ => None,
+    }
+}
+
+/// Grow a GJK simplex that contains the origin into a triangle of the minkowski difference.
+///
+/// A point or a segment through the origin is pushed out with support queries along its
+/// normal. Returns `None` when the minkowski difference itself has no area.
+fn get_epa_polytope(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap, mut polytope: Vec<QVec2>) -> Option<Vec<QVec2>> {
+    if polytope.len() == 3 && polytope[1].saturating_sub(polytope[0]).cross(polytope[2].saturating_sub(polytope[0])) == Q64::ZERO {
+        // A flat triangle, keep its two farthest points.
+        let (i, j) = [(0, 1), (0, 2), (1, 2)].into_iter()
+            .max_by_key(|&(i, j)| polytope[i].distance_squared(polytope[j]))
+            .unwrap_or((0, 1));
+        polytope = vec![polytope[i], polytope[j]];
+    }
+    if polytope.len() == 1 {
+        let directions = [QVec2::X, -QVec2::X, QVec2::Y, -QVec2::Y];
+        let point = directions.into_iter()
+            .map(|dir| support_difference(shape_a, shape_b, dir))
+            .find(|point| *point != polytope[0])?;
+        polytope.push(point);
+    }
+    if polytope.len() == 2 {
+        let edge = polytope[1].saturating_sub(polytope[0]);
+        let normal = QVec2::new(-edge.y, edge.x);
+        let point = [normal, -normal].into_iter()
+            .map(|dir| support_difference(shape_a, shape_b, dir))
+            .find(|point| edge.cross(point.saturating_sub(polytope[0])) != Q64::ZERO)?;
+        polytope.push(point);
+    }
+    if polytope[1].saturating_sub(polytope[0]).cross(polytope[2].saturating_sub(polytope[0])) < Q64::ZERO {
+        polytope.swap(1, 2);
+    }
+    Some(polytope)
+}
+
+/// GJK with its outcome, final simplex and the number of support queries after the first.
+fn run_gjk(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap, max_iterations: usize) -> (QGjkResult, Vec<QVec2>, usize) {
+    let first = support_difference(shape_a, shape_b, QVec2::X);
+    let mut simplex = vec![first];
+    let mut dir = -first;
+
+    for iterations in 0..max_iterations {
+        if dir == QVec2::ZERO {
+            // The origin lies on the current simplex.
+            return (QGjkResult::Intersecting, simplex, iterations);
+        }
+
+        let point = support_difference(shape_a, shape_b, dir);
+        if point.dot(dir) < Q64::ZERO {
+            let distance = point.dot(dir).abs().saturating_div(dir.length());
+            return (QGjkResult::Separated { distance }, simplex, iterations + 1);
+        }
+        if simplex.contains(&point) {
+            // No progress, the support plane passes through the origin.
+            return (QGjkResult::Intersecting, simplex, iterations + 1);
+        }
+        simplex.push(point);
+
+        match simplex.len() {
+            2 => {
+                let (b, a) = (simplex[0], simplex[1]);
+                dir = perpendicular_towards(b.saturating_sub(a), -a);
+                if dir.dot(-a) == Q64::ZERO {
+                    dir = QVec2::ZERO;
+                }
+            }
+            _ => {
+                let (c, b, a) = (simplex[0], simplex[1], simplex[2]);
+                let ab = b.saturating_sub(a);
+                let ac = c.saturating_sub(a);
+                let ao = -a;
+                let ab_perpendicular = -perpendicular_towards(ab, ac);
+                let ac_perpendicular = -perpendicular_towards(ac, ab);
+                if ab_perpendicular.dot(ao) > Q64::ZERO {
+                    simplex = vec![b, a];
+                    dir = ab_perpendicular;
+                } else if ac_perpendicular.dot(ao) > Q64::ZERO {
+                    simplex = vec![c, a];
+                    dir = ac_perpendicular;
+                } else {
+                    return (QGjkResult::Intersecting, simplex, iterations + 1);
+                }
+            }
+        }
+    }
+
+    // Out of iterations, only reachable through rounding on curved shapes.
+    (QGjkResult::MaxIterations { last_simplex: simplex.clone() }, simplex, max_iterations)
+}
+
 /// GJK algorithm.
 /// 
-/// This function checks if two convex polygons intersect using the Gilbert-Johnson-Keerthi algorithm.
-/// It works by trying to build a simplex (in 2D, a triangle) around the origin using points from 
-/// the Minkowski difference of the two shapes.
+/// This function checks if two convex shapes intersect using the Gilbert-Johnson-Keerthi algorithm.
+/// It works by trying to build a simplex (in 2D, a triangle) around the origin using support points
+/// of the Minkowski difference of the two shapes.
 /// 
 /// # Arguments
 /// 
-/// * `shape_a` - First polygon
-/// * `shape_b` - Second polygon
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
 /// 
 /// # Returns
 /// 
-/// True if the polygons intersect, false otherwise
-pub fn gjk(shape_a: &QPolygon, shape_b: &QPolygon) -> bool {
-    let minkowski_difference = get_minkowski_difference(shape_a, shape_b);
-    minkowski_difference.is_point_inside(&QPoint::ZERO)
+/// True if the shapes intersect, false otherwise
+pub fn gjk(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap) -> bool {
+    gjk_simplex(shape_a, shape_b).is_some()
+}
+
+/// GJK with the reason it stopped, for debugging convergence.
+///
+/// # Arguments
+///
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
+/// * `max_iterations` - Cap on simplex refinements, `gjk` uses `GJK_MAX_ITERATIONS`
+///
+/// # Returns
+///
+/// How the run ended and the number of support queries after the first one
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::{ gjk_with_diagnostics, QGjkResult, GJK_MAX_ITERATIONS };
+///
+/// let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(1.0, 1.0), qvec2!(0.0, 1.0)]);
+/// let (result, iterations) = gjk_with_diagnostics(&square, &square.translated(qvec2!(3.0, 0.0)), GJK_MAX_ITERATIONS);
+/// assert!(matches!(result, QGjkResult::Separated { distance } if distance > Q64::ZERO && distance <= q64!(2)));
+/// assert!(iterations >= 1);
+/// let (result, _) = gjk_with_diagnostics(&square, &square.translated(qvec2!(0.5, 0.5)), GJK_MAX_ITERATIONS);
+/// assert_eq!(result, QGjkResult::Intersecting);
+/// let (result, iterations) = gjk_with_diagnostics(&square, &square.translated(qvec2!(0.5, 0.5)), 0);
+/// assert!(matches!(result, QGjkResult::MaxIterations { last_simplex } if last_simplex.len() == 1));
+/// assert_eq!(iterations, 0);
+/// ```
+pub fn gjk_with_diagnostics(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap, max_iterations: usize) -> (QGjkResult, usize) {
+    let (result, _, iterations) = run_gjk(shape_a, shape_b, max_iterations);
+    (result, iterations)
 }
 
 /// EPA algorithm.
 /// 
-/// This function computes the penetration depth and direction between two convex polygons
+/// This function computes the penetration depth and direction between two convex shapes
 /// that are known to be intersecting (using GJK).
 /// 
 /// # Arguments
 /// 
-/// * `shape_a` - First polygon
-/// * `shape_b` - Second polygon
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
 /// 
 /// # Returns
 /// 
 /// Separation vector for shape_b (direction and magnitude of penetration)
-pub fn epa(shape_a: &QPolygon, shape_b: &QPolygon) -> Option<QVec2> {
-    let minkowski_difference = get_minkowski_difference(shape_a, shape_b);
-    if minkowski_difference.is_point_inside(&QPoint::ZERO) {
-        let nearest_lines_index = minkowski_difference.get_nearest_lines_index_to_point(&QPoint::ZERO);
-        assert!(nearest_lines_index.len() >= 2, "[algorithm::epa] Nearest lines index must have at least 2 elements, shape_a: {:?}, shape_b: {:?}, minksowski_difference: {:?}", shape_a, shape_b, minkowski_difference);
-        let line = QLine::new(minkowski_difference.points()[nearest_lines_index[0]], minkowski_difference.points()[nearest_lines_index[1]]);
-        return Some(line.get_perpendicular_vector_from_point(&QPoint::ZERO));
+pub fn epa(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap) -> Option<QVec2> {
+    let simplex = gjk_simplex(shape_a, shape_b)?;
+    let Some(mut polytope) = get_epa_polytope(shape_a, shape_b, simplex) else {
+        // The minkowski difference is flat, the shapes only touch.
+        return Some(QVec2::ZERO);
+    };
+
+    let mut rst = QVec2::ZERO;
+    for _ in 0..EPA_MAX_ITERATIONS {
+        // Find the edge nearest to the origin, the polytope is kept CCW so normals point outwards.
+        let (index, normal, distance) = (0..polytope.len())
+            .map(|i| {
+                let edge = polytope[(i + 1) % polytope.len()].saturating_sub(polytope[i]);
+                let normal = QDir::new_from_vec(QVec2::new(edge.y, -edge.x)).to_vec();
+                (i, normal, normal.dot(polytope[i]))
+            })
+            .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(core::cmp::Ordering::Equal))
+            .expect("[algorithm::epa] Polytope must not be empty.");
+        rst = normal.saturating_mul_num(distance);
+
+        // An edge through the origin may still be inside, only the support query can tell.
+        let point = support_difference(shape_a, shape_b, normal);
+        if polytope.contains(&point) || point.dot(normal).saturating_sub(distance) <= Q64::EPS {
+            break;
+        }
+        polytope.insert(index + 1, point);
+    }
+    Some(rst)
+}
+
+/// GJK distance.
+///
+/// This function computes the minimum distance between two convex shapes, as the distance
+/// from the origin to their Minkowski difference. The simplex is moved towards the origin
+/// with support queries only, the difference itself is never built.
+///
+/// # Arguments
+///
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
+///
+/// # Returns
+///
+/// Distance between the shapes, zero if they intersect
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::gjk_distance;
+///
+/// let a = QCircle::new(QPoint::ZERO, Q64::ONE);
+/// let b = QCircle::new(QPoint::new(qvec2!(5.0, 0.0)), Q64::ONE);
+/// assert!((gjk_distance(&a, &b) - q64!(3)).abs() < q64!(0.01));
+/// assert_eq!(gjk_distance(&a, &a), Q64::ZERO);
+/// ```
+pub fn gjk_distance(shape_a: &impl QSupportMap, shape_b: &impl QSupportMap) -> Q64 {
+    let mut simplex = vec![support_difference(shape_a, shape_b, QVec2::X)];
+    let mut closest = simplex[0];
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        if closest == QVec2::ZERO {
+            return Q64::ZERO;
+        }
+        let point = support_difference(shape_a, shape_b, -closest);
+        // Stop once the support plane can no longer bring the simplex closer to the origin.
+        if simplex.contains(&point) || closest.dot(closest).saturating_sub(closest.dot(point)) <= Q64::EPS {
+            break;
+        }
+        simplex.push(point);
+        (closest, simplex) = closest_on_simplex(&simplex);
+    }
+    closest.length()
+}
+
+/// Point of a simplex closest to the origin, with the simplex reduced to the points spanning
+/// the feature it lies on.
+fn closest_on_simplex(simplex: &[QVec2]) -> (QVec2, Vec<QVec2>) {
+    match *simplex {
+        [a, b] => {
+            let ab = b.saturating_sub(a);
+            let t = -a.dot(ab);
+            let length_squared = ab.dot(ab);
+            if t <= Q64::ZERO {
+                (a, vec![a])
+            } else if t >= length_squared {
+                (b, vec![b])
+            } else {
+                (a.saturating_add(ab.saturating_mul_num(t.saturating_div(length_squared))), vec![a, b])
+            }
+        }
+        [a, b, c] => {
+            let sides = [orient2d(a, b, QVec2::ZERO), orient2d(b, c, QVec2::ZERO), orient2d(c, a, QVec2::ZERO)];
+            let degenerate = sides.iter().all(|side| side.is_eq());
+            let enclosed = !sides.contains(&core::cmp::Ordering::Greater) || !sides.contains(&core::cmp::Ordering::Less);
+            if enclosed && !degenerate {
+                return (QVec2::ZERO, simplex.to_vec());
+            }
+            [[a, b], [b, c], [c, a]]
+                .iter()
+                .map(|edge| closest_on_simplex(edge))
+                .min_by(|x, y| x.0.length_squared().partial_cmp(&y.0.length_squared()).unwrap_or(core::cmp::Ordering::Equal))
+                .expect("[algorithm::closest_on_simplex] A triangle has three edges.")
+        }
+        _ => (simplex[0], vec![simplex[0]]),
     }
-    None
 }
 
 /// Calculate the Minkowski difference of two convex polygons.
 /// 
 /// The Minkowski difference of two shapes A and B is defined as the set of all points a - b
 /// where a is in A and b is in B. For convex polygons, this can be computed efficiently by
-/// taking the Minkowski sum of A and -B.
+/// taking the Minkowski sum of A and -B, which merges the edges of both in O(n + m).
+/// Concave inputs are replaced by their convex hulls first.
 /// 
 /// # Arguments
 /// 
@@ -68,13 +339,199 @@ pub fn epa(shape_a: &QPolygon, shape_b: &QPolygon) -> Option<QVec2> {
 /// 
 /// A new polygon representing the Minkowski difference
 pub fn get_minkowski_difference(shape_a: &QPolygon, shape_b: &QPolygon) -> QPolygon {
-    let mut all_diff_points = vec![];
-    shape_a.points().iter().for_each(|pa|
-        shape_b.points().iter().for_each(|pb|
-            all_diff_points.push(pa.pos().saturating_sub(pb.pos()))
-        )
-    );
-    QPolygon::new_from_parts(andrew_graham_scan(&all_diff_points))
+    let negated_b = QPolygon::new_from_parts(shape_b.points().iter().map(|pb| -pb.pos()).collect());
+    get_minkowski_sum(shape_a, &negated_b)
+}
+
+/// Calculate the Minkowski sum of two convex polygons.
+///
+/// The edges of both polygons are merged in angular order, so the sum is built in O(n + m)
+/// without generating every pairwise vertex sum. Concave inputs are replaced by their
+/// convex hulls first. Degenerate inputs with fewer than three
+/// distinct vertices or no area fall back to the hull of all pairwise sums.
+///
+/// # Arguments
+///
+/// * `shape_a` - First convex polygon
+/// * `shape_b` - Second convex polygon
+///
+/// # Returns
+///
+/// A new CCW polygon representing the Minkowski sum
+pub fn get_minkowski_sum(shape_a: &QPolygon, shape_b: &QPolygon) -> QPolygon {
+    let points_a = get_convex_ring(shape_a.points());
+    let points_b = get_convex_ring(shape_b.points());
+    let is_degenerate = |ring: &[QVec2]| ring.len() < 3 || signed_double_area(ring) == Q64::ZERO;
+    if is_degenerate(&points_a) || is_degenerate(&points_b) {
+        let mut all_sum_points = vec![];
+        points_a.iter().for_each(|pa| points_b.iter().for_each(|pb| all_sum_points.push(pa.saturating_add(*pb))));
+        return QPolygon::new_from_parts(andrew_graham_scan(&all_sum_points));
+    }
+
+    let (n, m) = (points_a.len(), points_b.len());
+    let (mut i, mut j) = (0, 0);
+    let mut rst = Vec::with_capacity(n + m);
+    while i < n || j < m {
+        rst.push(points_a[i % n].saturating_add(points_b[j % m]));
+        let edge_a = points_a[(i + 1) % n].saturating_sub(points_a[i % n]);
+        let edge_b = points_b[(j + 1) % m].saturating_sub(points_b[j % m]);
+        let order = if i == n {
+            core::cmp::Ordering::Greater
+        } else if j == m {
+            core::cmp::Ordering::Less
+        } else {
+            compare_edge_angle(edge_a, edge_b)
+        };
+        match order {
+            core::cmp::Ordering::Less => i += 1,
+            core::cmp::Ordering::Greater => j += 1,
+            core::cmp::Ordering::Equal => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    QPolygon::new_from_parts(rst)
+}
+
+/// Vertices of a convex polygon without repeats, CCW and starting from the bottom-left vertex.
+///
+/// Concave or self-intersecting rings are replaced by their convex hull.
+fn get_convex_ring(points: &[QPoint]) -> Vec<QVec2> {
+    let ring = get_oriented_ring(points);
+    let n = ring.len();
+    let is_convex = n < 3 || (0..n - 1).all(|i| {
+        let edge = ring[(i + 1) % n].saturating_sub(ring[i]);
+        let next_edge = ring[(i + 2) % n].saturating_sub(ring[(i + 1) % n]);
+        compare_edge_angle(edge, next_edge) != core::cmp::Ordering::Greater
+    });
+    if is_convex {
+        return ring;
+    }
+    let hull: Vec<QPoint> = andrew_graham_scan(&ring).into_iter().map(QPoint::new).collect();
+    get_oriented_ring(&hull)
+}
+
+/// Vertices without repeats, CCW and starting from the bottom-left vertex.
+fn get_oriented_ring(points: &[QPoint]) -> Vec<QVec2> {
+    let mut ring: Vec<QVec2> = points.iter().map(|point| point.pos()).collect();
+    ring.dedup();
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if signed_double_area(&ring) < Q64::ZERO {
+        ring.reverse();
+    }
+    let start = (0..ring.len())
+        .min_by(|&a, &b| {
+            ring[a].y.partial_cmp(&ring[b].y)
+                .unwrap_or(core::cmp::Ordering::Equal)
+                .then_with(|| ring[a].x.partial_cmp(&ring[b].x).unwrap_or(core::cmp::Ordering::Equal))
+        })
+        .unwrap_or(0);
+    ring.rotate_left(start);
+    ring
+}
+
+/// Order two edge vectors by polar angle in [0, 2π).
+pub(crate) fn compare_edge_angle(a: QVec2, b: QVec2) -> core::cmp::Ordering {
+    let is_upper = |v: QVec2| v.y > Q64::ZERO || (v.y == Q64::ZERO && v.x > Q64::ZERO);
+    match (is_upper(a), is_upper(b)) {
+        (true, false) => core::cmp::Ordering::Less,
+        (false, true) => core::cmp::Ordering::Greater,
+        _ => Q64::ZERO.partial_cmp(&a.cross(b)).unwrap_or(core::cmp::Ordering::Equal),
+    }
+}
+
+/// Exact orientation of the triangle `a`, `b`, `c`.
+///
+/// Returns `Greater` for a counter-clockwise turn, `Less` for a clockwise turn and `Equal`
+/// when the points are collinear. The cross product is evaluated on the raw fixed-point
+/// bits with 128-bit intermediates, so it never saturates whatever the coordinates are.
+/// # Examples
+/// ```
+/// use core::cmp::Ordering;
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::algorithm::orient2d;
+///
+/// assert_eq!(orient2d(QVec2::ZERO, QVec2::X, QVec2::Y), Ordering::Greater);
+/// assert_eq!(orient2d(QVec2::ZERO, QVec2::Y, QVec2::X), Ordering::Less);
+/// let far = QVec2::new(Q64::MAX, Q64::MAX);
+/// assert_eq!(orient2d(QVec2::new(Q64::MIN, Q64::MIN), QVec2::ZERO, far), Ordering::Equal);
+/// ```
+pub fn orient2d(a: QVec2, b: QVec2, c: QVec2) -> core::cmp::Ordering {
+    cross_sign(a, b, a, c)
+}
+
+/// Exact sign of the cross product of `b - a` and `d - c`, see `orient2d`.
+pub(crate) fn cross_sign(a: QVec2, b: QVec2, c: QVec2, d: QVec2) -> core::cmp::Ordering {
+    let bits = |v: QVec2| (v.x.to_bits() as i128, v.y.to_bits() as i128);
+    let ((ax, ay), (bx, by), (cx, cy), (dx, dy)) = (bits(a), bits(b), bits(c), bits(d));
+    // Differences of i64 values fit in 65 bits, their magnitudes in u64.
+    let product = |p: i128, q: i128| ((p < 0) != (q < 0) && p != 0 && q != 0, p.unsigned_abs() * q.unsigned_abs());
+    let compare = |(neg_l, l): (bool, u128), (neg_r, r): (bool, u128)| match (neg_l, neg_r) {
+        (false, true) => core::cmp::Ordering::Greater,
+        (true, false) => core::cmp::Ordering::Less,
+        (false, false) => l.cmp(&r),
+        (true, true) => r.cmp(&l),
+    };
+    compare(product(bx - ax, dy - cy), product(by - ay, dx - cx))
+}
+
+/// Output layout of `andrew_graham_scan_with_options`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QHullOptions {
+    is_closed: bool,
+    is_ccw: bool,
+    keep_collinear: bool,
+}
+
+impl QHullOptions {
+    /// An open counter-clockwise ring without collinear points, as `andrew_graham_scan` gives.
+    pub const DEFAULT: Self = Self::new(false, true, false);
+
+    pub const fn new(is_closed: bool, is_ccw: bool, keep_collinear: bool) -> Self {
+        Self {
+            is_closed,
+            is_ccw,
+            keep_collinear,
+        }
+    }
+
+    /// Repeat the first point at the end of the ring.
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    /// Counter-clockwise order, clockwise otherwise.
+    pub fn is_ccw(&self) -> bool {
+        self.is_ccw
+    }
+
+    /// Keep the points lying on the hull edges.
+    pub fn keep_collinear(&self) -> bool {
+        self.keep_collinear
+    }
+
+    pub fn set_is_closed(&mut self, is_closed: bool) {
+        self.is_closed = is_closed;
+    }
+
+    pub fn set_is_ccw(&mut self, is_ccw: bool) {
+        self.is_ccw = is_ccw;
+    }
+
+    pub fn set_keep_collinear(&mut self, keep_collinear: bool) {
+        self.keep_collinear = keep_collinear;
+    }
+}
+
+impl Default for QHullOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
 }
 
 /// Andrew's monotone chain convex hull algorithm.
@@ -83,32 +540,74 @@ pub fn get_minkowski_difference(shape_a: &QPolygon, shape_b: &QPolygon) -> QPoly
 /// 
 /// The convex hull of the points.
 pub fn andrew_graham_scan(points: &Vec<QVec2>) -> Vec<QVec2> {
-    use std::collections::HashSet;
-    let mut unique_points: Vec<QVec2> = points.into_iter().collect::<HashSet<_>>().into_iter().map(|p| *p).collect();
+    andrew_graham_scan_with_tolerance(points, QTolerance::EXACT)
+}
+
+/// Andrew's monotone chain, also dropping hull points within `tolerance` of the line
+/// through their neighbours.
+pub fn andrew_graham_scan_with_tolerance(points: &Vec<QVec2>, tolerance: QTolerance) -> Vec<QVec2> {
+    get_monotone_chain(points, tolerance, false)
+}
+
+/// Andrew's monotone chain with the layout of the ring chosen by `options`.
+///
+/// The ring always starts at the lowest point by x then y, so the output only depends on
+/// the set of input points, not on their order. Points all on one line give that line,
+/// sorted, with its inner points only if collinear points are kept.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::{ andrew_graham_scan_with_options, QHullOptions };
+///
+/// let points = vec![qvec2!(2.0, 2.0), qvec2!(1.0, 0.0), qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(0.0, 2.0), qvec2!(1.0, 1.0)];
+/// let hull = andrew_graham_scan_with_options(&points, QHullOptions::new(true, false, true));
+/// assert_eq!(hull, vec![qvec2!(0.0, 0.0), qvec2!(0.0, 2.0), qvec2!(2.0, 2.0), qvec2!(2.0, 0.0), qvec2!(1.0, 0.0), qvec2!(0.0, 0.0)]);
+/// assert_eq!(andrew_graham_scan_with_options(&points, QHullOptions::DEFAULT).len(), 4);
+/// ```
+pub fn andrew_graham_scan_with_options(points: &[QVec2], options: QHullOptions) -> Vec<QVec2> {
+    let mut rst = get_monotone_chain(points, QTolerance::EXACT, options.keep_collinear);
+    if !options.is_ccw && rst.len() > 2 {
+        rst[1..].reverse();
+    }
+    if options.is_closed && !rst.is_empty() {
+        rst.push(rst[0]);
+    }
+    rst
+}
+
+/// Counter-clockwise monotone chain starting at the lowest point by x then y.
+fn get_monotone_chain(points: &[QVec2], tolerance: QTolerance, keep_collinear: bool) -> Vec<QVec2> {
+    let mut unique_points: Vec<QVec2> = points.to_vec();
+
+    // A total order, so equal points end up next to each other and the result only depends
+    // on the set of points.
+    unique_points.sort_by(|a, b| a.x.cmp(&b.x).then(a.y.cmp(&b.y)));
+    unique_points.dedup();
 
     let n = unique_points.len();
     if n <= 2 {
         return unique_points;
     }
-
-    // Sort points lexicographically (first by x, then by y)
-    unique_points.sort_by(|a, b| {
-        a.x.partial_cmp(&b.x)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal))
-    });
-
-    /// Computes the 2D cross product of OA and OB vectors, i.e. z-component of their 3D cross product.
-    /// Returns a positive value, if OAB makes a counter-clockwise turn,
-    /// negative for clockwise turn, and zero if the points are collinear.
-    fn cross(o: &QVec2, a: &QVec2, b: &QVec2) -> Q64 {
-        (a.saturating_sub(*o)).cross(b.saturating_sub(*o))
+    let (first, last) = (unique_points[0], unique_points[n - 1]);
+    if unique_points.iter().all(|p| orient2d(first, last, *p) == core::cmp::Ordering::Equal) {
+        return if keep_collinear { unique_points } else { vec![first, last] };
     }
 
+    // Pop while the last two hull points and `p` don't make a counter-clockwise turn,
+    // straight turns are kept along with collinear points.
+    let is_not_ccw = |o: QVec2, a: QVec2, b: QVec2| match orient2d(o, a, b) {
+        core::cmp::Ordering::Greater => !keep_collinear && tolerance.is_collinear(o, b, a),
+        core::cmp::Ordering::Equal => !keep_collinear,
+        core::cmp::Ordering::Less => true,
+    };
+
     // Build lower hull
     let mut lower = Vec::with_capacity(n);
     for p in &unique_points {
-        while lower.len() >= 2 && cross(&lower[lower.len()-2], &lower[lower.len()-1], p) <= Q64::ZERO {
+        while lower.len() >= 2 && is_not_ccw(lower[lower.len()-2], lower[lower.len()-1], *p) {
             lower.pop();
         }
         lower.push(*p);
@@ -117,7 +616,7 @@ pub fn andrew_graham_scan(points: &Vec<QVec2>) -> Vec<QVec2> {
     // Build upper hull
     let mut upper = Vec::with_capacity(n);
     for p in unique_points.iter().rev() {
-        while upper.len() >= 2 && cross(&upper[upper.len()-2], &upper[upper.len()-1], p) <= Q64::ZERO {
+        while upper.len() >= 2 && is_not_ccw(upper[upper.len()-2], upper[upper.len()-1], *p) {
             upper.pop();
         }
         upper.push(*p);
@@ -131,3 +630,1578 @@ pub fn andrew_graham_scan(points: &Vec<QVec2>) -> Vec<QVec2> {
     lower.extend(upper);
     lower
 }
+
+/// Convex hull of several shapes together, counter-clockwise.
+///
+/// Circles are bounded by their tangent lines at the exact tangent points from the other
+/// shapes' vertices and from the other circles, and at `SAMPLE_RESOLUTION` even angles in
+/// between. The hull runs along the exact tangents and contains every circle, around arcs
+/// it stays within `r * (1 / cos(π / SAMPLE_RESOLUTION) - 1)` of them.
+///
+/// # Arguments
+///
+/// * `shapes` - Shapes to bound, an empty polygon is returned without any
+///
+/// # Returns
+///
+/// The convex polygon around all the shapes
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::hull_of;
+///
+/// let shapes = [
+///     QShape::from(QCircle::new(QPoint::ZERO, Q64::ONE)),
+///     QShape::from(QCircle::new(QPoint::new(qvec2!(4.0, 0.0)), Q64::ONE)),
+///     QShape::from(QPoint::new(qvec2!(2.0, 3.0))),
+/// ];
+/// let hull = hull_of(&shapes);
+/// assert!(hull.is_ccw());
+/// assert!(hull.points().contains(&QPoint::new(qvec2!(2.0, 3.0))));
+/// assert!(hull.is_point_inside(&QPoint::new(qvec2!(2.0, -0.999))));
+/// assert!(!hull.is_point_inside(&QPoint::new(qvec2!(2.0, -1.001))));
+/// assert!(shapes.iter().all(|shape| (0..64).all(|k| hull.is_point_inside(&shape.boundary_point(q64!(k) / q64!(64))))));
+/// ```
+pub fn hull_of(shapes: &[QShape]) -> QPolygon {
+    let circles: Vec<&QCircle> = shapes
+        .iter()
+        .filter_map(|shape| match shape {
+            QShape::QCircle(circle) => Some(circle),
+            _ => None,
+        })
+        .collect();
+    let corners: Vec<QPoint> = shapes
+        .iter()
+        .filter(|shape| !matches!(shape, QShape::QCircle(_)))
+        .flat_map(|shape| shape.points())
+        .collect();
+    // Unit directions from each center towards the points its tangent lines touch.
+    let count = q64!(SAMPLE_RESOLUTION as i64);
+    let get_dir = |circle: &QCircle, touch: QVec2| QDir::new_from_vec(touch.saturating_sub(circle.center().pos())).to_vec();
+    let mut touches: Vec<Vec<QVec2>> = circles
+        .iter()
+        .map(|&circle| (0..SAMPLE_RESOLUTION).map(|k| get_dir(circle, circle.boundary_point(q64!(k as i64).saturating_div(count)).pos())).collect())
+        .collect();
+    for (i, &circle) in circles.iter().enumerate() {
+        for corner in &corners {
+            touches[i].extend(circle.tangents_from_point(corner).iter().map(|line| get_dir(circle, line.end().pos())));
+        }
+        for (j, &other) in circles.iter().enumerate().skip(i + 1) {
+            for line in circle.common_tangents(other) {
+                touches[i].push(get_dir(circle, line.start().pos()));
+                touches[j].push(get_dir(other, line.end().pos()));
+            }
+        }
+    }
+
+    let mut candidates: Vec<QVec2> = corners.iter().map(|point| point.pos()).collect();
+    for (circle, dirs) in circles.iter().zip(touches.iter_mut()) {
+        dirs.sort_by(|a, b| compare_edge_angle(*a, *b));
+        dirs.dedup();
+        let (center, radius) = (circle.center().pos(), circle.radius());
+        for (i, dir) in dirs.iter().enumerate() {
+            // Adjacent tangent lines meet at `(u + v) * r / (1 + u·v)` from the center.
+            let next = dirs[(i + 1) % dirs.len()];
+            let scale = radius.saturating_div(Q64::ONE.saturating_add(dir.dot(next)));
+            candidates.push(center.saturating_add(dir.saturating_mul_num(radius)));
+            candidates.push(center.saturating_add(dir.saturating_add(next).saturating_mul_num(scale)));
+        }
+    }
+    QPolygon::new_from_parts(andrew_graham_scan(&candidates))
+}
+
+/// Get the candidate separating axes between two convex shapes.
+///
+/// Edge normals are used for polygonal shapes, circles contribute the axis from their center
+/// towards the closest point of the other shape.
+fn get_sat_axes(shape_a: &impl QShapeCommon, shape_b: &impl QShapeCommon) -> Vec<QDir> {
+    fn add_axes(shape: &impl QShapeCommon, other: &impl QShapeCommon, axes: &mut Vec<QDir>) {
+        if shape.get_shape_type() == QShapeType::QCircle {
+            let center = shape.get_centroid();
+            let closest = other.closest_point(&center);
+            if closest != center {
+                axes.push(QDir::new_from_vec(closest.pos().saturating_sub(center.pos())));
+            }
+        } else {
+            match shape.precomputed_normals() {
+                Some(normals) => axes.extend_from_slice(normals),
+                None => shape.edges_iter().for_each(|segment| axes.push(segment.get_perpendicular_dir())),
+            }
+        }
+    }
+
+    let mut axes = vec![];
+    add_axes(shape_a, shape_b, &mut axes);
+    add_axes(shape_b, shape_a, &mut axes);
+    if axes.is_empty() {
+        axes.push(QDir::new_from_vec(QVec2::X));
+    }
+    axes
+}
+
+/// SAT collision check.
+///
+/// This function checks if two convex shapes intersect using the Separating Axis Theorem.
+/// Polygons, bboxes and circles are supported, touching shapes count as colliding.
+///
+/// # Arguments
+///
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
+///
+/// # Returns
+///
+/// True if the shapes intersect, false otherwise
+pub fn sat_collide(shape_a: &impl QShapeCommon, shape_b: &impl QShapeCommon) -> bool {
+    get_sat_axes(shape_a, shape_b).into_iter().all(|axis| {
+        let (min_a, max_a) = shape_a.project_onto(axis);
+        let (min_b, max_b) = shape_b.project_onto(axis);
+        min_a <= max_b && min_b <= max_a
+    })
+}
+
+/// SAT minimum translation vector.
+///
+/// This function finds the axis of least overlap between two convex shapes using the
+/// Separating Axis Theorem.
+///
+/// # Arguments
+///
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
+///
+/// # Returns
+///
+/// Translation for shape_b that separates the shapes, same convention as `epa`
+pub fn sat_mtv(shape_a: &impl QShapeCommon, shape_b: &impl QShapeCommon) -> Option<QVec2> {
+    let mut rst: Option<(Q64, QVec2)> = None;
+    for axis in get_sat_axes(shape_a, shape_b) {
+        let (min_a, max_a) = shape_a.project_onto(axis);
+        let (min_b, max_b) = shape_b.project_onto(axis);
+        if min_a > max_b || min_b > max_a {
+            return None;
+        }
+
+        let push_forward = max_a.saturating_sub(min_b);
+        let push_backward = max_b.saturating_sub(min_a);
+        let (overlap, vector) = if push_forward <= push_backward {
+            (push_forward, axis.to_vec().saturating_mul_num(push_forward))
+        } else {
+            (push_backward, axis.to_vec().saturating_mul_num(-push_backward))
+        };
+        if rst.is_none_or(|(min_overlap, _)| overlap < min_overlap) {
+            rst = Some((overlap, vector));
+        }
+    }
+    rst.map(|(_, vector)| vector)
+}
+
+/// Is the ring convex, in any orientation.
+pub(crate) fn is_convex_ring(points: &[QPoint]) -> bool {
+    get_convex_ring(points).len() == get_oriented_ring(points).len()
+}
+
+/// Area enclosed by the boundary of a shape, zero for points, lines and polylines.
+fn get_shape_area(shape: &impl QShapeCommon) -> Q64 {
+    match shape.get_shape_type() {
+        QShapeType::QPoint | QShapeType::QLine | QShapeType::QPolyline => Q64::ZERO,
+        _ => {
+            let ring: Vec<QVec2> = shape.points_iter().map(|point| point.pos()).collect();
+            signed_double_area(&ring).abs().saturating_mul(Q64::HALF)
+        }
+    }
+}
+
+/// Sutherland-Hodgman clipping of `subject` by the convex CCW ring `clip`.
+fn clip_by_convex_ring(subject: Vec<QVec2>, clip: &[QVec2]) -> Vec<QVec2> {
+    let mut rst = subject;
+    for i in 0..clip.len() {
+        if rst.is_empty() {
+            break;
+        }
+        let clip_start = clip[i];
+        let clip_edge = clip[(i + 1) % clip.len()].saturating_sub(clip_start);
+        let side = |p: QVec2| clip_edge.cross(p.saturating_sub(clip_start));
+
+        let input = core::mem::take(&mut rst);
+        for j in 0..input.len() {
+            let start = input[j];
+            let end = input[(j + 1) % input.len()];
+            let (side_start, side_end) = (side(start), side(end));
+            if side_start >= Q64::ZERO {
+                rst.push(start);
+            }
+            if (side_start >= Q64::ZERO) != (side_end >= Q64::ZERO) {
+                let t = side_start.saturating_div(side_start.saturating_sub(side_end));
+                rst.push(start.saturating_add(end.saturating_sub(start).saturating_mul_num(t)));
+            }
+        }
+    }
+    rst
+}
+
+/// Area of the overlap of two shapes.
+///
+/// The boundary of `shape_a` is clipped by the boundary of `shape_b` using Sutherland-Hodgman.
+/// When both are concave `shape_a` is clipped by every ear triangle of `shape_b` instead and
+/// the parts are summed. Circles are measured by their vertex polygons.
+///
+/// # Arguments
+///
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
+///
+/// # Returns
+///
+/// The overlapping area
+pub fn intersection_area(shape_a: &impl QShapeCommon, shape_b: &impl QShapeCommon) -> Q64 {
+    if get_shape_area(shape_a) == Q64::ZERO || get_shape_area(shape_b) == Q64::ZERO {
+        return Q64::ZERO;
+    }
+    let (points_a, points_b) = (shape_a.points(), shape_b.points());
+    let (subject, clip) = if is_convex_ring(&points_b) || !is_convex_ring(&points_a) {
+        (points_a, points_b)
+    } else {
+        (points_b, points_a)
+    };
+
+    let subject: Vec<QVec2> = subject.iter().map(|point| point.pos()).collect();
+    if is_convex_ring(&clip) {
+        let clipped = clip_by_convex_ring(subject, &get_convex_ring(&clip));
+        return signed_double_area(&clipped).abs().saturating_mul(Q64::HALF);
+    }
+    shape_b
+        .ear_clipping_triangulation()
+        .chunks_exact(3)
+        .map(|indices| {
+            // Ear clipping gives clockwise triangles, the clipper takes counter-clockwise rings.
+            let triangle = [clip[indices[2]].pos(), clip[indices[1]].pos(), clip[indices[0]].pos()];
+            let clipped = clip_by_convex_ring(subject.clone(), &triangle);
+            signed_double_area(&clipped).abs().saturating_mul(Q64::HALF)
+        })
+        .fold(Q64::ZERO, |acc, area| acc.saturating_add(area))
+}
+
+/// Intersection over union of two shapes.
+///
+/// # Arguments
+///
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
+///
+/// # Returns
+///
+/// Overlap area divided by the area of the union, in [0, 1]
+pub fn iou(shape_a: &impl QShapeCommon, shape_b: &impl QShapeCommon) -> Q64 {
+    let intersection = intersection_area(shape_a, shape_b);
+    let union = get_shape_area(shape_a).saturating_add(get_shape_area(shape_b)).saturating_sub(intersection);
+    if union <= Q64::ZERO {
+        return Q64::ZERO;
+    }
+    intersection.saturating_div(union)
+}
+
+/// Is the boundary of the shape a closed ring.
+fn is_closed_boundary(shape: &impl QShapeCommon) -> bool {
+    !matches!(shape.get_shape_type(), QShapeType::QPoint | QShapeType::QLine | QShapeType::QPolyline)
+}
+
+/// Liang-Barsky clipping of the segment from `start` to `end` to `bbox`, `None` if less
+/// than a segment is left.
+fn clip_segment_to_bbox(start: QVec2, end: QVec2, bbox: &QBbox) -> Option<(QVec2, QVec2)> {
+    let (min, max) = (bbox.left_bottom().pos(), bbox.right_top().pos());
+    let delta = end.saturating_sub(start);
+    // Keep each bound as `q / p` so the clipped ends are computed with a single rounding.
+    let (mut enter, mut exit) = ((Q64::ZERO, Q64::ONE), (Q64::ONE, Q64::ONE));
+    for (p, q) in [
+        (-delta.x, start.x.saturating_sub(min.x)),
+        (delta.x, max.x.saturating_sub(start.x)),
+        (-delta.y, start.y.saturating_sub(min.y)),
+        (delta.y, max.y.saturating_sub(start.y)),
+    ] {
+        if p == Q64::ZERO {
+            if q < Q64::ZERO {
+                return None;
+            }
+            continue;
+        }
+        let t = q.saturating_div(p);
+        if p < Q64::ZERO && t > enter.0.saturating_div(enter.1) {
+            enter = (q, p);
+        } else if p > Q64::ZERO && t < exit.0.saturating_div(exit.1) {
+            exit = (q, p);
+        }
+        if enter.0.saturating_div(enter.1) > exit.0.saturating_div(exit.1) {
+            return None;
+        }
+    }
+    // Clamp so rounding never leaves a clipped end outside the bbox.
+    let get_point = |(q, p): (Q64, Q64)| {
+        let offset = QVec2::new(delta.x.saturating_mul(q).saturating_div(p), delta.y.saturating_mul(q).saturating_div(p));
+        start.saturating_add(offset).max(min).min(max)
+    };
+    let clipped_start = if enter.0 == Q64::ZERO { start } else { get_point(enter) };
+    let clipped_end = if exit == (Q64::ONE, Q64::ONE) { end } else { get_point(exit) };
+    (clipped_start != clipped_end).then_some((clipped_start, clipped_end))
+}
+
+const OUTCODE_LEFT: u8 = 1;
+const OUTCODE_RIGHT: u8 = 2;
+const OUTCODE_BOTTOM: u8 = 4;
+const OUTCODE_TOP: u8 = 8;
+
+/// Cohen-Sutherland region code of `pos` around the bbox from `min` to `max`.
+fn get_outcode(pos: QVec2, min: QVec2, max: QVec2) -> u8 {
+    let mut code = 0;
+    if pos.x < min.x {
+        code |= OUTCODE_LEFT;
+    } else if pos.x > max.x {
+        code |= OUTCODE_RIGHT;
+    }
+    if pos.y < min.y {
+        code |= OUTCODE_BOTTOM;
+    } else if pos.y > max.y {
+        code |= OUTCODE_TOP;
+    }
+    code
+}
+
+/// Cohen-Sutherland clipping of the segment from `start` to `end` to `bbox`, `None` if
+/// less than a segment is left.
+fn clip_segment_by_outcodes(start: QVec2, end: QVec2, bbox: &QBbox) -> Option<(QVec2, QVec2)> {
+    let (min, max) = (bbox.left_bottom().pos(), bbox.right_top().pos());
+    let (mut start, mut end) = (start, end);
+    let (mut code_start, mut code_end) = (get_outcode(start, min, max), get_outcode(end, min, max));
+    // Every end crosses at most two borders, more moves only chase rounding around a corner.
+    for _ in 0..5 {
+        if code_start | code_end == 0 {
+            return (start != end).then_some((start, end));
+        }
+        if code_start & code_end != 0 {
+            return None;
+        }
+        // Move an outside end onto the border it is beyond, the cut coordinate is exact.
+        let code = if code_start != 0 { code_start } else { code_end };
+        let delta = end.saturating_sub(start);
+        let moved = if code & (OUTCODE_LEFT | OUTCODE_RIGHT) != 0 {
+            let x = if code & OUTCODE_LEFT != 0 { min.x } else { max.x };
+            QVec2::new(x, start.y.saturating_add(delta.y.saturating_mul(x.saturating_sub(start.x)).saturating_div(delta.x)))
+        } else {
+            let y = if code & OUTCODE_BOTTOM != 0 { min.y } else { max.y };
+            QVec2::new(start.x.saturating_add(delta.x.saturating_mul(y.saturating_sub(start.y)).saturating_div(delta.y)), y)
+        };
+        if code == code_start {
+            start = moved;
+            code_start = get_outcode(start, min, max);
+        } else {
+            end = moved;
+            code_end = get_outcode(end, min, max);
+        }
+    }
+    None
+}
+
+/// Clip many segments to a bbox.
+///
+/// Segments with both ends inside are accepted and segments with both ends beyond the
+/// same side are rejected by their region codes alone, only the rest are cut. Clipped
+/// ends lie exactly on the border they were cut at.
+///
+/// # Arguments
+///
+/// * `lines` - Segments to clip
+/// * `bbox` - Clipping area
+///
+/// # Returns
+///
+/// The part of every segment inside `bbox`, `None` for segments missing it or only
+/// touching it at a point
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::clip_segments;
+///
+/// let bbox = QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 4.0));
+/// let lines = [
+///     QLine::new_from_parts(qvec2!(1.0, 1.0), qvec2!(2.0, 3.0)),
+///     QLine::new_from_parts(qvec2!(-2.0, 2.0), qvec2!(6.0, 2.0)),
+///     QLine::new_from_parts(qvec2!(5.0, 0.0), qvec2!(6.0, 4.0)),
+///     QLine::new_from_parts(qvec2!(-1.0, 3.0), qvec2!(1.0, 5.0)),
+/// ];
+/// assert_eq!(clip_segments(&lines, &bbox), vec![
+///     Some(lines[0]),
+///     Some(QLine::new_from_parts(qvec2!(0.0, 2.0), qvec2!(4.0, 2.0))),
+///     None,
+///     None,
+/// ]);
+/// ```
+pub fn clip_segments(lines: &[QLine], bbox: &QBbox) -> Vec<Option<QLine>> {
+    lines
+        .iter()
+        .map(|line| {
+            let (start, end) = clip_segment_by_outcodes(line.start().pos(), line.end().pos(), bbox)?;
+            Some(if (start, end) == (line.start().pos(), line.end().pos()) { *line } else { QLine::new_from_parts(start, end) })
+        })
+        .collect()
+}
+
+/// Prepare shapes for drawing in a viewport.
+///
+/// Shapes entirely outside `view` are dropped and shapes entirely inside it are kept as
+/// they are. Lines, polylines, bboxes and polygons crossing its border are clipped to it,
+/// a polyline may fall apart into several. Points on the border are kept and circles
+/// crossing it are kept whole. Concave polygons are clipped with Sutherland-Hodgman, so
+/// parts leaving and reentering the view stay joined along its border.
+///
+/// # Arguments
+///
+/// * `shapes` - Shapes to draw
+/// * `view` - Visible area
+///
+/// # Returns
+///
+/// The visible shapes, in the order of `shapes`
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::clip_to_viewport;
+///
+/// let view = QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(10.0, 10.0));
+/// let shapes = vec![
+///     QShape::QCircle(QCircle::new(QPoint::new(qvec2!(5.0, 5.0)), Q64::ONE)),
+///     QShape::QLine(QLine::new_from_parts(qvec2!(-5.0, 5.0), qvec2!(5.0, 5.0))),
+///     QShape::QPoint(QPoint::new(qvec2!(20.0, 5.0))),
+/// ];
+/// let visible = clip_to_viewport(&shapes, &view);
+/// assert_eq!(visible.len(), 2);
+/// assert_eq!(visible[0], shapes[0]);
+/// assert_eq!(visible[1], QShape::QLine(QLine::new_from_parts(qvec2!(0.0, 5.0), qvec2!(5.0, 5.0))));
+/// ```
+pub fn clip_to_viewport(shapes: &[QShape], view: &QBbox) -> Vec<QShape> {
+    let (min, max) = (view.left_bottom().pos(), view.right_top().pos());
+    let view_ring = [min, QVec2::new(max.x, min.y), max, QVec2::new(min.x, max.y)];
+    let mut rst = Vec::with_capacity(shapes.len());
+    for shape in shapes {
+        let bbox = shape.get_bbox();
+        if !bbox.is_overlap(view) {
+            continue;
+        }
+        if view.is_bbox_inside(&bbox) {
+            rst.push(shape.clone());
+            continue;
+        }
+        match shape {
+            QShape::QPoint(_) | QShape::QCircle(_) => rst.push(shape.clone()),
+            QShape::QBbox(bbox) => {
+                // `is_overlap` counts touching borders, those leave no area to draw.
+                let (low, high) = (bbox.left_bottom().pos().max(min), bbox.right_top().pos().min(max));
+                if low.x < high.x && low.y < high.y {
+                    rst.push(QShape::QBbox(QBbox::new_from_parts(low, high)));
+                }
+            }
+            QShape::QLine(line) => {
+                if let Some((start, end)) = clip_segment_to_bbox(line.start().pos(), line.end().pos(), view) {
+                    rst.push(QShape::QLine(QLine::new_from_parts(start, end)));
+                }
+            }
+            QShape::QPolyline(polyline) => {
+                let mut run: Vec<QVec2> = vec![];
+                for segment in polyline.points().windows(2) {
+                    match clip_segment_to_bbox(segment[0].pos(), segment[1].pos(), view) {
+                        Some((start, end)) => {
+                            if run.last() != Some(&start) {
+                                if run.len() >= 2 {
+                                    rst.push(QShape::QPolyline(QPolyline::new_from_parts(core::mem::take(&mut run))));
+                                }
+                                run = vec![start];
+                            }
+                            run.push(end);
+                        }
+                        None => {
+                            if run.len() >= 2 {
+                                rst.push(QShape::QPolyline(QPolyline::new_from_parts(core::mem::take(&mut run))));
+                            }
+                            run.clear();
+                        }
+                    }
+                }
+                if run.len() >= 2 {
+                    rst.push(QShape::QPolyline(QPolyline::new_from_parts(run)));
+                }
+            }
+            QShape::QPolygon(polygon) => {
+                let clipped = clip_by_convex_ring(polygon.points().iter().map(|point| point.pos()).collect(), &view_ring);
+                let mut clipped: Vec<QVec2> = clipped.into_iter().map(|pos| pos.max(min).min(max)).collect();
+                clipped.dedup();
+                if clipped.len() > 1 && clipped.first() == clipped.last() {
+                    clipped.pop();
+                }
+                if clipped.len() >= 3 && signed_double_area(&clipped) != Q64::ZERO {
+                    rst.push(QShape::QPolygon(QPolygon::new_from_parts(clipped)));
+                }
+            }
+        }
+    }
+    rst
+}
+
+/// Directed Hausdorff distance from the boundary of `shape_a` to the boundary of `shape_b`.
+///
+/// The farthest point may lie inside an edge of `shape_a`, so every edge is sampled at
+/// `SAMPLE_RESOLUTION` points and the farthest sample is refined with a golden section search.
+///
+/// # Arguments
+///
+/// * `shape_a` - Shape whose boundary is measured
+/// * `shape_b` - Shape measured against
+///
+/// # Returns
+///
+/// The largest distance from a point on the boundary of `shape_a` to the boundary of `shape_b`
+pub fn directed_hausdorff_distance(shape_a: &impl QShapeCommon, shape_b: &impl QShapeCommon) -> Q64 {
+    let boundary_b = shape_b.points();
+    let is_closed_b = is_closed_boundary(shape_b);
+    let get_distance = |pos: QVec2| {
+        let point = QPoint::new(pos);
+        get_closest_point_on_chain(&boundary_b, is_closed_b, &point).distance(&point)
+    };
+    let boundary_a = shape_a.points();
+    let n = boundary_a.len();
+    let edge_count = if is_closed_boundary(shape_a) && n > 2 { n } else { n.saturating_sub(1) };
+    let count = q64!(SAMPLE_RESOLUTION as i64);
+    let golden = q64!(0.381966);
+
+    let mut rst = boundary_a.iter().map(|point| get_distance(point.pos())).fold(Q64::ZERO, |acc, distance| acc.max(distance));
+    for i in 0..edge_count {
+        let (start, end) = (boundary_a[i].pos(), boundary_a[(i + 1) % n].pos());
+        let get_edge_distance = |t: Q64| get_distance(start.saturating_add(end.saturating_sub(start).saturating_mul_num(t)));
+        let Some((best, distance)) = (1..SAMPLE_RESOLUTION)
+            .map(|k| {
+                let t = q64!(k as i64).saturating_div(count);
+                (t, get_edge_distance(t))
+            })
+            .max_by_key(|(_, distance)| *distance)
+        else {
+            continue;
+        };
+        rst = rst.max(distance);
+        let step = Q64::ONE.saturating_div(count);
+        let (mut low, mut high) = (best.saturating_sub(step).max(Q64::ZERO), best.saturating_add(step).min(Q64::ONE));
+        for _ in 0..HAUSDORFF_REFINEMENTS {
+            let width = high.saturating_sub(low).saturating_mul(golden);
+            let (left, right) = (low.saturating_add(width), high.saturating_sub(width));
+            let (left_distance, right_distance) = (get_edge_distance(left), get_edge_distance(right));
+            rst = rst.max(left_distance).max(right_distance);
+            if left_distance < right_distance {
+                low = left;
+            } else {
+                high = right;
+            }
+        }
+    }
+    rst
+}
+
+/// Hausdorff distance between the boundaries of two shapes.
+///
+/// # Arguments
+///
+/// * `shape_a` - First shape
+/// * `shape_b` - Second shape
+///
+/// # Returns
+///
+/// The larger of both directed Hausdorff distances
+pub fn hausdorff_distance(shape_a: &impl QShapeCommon, shape_b: &impl QShapeCommon) -> Q64 {
+    directed_hausdorff_distance(shape_a, shape_b).max(directed_hausdorff_distance(shape_b, shape_a))
+}
+
+/// Discrete Fréchet distance between two polylines.
+///
+/// Both paths are walked forward from their start vertices, the result is the shortest leash
+/// that connects the walkers at every step. Runs in O(n * m) time and O(m) memory.
+///
+/// # Arguments
+///
+/// * `path_a` - First path
+/// * `path_b` - Second path
+///
+/// # Returns
+///
+/// The discrete Fréchet distance over the vertices of both paths
+pub fn frechet_distance(path_a: &QPolyline, path_b: &QPolyline) -> Q64 {
+    let (points_a, points_b) = (path_a.points(), path_b.points());
+    assert!(!points_a.is_empty() && !points_b.is_empty(), "[algorithm::frechet_distance] Paths must not be empty.");
+
+    let mut previous_row: Vec<Q64> = Vec::with_capacity(points_b.len());
+    let mut row: Vec<Q64> = Vec::with_capacity(points_b.len());
+    for (i, point_a) in points_a.iter().enumerate() {
+        row.clear();
+        for (j, point_b) in points_b.iter().enumerate() {
+            let distance = point_a.distance(point_b);
+            let reachable = match (i, j) {
+                (0, 0) => distance,
+                (0, _) => row[j - 1],
+                (_, 0) => previous_row[0],
+                _ => previous_row[j - 1].min(previous_row[j]).min(row[j - 1]),
+            };
+            row.push(reachable.max(distance));
+        }
+        core::mem::swap(&mut previous_row, &mut row);
+    }
+    previous_row[points_b.len() - 1]
+}
+
+/// Interpolate between two polygons.
+///
+/// Both polygons are resampled by arc length to a common vertex count, the second one is
+/// rotated to the start index that best matches the first one, then vertices are linearly
+/// interpolated. Polygons of opposite winding are matched after reversing the second one.
+///
+/// # Arguments
+///
+/// * `shape_a` - Polygon at `t = 0`
+/// * `shape_b` - Polygon at `t = 1`
+/// * `t` - Interpolation factor, clamped to [0, 1]
+///
+/// # Returns
+///
+/// The interpolated polygon
+pub fn lerp_polygons(shape_a: &QPolygon, shape_b: &QPolygon, t: Q64) -> QPolygon {
+    assert!(
+        shape_a.points().len() >= 3 && shape_b.points().len() >= 3,
+        "[algorithm::lerp_polygons] Polygons must have at least 3 points, shape_a: {:?}, shape_b: {:?}", shape_a, shape_b
+    );
+    let t = t.max(Q64::ZERO).min(Q64::ONE);
+    let count = shape_a.points().len().max(shape_b.points().len());
+
+    let points_a = resample_closed(shape_a, count);
+    let mut points_b = resample_closed(shape_b, count);
+    if (signed_double_area(&points_a) < Q64::ZERO) != (signed_double_area(&points_b) < Q64::ZERO) {
+        points_b.reverse();
+    }
+
+    // Pick the start offset of b with the smallest total squared distance.
+    let mut best_shift = 0;
+    let mut best_cost = Q64::MAX;
+    for shift in 0..count {
+        let cost = (0..count).fold(Q64::ZERO, |acc, i| {
+            acc.saturating_add(points_a[i].distance_squared(points_b[(i + shift) % count]))
+        });
+        if cost < best_cost {
+            best_cost = cost;
+            best_shift = shift;
+        }
+    }
+
+    QPolygon::new_from_parts(
+        (0..count)
+            .map(|i| {
+                let pa = points_a[i];
+                let pb = points_b[(i + best_shift) % count];
+                pa.saturating_add(pb.saturating_sub(pa).saturating_mul_num(t))
+            })
+            .collect()
+    )
+}
+
+/// Resample the closed boundary of a polygon to `count` points evenly spaced by arc length.
+fn resample_closed(shape: &QPolygon, count: usize) -> Vec<QVec2> {
+    let mut closed = shape.points().clone();
+    closed.push(shape.points()[0]);
+    let mut rst: Vec<QVec2> = QPolyline::new(closed)
+        .arc_length_param()
+        .resample_count(count + 1)
+        .points()
+        .iter()
+        .map(|p| p.pos())
+        .collect();
+    rst.pop();
+    rst
+}
+
+/// Twice the signed area of a closed ring, positive when the ring is CCW.
+pub(crate) fn signed_double_area(points: &[QVec2]) -> Q64 {
+    let n = points.len();
+    (0..n).fold(Q64::ZERO, |acc, i| acc.saturating_add(points[i].cross(points[(i + 1) % n])))
+}
+
+/// Angle of the vector `(x, y)` from the x axis in [-π, π], zero for the zero vector.
+///
+/// Deterministic fixed-point approximation, the error stays below 2e-5 radians.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qgeometry::algorithm::atan2;
+///
+/// assert_eq!(atan2(Q64::ZERO, Q64::ONE), Q64::ZERO);
+/// assert!((atan2(Q64::ONE, Q64::ONE) - Q64::PI / q64!(4)).abs() < q64!(0.0001));
+/// assert!((atan2(Q64::NEG_ONE, -Q64::ONE) + Q64::PI * q64!(0.75)).abs() < q64!(0.0001));
+/// ```
+pub fn atan2(y: Q64, x: Q64) -> Q64 {
+    if x == Q64::ZERO && y == Q64::ZERO {
+        return Q64::ZERO;
+    }
+    let (ax, ay) = (x.abs(), y.abs());
+    // Reduce to z in [0, 1] where the minimax polynomial of atan holds.
+    let z = if ay <= ax { ay.saturating_div(ax) } else { ax.saturating_div(ay) };
+    let z2 = z.saturating_mul(z);
+    let poly = q64!(0.0208351)
+        .saturating_mul(z2).saturating_add(q64!(-0.0851330))
+        .saturating_mul(z2).saturating_add(q64!(0.1801410))
+        .saturating_mul(z2).saturating_add(q64!(-0.3302995))
+        .saturating_mul(z2).saturating_add(q64!(0.9998660));
+    let mut angle = z.saturating_mul(poly);
+    if ay > ax {
+        angle = Q64::PI.saturating_mul(Q64::HALF).saturating_sub(angle);
+    }
+    if x < Q64::ZERO {
+        angle = Q64::PI.saturating_sub(angle);
+    }
+    if y < Q64::ZERO { -angle } else { angle }
+}
+
+/// Square root by Newton's method, zero for values that are not positive.
+pub(crate) fn sqrt(value: Q64) -> Q64 {
+    if value <= Q64::ZERO {
+        return Q64::ZERO;
+    }
+    // Starting above the root, the iterates decrease until rounding stops them.
+    let mut x = value.max(Q64::ONE);
+    loop {
+        let next = x.saturating_add(value.saturating_div(x)).saturating_mul(Q64::HALF);
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// Oriented box of a point set aligned with its principal axes.
+///
+/// The axes come from the covariance of the points, so the box is quick but not the
+/// smallest one, outliers can tilt it.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::pca_obb;
+///
+/// let points = [qvec2!(0.0, 0.0), qvec2!(1.0, 1.0), qvec2!(2.0, 2.0), qvec2!(3.0, 3.0), qvec2!(2.0, 1.0), qvec2!(1.0, 2.0)];
+/// let obb = pca_obb(&points);
+/// assert!((obb.axis().x - obb.axis().y).abs() < q64!(0.001));
+/// assert!((obb.area() - q64!(6)).abs() < q64!(0.01));
+/// ```
+pub fn pca_obb(points: &[QVec2]) -> QObb {
+    assert!(!points.is_empty(), "[algorithm::pca_obb] points should not be empty.");
+    let count = q64!(points.len() as i64);
+    let origin = points[0];
+    let sum = points.iter().fold(QVec2::ZERO, |acc, p| acc.saturating_add(p.saturating_sub(origin)));
+    let mean = origin.saturating_add(QVec2::new(sum.x.saturating_div(count), sum.y.saturating_div(count)));
+    let (mut xx, mut yy, mut xy) = (Q64::ZERO, Q64::ZERO, Q64::ZERO);
+    for p in points {
+        let d = p.saturating_sub(mean);
+        xx = xx.saturating_add(d.x.saturating_mul(d.x));
+        yy = yy.saturating_add(d.y.saturating_mul(d.y));
+        xy = xy.saturating_add(d.x.saturating_mul(d.y));
+    }
+    // Principal direction of the covariance matrix, the scale of the sums cancels out.
+    let angle = atan2(xy.saturating_mul(q64!(2)), xx.saturating_sub(yy)).saturating_mul(Q64::HALF);
+    let (sin, cos) = angle.sin_cos();
+    let axis = QDir::new_from_vec(QVec2::new(cos, sin));
+    let (u, v) = (axis.to_vec(), QVec2::new(-axis.to_vec().y, axis.to_vec().x));
+    let first = points[0].saturating_sub(mean);
+    let (mut min, mut max) = (QVec2::new(first.dot(u), first.dot(v)), QVec2::new(first.dot(u), first.dot(v)));
+    for p in points {
+        let d = p.saturating_sub(mean);
+        let local = QVec2::new(d.dot(u), d.dot(v));
+        min = min.min(local);
+        max = max.max(local);
+    }
+    let mid = min.saturating_add(max).saturating_mul_num(Q64::HALF);
+    let center = mean.saturating_add(u.saturating_mul_num(mid.x)).saturating_add(v.saturating_mul_num(mid.y));
+    QObb::new(QPoint::new(center), axis, max.saturating_sub(min).saturating_mul_num(Q64::HALF))
+}
+
+/// Center of the circle through three points, `None` if they are collinear.
+pub(crate) fn get_circumcenter(a: QVec2, b: QVec2, c: QVec2) -> Option<QVec2> {
+    if orient2d(a, b, c) == core::cmp::Ordering::Equal {
+        return None;
+    }
+    let ab = b.saturating_sub(a);
+    let ac = c.saturating_sub(a);
+    let double_cross = ab.cross(ac).saturating_mul(q64!(2));
+    if double_cross == Q64::ZERO {
+        return None;
+    }
+    let (ab_squared, ac_squared) = (ab.length_squared(), ac.length_squared());
+    let offset = QVec2::new(
+        ac.y.saturating_mul(ab_squared).saturating_sub(ab.y.saturating_mul(ac_squared)).saturating_div(double_cross),
+        ab.x.saturating_mul(ac_squared).saturating_sub(ac.x.saturating_mul(ab_squared)).saturating_div(double_cross)
+    );
+    Some(a.saturating_add(offset))
+}
+
+/// Triangle of the Bowyer-Watson triangulation with its circumcircle.
+struct QDelaunayTriangle {
+    vertices: [usize; 3],
+    center: QVec2,
+    radius_squared: Q64,
+}
+
+impl QDelaunayTriangle {
+    fn new(vertices: [usize; 3], positions: &[QVec2]) -> Self {
+        let [a, b, c] = vertices.map(|i| positions[i]);
+        // Rounding can flatten a sliver, it is then replaced by the next point inserted anywhere.
+        let (center, radius_squared) = match get_circumcenter(a, b, c) {
+            Some(center) => (center, center.distance_squared(a)),
+            None => (a, Q64::MAX),
+        };
+        Self { vertices, center, radius_squared }
+    }
+}
+
+/// Delaunay triangulation of a point set by Bowyer-Watson insertion in O(n²).
+///
+/// Points are scaled so their bbox fits the unit square first so the circumcircles don't
+/// overflow, the result is exact only up to that rounding. Repeated points are used once
+/// and collinear point sets give no triangles.
+///
+/// # Returns
+///
+/// Counter-clockwise index triples into `points`.
+pub(crate) fn delaunay_triangulation(points: &[QVec2]) -> Vec<[usize; 3]> {
+    let Some(bbox) = QBbox::from_points(points.iter().map(|p| QPoint::new(*p))) else {
+        return vec![];
+    };
+    let origin = bbox.left_bottom().pos();
+    let scale = bbox.width().max(bbox.height());
+    let n = points.len();
+    let mut positions: Vec<QVec2> = points
+        .iter()
+        .map(|p| {
+            let offset = p.saturating_sub(origin);
+            QVec2::new(offset.x.saturating_div(scale), offset.y.saturating_div(scale))
+        })
+        .collect();
+    // A super triangle far around the scaled bbox, removed at the end.
+    let center = QVec2::new(bbox.width().saturating_div(scale), bbox.height().saturating_div(scale)).saturating_mul_num(Q64::HALF);
+    let reach = q64!(DELAUNAY_SUPER_TRIANGLE_REACH);
+    positions.extend([
+        center.saturating_add(QVec2::new(-reach.saturating_mul(q64!(2)), -reach)),
+        center.saturating_add(QVec2::new(reach.saturating_mul(q64!(2)), -reach)),
+        center.saturating_add(QVec2::new(Q64::ZERO, reach.saturating_mul(q64!(2)))),
+    ]);
+    let mut triangles = vec![QDelaunayTriangle::new([n, n + 1, n + 2], &positions)];
+    for i in 0..n {
+        let p = positions[i];
+        if points[..i].contains(&points[i]) {
+            continue;
+        }
+        let (bad, good): (Vec<QDelaunayTriangle>, Vec<QDelaunayTriangle>) =
+            triangles.into_iter().partition(|t| t.center.distance_squared(p) < t.radius_squared);
+        // Edges used by one bad triangle only bound the cavity, counter-clockwise around it.
+        let mut boundary: Vec<(usize, usize)> = vec![];
+        for [a, b, c] in bad.iter().map(|t| t.vertices) {
+            for edge in [(a, b), (b, c), (c, a)] {
+                match boundary.iter().position(|other| *other == (edge.1, edge.0)) {
+                    Some(k) => { boundary.swap_remove(k); }
+                    None => boundary.push(edge),
+                }
+            }
+        }
+        triangles = good;
+        triangles.extend(boundary.into_iter().map(|(a, b)| QDelaunayTriangle::new([a, b, i], &positions)));
+    }
+    triangles.into_iter().map(|t| t.vertices).filter(|t| t.iter().all(|&i| i < n)).collect()
+}
+
+/// Largest circle centered in `bounds` without any of `points` strictly inside.
+///
+/// The center is one of the Voronoi vertices, where a Voronoi edge leaves the bounds or
+/// a corner of the bounds. Only the center is kept in `bounds`, the circle may reach past them.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::largest_empty_circle;
+///
+/// let points = [qvec2!(0.0, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 6.0), qvec2!(0.0, 6.0), qvec2!(1.0, 3.0)];
+/// let bounds = QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(6.0, 6.0));
+/// let circle = largest_empty_circle(&points, &bounds);
+/// assert!((circle.center().pos() - qvec2!(4.4, 3.0)).length() < q64!(0.001));
+/// assert!((circle.radius() - q64!(3.4)).abs() < q64!(0.001));
+/// ```
+pub fn largest_empty_circle(points: &[QVec2], bounds: &QBbox) -> QCircle {
+    assert!(!points.is_empty(), "[algorithm::largest_empty_circle] points should not be empty.");
+    let (min, max) = (bounds.left_bottom().pos(), bounds.right_top().pos());
+    let triangles = delaunay_triangulation(points);
+    let mut candidates: Vec<QVec2> = vec![min, QVec2::new(max.x, min.y), max, QVec2::new(min.x, max.y)];
+    let mut edges: Vec<(usize, usize)> = triangles
+        .iter()
+        .flat_map(|&[a, b, c]| [(a.min(b), a.max(b)), (b.min(c), b.max(c)), (c.min(a), c.max(a))])
+        .collect();
+    if triangles.is_empty() {
+        // Collinear points, neighbours along the line share the Voronoi edges.
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        order.sort_by(|&a, &b| points[a].x.cmp(&points[b].x).then(points[a].y.cmp(&points[b].y)));
+        edges = order.windows(2).map(|w| (w[0], w[1])).collect();
+    }
+    edges.sort_unstable();
+    edges.dedup();
+    for [a, b, c] in &triangles {
+        candidates.extend(get_circumcenter(points[*a], points[*b], points[*c]));
+    }
+    // Where the bisector of a Delaunay edge crosses a side of the bounds.
+    for (a, b) in edges {
+        let (mid, dir) = (points[a].midpoint(points[b]), points[b].saturating_sub(points[a]));
+        if dir.y != Q64::ZERO {
+            for x in [min.x, max.x] {
+                let y = mid.y.saturating_sub(x.saturating_sub(mid.x).saturating_mul(dir.x).saturating_div(dir.y));
+                candidates.push(QVec2::new(x, y));
+            }
+        }
+        if dir.x != Q64::ZERO {
+            for y in [min.y, max.y] {
+                let x = mid.x.saturating_sub(y.saturating_sub(mid.y).saturating_mul(dir.y).saturating_div(dir.x));
+                candidates.push(QVec2::new(x, y));
+            }
+        }
+    }
+    let tree = QKdTree::new(points.iter().map(|p| QPoint::new(*p)).collect());
+    let (center, radius) = candidates
+        .into_iter()
+        .filter(|c| c.x >= min.x && c.x <= max.x && c.y >= min.y && c.y <= max.y)
+        .map(|c| {
+            let nearest = tree.nearest(&QPoint::new(c)).expect("[algorithm::largest_empty_circle] Points are not empty.");
+            (c, c.distance(points[nearest]))
+        })
+        .fold((min, Q64::NEG_ONE), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+    QCircle::new(QPoint::new(center), radius.max(Q64::EPS))
+}
+
+/// Get the parts of `part` nearer to `seed` than to `other`, cut along their bisector.
+fn get_near_side(part: QPolygon, seed: QVec2, other: QVec2) -> Vec<QPolygon> {
+    let (mid, axis) = (seed.midpoint(other), other.saturating_sub(seed));
+    let side = |pos: QVec2| pos.saturating_sub(mid).dot(axis);
+    let get_extent = |piece: &QPolygon| {
+        piece.points().iter().fold((Q64::ZERO, Q64::ZERO), |(min, max), point| {
+            let value = side(point.pos());
+            (min.min(value), max.max(value))
+        })
+    };
+    let (min, max) = get_extent(&part);
+    if max <= Q64::ZERO {
+        return vec![part];
+    }
+    if min >= Q64::ZERO {
+        return vec![];
+    }
+    // Long enough to cross the whole part wherever the bisector meets it.
+    let bbox = part.get_bbox();
+    let reach = bbox.width().saturating_add(bbox.height()).saturating_add(mid.distance(bbox.get_centroid().pos()));
+    let across = QVec2::new(-axis.y, axis.x).saturating_mul_num(reach.saturating_div(axis.length()));
+    let bisector = QPolyline::new_from_parts(vec![mid.saturating_sub(across), mid.saturating_add(across)]);
+    // Cut points are rounded off the bisector, so a piece is on the side it reaches farthest into.
+    part.slice(&bisector)
+        .into_iter()
+        .filter(|piece| {
+            let (min, max) = get_extent(piece);
+            -min > max
+        })
+        .collect()
+}
+
+/// Break a polygon into the Voronoi cells of `seeds`, clipped to the polygon.
+///
+/// Every cell is cut out of the polygon with `QPolygon::slice` along the bisectors with
+/// the other seeds, so on a concave polygon a cell may come out in several parts. Seeds
+/// outside the polygon still get the parts nearest to them and repeated seeds are skipped.
+///
+/// # Arguments
+///
+/// * `polygon` - Polygon to break
+/// * `seeds` - Centers of the cells
+///
+/// # Returns
+///
+/// Counter-clockwise parts in the order of their seeds, the polygon itself without seeds
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::fracture;
+///
+/// let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 4.0), qvec2!(0.0, 4.0)]);
+/// let parts = fracture(&square, &[qvec2!(1.0, 2.0), qvec2!(3.0, 2.0), qvec2!(3.0, 2.0)]);
+/// assert_eq!(parts.len(), 2);
+/// assert_eq!(parts[0].area(), q64!(8));
+/// assert!(parts[0].is_point_inside(&QPoint::new(qvec2!(1.0, 1.0))));
+/// assert!(parts[1].is_point_inside(&QPoint::new(qvec2!(3.0, 3.0))));
+/// ```
+pub fn fracture(polygon: &QPolygon, seeds: &[QVec2]) -> Vec<QPolygon> {
+    if seeds.is_empty() || polygon.points().len() < 3 {
+        return vec![polygon.clone()];
+    }
+    let mut ring: Vec<QVec2> = polygon.points().iter().map(|point| point.pos()).collect();
+    if !polygon.is_ccw() {
+        ring.reverse();
+    }
+    let whole = QPolygon::new_from_parts(ring);
+    let mut rst = vec![];
+    for (i, &seed) in seeds.iter().enumerate() {
+        if seeds[..i].contains(&seed) {
+            continue;
+        }
+        let mut cell = vec![whole.clone()];
+        for &other in seeds.iter().filter(|other| **other != seed) {
+            cell = cell.into_iter().flat_map(|part| get_near_side(part, seed, other)).collect();
+        }
+        rst.extend(cell);
+    }
+    rst
+}
+
+/// Stopping rules of `icp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QIcpOptions {
+    max_iterations: usize,
+    max_distance: Q64,
+    min_improvement: Q64,
+}
+
+impl QIcpOptions {
+    /// 30 iterations, every pair matched, stop once the error improves by less than `Q64::EPS`.
+    pub const DEFAULT: Self = Self::new(30, Q64::MAX, Q64::EPS);
+
+    pub const fn new(max_iterations: usize, max_distance: Q64, min_improvement: Q64) -> Self {
+        Self {
+            max_iterations,
+            max_distance,
+            min_improvement,
+        }
+    }
+
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+
+    /// Source points farther than this from their nearest target point are left unmatched.
+    pub fn max_distance(&self) -> Q64 {
+        self.max_distance
+    }
+
+    /// Stop once the mean squared distance of the matches drops by less than this.
+    pub fn min_improvement(&self) -> Q64 {
+        self.min_improvement
+    }
+
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    pub fn set_max_distance(&mut self, max_distance: Q64) {
+        self.max_distance = max_distance;
+    }
+
+    pub fn set_min_improvement(&mut self, min_improvement: Q64) {
+        self.min_improvement = min_improvement;
+    }
+}
+
+impl Default for QIcpOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Rotation and translation moving the first point of every pair onto the second with the
+/// least squared error, the 2D Kabsch solution. Identity without pairs.
+pub fn estimate_rigid(pairs: &[(QVec2, QVec2)]) -> QTransform {
+    get_best_fit(pairs, false)
+}
+
+/// Uniform scale, rotation and translation moving the first point of every pair onto the
+/// second with the least squared error, the 2D Umeyama solution. Identity without pairs.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::estimate_similarity;
+///
+/// let pairs = [
+///     (qvec2!(0.0, 0.0), qvec2!(1.0, 1.0)),
+///     (qvec2!(1.0, 0.0), qvec2!(1.0, 3.0)),
+///     (qvec2!(0.0, 1.0), qvec2!(-1.0, 1.0)),
+/// ];
+/// let transform = estimate_similarity(&pairs);
+/// let moved = transform.transform_point(&QPoint::new(qvec2!(1.0, 1.0))).pos();
+/// assert!((moved - qvec2!(-1.0, 3.0)).length() < q64!(0.001));
+/// assert!((transform.determinant() - q64!(4)).abs() < q64!(0.001));
+/// ```
+pub fn estimate_similarity(pairs: &[(QVec2, QVec2)]) -> QTransform {
+    get_best_fit(pairs, true)
+}
+
+fn get_best_fit(pairs: &[(QVec2, QVec2)], with_scale: bool) -> QTransform {
+    if pairs.is_empty() {
+        return QTransform::IDENTITY;
+    }
+    let count = q64!(pairs.len() as i64);
+    let (source_origin, target_origin) = pairs[0];
+    let (source_sum, target_sum) = pairs.iter().fold((QVec2::ZERO, QVec2::ZERO), |(s, t), (source, target)| {
+        (s.saturating_add(source.saturating_sub(source_origin)), t.saturating_add(target.saturating_sub(target_origin)))
+    });
+    let source_mean = source_origin.saturating_add(QVec2::new(source_sum.x.saturating_div(count), source_sum.y.saturating_div(count)));
+    let target_mean = target_origin.saturating_add(QVec2::new(target_sum.x.saturating_div(count), target_sum.y.saturating_div(count)));
+    let (mut dot, mut cross, mut spread) = (Q64::ZERO, Q64::ZERO, Q64::ZERO);
+    for (source, target) in pairs {
+        let (s, t) = (source.saturating_sub(source_mean), target.saturating_sub(target_mean));
+        dot = dot.saturating_add(s.dot(t));
+        cross = cross.saturating_add(s.cross(t));
+        spread = spread.saturating_add(s.length_squared());
+    }
+    // The best angle has its cosine and sine along (dot, cross), the best scale is its length over the spread.
+    let length = QVec2::new(dot, cross).length();
+    let (cos, sin) = if length == Q64::ZERO { (Q64::ONE, Q64::ZERO) } else { (dot.saturating_div(length), cross.saturating_div(length)) };
+    let scale = if with_scale && spread > Q64::ZERO { length.saturating_div(spread) } else { Q64::ONE };
+    let (cos, sin) = (cos.saturating_mul(scale), sin.saturating_mul(scale));
+    let linear = QTransform::new(QVec2::new(cos, sin), QVec2::new(-sin, cos), QVec2::ZERO);
+    QTransform::from_translation(target_mean.saturating_sub(linear.transform_vector(source_mean))) * linear
+}
+
+/// Iterative closest point registration of two point sets.
+///
+/// Every round matches each moved source point with its nearest target point and
+/// solves the best rigid fit of those matches, so the start must be roughly aligned.
+///
+/// # Returns
+///
+/// The rotation and translation moving `source` onto `target`.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::{ icp, QIcpOptions };
+///
+/// let target: Vec<QVec2> = (0..20).map(|i| qvec2!(i, (i * i) % 7)).collect();
+/// let offset = QTransform::from_translation(qvec2!(0.3, -0.2));
+/// let source: Vec<QVec2> = target.iter().map(|p| offset.transform_point(&QPoint::new(*p)).pos()).collect();
+/// let transform = icp(&source, &target, QIcpOptions::default());
+/// let moved = transform.transform_point(&QPoint::new(source[5])).pos();
+/// assert!((moved - target[5]).length() < q64!(0.001));
+/// ```
+pub fn icp(source: &[QVec2], target: &[QVec2], options: QIcpOptions) -> QTransform {
+    let mut rst = QTransform::IDENTITY;
+    if source.is_empty() || target.is_empty() {
+        return rst;
+    }
+    let tree = QKdTree::new(target.iter().map(|p| QPoint::new(*p)).collect());
+    let max_distance_squared = options.max_distance.saturating_mul(options.max_distance);
+    let mut last_error = Q64::MAX;
+    for _ in 0..options.max_iterations {
+        let pairs: Vec<(QVec2, QVec2)> = source
+            .iter()
+            .filter_map(|p| {
+                let moved = rst.transform_point(&QPoint::new(*p)).pos();
+                let nearest = target[tree.nearest(&QPoint::new(moved))?];
+                (moved.distance_squared(nearest) <= max_distance_squared).then_some((moved, nearest))
+            })
+            .collect();
+        if pairs.is_empty() {
+            break;
+        }
+        let error = pairs
+            .iter()
+            .fold(Q64::ZERO, |acc, (moved, nearest)| acc.saturating_add(moved.distance_squared(*nearest)))
+            .saturating_div(q64!(pairs.len() as i64));
+        if last_error.saturating_sub(error) < options.min_improvement {
+            break;
+        }
+        last_error = error;
+        rst = rst.then(&estimate_rigid(&pairs));
+    }
+    rst
+}
+
+/// Find the two points closest to each other in O(n log n) by divide and conquer.
+///
+/// # Returns
+///
+/// The indices of the pair, smaller first, and their distance. Duplicated points give a
+/// distance of zero.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::algorithm::closest_pair;
+///
+/// let points = [qvec2!(0.0, 0.0), qvec2!(5.0, 4.0), qvec2!(9.0, 1.0), qvec2!(3.0, 8.0), qvec2!(5.5, 4.0)];
+/// assert_eq!(closest_pair(&points), (1, 4, q64!(0.5)));
+/// ```
+pub fn closest_pair(points: &[QVec2]) -> (usize, usize, Q64) {
+    assert!(points.len() >= 2, "[algorithm::closest_pair] points({}) should have at least two entries.", points.len());
+    let mut sorted: Vec<(QVec2, usize)> = points.iter().copied().zip(0..).collect();
+    sorted.sort_by(|a, b| a.0.x.cmp(&b.0.x).then(a.0.y.cmp(&b.0.y)).then(a.1.cmp(&b.1)));
+    let mut best = (Q64::MAX, 0, 0);
+    get_closest_pair_in(&mut sorted, &mut best);
+    let (_, i, j) = best;
+    (i, j, points[i].distance(points[j]))
+}
+
+/// Update `best` with the closest pair of `points` sorted by x, leaving them sorted by y.
+fn get_closest_pair_in(points: &mut [(QVec2, usize)], best: &mut (Q64, usize, usize)) {
+    let consider = |a: (QVec2, usize), b: (QVec2, usize), best: &mut (Q64, usize, usize)| {
+        let candidate = (a.0.distance_squared(b.0), a.1.min(b.1), a.1.max(b.1));
+        if candidate < *best {
+            *best = candidate;
+        }
+    };
+    if points.len() <= 3 {
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                consider(points[i], points[j], best);
+            }
+        }
+        points.sort_by(|a, b| a.0.y.cmp(&b.0.y));
+        return;
+    }
+    let mid = points.len() / 2;
+    let mid_x = points[mid].0.x;
+    let (left, right) = points.split_at_mut(mid);
+    get_closest_pair_in(left, best);
+    get_closest_pair_in(right, best);
+
+    let mut merged = Vec::with_capacity(points.len());
+    let (mut i, mut j) = (0, mid);
+    while i < mid || j < points.len() {
+        if j == points.len() || (i < mid && points[i].0.y <= points[j].0.y) {
+            merged.push(points[i]);
+            i += 1;
+        } else {
+            merged.push(points[j]);
+            j += 1;
+        }
+    }
+    points.copy_from_slice(&merged);
+
+    let strip: Vec<(QVec2, usize)> = points
+        .iter()
+        .copied()
+        .filter(|p| {
+            let dx = p.0.x.saturating_sub(mid_x);
+            dx.saturating_mul(dx) < best.0
+        })
+        .collect();
+    for i in 0..strip.len() {
+        for j in i + 1..strip.len() {
+            let dy = strip[j].0.y.saturating_sub(strip[i].0.y);
+            if dy.saturating_mul(dy) >= best.0 {
+                break;
+            }
+            consider(strip[i], strip[j], best);
+        }
+    }
+}
+
+/// Get the shadow cast by an occluder, one polygon behind every edge facing the light.
+///
+/// Each polygon runs from the part of its edge within `range` of the light away from the
+/// light until `range`. The far side follows the circle of `range` with tangent segments at
+/// least every `1 / SAMPLE_RESOLUTION` turn, so its vertices lie slightly outside the circle.
+/// Together the polygons cover every point within `range` whose view of the light is
+/// blocked, the lit side of the occluder excluded. A light inside or on the occluder casts
+/// nothing.
+///
+/// # Arguments
+///
+/// * `light` - Position of the point light
+/// * `occluder` - Polygon blocking the light, of either winding
+/// * `range` - How far from the light the shadows reach
+///
+/// # Returns
+///
+/// Counter-clockwise polygons, in the order of the occluder edges
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::shadow_polygons;
+///
+/// let wall = QPolygon::new_from_parts(vec![qvec2!(2.0, -1.0), qvec2!(3.0, -1.0), qvec2!(3.0, 1.0), qvec2!(2.0, 1.0)]);
+/// let shadows = shadow_polygons(QPoint::ZERO, &wall, q64!(10));
+/// assert_eq!(shadows.len(), 1);
+/// assert!(shadows[0].is_ccw());
+/// assert!(shadows[0].is_point_inside(&QPoint::new(qvec2!(6.0, 2.0))));
+/// assert!(shadows[0].is_point_inside(&QPoint::new(qvec2!(9.99, 0.0))));
+/// assert!(!shadows[0].is_point_inside(&QPoint::new(qvec2!(6.0, 4.0))));
+/// assert!(!shadows[0].is_point_inside(&QPoint::new(qvec2!(1.0, 0.0))));
+/// ```
+pub fn shadow_polygons(light: QPoint, occluder: &QPolygon, range: Q64) -> Vec<QPolygon> {
+    if occluder.points().len() < 3 || occluder.is_point_inside(&light) {
+        return vec![];
+    }
+    let is_ccw = occluder.is_ccw();
+    let origin = light.pos();
+    let range_squared = range.saturating_mul(range);
+    let get_angle = |pos: QVec2| atan2(pos.y.saturating_sub(origin.y), pos.x.saturating_sub(origin.x));
+    let get_on_range = |angle: Q64, distance: Q64| {
+        let (sin, cos) = angle.sin_cos();
+        origin.saturating_add(QVec2::new(cos, sin).saturating_mul_num(distance))
+    };
+    occluder
+        .edges_iter()
+        .map(|edge| if is_ccw { edge } else { QLine::new(edge.end(), edge.start()) })
+        .filter(|edge| orient2d(edge.start().pos(), edge.end().pos(), origin) == core::cmp::Ordering::Less)
+        .filter_map(|edge| {
+            // Keep the part of the edge inside the circle, `|a + t * d - origin| = range` at its ends.
+            let (a, d) = (edge.start().pos(), edge.vector());
+            let f = a.saturating_sub(origin);
+            let (dd, fd) = (d.length_squared(), f.dot(d));
+            let discriminant = fd.saturating_mul(fd).saturating_sub(dd.saturating_mul(f.length_squared().saturating_sub(range_squared)));
+            if discriminant <= Q64::ZERO {
+                return None;
+            }
+            let root = sqrt(discriminant);
+            let t_start = Q64::ZERO.max((-fd).saturating_sub(root).saturating_div(dd));
+            let t_end = Q64::ONE.min((-fd).saturating_add(root).saturating_div(dd));
+            if t_start >= t_end {
+                return None;
+            }
+            let near_a = if t_start == Q64::ZERO { a } else { edge.point_at(t_start).pos() };
+            let near_b = if t_end == Q64::ONE { edge.end().pos() } else { edge.point_at(t_end).pos() };
+
+            // The far side runs counter-clockwise around the light from behind `b` to behind `a`.
+            let (angle_b, mut span) = (get_angle(near_b), get_angle(near_a).saturating_sub(get_angle(near_b)));
+            if span < Q64::ZERO {
+                span = span.saturating_add(Q64::TAU);
+            }
+            let steps = span.saturating_mul(q64!(SAMPLE_RESOLUTION as i64)).saturating_div(Q64::TAU).ceil().to_num::<i64>().max(1);
+            let step = span.saturating_div(q64!(steps));
+            // Adjacent tangents of the circle meet `range / cos(step / 2)` from the light.
+            let corner_distance = range.saturating_div(step.saturating_mul(Q64::HALF).cos());
+            // Ends cut by the circle are already on it.
+            let mut rst = vec![near_a, near_b];
+            if t_end == Q64::ONE {
+                rst.push(get_on_range(angle_b, range));
+            }
+            for k in 0..steps {
+                let angle = angle_b.saturating_add(step.saturating_mul(q64!(k)).saturating_add(step.saturating_mul(Q64::HALF)));
+                rst.push(get_on_range(angle, corner_distance));
+            }
+            if t_start == Q64::ZERO {
+                rst.push(get_on_range(angle_b.saturating_add(span), range));
+            }
+            Some(QPolygon::new_from_parts(rst))
+        })
+        .collect()
+}
+
+/// Rotate `v` counter-clockwise by the angle with the given sine and cosine.
+fn get_rotated(v: QVec2, sin: Q64, cos: Q64) -> QVec2 {
+    QVec2::new(
+        v.x.saturating_mul(cos).saturating_sub(v.y.saturating_mul(sin)),
+        v.x.saturating_mul(sin).saturating_add(v.y.saturating_mul(cos)),
+    )
+}
+
+/// Get the region visible from `origin` inside a view cone, occluder edges block sight.
+///
+/// Rays are cast towards every occluder vertex in the cone, slightly to both sides of it,
+/// to where edges leave the view radius and along the cone's arc at least every 1/64 turn,
+/// so the result is exact up to the arc, which is replaced by chords. Occluders are walls,
+/// an origin inside one sees its inside.
+///
+/// # Arguments
+///
+/// * `origin` - Position of the viewer
+/// * `dir` - Direction the viewer faces, the middle of the cone
+/// * `angle` - Full opening of the cone, a full turn or more sees all around
+/// * `radius` - How far the viewer sees
+/// * `occluders` - Polygons blocking the view
+///
+/// # Returns
+///
+/// A counter-clockwise polygon, starting at `origin` unless the cone is a full turn
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qmath::dir::QDir;
+/// use qgeometry::prelude::*;
+/// use qgeometry::algorithm::field_of_view;
+///
+/// let crate_box = QPolygon::new_from_parts(vec![qvec2!(4.0, -1.0), qvec2!(5.0, -1.0), qvec2!(5.0, 1.0), qvec2!(4.0, 1.0)]);
+/// let view = field_of_view(QPoint::ZERO, QDir::new_from_vec(QVec2::X), Q64::PI * Q64::HALF, q64!(10), &[crate_box]);
+/// assert_eq!(view.points()[0], QPoint::ZERO);
+/// assert!(view.is_point_inside(&QPoint::new(qvec2!(3.0, 0.0))));
+/// assert!(view.is_point_inside(&QPoint::new(qvec2!(8.0, 3.0))));
+/// assert!(!view.is_point_inside(&QPoint::new(qvec2!(8.0, 1.0))));
+/// assert!(!view.is_point_inside(&QPoint::new(qvec2!(0.0, 8.0))));
+/// assert!(!view.is_point_inside(&QPoint::new(qvec2!(9.0, 5.0))));
+/// ```
+pub fn field_of_view(origin: QPoint, dir: QDir, angle: Q64, radius: Q64, occluders: &[QPolygon]) -> QPolygon {
+    assert!(radius > Q64::ZERO, "[algorithm::field_of_view] radius({radius}) should be positive.");
+    assert!(angle > Q64::ZERO, "[algorithm::field_of_view] angle({angle}) should be positive.");
+    let is_full_turn = angle >= Q64::TAU;
+    let angle = angle.min(Q64::TAU);
+    let (sin, cos) = angle.saturating_mul(Q64::HALF).sin_cos();
+    let start = get_rotated(dir.to_vec(), -sin, cos);
+    let end = get_rotated(dir.to_vec(), sin, cos);
+    let is_in_cone = |v: QVec2| {
+        if is_full_turn {
+            true
+        } else if angle <= Q64::PI {
+            start.cross(v) >= Q64::ZERO && v.cross(end) >= Q64::ZERO
+        } else {
+            !(end.cross(v) > Q64::ZERO && v.cross(start) > Q64::ZERO)
+        }
+    };
+    // Work around the origin so products stay small.
+    let center = origin.pos();
+    let edges: Vec<(QVec2, QVec2)> = occluders
+        .iter()
+        .flat_map(|occluder| occluder.edges_iter())
+        .map(|edge| (edge.start().pos().saturating_sub(center), edge.end().pos().saturating_sub(center)))
+        .collect();
+
+    // Corners hide what lies right behind them, so rays also pass slightly beside each one.
+    let (side_sin, side_cos) = q64!(0.0001).sin_cos();
+    let mut targets: Vec<QVec2> = vec![];
+    let mut add_corner = |v: QVec2| {
+        targets.extend([v, get_rotated(v, side_sin, side_cos), get_rotated(v, -side_sin, side_cos)]);
+    };
+    let radius_squared = radius.saturating_mul(radius);
+    for &(a, b) in &edges {
+        if a != QVec2::ZERO && a.length_squared() < radius_squared {
+            add_corner(a);
+        }
+        let e = b.saturating_sub(a);
+        let (qa, qb, qc) = (e.length_squared(), a.dot(e), a.length_squared().saturating_sub(radius_squared));
+        let discriminant = qb.saturating_mul(qb).saturating_sub(qa.saturating_mul(qc));
+        if qa > Q64::ZERO && discriminant >= Q64::ZERO {
+            let root = sqrt(discriminant);
+            for s in [(-qb).saturating_sub(root), (-qb).saturating_add(root)] {
+                let s = s.saturating_div(qa);
+                if Q64::ZERO <= s && s <= Q64::ONE {
+                    add_corner(a.saturating_add(e.saturating_mul_num(s)));
+                }
+            }
+        }
+    }
+    let steps = angle.saturating_mul(q64!(64)).saturating_div(Q64::TAU).ceil().to_num::<i64>().max(1);
+    let (step_sin, step_cos) = angle.saturating_div(q64!(steps)).sin_cos();
+    let mut arc = start;
+    for _ in 0..=steps {
+        targets.push(arc);
+        arc = get_rotated(arc, step_sin, step_cos);
+    }
+    if !is_full_turn {
+        targets.push(end);
+    }
+
+    let mut directions: Vec<QVec2> = targets
+        .into_iter()
+        .filter(|v| *v != QVec2::ZERO)
+        .map(|v| QDir::new_from_vec(v).to_vec())
+        .filter(|v| is_in_cone(*v))
+        .collect();
+    // Sort counter-clockwise from the start of the cone.
+    let is_past_half_turn = |v: QVec2| {
+        let cross = start.cross(v);
+        cross < Q64::ZERO || (cross == Q64::ZERO && start.dot(v) < Q64::ZERO)
+    };
+    directions.sort_by(|a, b| is_past_half_turn(*a).cmp(&is_past_half_turn(*b)).then_with(|| Q64::ZERO.cmp(&a.cross(*b))));
+    directions.dedup_by(|a, b| a.cross(*b) == Q64::ZERO && a.dot(*b) > Q64::ZERO);
+
+    let mut points: Vec<QPoint> = if is_full_turn { vec![] } else { vec![origin] };
+    for direction in directions {
+        let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(direction));
+        let t = edges
+            .iter()
+            .filter_map(|(a, b)| ray.intersect_segment(&QLine::new_from_parts(*a, *b)))
+            .fold(radius, |acc, t| acc.min(t));
+        let point = QPoint::new(center.saturating_add(direction.saturating_mul_num(t)));
+        if points.last() != Some(&point) {
+            points.push(point);
+        }
+    }
+    QPolygon::new(points)
+}
+
+/// Check many pairs of shapes for collision.
+///
+/// Runs on the rayon thread pool when the `parallel` feature is enabled.
+///
+/// # Arguments
+///
+/// * `shapes` - All shapes
+/// * `pairs` - Index pairs into `shapes`
+///
+/// # Returns
+///
+/// Whether each pair collides, in the order of `pairs`
+pub fn collide_pairs(shapes: &[QShape], pairs: &[(usize, usize)]) -> Vec<bool> {
+    let collide = |&(i, j): &(usize, usize)| shapes[i].is_collide(&shapes[j]);
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        pairs.par_iter().map(collide).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        pairs.iter().map(collide).collect()
+    }
+}
+
+/// Find every colliding pair among `shapes`.
+///
+/// Pairs whose bboxes don't overlap are skipped before the narrowphase.
+/// Runs on the rayon thread pool when the `parallel` feature is enabled.
+///
+/// # Returns
+///
+/// Colliding index pairs `(i, j)` with `i < j`, sorted
+pub fn collide_all_pairs(shapes: &[QShape]) -> Vec<(usize, usize)> {
+    let bboxes: Vec<QBbox> = shapes.iter().map(|shape| shape.get_bbox()).collect();
+    let pairs_of = |i: usize| -> Vec<(usize, usize)> {
+        ((i + 1)..shapes.len())
+            .filter(|&j| bboxes[i].is_overlap(&bboxes[j]) && shapes[i].is_collide(&shapes[j]))
+            .map(|j| (i, j))
+            .collect()
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        (0..shapes.len()).into_par_iter().flat_map_iter(pairs_of).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (0..shapes.len()).flat_map(pairs_of).collect()
+    }
+}
@@ -0,0 +1,177 @@
+//! Random shapes for property based tests.
+//!
+//! The `arbitrary` feature implements `arbitrary::Arbitrary` for the basic shapes and the
+//! `proptest` feature adds the strategies in `strategy`. Both only produce valid shapes:
+//! lines have distinct end points, circles and bboxes a positive size, and polygons are
+//! simple, counter-clockwise and star shaped around their vertex mean.
+//!
+//! Coordinates are multiples of 1/256 in [-1024, 1024], so shrinking a raw integer moves
+//! the shape towards the origin and every coordinate is exact in `Q64`.
+
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::compare_edge_angle;
+use crate::shape::QPolygon;
+
+/// Largest absolute raw coordinate, 1024 in shape units.
+pub const COORDINATE_RANGE: i32 = 1 << 18;
+
+/// Map a raw integer in `-COORDINATE_RANGE..=COORDINATE_RANGE` to a coordinate.
+pub fn coordinate_from_raw(raw: i32) -> Q64 {
+    q64!(raw.clamp(-COORDINATE_RANGE, COORDINATE_RANGE)).saturating_div(q64!(256))
+}
+
+fn pos_from_raw(raw: (i32, i32)) -> QVec2 {
+    QVec2::new(coordinate_from_raw(raw.0), coordinate_from_raw(raw.1))
+}
+
+/// Build a simple polygon from arbitrary points by sorting them by angle around their mean.
+///
+/// Points in the same direction keep only the farthest one. Return `None` if fewer than
+/// three directions remain or the ring isn't strictly star shaped around the mean.
+pub fn simple_polygon_from_points(points: &[QVec2]) -> Option<QPolygon> {
+    if points.len() < 3 {
+        return None;
+    }
+    let count = q64!(points.len() as i64);
+    let sum = points.iter().fold(QVec2::ZERO, |acc, p| acc.saturating_add(*p));
+    let center = QVec2::new(sum.x.saturating_div(count), sum.y.saturating_div(count));
+
+    let mut offsets: Vec<QVec2> = points.iter().map(|p| p.saturating_sub(center)).filter(|v| *v != QVec2::ZERO).collect();
+    offsets.sort_by(|a, b| compare_edge_angle(*a, *b).then_with(|| b.length_squared().partial_cmp(&a.length_squared()).unwrap_or(core::cmp::Ordering::Equal)));
+    offsets.dedup_by(|b, a| compare_edge_angle(*a, *b) == core::cmp::Ordering::Equal);
+
+    let n = offsets.len();
+    if n < 3 || (0..n).any(|i| offsets[i].cross(offsets[(i + 1) % n]) <= Q64::ZERO) {
+        return None;
+    }
+    Some(QPolygon::new_from_parts(offsets.into_iter().map(|v| v.saturating_add(center)).collect()))
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls {
+    use alloc::{ vec, vec::Vec };
+    use arbitrary::{ Arbitrary, Result, Unstructured };
+    use qmath::prelude::*;
+    use qmath::vec2::QVec2;
+    use crate::shape::{ QPoint, QLine, QBbox, QCircle, QPolygon, QPolyline };
+    use super::{ pos_from_raw, coordinate_from_raw, simple_polygon_from_points, COORDINATE_RANGE };
+
+    fn raw_pos(u: &mut Unstructured<'_>) -> Result<(i32, i32)> {
+        Ok((u.int_in_range(-COORDINATE_RANGE..=COORDINATE_RANGE)?, u.int_in_range(-COORDINATE_RANGE..=COORDINATE_RANGE)?))
+    }
+
+    fn positive_size(u: &mut Unstructured<'_>) -> Result<Q64> {
+        Ok(coordinate_from_raw(u.int_in_range(1..=COORDINATE_RANGE)?))
+    }
+
+    impl<'a> Arbitrary<'a> for QPoint {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(QPoint::new(pos_from_raw(raw_pos(u)?)))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for QLine {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let start = raw_pos(u)?;
+            let mut end = raw_pos(u)?;
+            if start == end {
+                end.0 = if end.0 < COORDINATE_RANGE { end.0 + 1 } else { end.0 - 1 };
+            }
+            Ok(QLine::new_from_parts(pos_from_raw(start), pos_from_raw(end)))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for QBbox {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let left_bottom = pos_from_raw(raw_pos(u)?);
+            let size = QVec2::new(positive_size(u)?, positive_size(u)?);
+            Ok(QBbox::new_from_parts(left_bottom, left_bottom.saturating_add(size)))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for QCircle {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let center = QPoint::arbitrary(u)?;
+            Ok(QCircle::new(center, positive_size(u)?))
+        }
+    }
+
+    /// Falls back to a small triangle when the drawn points don't form a polygon.
+    impl<'a> Arbitrary<'a> for QPolygon {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let count = u.int_in_range(3..=16)?;
+            let points = (0..count).map(|_| raw_pos(u).map(pos_from_raw)).collect::<Result<Vec<_>>>()?;
+            Ok(simple_polygon_from_points(&points).unwrap_or_else(|| {
+                let origin = points[0];
+                QPolygon::new_from_parts(vec![origin, origin.saturating_add(QVec2::X), origin.saturating_add(QVec2::Y)])
+            }))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for QPolyline {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let count = u.int_in_range(2..=16)?;
+            let points = (0..count).map(|_| raw_pos(u).map(pos_from_raw)).collect::<Result<Vec<_>>>()?;
+            Ok(QPolyline::new_from_parts(points))
+        }
+    }
+}
+
+/// Shrinkable `proptest` strategies producing valid shapes.
+#[cfg(feature = "proptest")]
+pub mod strategy {
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+    use qmath::prelude::*;
+    use qmath::vec2::QVec2;
+    use crate::shape::{ QPoint, QLine, QBbox, QCircle, QPolygon, QPolyline };
+    use super::{ pos_from_raw, coordinate_from_raw, simple_polygon_from_points, COORDINATE_RANGE };
+
+    fn raw_pos() -> impl Strategy<Value = (i32, i32)> {
+        (-COORDINATE_RANGE..=COORDINATE_RANGE, -COORDINATE_RANGE..=COORDINATE_RANGE)
+    }
+
+    fn positive_size() -> impl Strategy<Value = Q64> {
+        (1..=COORDINATE_RANGE).prop_map(coordinate_from_raw)
+    }
+
+    pub fn pos() -> impl Strategy<Value = QVec2> {
+        raw_pos().prop_map(pos_from_raw)
+    }
+
+    pub fn point() -> impl Strategy<Value = QPoint> {
+        pos().prop_map(QPoint::new)
+    }
+
+    /// Segments with distinct end points.
+    pub fn line() -> impl Strategy<Value = QLine> {
+        (pos(), pos())
+            .prop_filter("degenerate line", |(start, end)| start != end)
+            .prop_map(|(start, end)| QLine::new_from_parts(start, end))
+    }
+
+    pub fn bbox() -> impl Strategy<Value = QBbox> {
+        (pos(), positive_size(), positive_size())
+            .prop_map(|(left_bottom, width, height)| QBbox::new_from_parts(left_bottom, left_bottom.saturating_add(QVec2::new(width, height))))
+    }
+
+    /// Circles with a positive radius.
+    pub fn circle() -> impl Strategy<Value = QCircle> {
+        (point(), positive_size()).prop_map(|(center, radius)| QCircle::new(center, radius))
+    }
+
+    /// Simple counter-clockwise polygons with 3 to `max_vertices` vertices.
+    pub fn polygon(max_vertices: usize) -> impl Strategy<Value = QPolygon> {
+        assert!(max_vertices >= 3, "[strategy::polygon] max_vertices({max_vertices}) should be at least 3.");
+        prop::collection::vec(pos(), 3..=max_vertices)
+            .prop_filter_map("degenerate polygon", |points: Vec<QVec2>| simple_polygon_from_points(&points))
+    }
+
+    /// Open chains with 2 to `max_vertices` vertices.
+    pub fn polyline(max_vertices: usize) -> impl Strategy<Value = QPolyline> {
+        assert!(max_vertices >= 2, "[strategy::polyline] max_vertices({max_vertices}) should be at least 2.");
+        prop::collection::vec(pos(), 2..=max_vertices).prop_map(QPolyline::new_from_parts)
+    }
+}
@@ -0,0 +1,108 @@
+//! Clustering of point sets.
+//!
+//! Everything is fixed-point and ties are broken by the smaller index, so the same input
+//! and seed give the same clusters on every platform.
+
+use alloc::{ vec, vec::Vec };
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::generate::QRng;
+use crate::shape::QPoint;
+
+/// Index of the centroid nearest to `pos`, the smaller index on ties.
+fn get_nearest_centroid(centroids: &[QPoint], pos: QVec2) -> usize {
+    (0..centroids.len())
+        .min_by_key(|&i| (centroids[i].pos().distance_squared(pos), i))
+        .expect("[cluster::get_nearest_centroid] centroids should not be empty.")
+}
+
+/// Pick `k` distinct starting centroids with k-means++, farther points are more likely.
+fn get_initial_centroids(points: &[QPoint], k: usize, rng: &mut impl QRng) -> Vec<QPoint> {
+    let mut rst = vec![points[rng.next_index(points.len())]];
+    let mut distances: Vec<Q64> = points.iter().map(|point| point.pos().distance_squared(rst[0].pos())).collect();
+    while rst.len() < k {
+        let total = distances.iter().fold(Q64::ZERO, |acc, distance| acc.saturating_add(*distance));
+        if total == Q64::ZERO {
+            break;
+        }
+        let target = total.saturating_mul(rng.next_unit());
+        let mut acc = Q64::ZERO;
+        let mut chosen = distances.iter().rposition(|distance| *distance > Q64::ZERO).unwrap_or(0);
+        for (i, distance) in distances.iter().enumerate() {
+            acc = acc.saturating_add(*distance);
+            if *distance > Q64::ZERO && acc > target {
+                chosen = i;
+                break;
+            }
+        }
+        let centroid = points[chosen];
+        rst.push(centroid);
+        for (distance, point) in distances.iter_mut().zip(points) {
+            *distance = (*distance).min(point.pos().distance_squared(centroid.pos()));
+        }
+    }
+    rst
+}
+
+/// Lloyd's k-means with k-means++ seeding.
+///
+/// # Arguments
+///
+/// * `points` - Points to cluster
+/// * `k` - Number of clusters, fewer are returned if there are fewer distinct points
+/// * `max_iters` - Maximum number of assign and update rounds
+/// * `rng` - Source of randomness for the seeding
+///
+/// # Returns
+///
+/// The cluster index of every point and the centroid of every cluster. A cluster that
+/// loses all its points keeps its last centroid.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::cluster::kmeans;
+/// use qgeometry::generate::QSplitMix64;
+///
+/// let points: Vec<QPoint> = [qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(10.0, 10.0), qvec2!(11.0, 10.0)]
+///     .into_iter()
+///     .map(QPoint::new)
+///     .collect();
+/// let (assignments, centroids) = kmeans(&points, 2, 10, &mut QSplitMix64::new(7));
+/// assert_eq!(assignments[0], assignments[1]);
+/// assert_eq!(assignments[2], assignments[3]);
+/// assert_ne!(assignments[0], assignments[2]);
+/// assert_eq!(centroids[assignments[0]].pos(), qvec2!(0.5, 0.0));
+/// ```
+pub fn kmeans(points: &[QPoint], k: usize, max_iters: usize, rng: &mut impl QRng) -> (Vec<usize>, Vec<QPoint>) {
+    assert!(k > 0, "[cluster::kmeans] k should be larger than zero.");
+    if points.is_empty() {
+        return (vec![], vec![]);
+    }
+    let mut centroids = get_initial_centroids(points, k, rng);
+    let mut assignments: Vec<usize> = points.iter().map(|point| get_nearest_centroid(&centroids, point.pos())).collect();
+    for _ in 0..max_iters {
+        // Average the offsets from the old centroid so the sums stay small.
+        let mut sums = vec![QVec2::ZERO; centroids.len()];
+        let mut counts = vec![0i64; centroids.len()];
+        for (point, &cluster) in points.iter().zip(&assignments) {
+            sums[cluster] = sums[cluster].saturating_add(point.pos().saturating_sub(centroids[cluster].pos()));
+            counts[cluster] += 1;
+        }
+        for (cluster, centroid) in centroids.iter_mut().enumerate() {
+            if counts[cluster] > 0 {
+                let count = q64!(counts[cluster]);
+                let offset = QVec2::new(sums[cluster].x.saturating_div(count), sums[cluster].y.saturating_div(count));
+                *centroid = QPoint::new(centroid.pos().saturating_add(offset));
+            }
+        }
+        let next: Vec<usize> = points.iter().map(|point| get_nearest_centroid(&centroids, point.pos())).collect();
+        if next == assignments {
+            break;
+        }
+        assignments = next;
+    }
+    (assignments, centroids)
+}
@@ -0,0 +1,277 @@
+//! Random shape generators.
+//!
+//! Randomness comes from `QRng`, a one method trait, so any generator can be plugged in.
+//! `QSplitMix64` is provided for reproducible results: the same seed gives the same
+//! shapes on every platform since all the math is fixed-point.
+
+use alloc::{ vec, vec::Vec };
+use core::ops::RangeInclusive;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::compare_edge_angle;
+use crate::shape::{ QPoint, QPolygon, QShapeCommon };
+
+/// Source of random bits.
+pub trait QRng {
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniform value in [0, 1) with a step of 2^-24.
+    fn next_unit(&mut self) -> Q64 {
+        q64!((self.next_u64() >> 40) as i64).saturating_div(q64!(1i64 << 24))
+    }
+
+    /// Uniform value in `range`.
+    fn next_in_range(&mut self, range: RangeInclusive<Q64>) -> Q64 {
+        let (start, end) = (*range.start(), *range.end());
+        start.saturating_add(end.saturating_sub(start).saturating_mul(self.next_unit()))
+    }
+
+    /// Uniform index in `0..len`, `len` must not be zero.
+    fn next_index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "[QRng::next_index] len should not be zero.");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+}
+
+/// Small deterministic generator, SplitMix64 by Sebastiano Vigna.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QSplitMix64 {
+    state: u64,
+}
+
+impl QSplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl QRng for QSplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Shuffle `values` in place with Fisher-Yates.
+pub fn shuffle<T>(values: &mut [T], rng: &mut impl QRng) {
+    for i in (1..values.len()).rev() {
+        values.swap(i, rng.next_index(i + 1));
+    }
+}
+
+/// Split sorted values into two chains from the min to the max and return the steps
+/// along both, which sum to zero.
+fn get_chain_steps(sorted: &[Q64], rng: &mut impl QRng) -> Vec<Q64> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let (mut last_top, mut last_bottom) = (min, min);
+    let mut rst = Vec::with_capacity(sorted.len());
+    for &value in &sorted[1..sorted.len() - 1] {
+        if rng.next_bool() {
+            rst.push(value.saturating_sub(last_top));
+            last_top = value;
+        } else {
+            rst.push(last_bottom.saturating_sub(value));
+            last_bottom = value;
+        }
+    }
+    rst.push(max.saturating_sub(last_top));
+    rst.push(last_bottom.saturating_sub(max));
+    rst
+}
+
+/// Generate a random convex polygon with Valtr's algorithm.
+///
+/// The polygon is counter-clockwise, centered on the vertex mean at the origin and scaled
+/// so its farthest vertex is at a distance drawn from `radius_range`. The vertex count is
+/// `n` unless two edges happen to be parallel, then they are merged.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qgeometry::prelude::*;
+/// use qgeometry::generate::{ random_convex_polygon, QSplitMix64 };
+///
+/// let mut rng = QSplitMix64::new(7);
+/// let polygon = random_convex_polygon(&mut rng, 12, q64!(1)..=q64!(2));
+/// assert_eq!(polygon.points().len(), 12);
+/// assert_eq!(qgeometry::algorithm::andrew_graham_scan(&polygon.points().iter().map(|p| p.pos()).collect()).len(), 12);
+/// ```
+pub fn random_convex_polygon(rng: &mut impl QRng, n: usize, radius_range: RangeInclusive<Q64>) -> QPolygon {
+    assert!(n >= 3, "[generate::random_convex_polygon] n({n}) should be at least 3.");
+    assert!(
+        *radius_range.start() > Q64::ZERO && radius_range.start() <= radius_range.end(),
+        "[generate::random_convex_polygon] radius_range({radius_range:?}) should be positive and not empty."
+    );
+
+    let mut xs: Vec<Q64> = (0..n).map(|_| rng.next_unit()).collect();
+    let mut ys: Vec<Q64> = (0..n).map(|_| rng.next_unit()).collect();
+    xs.sort();
+    ys.sort();
+    let x_steps = get_chain_steps(&xs, rng);
+    let mut y_steps = get_chain_steps(&ys, rng);
+    shuffle(&mut y_steps, rng);
+
+    let mut edges: Vec<QVec2> = x_steps.into_iter().zip(y_steps).map(|(x, y)| QVec2::new(x, y)).filter(|v| *v != QVec2::ZERO).collect();
+    edges.sort_by(|a, b| compare_edge_angle(*a, *b));
+    edges.dedup_by(|b, a| {
+        let is_parallel = compare_edge_angle(*a, *b) == core::cmp::Ordering::Equal;
+        if is_parallel {
+            *a = a.saturating_add(*b);
+        }
+        is_parallel
+    });
+
+    let mut pos = QVec2::ZERO;
+    let mut ring: Vec<QVec2> = edges.iter().map(|edge| {
+        let vertex = pos;
+        pos = pos.saturating_add(*edge);
+        vertex
+    }).collect();
+
+    let count = q64!(ring.len() as i64);
+    let sum = ring.iter().fold(QVec2::ZERO, |acc, p| acc.saturating_add(*p));
+    let center = QVec2::new(sum.x.saturating_div(count), sum.y.saturating_div(count));
+    let farthest = ring.iter().map(|p| p.distance(center)).max().unwrap_or(Q64::ONE);
+    let scale = rng.next_in_range(radius_range).saturating_div(farthest);
+    ring.iter_mut().for_each(|p| *p = p.saturating_sub(center).saturating_mul_num(scale));
+    QPolygon::new_from_parts(ring)
+}
+
+/// Candidates tried around each active sample before it is retired.
+pub const POISSON_DISK_ATTEMPTS: usize = 30;
+
+/// Scatter points inside `shape` with blue-noise spacing using Bridson's algorithm.
+///
+/// No two points are closer than `min_distance` and the shape is filled until no more
+/// points fit, up to the `POISSON_DISK_ATTEMPTS` random tries per point. Candidates are
+/// kept only if they are inside the shape, so concave shapes and holes are respected.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::generate::{ poisson_disk, QSplitMix64 };
+///
+/// let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(10.0, 10.0));
+/// let points = poisson_disk(&bbox, Q64::ONE, &mut QSplitMix64::new(5));
+/// assert!(points.len() > 50);
+/// assert!(points.iter().all(|point| bbox.is_point_inside(point)));
+/// ```
+pub fn poisson_disk(shape: &impl QShapeCommon, min_distance: Q64, rng: &mut impl QRng) -> Vec<QPoint> {
+    assert!(min_distance > Q64::ZERO, "[generate::poisson_disk] min_distance({min_distance}) should be positive.");
+    let bbox = shape.get_bbox();
+    let origin = bbox.left_bottom().pos();
+    // A cell of side r / sqrt(2) holds at most one point.
+    let cell = min_distance.saturating_mul(q64!(0.70710678));
+    let cols = (bbox.width().saturating_div(cell).floor().to_num::<i64>() + 1) as usize;
+    let rows = (bbox.height().saturating_div(cell).floor().to_num::<i64>() + 1) as usize;
+    let cell_of = |pos: QVec2| {
+        let offset = pos.saturating_sub(origin);
+        let col = (offset.x.saturating_div(cell).floor().to_num::<i64>().max(0) as usize).min(cols - 1);
+        let row = (offset.y.saturating_div(cell).floor().to_num::<i64>().max(0) as usize).min(rows - 1);
+        (col, row)
+    };
+
+    let mut grid: Vec<Option<usize>> = vec![None; cols.saturating_mul(rows)];
+    let mut rst: Vec<QPoint> = vec![];
+    let mut active: Vec<usize> = vec![];
+    let min_distance_squared = min_distance.saturating_mul(min_distance);
+
+    let first = shape.sample_interior(rng);
+    if !shape.is_point_inside(&first) {
+        return rst;
+    }
+    let (col, row) = cell_of(first.pos());
+    grid[row * cols + col] = Some(0);
+    rst.push(first);
+    active.push(0);
+
+    while !active.is_empty() {
+        let slot = rng.next_index(active.len());
+        let center = rst[active[slot]].pos();
+        let mut is_placed = false;
+        for _ in 0..POISSON_DISK_ATTEMPTS {
+            let radius = min_distance.saturating_mul(Q64::ONE.saturating_add(rng.next_unit()));
+            let (sin, cos) = Q64::TAU.saturating_mul(rng.next_unit()).sin_cos();
+            let candidate = QPoint::new(center.saturating_add(QVec2::new(cos, sin).saturating_mul_num(radius)));
+            if !bbox.is_point_inside(&candidate) || !shape.is_point_inside(&candidate) {
+                continue;
+            }
+            let (col, row) = cell_of(candidate.pos());
+            let is_free = (row.saturating_sub(2)..(row + 3).min(rows)).all(|r| {
+                (col.saturating_sub(2)..(col + 3).min(cols)).all(|c| {
+                    grid[r * cols + c].is_none_or(|i| rst[i].pos().distance_squared(candidate.pos()) >= min_distance_squared)
+                })
+            });
+            if is_free {
+                grid[row * cols + col] = Some(rst.len());
+                active.push(rst.len());
+                rst.push(candidate);
+                is_placed = true;
+                break;
+            }
+        }
+        if !is_placed {
+            active.swap_remove(slot);
+        }
+    }
+    rst
+}
+
+/// Pick a point uniformly in a triangle list, triangles are weighted by their area.
+///
+/// Returns `None` if the triangles have no area.
+pub(crate) fn sample_triangles(positions: &[QVec2], triangles: &[[u32; 3]], rng: &mut impl QRng) -> Option<QPoint> {
+    let corners = |t: &[u32; 3]| (positions[t[0] as usize], positions[t[1] as usize], positions[t[2] as usize]);
+    let area = |t: &[u32; 3]| {
+        let (a, b, c) = corners(t);
+        b.saturating_sub(a).cross(c.saturating_sub(a)).abs()
+    };
+    let total = triangles.iter().fold(Q64::ZERO, |acc, t| acc.saturating_add(area(t)));
+    if total == Q64::ZERO {
+        return None;
+    }
+
+    let mut target = total.saturating_mul(rng.next_unit());
+    let chosen = triangles
+        .iter()
+        .find(|t| {
+            let weight = area(t);
+            if target < weight {
+                return true;
+            }
+            target = target.saturating_sub(weight);
+            false
+        })
+        .or(triangles.last())?;
+
+    let (a, b, c) = corners(chosen);
+    let (mut u, mut v) = (rng.next_unit(), rng.next_unit());
+    if u.saturating_add(v) > Q64::ONE {
+        (u, v) = (Q64::ONE.saturating_sub(u), Q64::ONE.saturating_sub(v));
+    }
+    Some(QPoint::new(a.saturating_add(b.saturating_sub(a).saturating_mul_num(u)).saturating_add(c.saturating_sub(a).saturating_mul_num(v))))
+}
+
+/// Pick a point uniformly by length along the open chain through `points`.
+pub(crate) fn sample_chain(points: &[QPoint], rng: &mut impl QRng) -> QPoint {
+    assert!(!points.is_empty(), "[generate::sample_chain] points should not be empty.");
+    let total = points.windows(2).fold(Q64::ZERO, |acc, w| acc.saturating_add(w[0].distance(&w[1])));
+    let mut target = total.saturating_mul(rng.next_unit());
+    for w in points.windows(2) {
+        let length = w[0].distance(&w[1]);
+        if length > Q64::ZERO && target <= length {
+            let t = target.saturating_div(length);
+            return QPoint::new(w[0].pos().saturating_add(w[1].pos().saturating_sub(w[0].pos()).saturating_mul_num(t)));
+        }
+        target = target.saturating_sub(length);
+    }
+    points[points.len() - 1]
+}
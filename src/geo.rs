@@ -0,0 +1,118 @@
+//! Conversions between the shapes of this crate and `geo-types`.
+//!
+//! `geo-types` uses `f64` coordinates, converting to it rounds `Q64` values to the nearest
+//! double and converting back fails for coordinates that don't fit in `Q64`.
+use alloc::{ vec, vec::Vec };
+use core::fmt;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::shape::{ QPoint, QBbox, QPolygon, QPolyline };
+use crate::shape::point::try_q64_from_f64;
+
+/// Error produced when a `geo-types` geometry can't be represented by a shape.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QGeoTypesError {
+    /// A coordinate that is not finite or doesn't fit in `Q64`.
+    OutOfRange(f64),
+    /// Polygons with interior rings have no equivalent shape.
+    PolygonHasHoles,
+    /// A rect with zero width or height.
+    EmptyRect,
+}
+
+impl fmt::Display for QGeoTypesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QGeoTypesError::OutOfRange(value) => write!(f, "coordinate {value} doesn't fit in Q64"),
+            QGeoTypesError::PolygonHasHoles => write!(f, "polygon has interior rings"),
+            QGeoTypesError::EmptyRect => write!(f, "rect has no area"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QGeoTypesError {}
+
+fn to_coord(pos: QVec2) -> geo_types::Coord<f64> {
+    geo_types::coord! { x: pos.x.to_num::<f64>(), y: pos.y.to_num::<f64>() }
+}
+
+fn to_number(value: f64) -> Result<Q64, QGeoTypesError> {
+    try_q64_from_f64(value).ok_or(QGeoTypesError::OutOfRange(value))
+}
+
+fn from_coord(coord: geo_types::Coord<f64>) -> Result<QVec2, QGeoTypesError> {
+    Ok(QVec2::new(to_number(coord.x)?, to_number(coord.y)?))
+}
+
+impl From<QPoint> for geo_types::Point<f64> {
+    fn from(point: QPoint) -> Self {
+        geo_types::Point(to_coord(point.pos()))
+    }
+}
+
+impl TryFrom<geo_types::Point<f64>> for QPoint {
+    type Error = QGeoTypesError;
+
+    fn try_from(point: geo_types::Point<f64>) -> Result<Self, Self::Error> {
+        Ok(QPoint::new(from_coord(point.0)?))
+    }
+}
+
+impl From<&QPolyline> for geo_types::LineString<f64> {
+    fn from(polyline: &QPolyline) -> Self {
+        geo_types::LineString::new(polyline.points().iter().map(|point| to_coord(point.pos())).collect())
+    }
+}
+
+impl TryFrom<&geo_types::LineString<f64>> for QPolyline {
+    type Error = QGeoTypesError;
+
+    fn try_from(line_string: &geo_types::LineString<f64>) -> Result<Self, Self::Error> {
+        let points = line_string.0.iter().map(|coord| from_coord(*coord)).collect::<Result<Vec<_>, _>>()?;
+        Ok(QPolyline::new_from_parts(points))
+    }
+}
+
+/// The exterior ring is closed by `geo-types`.
+impl From<&QPolygon> for geo_types::Polygon<f64> {
+    fn from(polygon: &QPolygon) -> Self {
+        let exterior = polygon.points().iter().map(|point| to_coord(point.pos())).collect();
+        geo_types::Polygon::new(geo_types::LineString::new(exterior), vec![])
+    }
+}
+
+/// The closing vertex of the exterior ring is dropped.
+impl TryFrom<&geo_types::Polygon<f64>> for QPolygon {
+    type Error = QGeoTypesError;
+
+    fn try_from(polygon: &geo_types::Polygon<f64>) -> Result<Self, Self::Error> {
+        if !polygon.interiors().is_empty() {
+            return Err(QGeoTypesError::PolygonHasHoles);
+        }
+        let mut points = polygon.exterior().0.iter().map(|coord| from_coord(*coord)).collect::<Result<Vec<_>, _>>()?;
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        Ok(QPolygon::new_from_parts(points))
+    }
+}
+
+impl From<QBbox> for geo_types::Rect<f64> {
+    fn from(bbox: QBbox) -> Self {
+        geo_types::Rect::new(to_coord(bbox.left_bottom().pos()), to_coord(bbox.right_top().pos()))
+    }
+}
+
+impl TryFrom<geo_types::Rect<f64>> for QBbox {
+    type Error = QGeoTypesError;
+
+    fn try_from(rect: geo_types::Rect<f64>) -> Result<Self, Self::Error> {
+        let left_bottom = from_coord(rect.min())?;
+        let right_top = from_coord(rect.max())?;
+        if left_bottom.x >= right_top.x || left_bottom.y >= right_top.y {
+            return Err(QGeoTypesError::EmptyRect);
+        }
+        Ok(QBbox::new_from_parts(left_bottom, right_top))
+    }
+}
@@ -1,8 +1,42 @@
+//! 2D fixed-point geometry on top of `qmath`.
+//!
+//! The crate is `no_std` and only needs `alloc`. The default `std` feature adds
+//! `std::error::Error` impls and the hash based `QSpatialHash`. `scalar` runs the core
+//! shapes and algorithms on any number type, `f32` and `f64` included with `std`.
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod shape;
 pub mod algorithm;
+pub mod transform;
+pub mod spatial;
+pub mod world;
+pub mod sdf;
+pub mod mesh;
+pub mod wkt;
+pub mod wkb;
+pub mod tolerance;
+pub mod scalar;
+pub mod generate;
+pub mod raster;
+pub mod cluster;
+pub mod nav;
+#[cfg(feature = "geo-types")]
+pub mod geo;
+#[cfg(feature = "parry2d")]
+pub mod parry;
+#[cfg(feature = "lyon")]
+pub mod lyon;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub mod arbitrary;
 
 pub mod prelude {
     pub use crate::{
         shape::*,
+        transform::*,
+        tolerance::*,
     };
 }
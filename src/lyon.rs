@@ -0,0 +1,148 @@
+//! Conversions between the shapes of this crate and `lyon` paths, and tessellation into `QMesh`.
+//!
+//! `lyon` uses `f32` coordinates, converting to it rounds `Q64` values to the nearest float
+//! and converting back fails for coordinates that don't fit in `Q64`.
+use alloc::{ vec, vec::Vec };
+use core::fmt;
+use lyon::math::{ point, Point };
+use lyon::path::iterator::PathIterator;
+use lyon::path::{ Path, PathEvent };
+use lyon::tessellation::{
+    BuffersBuilder, FillOptions, FillTessellator, FillVertex, StrokeOptions, StrokeTessellator, StrokeVertex,
+    TessellationError, VertexBuffers,
+};
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::mesh::QMesh;
+use crate::shape::{ QPolygon, QPolyline, QShape };
+use crate::shape::point::try_q64_from_f64;
+
+/// Error produced when a `lyon` path or tessellation can't be represented by shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QLyonError {
+    /// A coordinate that is not finite or doesn't fit in `Q64`.
+    OutOfRange(f64),
+    /// The tessellator rejected the path.
+    Tessellation(TessellationError),
+}
+
+impl fmt::Display for QLyonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QLyonError::OutOfRange(value) => write!(f, "coordinate {value} doesn't fit in Q64"),
+            QLyonError::Tessellation(err) => write!(f, "tessellation failed: {err:?}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QLyonError {}
+
+fn to_point(pos: QVec2) -> Point {
+    point(pos.x.to_num::<f32>(), pos.y.to_num::<f32>())
+}
+
+fn to_number(value: f32) -> Result<Q64, QLyonError> {
+    try_q64_from_f64(value as f64).ok_or(QLyonError::OutOfRange(value as f64))
+}
+
+fn from_point(point: Point) -> Result<QVec2, QLyonError> {
+    Ok(QVec2::new(to_number(point.x)?, to_number(point.y)?))
+}
+
+fn build_path(points: &[QVec2], is_closed: bool) -> Path {
+    let mut builder = Path::builder();
+    if let Some((first, rest)) = points.split_first() {
+        builder.begin(to_point(*first));
+        rest.iter().for_each(|pos| { builder.line_to(to_point(*pos)); });
+        builder.end(is_closed);
+    }
+    builder.build()
+}
+
+impl From<&QPolygon> for Path {
+    fn from(polygon: &QPolygon) -> Self {
+        build_path(&polygon.points().iter().map(|point| point.pos()).collect::<Vec<_>>(), true)
+    }
+}
+
+impl From<&QPolyline> for Path {
+    fn from(polyline: &QPolyline) -> Self {
+        build_path(&polyline.points().iter().map(|point| point.pos()).collect::<Vec<_>>(), false)
+    }
+}
+
+/// Flatten a path into shapes, closed sub-paths become polygons and open ones polylines.
+///
+/// Curves are approximated by segments no further than `tolerance` from the curve.
+pub fn shapes_from_path(path: &Path, tolerance: f32) -> Result<Vec<QShape>, QLyonError> {
+    let mut rst = vec![];
+    let mut points: Vec<QVec2> = vec![];
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            PathEvent::Begin { at } => {
+                points.clear();
+                points.push(from_point(at)?);
+            }
+            PathEvent::Line { to, .. } => points.push(from_point(to)?),
+            PathEvent::End { close, .. } => {
+                let sub_path = core::mem::take(&mut points);
+                if close {
+                    let mut ring = sub_path;
+                    if ring.len() > 1 && ring.first() == ring.last() {
+                        ring.pop();
+                    }
+                    rst.push(QShape::QPolygon(QPolygon::new_from_parts(ring)));
+                } else {
+                    rst.push(QShape::QPolyline(QPolyline::new_from_parts(sub_path)));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(rst)
+}
+
+fn mesh_from_buffers(buffers: VertexBuffers<Point, u32>) -> Result<QMesh, QLyonError> {
+    let positions = buffers.vertices.into_iter().map(from_point).collect::<Result<Vec<_>, _>>()?;
+    let indices = buffers.indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect();
+    Ok(QMesh::new(positions, indices))
+}
+
+/// Fill a path with the even-odd rule, sub-paths inside others become holes.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::lyon::fill_mesh;
+///
+/// let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)]);
+/// let mesh = fill_mesh(&lyon::path::Path::from(&square), 0.01).unwrap();
+/// assert_eq!(mesh.indices().len(), 2);
+/// ```
+pub fn fill_mesh(path: &Path, tolerance: f32) -> Result<QMesh, QLyonError> {
+    let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate_path(
+            path,
+            &FillOptions::tolerance(tolerance),
+            &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| vertex.position())
+        )
+        .map_err(QLyonError::Tessellation)?;
+    mesh_from_buffers(buffers)
+}
+
+/// Stroke a path with lines of `width`.
+pub fn stroke_mesh(path: &Path, width: f32, tolerance: f32) -> Result<QMesh, QLyonError> {
+    let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
+    StrokeTessellator::new()
+        .tessellate_path(
+            path,
+            &StrokeOptions::tolerance(tolerance).with_line_width(width),
+            &mut BuffersBuilder::new(&mut buffers, |vertex: StrokeVertex| vertex.position())
+        )
+        .map_err(QLyonError::Tessellation)?;
+    mesh_from_buffers(buffers)
+}
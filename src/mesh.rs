@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::shape::QBbox;
+
+/// Triangle mesh ready to be uploaded as vertex and index buffers.
+///
+/// Triangles are wound counter-clockwise, `uvs` has one entry per position when present.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QMesh {
+    positions: Vec<QVec2>,
+    indices: Vec<[u32; 3]>,
+    uvs: Option<Vec<QVec2>>,
+}
+
+impl QMesh {
+    /// Create a mesh from positions and index triples into them.
+    ///
+    /// Clockwise triangles are flipped so the whole mesh is counter-clockwise.
+    pub fn new(positions: Vec<QVec2>, indices: Vec<[u32; 3]>) -> Self {
+        assert!(
+            indices.iter().flatten().all(|&i| (i as usize) < positions.len()),
+            "[QMesh::new] indices should be smaller than the number of positions({}).", positions.len()
+        );
+        let indices = indices
+            .into_iter()
+            .map(|[a, b, c]| {
+                let (pa, pb, pc) = (positions[a as usize], positions[b as usize], positions[c as usize]);
+                if pb.saturating_sub(pa).cross(pc.saturating_sub(pa)) < Q64::ZERO { [a, c, b] } else { [a, b, c] }
+            })
+            .collect();
+        Self {
+            positions,
+            indices,
+            uvs: None,
+        }
+    }
+
+    /// Create a mesh from the flat triangle list returned by `ear_clipping_triangulation`.
+    pub fn from_triangulation(positions: Vec<QVec2>, triangles: &[usize]) -> Self {
+        assert!(
+            positions.len() <= u32::MAX as usize,
+            "[QMesh::from_triangulation] positions({}) don't fit in u32 indices.", positions.len()
+        );
+        let indices = triangles.chunks_exact(3).map(|t| [t[0] as u32, t[1] as u32, t[2] as u32]).collect();
+        Self::new(positions, indices)
+    }
+
+    pub fn positions(&self) -> &Vec<QVec2> {
+        &self.positions
+    }
+
+    pub fn indices(&self) -> &Vec<[u32; 3]> {
+        &self.indices
+    }
+
+    pub fn uvs(&self) -> Option<&Vec<QVec2>> {
+        self.uvs.as_ref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Append another mesh, its indices are shifted past the current positions.
+    ///
+    /// UVs are kept only if both meshes have them.
+    pub fn append(&mut self, other: QMesh) {
+        let offset = self.positions.len() as u32;
+        self.uvs = match (self.uvs.take(), other.uvs) {
+            (Some(mut uvs), Some(other_uvs)) => {
+                uvs.extend(other_uvs);
+                Some(uvs)
+            }
+            _ => None,
+        };
+        self.positions.extend(other.positions);
+        self.indices.extend(other.indices.into_iter().map(|t| t.map(|i| i + offset)));
+    }
+
+    /// Set UVs to the positions multiplied by `scale`, so textures tile in world space.
+    pub fn with_planar_uvs(mut self, scale: Q64) -> Self {
+        self.uvs = Some(self.positions.iter().map(|p| p.saturating_mul_num(scale)).collect());
+        self
+    }
+
+    /// Set UVs so the bbox of the mesh maps onto the unit square.
+    ///
+    /// A mesh whose bbox has no area along an axis gets no UVs, there is nothing to map.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(qvec2!(2.0, 2.0), qvec2!(6.0, 4.0));
+    /// let mesh = bbox.to_mesh(0).with_bbox_uvs();
+    /// assert_eq!(mesh.indices().len(), 2);
+    /// assert_eq!(mesh.uvs().unwrap()[0], QVec2::ZERO);
+    /// assert_eq!(mesh.uvs().unwrap()[2], QVec2::ONE);
+    /// ```
+    pub fn with_bbox_uvs(mut self) -> Self {
+        let Some(bbox) = self.get_bbox() else {
+            self.uvs = None;
+            return self;
+        };
+        let origin = bbox.left_bottom().pos();
+        let (width, height) = (bbox.width(), bbox.height());
+        self.uvs = Some(
+            self.positions
+                .iter()
+                .map(|p| {
+                    let offset = p.saturating_sub(origin);
+                    QVec2::new(offset.x.saturating_div(width), offset.y.saturating_div(height))
+                })
+                .collect()
+        );
+        self
+    }
+
+    /// Get the bbox of the positions, `None` if the mesh has no area along an axis.
+    pub fn get_bbox(&self) -> Option<QBbox> {
+        let first = *self.positions.first()?;
+        let (min, max) = self.positions.iter().fold((first, first), |(min, max), p| (min.min(*p), max.max(*p)));
+        (min.x < max.x && min.y < max.y).then(|| QBbox::new_from_parts(min, max))
+    }
+}
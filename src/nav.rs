@@ -0,0 +1,331 @@
+//! Pathfinding over walkable triangle meshes.
+//!
+//! Everything is fixed-point and ties are broken by the smaller index, so the same mesh
+//! and query give the same path on every platform.
+
+use alloc::{ collections::{ BTreeMap, BinaryHeap }, vec, vec::Vec };
+use core::cmp::{ Ordering, Reverse };
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::orient2d;
+use crate::mesh::QMesh;
+use crate::shape::{ QPoint, QLine, QPolygon, QShape, QShapeCommon };
+
+/// Walkable area as counter-clockwise triangles that know their neighbours.
+///
+/// Triangles are neighbours when they share an edge with the same end positions, so
+/// meshes appended from separate pieces connect where their vertices meet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QNavMesh {
+    triangles: Vec<[QVec2; 3]>,
+    /// Neighbour across edge `k` of each triangle, edge `k` runs from corner `k` to the next.
+    neighbors: Vec<[Option<usize>; 3]>,
+}
+
+/// Triangles a path walks through and the edges it crosses between them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QNavCorridor {
+    start: QPoint,
+    goal: QPoint,
+    triangles: Vec<usize>,
+    portals: Vec<(QPoint, QPoint)>,
+}
+
+impl QNavMesh {
+    pub fn new(mesh: &QMesh) -> Self {
+        let positions = mesh.positions();
+        let triangles: Vec<[QVec2; 3]> = mesh.indices().iter().map(|t| t.map(|i| positions[i as usize])).collect();
+        let mut neighbors = vec![[None; 3]; triangles.len()];
+        let mut open_edges: BTreeMap<((Q64, Q64), (Q64, Q64)), (usize, usize)> = BTreeMap::new();
+        for (i, triangle) in triangles.iter().enumerate() {
+            for k in 0..3 {
+                let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+                let key = if (a.x, a.y) <= (b.x, b.y) { ((a.x, a.y), (b.x, b.y)) } else { ((b.x, b.y), (a.x, a.y)) };
+                match open_edges.remove(&key) {
+                    Some((j, l)) => {
+                        neighbors[i][k] = Some(j);
+                        neighbors[j][l] = Some(i);
+                    }
+                    None => {
+                        open_edges.insert(key, (i, k));
+                    }
+                }
+            }
+        }
+        Self {
+            triangles,
+            neighbors,
+        }
+    }
+
+    /// Triangulate a polygon into a navmesh, see `QShapeCommon::to_mesh`.
+    pub fn from_polygon(polygon: &QPolygon) -> Self {
+        Self::new(&polygon.to_mesh(0))
+    }
+
+    pub fn triangles(&self) -> &Vec<[QVec2; 3]> {
+        &self.triangles
+    }
+
+    /// Neighbours across the three edges of `triangle`, `None` on the boundary.
+    pub fn neighbors(&self, triangle: usize) -> [Option<usize>; 3] {
+        self.neighbors[triangle]
+    }
+
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// Get the first triangle containing `point`, edges count as inside.
+    pub fn locate(&self, point: &QPoint) -> Option<usize> {
+        self.triangles.iter().position(|triangle| {
+            (0..3).all(|k| orient2d(triangle[k], triangle[(k + 1) % 3], point.pos()) != Ordering::Less)
+        })
+    }
+
+    /// Find the corridor of triangles from `start` to `goal` with A*.
+    ///
+    /// A path enters every triangle at the middle of an edge, so costs and the heuristic
+    /// are measured between edge midpoints. `None` if either point is off the mesh or the
+    /// two are not connected.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::nav::QNavMesh;
+    ///
+    /// let u_shape = QPolygon::new_from_parts(vec![
+    ///     qvec2!(0.0, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 4.0), qvec2!(4.0, 4.0),
+    ///     qvec2!(4.0, 1.0), qvec2!(2.0, 1.0), qvec2!(2.0, 4.0), qvec2!(0.0, 4.0),
+    /// ]);
+    /// let navmesh = QNavMesh::from_polygon(&u_shape);
+    /// let corridor = navmesh.find_path(&QPoint::new(qvec2!(1.0, 3.0)), &QPoint::new(qvec2!(5.0, 3.0))).unwrap();
+    /// assert_eq!(corridor.portals().len(), corridor.triangles().len() - 1);
+    /// assert!(corridor.points().iter().all(|point| u_shape.is_point_inside(point)));
+    /// assert!(navmesh.find_path(&QPoint::new(qvec2!(1.0, 3.0)), &QPoint::new(qvec2!(3.0, 3.0))).is_none());
+    /// ```
+    pub fn find_path(&self, start: &QPoint, goal: &QPoint) -> Option<QNavCorridor> {
+        let (from, to) = (self.locate(start)?, self.locate(goal)?);
+        let mut costs: Vec<Option<Q64>> = vec![None; self.triangles.len()];
+        let mut entries: Vec<QVec2> = vec![start.pos(); self.triangles.len()];
+        let mut parents: Vec<Option<usize>> = vec![None; self.triangles.len()];
+        let mut is_closed = vec![false; self.triangles.len()];
+        let mut open = BinaryHeap::new();
+        costs[from] = Some(Q64::ZERO);
+        open.push(Reverse((start.pos().distance(goal.pos()), from)));
+        while let Some(Reverse((_, current))) = open.pop() {
+            if is_closed[current] {
+                continue;
+            }
+            if current == to {
+                return Some(self.get_corridor(*start, *goal, &parents, to));
+            }
+            is_closed[current] = true;
+            let cost = costs[current].unwrap_or(Q64::ZERO);
+            for k in 0..3 {
+                let Some(next) = self.neighbors[current][k] else {
+                    continue;
+                };
+                if is_closed[next] {
+                    continue;
+                }
+                let triangle = &self.triangles[current];
+                let midpoint = triangle[k].midpoint(triangle[(k + 1) % 3]);
+                let next_cost = cost.saturating_add(entries[current].distance(midpoint));
+                if costs[next].is_none_or(|known| next_cost < known) {
+                    costs[next] = Some(next_cost);
+                    entries[next] = midpoint;
+                    parents[next] = Some(current);
+                    // Reaching the goal triangle still leaves the walk to the goal itself.
+                    let estimate = next_cost.saturating_add(midpoint.distance(goal.pos()));
+                    open.push(Reverse((estimate, next)));
+                }
+            }
+        }
+        None
+    }
+
+    fn get_corridor(&self, start: QPoint, goal: QPoint, parents: &[Option<usize>], to: usize) -> QNavCorridor {
+        let mut triangles = vec![to];
+        while let Some(parent) = parents[*triangles.last().unwrap()] {
+            triangles.push(parent);
+        }
+        triangles.reverse();
+        let portals = triangles
+            .windows(2)
+            .map(|pair| {
+                let k = (0..3).find(|&k| self.neighbors[pair[0]][k] == Some(pair[1])).unwrap();
+                let triangle = &self.triangles[pair[0]];
+                // Walking out of a counter-clockwise triangle, the edge end is on the left.
+                (QPoint::new(triangle[(k + 1) % 3]), QPoint::new(triangle[k]))
+            })
+            .collect();
+        QNavCorridor {
+            start,
+            goal,
+            triangles,
+            portals,
+        }
+    }
+}
+
+impl QNavCorridor {
+    pub fn start(&self) -> QPoint {
+        self.start
+    }
+
+    pub fn goal(&self) -> QPoint {
+        self.goal
+    }
+
+    /// Triangle indices from the one holding `start` to the one holding `goal`.
+    pub fn triangles(&self) -> &Vec<usize> {
+        &self.triangles
+    }
+
+    /// Shared edges in walking order as `(left, right)` seen facing forward.
+    pub fn portals(&self) -> &Vec<(QPoint, QPoint)> {
+        &self.portals
+    }
+
+    /// Get the raw path, `start` then the middle of every portal then `goal`.
+    pub fn points(&self) -> Vec<QPoint> {
+        let mut rst = vec![self.start];
+        rst.extend(self.portals.iter().map(|(left, right)| QPoint::new(left.pos().midpoint(right.pos()))));
+        rst.push(self.goal);
+        rst
+    }
+}
+
+/// Pull the path through a corridor tight with the funnel algorithm.
+///
+/// Every portal is first narrowed by `agent_radius` at both ends, portals narrower than
+/// the agent collapse to their middle. The path then only bends at the narrowed portal
+/// ends, so its bends are `agent_radius` away from the corners it turns around. The legs
+/// between bends are not checked and may pass closer to a corner, use `shortcut_path` on
+/// the result to test them against obstacles.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::nav::{ self, QNavMesh };
+///
+/// let l_shape = QPolygon::new_from_parts(vec![
+///     qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 1.0), qvec2!(1.0, 1.0), qvec2!(1.0, 4.0), qvec2!(0.0, 4.0),
+/// ]);
+/// let corridor = QNavMesh::from_polygon(&l_shape).find_path(&QPoint::new(qvec2!(3.5, 0.5)), &QPoint::new(qvec2!(0.5, 3.5))).unwrap();
+/// let path = nav::smooth_path(&corridor, Q64::ZERO);
+/// assert_eq!(path, vec![QPoint::new(qvec2!(3.5, 0.5)), QPoint::new(qvec2!(1.0, 1.0)), QPoint::new(qvec2!(0.5, 3.5))]);
+/// ```
+pub fn smooth_path(corridor: &QNavCorridor, agent_radius: Q64) -> Vec<QPoint> {
+    assert!(agent_radius >= Q64::ZERO, "[nav::smooth_path] agent_radius({agent_radius}) should not be negative.");
+    let (start, goal) = (corridor.start.pos(), corridor.goal.pos());
+    let mut portals = vec![(start, start)];
+    portals.extend(corridor.portals.iter().map(|(left, right)| {
+        let (left, right) = (left.pos(), right.pos());
+        let width = left.distance(right);
+        if width <= agent_radius.saturating_mul(q64!(2)) {
+            let middle = left.midpoint(right);
+            return (middle, middle);
+        }
+        let inset = right.saturating_sub(left).saturating_mul_num(agent_radius.saturating_div(width));
+        (left.saturating_add(inset), right.saturating_sub(inset))
+    }));
+    portals.push((goal, goal));
+
+    let mut rst = vec![start];
+    let (mut apex, mut left, mut right) = (start, start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+    let mut i = 1;
+    while i < portals.len() {
+        let (next_left, next_right) = portals[i];
+        // Tighten the right side unless it would cross over the left one.
+        if orient2d(apex, right, next_right) != Ordering::Less {
+            if apex == right || orient2d(apex, left, next_right) == Ordering::Less {
+                (right, right_index) = (next_right, i);
+            } else {
+                rst.push(QPoint::new(left));
+                (apex, right, right_index) = (left, left, left_index);
+                i = left_index + 1;
+                continue;
+            }
+        }
+        if orient2d(apex, left, next_left) != Ordering::Greater {
+            if apex == left || orient2d(apex, right, next_left) == Ordering::Greater {
+                (left, left_index) = (next_left, i);
+            } else {
+                rst.push(QPoint::new(right));
+                (apex, left, left_index) = (right, right, right_index);
+                i = right_index + 1;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    if rst.last() != Some(&corridor.goal) {
+        rst.push(corridor.goal);
+    }
+    rst
+}
+
+/// Skip path points whenever a straight move stays `agent_radius` clear of every obstacle.
+///
+/// From each kept point the farthest clear point is taken next, a leg that is already
+/// blocked is kept as it is. Areas of closed obstacles are solid.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::nav;
+///
+/// let path: Vec<QPoint> = [qvec2!(0.0, 0.0), qvec2!(1.0, 1.0), qvec2!(2.0, 0.0), qvec2!(3.0, 1.0), qvec2!(4.0, 0.0)]
+///     .into_iter()
+///     .map(QPoint::new)
+///     .collect();
+/// let rock = QShape::QBbox(QBbox::new_from_parts(qvec2!(1.5, -0.5), qvec2!(2.5, 0.2)));
+/// let shortcut = nav::shortcut_path(&path, &[rock], q64!(0.1));
+/// assert_eq!(shortcut, vec![path[0], path[3], path[4]]);
+/// ```
+pub fn shortcut_path(path: &[QPoint], obstacles: &[QShape], agent_radius: Q64) -> Vec<QPoint> {
+    let Some(&first) = path.first() else {
+        return vec![];
+    };
+    let mut rst = vec![first];
+    let mut i = 0;
+    while i + 1 < path.len() {
+        let next = (i + 2..path.len())
+            .rev()
+            .find(|&j| {
+                path[i] == path[j] || {
+                    let leg = QLine::new(path[i], path[j]);
+                    obstacles.iter().all(|obstacle| is_leg_clear(&leg, obstacle, agent_radius))
+                }
+            })
+            .unwrap_or(i + 1);
+        rst.push(path[next]);
+        i = next;
+    }
+    rst
+}
+
+fn is_leg_clear(leg: &QLine, obstacle: &QShape, agent_radius: Q64) -> bool {
+    match obstacle {
+        QShape::QPoint(point) => leg.get_distance_from_point(point) >= agent_radius,
+        QShape::QCircle(circle) => leg.get_distance_from_point(&circle.center()) >= circle.radius().saturating_add(agent_radius),
+        _ => {
+            let is_closed = matches!(obstacle, QShape::QBbox(_) | QShape::QPolygon(_));
+            // Without a radius the distance check alone would let legs cross edges.
+            obstacle.edges_iter().all(|edge| leg.distance_to_segment(&edge) >= agent_radius && !leg.is_crossing(&edge))
+                && !(is_closed && obstacle.is_point_inside(&leg.start()))
+        }
+    }
+}
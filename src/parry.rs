@@ -0,0 +1,151 @@
+//! Conversions between the shapes of this crate and `parry2d`.
+//!
+//! `parry2d` uses `f32` coordinates, converting to it rounds `Q64` values to the nearest
+//! float and converting back fails for coordinates that don't fit in `Q64`. `Ball` and
+//! `Cuboid` are centered at the origin, so they are paired with an `Isometry` giving
+//! their position.
+use alloc::vec::Vec;
+use core::fmt;
+use parry2d::math::{ Isometry, Point, Real, Vector };
+use parry2d::shape::{ Ball, ConvexPolygon, Cuboid, Segment };
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::is_convex_ring;
+use crate::shape::{ QPoint, QLine, QBbox, QCircle, QPolygon, QShapeCommon };
+use crate::shape::point::try_q64_from_f64;
+
+/// Error produced when a `parry2d` shape can't be represented by a shape.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QParryError {
+    /// A coordinate that is not finite or doesn't fit in `Q64`.
+    OutOfRange(f64),
+    /// A shape without length or area, e.g. a zero radius ball.
+    Degenerate,
+    /// A polygon that is not convex.
+    NotConvex,
+    /// A cuboid with a rotated isometry has no axis aligned equivalent.
+    Rotated,
+}
+
+impl fmt::Display for QParryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QParryError::OutOfRange(value) => write!(f, "coordinate {value} doesn't fit in Q64"),
+            QParryError::Degenerate => write!(f, "shape has no extent"),
+            QParryError::NotConvex => write!(f, "polygon is not convex"),
+            QParryError::Rotated => write!(f, "cuboid is rotated"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QParryError {}
+
+fn to_real(value: Q64) -> Real {
+    value.to_num::<Real>()
+}
+
+fn to_point(pos: QVec2) -> Point<Real> {
+    Point::new(to_real(pos.x), to_real(pos.y))
+}
+
+fn to_number(value: Real) -> Result<Q64, QParryError> {
+    try_q64_from_f64(value as f64).ok_or(QParryError::OutOfRange(value as f64))
+}
+
+fn from_vector(vector: Vector<Real>) -> Result<QVec2, QParryError> {
+    Ok(QVec2::new(to_number(vector.x)?, to_number(vector.y)?))
+}
+
+fn from_point(point: Point<Real>) -> Result<QVec2, QParryError> {
+    from_vector(point.coords)
+}
+
+impl From<&QLine> for Segment {
+    fn from(line: &QLine) -> Self {
+        Segment::new(to_point(line.start().pos()), to_point(line.end().pos()))
+    }
+}
+
+impl TryFrom<&Segment> for QLine {
+    type Error = QParryError;
+
+    fn try_from(segment: &Segment) -> Result<Self, Self::Error> {
+        let (start, end) = (from_point(segment.a)?, from_point(segment.b)?);
+        if start == end {
+            return Err(QParryError::Degenerate);
+        }
+        Ok(QLine::new_from_parts(start, end))
+    }
+}
+
+/// Fails with `NotConvex` for concave polygons and `Degenerate` for polygons without area.
+impl TryFrom<&QPolygon> for ConvexPolygon {
+    type Error = QParryError;
+
+    fn try_from(polygon: &QPolygon) -> Result<Self, Self::Error> {
+        if !is_convex_ring(polygon.points()) {
+            return Err(QParryError::NotConvex);
+        }
+        let points = polygon.points().iter().map(|point| to_point(point.pos())).collect();
+        ConvexPolygon::from_convex_polyline(points).ok_or(QParryError::Degenerate)
+    }
+}
+
+impl TryFrom<&ConvexPolygon> for QPolygon {
+    type Error = QParryError;
+
+    fn try_from(polygon: &ConvexPolygon) -> Result<Self, Self::Error> {
+        let points = polygon.points().iter().map(|point| from_point(*point)).collect::<Result<Vec<_>, _>>()?;
+        Ok(QPolygon::new_from_parts(points))
+    }
+}
+
+/// Get a ball and the isometry placing it at the circle center.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::parry::{ from_parry_ball, to_parry_ball };
+///
+/// let circle = QCircle::new(QPoint::new(qvec2!(1.0, 2.0)), q64!(3));
+/// let (position, ball) = to_parry_ball(&circle);
+/// assert_eq!(ball.radius, 3.0);
+/// assert_eq!(from_parry_ball(&position, &ball), Ok(circle));
+/// ```
+pub fn to_parry_ball(circle: &QCircle) -> (Isometry<Real>, Ball) {
+    let center = circle.center().pos();
+    (Isometry::translation(to_real(center.x), to_real(center.y)), Ball::new(to_real(circle.radius())))
+}
+
+/// The rotation of `position` is ignored, a ball looks the same at any angle.
+pub fn from_parry_ball(position: &Isometry<Real>, ball: &Ball) -> Result<QCircle, QParryError> {
+    let center = from_vector(position.translation.vector)?;
+    let radius = to_number(ball.radius)?;
+    if radius <= Q64::ZERO {
+        return Err(QParryError::Degenerate);
+    }
+    Ok(QCircle::new(QPoint::new(center), radius))
+}
+
+/// Get a cuboid and the isometry placing it at the bbox center.
+pub fn to_parry_cuboid(bbox: &QBbox) -> (Isometry<Real>, Cuboid) {
+    let center = bbox.get_centroid().pos();
+    let half_extents = Vector::new(to_real(bbox.width().saturating_mul(Q64::HALF)), to_real(bbox.height().saturating_mul(Q64::HALF)));
+    (Isometry::translation(to_real(center.x), to_real(center.y)), Cuboid::new(half_extents))
+}
+
+/// Fails with `Rotated` if `position` has a rotation, use the cuboid polygon instead.
+pub fn from_parry_cuboid(position: &Isometry<Real>, cuboid: &Cuboid) -> Result<QBbox, QParryError> {
+    if position.rotation.angle() != 0.0 {
+        return Err(QParryError::Rotated);
+    }
+    let center = from_vector(position.translation.vector)?;
+    let half_extents = from_vector(cuboid.half_extents)?;
+    if half_extents.x <= Q64::ZERO || half_extents.y <= Q64::ZERO {
+        return Err(QParryError::Degenerate);
+    }
+    Ok(QBbox::new_from_parts(center.saturating_sub(half_extents), center.saturating_add(half_extents)))
+}
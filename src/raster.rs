@@ -0,0 +1,272 @@
+//! Conversions between shapes and grids of cells.
+//!
+//! Cell `(i, j)` covers `[i * cell_size, (i + 1) * cell_size)` along x and the same range
+//! of `j` along y, so the grid is anchored at the origin and indices can be negative.
+//! `marching_squares` goes the other way, from sampled fields back to polygons.
+
+use alloc::{ collections::BTreeMap, vec, vec::Vec };
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use crate::sdf::QSdfGrid;
+use crate::shape::{ QBbox, QLine, QPoint, QPolygon, QRay, QShapeCommon, QShapeType };
+use crate::spatial::cell_index;
+use crate::tolerance::QTolerance;
+
+fn from_cell(index: i64, cell_size: Q64) -> Q64 {
+    q64!(index).saturating_mul(cell_size)
+}
+
+/// Get the x of every edge crossing the horizontal line at `y`, unsorted.
+///
+/// Edges follow the half-open rule of `is_point_inside`, so a vertex on the line is
+/// counted once and the crossings pair up into interior spans after sorting.
+pub(crate) fn get_crossings_at_y(edges: &[QLine], y: Q64) -> Vec<Q64> {
+    edges
+        .iter()
+        .filter(|edge| (edge.start().y() > y) != (edge.end().y() > y))
+        .map(|edge| edge.get_x_at_y(y))
+        .collect()
+}
+
+/// Get the x range of the part of `edge` inside the band `[y_min, y_max]`.
+fn get_edge_range_in_band(edge: &QLine, y_min: Q64, y_max: Q64) -> Option<(Q64, Q64)> {
+    let (start, end) = (edge.start().pos(), edge.end().pos());
+    let (low, high) = (start.y.min(end.y), start.y.max(end.y));
+    if high < y_min || low > y_max {
+        return None;
+    }
+    if start.y == end.y {
+        return Some((start.x.min(end.x), start.x.max(end.x)));
+    }
+    let (xa, xb) = (edge.get_x_at_y(y_min.max(low)), edge.get_x_at_y(y_max.min(high)));
+    Some((xa.min(xb), xa.max(xb)))
+}
+
+/// Sort column ranges and merge the ones that overlap or touch.
+fn merge_ranges(mut ranges: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    ranges.sort();
+    let mut rst: Vec<(i64, i64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match rst.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => rst.push((start, end)),
+        }
+    }
+    rst
+}
+
+/// Get every grid cell the shape overlaps as `(column, row)`, row by row from the bottom.
+///
+/// The result is conservative: cells that only touch the boundary are included. Circles
+/// are tested exactly against each cell, polygonal shapes are walked edge by edge and
+/// their interior is filled between the edge crossings of each row, so no shape is
+/// tessellated into points. Lines and polylines only cover the cells along their path.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::raster::cells_covered;
+///
+/// let bbox = QBbox::new_from_parts(qvec2!(0.5, 0.5), qvec2!(2.5, 1.5));
+/// let cells: Vec<(i64, i64)> = cells_covered(&bbox, Q64::ONE).collect();
+/// assert_eq!(cells, vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]);
+///
+/// let line = QLine::new_from_parts(qvec2!(0.5, 0.5), qvec2!(2.5, 0.5));
+/// assert_eq!(cells_covered(&line, Q64::ONE).count(), 3);
+/// ```
+pub fn cells_covered(shape: &impl QShapeCommon, cell_size: Q64) -> impl Iterator<Item = (i64, i64)> {
+    assert!(cell_size > Q64::ZERO, "[raster::cells_covered] cell_size({cell_size}) should be positive.");
+    let rows = match shape.get_shape_type() {
+        QShapeType::QPoint => {
+            let point = shape.get_centroid();
+            let col = cell_index(point.x(), cell_size);
+            vec![(cell_index(point.y(), cell_size), vec![(col, col)])]
+        }
+        QShapeType::QCircle => get_circle_rows(shape, cell_size),
+        shape_type => get_polygon_rows(shape, cell_size, !matches!(shape_type, QShapeType::QLine | QShapeType::QPolyline)),
+    };
+    rows.into_iter().flat_map(|(row, ranges)| {
+        ranges.into_iter().flat_map(move |(start, end)| (start..=end).map(move |col| (col, row)))
+    })
+}
+
+fn get_circle_rows(circle: &impl QShapeCommon, cell_size: Q64) -> Vec<(i64, Vec<(i64, i64)>)> {
+    let bbox = circle.get_bbox();
+    let center = circle.get_centroid();
+    // A circle projects onto an axis as its center plus and minus its radius.
+    let (_, right) = circle.project_onto(QDir::new_from_vec(QVec2::X));
+    let radius = right.saturating_sub(center.x());
+    let radius_squared = radius.saturating_mul(radius);
+    let (col_min, col_max) = (cell_index(bbox.left_bottom().x(), cell_size), cell_index(bbox.right_top().x(), cell_size));
+    let (row_min, row_max) = (cell_index(bbox.left_bottom().y(), cell_size), cell_index(bbox.right_top().y(), cell_size));
+    (row_min..=row_max)
+        .filter_map(|row| {
+            let is_touching = |col: i64| {
+                let cell = QBbox::new_from_parts(
+                    QVec2::new(from_cell(col, cell_size), from_cell(row, cell_size)),
+                    QVec2::new(from_cell(col + 1, cell_size), from_cell(row + 1, cell_size))
+                );
+                cell.get_distance_squared_from_point(&center) <= radius_squared
+            };
+            // The circle cuts each row in a single run of cells.
+            let start = (col_min..=col_max).find(|&col| is_touching(col))?;
+            let end = (start..=col_max).rev().find(|&col| is_touching(col))?;
+            Some((row, vec![(start, end)]))
+        })
+        .collect()
+}
+
+fn get_polygon_rows(shape: &impl QShapeCommon, cell_size: Q64, has_interior: bool) -> Vec<(i64, Vec<(i64, i64)>)> {
+    let bbox = shape.get_bbox();
+    let edges: Vec<QLine> = shape.edges_iter().collect();
+    let (row_min, row_max) = (cell_index(bbox.left_bottom().y(), cell_size), cell_index(bbox.right_top().y(), cell_size));
+    (row_min..=row_max)
+        .filter_map(|row| {
+            let (y_min, y_max) = (from_cell(row, cell_size), from_cell(row + 1, cell_size));
+            let mut ranges: Vec<(i64, i64)> = edges
+                .iter()
+                .filter_map(|edge| get_edge_range_in_band(edge, y_min, y_max))
+                .map(|(x_min, x_max)| (cell_index(x_min, cell_size), cell_index(x_max, cell_size)))
+                .collect();
+            if has_interior {
+                let mut crossings = get_crossings_at_y(&edges, y_min.saturating_add(cell_size.saturating_mul(Q64::HALF)));
+                crossings.sort();
+                ranges.extend(crossings.chunks_exact(2).map(|pair| (cell_index(pair[0], cell_size), cell_index(pair[1], cell_size))));
+            }
+            (!ranges.is_empty()).then(|| (row, merge_ranges(ranges)))
+        })
+        .collect()
+}
+
+/// Get the cells a ray passes through in order as `(column, row)`, starting with the cell
+/// of its origin (Amanatides–Woo).
+///
+/// The iterator never ends, bound it with `take` or `take_while`. A ray through a cell
+/// corner steps along x first, so every pair of consecutive cells shares a side.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qmath::dir::QDir;
+/// use qgeometry::prelude::*;
+/// use qgeometry::raster::ray_grid_traversal;
+///
+/// let ray = QRay::new(QPoint::new(qvec2!(0.5, 0.5)), QDir::new_from_vec(qvec2!(2.0, 1.0)));
+/// let cells: Vec<(i64, i64)> = ray_grid_traversal(&ray, Q64::ONE).take(6).collect();
+/// assert_eq!(cells, vec![(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (3, 2)]);
+///
+/// let ray = QRay::new(QPoint::new(qvec2!(0.5, 0.5)), QDir::new_from_vec(-QVec2::X));
+/// let cells: Vec<(i64, i64)> = ray_grid_traversal(&ray, Q64::ONE).take(3).collect();
+/// assert_eq!(cells, vec![(0, 0), (-1, 0), (-2, 0)]);
+/// ```
+pub fn ray_grid_traversal(ray: &QRay, cell_size: Q64) -> impl Iterator<Item = (i64, i64)> {
+    assert!(cell_size > Q64::ZERO, "[raster::ray_grid_traversal] cell_size({cell_size}) should be positive.");
+    let (origin, dir) = (ray.origin().pos(), ray.vector());
+    // Distance to the border the ray leaves a cell through, measured from the origin so
+    // long walks do not accumulate rounding.
+    let get_exit_t = move |index: i64, o: Q64, d: Q64| {
+        if d > Q64::ZERO {
+            (from_cell(index + 1, cell_size).saturating_sub(o).saturating_div(d), 1)
+        } else if d < Q64::ZERO {
+            (from_cell(index, cell_size).saturating_sub(o).saturating_div(d), -1)
+        } else {
+            (Q64::MAX, 0)
+        }
+    };
+    let start = (cell_index(origin.x, cell_size), cell_index(origin.y, cell_size));
+    core::iter::successors(Some(start), move |&(col, row)| {
+        let ((tx, step_x), (ty, step_y)) = (get_exit_t(col, origin.x, dir.x), get_exit_t(row, origin.y, dir.y));
+        Some(if tx <= ty { (col + step_x, row) } else { (col, row + step_y) })
+    })
+}
+
+/// Extract the boundary of the region where `grid` is below `threshold` as polygons.
+///
+/// Outer boundaries are counter-clockwise and holes clockwise. Samples outside the grid
+/// count as outside, so regions touching the border are closed along it. Ambiguous
+/// saddle cells are resolved with the average of their corners. Each ring is cleaned with
+/// `QPolygon::new_cleaned`, an exact `tolerance` only merges the collinear vertices of
+/// straight runs while a larger one simplifies the boundary.
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::raster::marching_squares;
+/// use qgeometry::sdf;
+///
+/// let bbox = QBbox::new_from_parts(qvec2!(-1.0, -1.0), qvec2!(3.0, 2.0));
+/// let grid = sdf::bake(&QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 1.0)), &bbox, Q64::HALF);
+/// let polygons = marching_squares(&grid, Q64::ZERO, QTolerance::EXACT);
+/// assert_eq!(polygons.len(), 1);
+/// assert!(polygons[0].is_point_inside(&QPoint::new(qvec2!(1.0, 0.5))));
+/// assert!(!polygons[0].is_point_inside(&QPoint::new(qvec2!(2.5, 0.5))));
+/// ```
+pub fn marching_squares(grid: &QSdfGrid, threshold: Q64, tolerance: QTolerance) -> Vec<QPolygon> {
+    let (columns, rows) = (grid.columns() as i64, grid.rows() as i64);
+    let value = |i: i64, j: i64| {
+        (0..columns).contains(&i).then_some(())?;
+        (0..rows).contains(&j).then_some(())?;
+        Some(grid.value(i as usize, j as usize))
+    };
+    let is_inside = |i: i64, j: i64| value(i, j).is_some_and(|v| v < threshold);
+    let corner_pos = |i: i64, j: i64| grid.origin().saturating_add(QVec2::new(from_cell(i, grid.cell_size()), from_cell(j, grid.cell_size())));
+    // The crossing on the grid edge from corner `(i, j)` towards `(i + di, j + dj)`.
+    let edge_point = |(i, j): (i64, i64), (di, dj): (i64, i64)| match (value(i, j), value(i + di, j + dj)) {
+        (Some(a), Some(b)) => {
+            let t = threshold.saturating_sub(a).saturating_div(b.saturating_sub(a));
+            let (p, q) = (corner_pos(i, j), corner_pos(i + di, j + dj));
+            p.saturating_add(q.saturating_sub(p).saturating_mul_num(t))
+        }
+        (Some(_), None) => corner_pos(i, j),
+        _ => corner_pos(i + di, j + dj),
+    };
+
+    // Segments keyed by the grid edge they start on: (end edge, start point).
+    let mut segments: BTreeMap<(i64, i64, u8), ((i64, i64, u8), QVec2)> = BTreeMap::new();
+    for j in -1..rows {
+        for i in -1..columns {
+            let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+            let keys = [(i, j, 0), (i + 1, j, 1), (i, j + 1, 0), (i, j, 1)];
+            let inside = corners.map(|(ci, cj)| is_inside(ci, cj));
+            // Grid edges crossed by the boundary, counter-clockwise around the cell.
+            let crossings: Vec<usize> = (0..4).filter(|&k| inside[k] != inside[(k + 1) % 4]).collect();
+            let m = crossings.len();
+            if m == 0 {
+                continue;
+            }
+            let is_center_inside = m == 4 && {
+                let sum = corners.iter().fold(Q64::ZERO, |acc, &(ci, cj)| acc.saturating_add(value(ci, cj).unwrap_or(threshold)));
+                sum.saturating_mul(q64!(0.25)) < threshold
+            };
+            for (index, &k) in crossings.iter().enumerate() {
+                if !inside[k] {
+                    continue;
+                }
+                // Leave through an inside to outside edge and come back through the next
+                // outside to inside one, keeping the inside on the left.
+                let partner = crossings[if m == 4 && !is_center_inside { (index + 3) % 4 } else { (index + 1) % m }];
+                let key = |k: usize| keys[k];
+                let (ci, cj) = corners[k];
+                let (ni, nj) = corners[(k + 1) % 4];
+                // Start the edge from its lower corner so both cells compute the same point.
+                let start = if (ni, nj) < (ci, cj) { edge_point((ni, nj), (ci - ni, cj - nj)) } else { edge_point((ci, cj), (ni - ci, nj - cj)) };
+                segments.insert(key(k), (key(partner), start));
+            }
+        }
+    }
+
+    let mut rst = vec![];
+    while let Some((&first, _)) = segments.iter().next() {
+        let mut ring: Vec<QPoint> = vec![];
+        let mut key = first;
+        while let Some((next, start)) = segments.remove(&key) {
+            ring.push(QPoint::new(start));
+            key = next;
+        }
+        rst.extend(QPolygon::new_cleaned(ring, tolerance));
+    }
+    rst
+}
@@ -0,0 +1,697 @@
+//! Shapes and algorithms generic over the number type.
+//!
+//! Everything here is written against `QScalar`, so the same code runs on `Q64` where
+//! results must be deterministic and, with the `std` feature, on `f32` or `f64` where they
+//! need not be. The shapes convert to and from their `Q64` counterparts in `crate::shape`.
+//!
+//! The predicates use the scalar arithmetic as is, the exact `orient2d` of
+//! `crate::algorithm` stays specific to `Q64`.
+//!
+//! # Examples
+//! ```
+//! use qmath::prelude::*;
+//! use qgeometry::scalar::{ QScalarCircle, QScalarPolygon, QScalarShape, QScalarVec2 };
+//!
+//! let square = QScalarPolygon::new(vec![
+//!     QScalarVec2::new(0.0, 0.0), QScalarVec2::new(1.0, 0.0), QScalarVec2::new(1.0, 1.0), QScalarVec2::new(0.0, 1.0),
+//! ]);
+//! let circle = QScalarCircle::new(QScalarVec2::new(3.0, 0.5), 1.0);
+//! assert!(!square.is_collide(&circle));
+//! assert!((square.distance_to(&circle) - 1.0).abs() < 1e-9);
+//!
+//! // The same code on `Q64`, once the prototype has to be deterministic.
+//! let fixed: QScalarPolygon<Q64> = QScalarPolygon::from_qpolygon(&square.to_qpolygon());
+//! assert_eq!(fixed.area(), q64!(1));
+//! assert!(!fixed.is_collide(&QScalarCircle::from_qcircle(&circle.to_qcircle())));
+//! ```
+use alloc::{ vec, vec::Vec };
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::ops::Neg;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::GJK_MAX_ITERATIONS;
+use crate::shape::{ QPoint, QBbox, QCircle, QPolygon };
+
+/// Number type the generic shapes and algorithms compute with.
+///
+/// Operations saturate on `Q64` like everywhere else in the crate, floats follow IEEE 754.
+pub trait QScalar: Copy + PartialOrd + Debug {
+    const ZERO: Self;
+    const ONE: Self;
+    const HALF: Self;
+    const PI: Self;
+    /// Tolerance of the iterative algorithms, `gjk_distance` stops once a step gains less.
+    const EPS: Self;
+
+    /// Convert from `Q64`, exact for `Q64` and rounded to the nearest for floats.
+    fn from_q64(value: Q64) -> Self;
+    /// Convert to `Q64` rounding to the nearest, panics if the value doesn't fit.
+    fn to_q64(self) -> Q64;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+    fn saturating_div(self, rhs: Self) -> Self;
+    fn abs(self) -> Self;
+    /// Square root, zero for values not above zero.
+    fn sqrt(self) -> Self;
+}
+
+impl QScalar for Q64 {
+    const ZERO: Self = Q64::ZERO;
+    const ONE: Self = Q64::ONE;
+    const HALF: Self = Q64::HALF;
+    const PI: Self = Q64::PI;
+    const EPS: Self = Q64::EPS;
+
+    fn from_q64(value: Q64) -> Self {
+        value
+    }
+
+    fn to_q64(self) -> Q64 {
+        self
+    }
+
+    fn saturating_add(self, rhs: Self) -> Self {
+        Q64::saturating_add(self, rhs)
+    }
+
+    fn saturating_sub(self, rhs: Self) -> Self {
+        Q64::saturating_sub(self, rhs)
+    }
+
+    fn saturating_mul(self, rhs: Self) -> Self {
+        Q64::saturating_mul(self, rhs)
+    }
+
+    fn saturating_div(self, rhs: Self) -> Self {
+        Q64::saturating_div(self, rhs)
+    }
+
+    fn abs(self) -> Self {
+        Q64::abs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        crate::algorithm::sqrt(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl QScalar for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const HALF: Self = 0.5;
+    const PI: Self = core::f64::consts::PI;
+    const EPS: Self = 1e-12;
+
+    fn from_q64(value: Q64) -> Self {
+        value.to_num::<f64>()
+    }
+
+    fn to_q64(self) -> Q64 {
+        crate::shape::point::q64_from_f64(self)
+    }
+
+    fn saturating_add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+
+    fn saturating_sub(self, rhs: Self) -> Self {
+        self - rhs
+    }
+
+    fn saturating_mul(self, rhs: Self) -> Self {
+        self * rhs
+    }
+
+    fn saturating_div(self, rhs: Self) -> Self {
+        self / rhs
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        if self > 0.0 { f64::sqrt(self) } else { 0.0 }
+    }
+}
+
+#[cfg(feature = "std")]
+impl QScalar for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const HALF: Self = 0.5;
+    const PI: Self = core::f32::consts::PI;
+    const EPS: Self = 1e-6;
+
+    fn from_q64(value: Q64) -> Self {
+        value.to_num::<f32>()
+    }
+
+    fn to_q64(self) -> Q64 {
+        crate::shape::point::q64_from_f64(f64::from(self))
+    }
+
+    fn saturating_add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+
+    fn saturating_sub(self, rhs: Self) -> Self {
+        self - rhs
+    }
+
+    fn saturating_mul(self, rhs: Self) -> Self {
+        self * rhs
+    }
+
+    fn saturating_div(self, rhs: Self) -> Self {
+        self / rhs
+    }
+
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        if self > 0.0 { f32::sqrt(self) } else { 0.0 }
+    }
+}
+
+/// 2D vector over any `QScalar`, the generic counterpart of `QVec2`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QScalarVec2<S> {
+    pub x: S,
+    pub y: S,
+}
+
+impl<S: QScalar> QScalarVec2<S> {
+    pub const ZERO: Self = Self { x: S::ZERO, y: S::ZERO };
+    pub const X: Self = Self { x: S::ONE, y: S::ZERO };
+
+    pub fn new(x: S, y: S) -> Self {
+        Self { x, y }
+    }
+
+    pub fn from_qvec2(pos: QVec2) -> Self {
+        Self::new(S::from_q64(pos.x), S::from_q64(pos.y))
+    }
+
+    pub fn to_qvec2(self) -> QVec2 {
+        QVec2::new(self.x.to_q64(), self.y.to_q64())
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_add(rhs.x), self.y.saturating_add(rhs.y))
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_sub(rhs.x), self.y.saturating_sub(rhs.y))
+    }
+
+    pub fn saturating_mul_num(self, factor: S) -> Self {
+        Self::new(self.x.saturating_mul(factor), self.y.saturating_mul(factor))
+    }
+
+    pub fn dot(self, rhs: Self) -> S {
+        self.x.saturating_mul(rhs.x).saturating_add(self.y.saturating_mul(rhs.y))
+    }
+
+    pub fn cross(self, rhs: Self) -> S {
+        self.x.saturating_mul(rhs.y).saturating_sub(self.y.saturating_mul(rhs.x))
+    }
+
+    pub fn length_squared(self) -> S {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> S {
+        self.length_squared().sqrt()
+    }
+}
+
+impl<S: QScalar> Neg for QScalarVec2<S> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO.saturating_sub(self)
+    }
+}
+
+/// Axis aligned box over any `QScalar`, `min` is not larger than `max` on either axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QScalarBbox<S> {
+    min: QScalarVec2<S>,
+    max: QScalarVec2<S>,
+}
+
+impl<S: QScalar> QScalarBbox<S> {
+    pub fn new(min: QScalarVec2<S>, max: QScalarVec2<S>) -> Self {
+        assert!(min.x <= max.x && min.y <= max.y, "[QScalarBbox::new] min({min:?}) should not be larger than max({max:?}).");
+        Self { min, max }
+    }
+
+    pub fn from_qbbox(bbox: &QBbox) -> Self {
+        Self::new(QScalarVec2::from_qvec2(bbox.left_bottom().pos()), QScalarVec2::from_qvec2(bbox.right_top().pos()))
+    }
+
+    /// Convert to a `QBbox`, which must not be flat.
+    pub fn to_qbbox(&self) -> QBbox {
+        QBbox::new_from_parts(self.min.to_qvec2(), self.max.to_qvec2())
+    }
+
+    pub fn min(&self) -> QScalarVec2<S> {
+        self.min
+    }
+
+    pub fn max(&self) -> QScalarVec2<S> {
+        self.max
+    }
+}
+
+/// Circle over any `QScalar`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QScalarCircle<S> {
+    center: QScalarVec2<S>,
+    radius: S,
+}
+
+impl<S: QScalar> QScalarCircle<S> {
+    pub fn new(center: QScalarVec2<S>, radius: S) -> Self {
+        assert!(radius > S::ZERO, "[QScalarCircle::new] radius({radius:?}) should be larger than zero.");
+        Self { center, radius }
+    }
+
+    pub fn from_qcircle(circle: &QCircle) -> Self {
+        Self::new(QScalarVec2::from_qvec2(circle.center().pos()), S::from_q64(circle.radius()))
+    }
+
+    pub fn to_qcircle(&self) -> QCircle {
+        QCircle::new(QPoint::new(self.center.to_qvec2()), self.radius.to_q64())
+    }
+
+    pub fn center(&self) -> QScalarVec2<S> {
+        self.center
+    }
+
+    pub fn radius(&self) -> S {
+        self.radius
+    }
+}
+
+/// Simple polygon over any `QScalar`, in either winding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QScalarPolygon<S> {
+    points: Vec<QScalarVec2<S>>,
+}
+
+impl<S: QScalar> QScalarPolygon<S> {
+    pub fn new(points: Vec<QScalarVec2<S>>) -> Self {
+        Self { points }
+    }
+
+    pub fn from_qpolygon(polygon: &QPolygon) -> Self {
+        Self::new(polygon.points().iter().map(|point| QScalarVec2::from_qvec2(point.pos())).collect())
+    }
+
+    pub fn to_qpolygon(&self) -> QPolygon {
+        QPolygon::new_from_parts(self.points.iter().map(|point| point.to_qvec2()).collect())
+    }
+
+    pub fn points(&self) -> &[QScalarVec2<S>] {
+        &self.points
+    }
+
+    /// Get the counter-clockwise convex hull, see `convex_hull`.
+    pub fn convex_hull(&self) -> Self {
+        Self::new(convex_hull(&self.points))
+    }
+}
+
+/// The part of `QShapeCommon` that works on any `QScalar`.
+///
+/// Collision and distance go through GJK on the support points, so concave shapes are
+/// treated as their convex hulls like in `QShapeCommon`.
+pub trait QScalarShape<S: QScalar> {
+    /// Get the smallest box containing the shape.
+    fn get_bbox(&self) -> QScalarBbox<S>;
+
+    /// Get the center, the average of the vertices for polygons like `QPolygon::get_centroid`.
+    fn get_centroid(&self) -> QScalarVec2<S>;
+
+    /// Get the enclosed area.
+    fn area(&self) -> S;
+
+    /// Check if `point` is inside the shape or on its boundary.
+    fn is_point_inside(&self, point: QScalarVec2<S>) -> bool;
+
+    /// Get the point of the convex hull farthest along `dir`, which needs not be normalized.
+    fn get_support_point(&self, dir: QScalarVec2<S>) -> QScalarVec2<S>;
+
+    /// Check if the shapes overlap or touch.
+    fn is_collide(&self, other: &impl QScalarShape<S>) -> bool where Self: Sized {
+        gjk(self, other)
+    }
+
+    /// Get the distance between the shapes, zero if they overlap.
+    fn distance_to(&self, other: &impl QScalarShape<S>) -> S where Self: Sized {
+        gjk_distance(self, other)
+    }
+}
+
+impl<S: QScalar> QScalarShape<S> for QScalarVec2<S> {
+    fn get_bbox(&self) -> QScalarBbox<S> {
+        QScalarBbox::new(*self, *self)
+    }
+
+    fn get_centroid(&self) -> QScalarVec2<S> {
+        *self
+    }
+
+    fn area(&self) -> S {
+        S::ZERO
+    }
+
+    fn is_point_inside(&self, point: QScalarVec2<S>) -> bool {
+        *self == point
+    }
+
+    fn get_support_point(&self, _dir: QScalarVec2<S>) -> QScalarVec2<S> {
+        *self
+    }
+}
+
+impl<S: QScalar> QScalarShape<S> for QScalarBbox<S> {
+    fn get_bbox(&self) -> QScalarBbox<S> {
+        *self
+    }
+
+    fn get_centroid(&self) -> QScalarVec2<S> {
+        self.min.saturating_add(self.max).saturating_mul_num(S::HALF)
+    }
+
+    fn area(&self) -> S {
+        let size = self.max.saturating_sub(self.min);
+        size.x.saturating_mul(size.y)
+    }
+
+    fn is_point_inside(&self, point: QScalarVec2<S>) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x && self.min.y <= point.y && point.y <= self.max.y
+    }
+
+    fn get_support_point(&self, dir: QScalarVec2<S>) -> QScalarVec2<S> {
+        let x = if dir.x >= S::ZERO { self.max.x } else { self.min.x };
+        let y = if dir.y >= S::ZERO { self.max.y } else { self.min.y };
+        QScalarVec2::new(x, y)
+    }
+}
+
+impl<S: QScalar> QScalarShape<S> for QScalarCircle<S> {
+    fn get_bbox(&self) -> QScalarBbox<S> {
+        let extent = QScalarVec2::new(self.radius, self.radius);
+        QScalarBbox::new(self.center.saturating_sub(extent), self.center.saturating_add(extent))
+    }
+
+    fn get_centroid(&self) -> QScalarVec2<S> {
+        self.center
+    }
+
+    fn area(&self) -> S {
+        S::PI.saturating_mul(self.radius).saturating_mul(self.radius)
+    }
+
+    fn is_point_inside(&self, point: QScalarVec2<S>) -> bool {
+        point.saturating_sub(self.center).length_squared() <= self.radius.saturating_mul(self.radius)
+    }
+
+    fn get_support_point(&self, dir: QScalarVec2<S>) -> QScalarVec2<S> {
+        let length = dir.length();
+        if length == S::ZERO {
+            return self.center.saturating_add(QScalarVec2::new(self.radius, S::ZERO));
+        }
+        self.center.saturating_add(dir.saturating_mul_num(self.radius.saturating_div(length)))
+    }
+}
+
+impl<S: QScalar> QScalarShape<S> for QScalarPolygon<S> {
+    fn get_bbox(&self) -> QScalarBbox<S> {
+        let first = *self.points.first().expect("[QScalarPolygon::get_bbox] Polygon must not be empty.");
+        let (min, max) = self.points.iter().fold((first, first), |(min, max), point| {
+            (
+                QScalarVec2::new(partial_min(min.x, point.x), partial_min(min.y, point.y)),
+                QScalarVec2::new(partial_max(max.x, point.x), partial_max(max.y, point.y)),
+            )
+        });
+        QScalarBbox::new(min, max)
+    }
+
+    fn get_centroid(&self) -> QScalarVec2<S> {
+        let Some(&base) = self.points.first() else {
+            return QScalarVec2::ZERO;
+        };
+        // Sum the offsets from the first vertex so large coordinates don't saturate.
+        let sum = self.points.iter().fold(QScalarVec2::ZERO, |acc, point| acc.saturating_add(point.saturating_sub(base)));
+        let n = S::from_q64(q64!(self.points.len()));
+        base.saturating_add(QScalarVec2::new(sum.x.saturating_div(n), sum.y.saturating_div(n)))
+    }
+
+    fn area(&self) -> S {
+        signed_double_area(&self.points).abs().saturating_mul(S::HALF)
+    }
+
+    fn is_point_inside(&self, point: QScalarVec2<S>) -> bool {
+        let n = self.points.len();
+        if n < 3 {
+            return false;
+        }
+
+        let mut rst = false;
+        for i in 0..n {
+            let (start, end) = (self.points[i], self.points[(i + 1) % n]);
+            if is_point_on_segment(start, end, point) {
+                return true;
+            }
+            if (start.y > point.y) != (end.y > point.y) {
+                let t = point.y.saturating_sub(start.y).saturating_div(end.y.saturating_sub(start.y));
+                let intersect_x = start.x.saturating_add(end.x.saturating_sub(start.x).saturating_mul(t));
+                if point.x < intersect_x {
+                    rst = !rst;
+                }
+            }
+        }
+        rst
+    }
+
+    fn get_support_point(&self, dir: QScalarVec2<S>) -> QScalarVec2<S> {
+        *self
+            .points
+            .iter()
+            .max_by(|a, b| a.dot(dir).partial_cmp(&b.dot(dir)).unwrap_or(Ordering::Equal))
+            .expect("[QScalarPolygon::get_support_point] Polygon must not be empty.")
+    }
+}
+
+fn partial_min<S: QScalar>(a: S, b: S) -> S {
+    if b < a { b } else { a }
+}
+
+fn partial_max<S: QScalar>(a: S, b: S) -> S {
+    if b > a { b } else { a }
+}
+
+/// Side of `c` relative to the line from `a` to `b`, `Greater` when counter-clockwise.
+fn orient<S: QScalar>(a: QScalarVec2<S>, b: QScalarVec2<S>, c: QScalarVec2<S>) -> Ordering {
+    b.saturating_sub(a).cross(c.saturating_sub(a)).partial_cmp(&S::ZERO).unwrap_or(Ordering::Equal)
+}
+
+fn is_point_on_segment<S: QScalar>(start: QScalarVec2<S>, end: QScalarVec2<S>, point: QScalarVec2<S>) -> bool {
+    orient(start, end, point) == Ordering::Equal
+        && partial_min(start.x, end.x) <= point.x
+        && point.x <= partial_max(start.x, end.x)
+        && partial_min(start.y, end.y) <= point.y
+        && point.y <= partial_max(start.y, end.y)
+}
+
+/// Twice the signed area of the ring, positive when counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use qgeometry::scalar::{ signed_double_area, QScalarVec2 };
+///
+/// let triangle = [QScalarVec2::new(0.0, 0.0), QScalarVec2::new(4.0, 0.0), QScalarVec2::new(0.0, 3.0)];
+/// assert_eq!(signed_double_area(&triangle), 12.0);
+/// ```
+pub fn signed_double_area<S: QScalar>(points: &[QScalarVec2<S>]) -> S {
+    let n = points.len();
+    (0..n).fold(S::ZERO, |acc, i| acc.saturating_add(points[i].cross(points[(i + 1) % n])))
+}
+
+/// Andrew's monotone chain on any `QScalar`, see `algorithm::andrew_graham_scan`.
+///
+/// The hull is counter-clockwise from the lowest point by x then y, without collinear points.
+///
+/// # Examples
+/// ```
+/// use qgeometry::scalar::{ convex_hull, QScalarVec2 };
+///
+/// let points = [(2.0, 2.0), (1.0, 0.0), (0.0, 0.0), (2.0, 0.0), (0.0, 2.0), (1.0, 1.0)].map(|(x, y)| QScalarVec2::new(x, y));
+/// let hull = convex_hull(&points);
+/// assert_eq!(hull, [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)].map(|(x, y)| QScalarVec2::new(x, y)).to_vec());
+/// ```
+pub fn convex_hull<S: QScalar>(points: &[QScalarVec2<S>]) -> Vec<QScalarVec2<S>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal).then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal)));
+    sorted.dedup();
+    if sorted.len() <= 2 {
+        return sorted;
+    }
+
+    // Lower hull left to right, then upper hull right to left, popping turns that aren't left.
+    let mut hull: Vec<QScalarVec2<S>> = Vec::with_capacity(sorted.len() + 1);
+    for &point in &sorted {
+        while hull.len() >= 2 && orient(hull[hull.len() - 2], hull[hull.len() - 1], point) != Ordering::Greater {
+            hull.pop();
+        }
+        hull.push(point);
+    }
+    let lower_len = hull.len() + 1;
+    for &point in sorted.iter().rev().skip(1) {
+        while hull.len() >= lower_len && orient(hull[hull.len() - 2], hull[hull.len() - 1], point) != Ordering::Greater {
+            hull.pop();
+        }
+        hull.push(point);
+    }
+    // The upper hull ends back at the first point.
+    hull.pop();
+    hull
+}
+
+fn support_difference<S: QScalar>(shape_a: &impl QScalarShape<S>, shape_b: &impl QScalarShape<S>, dir: QScalarVec2<S>) -> QScalarVec2<S> {
+    shape_a.get_support_point(dir).saturating_sub(shape_b.get_support_point(-dir))
+}
+
+/// Perpendicular of `edge` on the side of `towards`.
+fn perpendicular_towards<S: QScalar>(edge: QScalarVec2<S>, towards: QScalarVec2<S>) -> QScalarVec2<S> {
+    let perpendicular = QScalarVec2::new(S::ZERO.saturating_sub(edge.y), edge.x);
+    if perpendicular.dot(towards) >= S::ZERO { perpendicular } else { -perpendicular }
+}
+
+/// GJK on any `QScalar`, see `algorithm::gjk`.
+///
+/// # Examples
+/// ```
+/// use qgeometry::scalar::{ gjk, QScalarBbox, QScalarCircle, QScalarVec2 };
+///
+/// let bbox = QScalarBbox::new(QScalarVec2::new(0.0, 0.0), QScalarVec2::new(2.0, 2.0));
+/// assert!(gjk(&bbox, &QScalarCircle::new(QScalarVec2::new(2.5, 1.0), 1.0)));
+/// assert!(!gjk(&bbox, &QScalarCircle::new(QScalarVec2::new(3.5, 1.0), 1.0)));
+/// ```
+pub fn gjk<S: QScalar>(shape_a: &impl QScalarShape<S>, shape_b: &impl QScalarShape<S>) -> bool {
+    let first = support_difference(shape_a, shape_b, QScalarVec2::X);
+    let mut simplex = vec![first];
+    let mut dir = -first;
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        if dir == QScalarVec2::ZERO {
+            return true;
+        }
+        let point = support_difference(shape_a, shape_b, dir);
+        if point.dot(dir) < S::ZERO {
+            return false;
+        }
+        if simplex.contains(&point) {
+            return true;
+        }
+        simplex.push(point);
+
+        match simplex.len() {
+            2 => {
+                let (b, a) = (simplex[0], simplex[1]);
+                dir = perpendicular_towards(b.saturating_sub(a), -a);
+                if dir.dot(-a) == S::ZERO {
+                    dir = QScalarVec2::ZERO;
+                }
+            }
+            _ => {
+                let (c, b, a) = (simplex[0], simplex[1], simplex[2]);
+                let ab = b.saturating_sub(a);
+                let ac = c.saturating_sub(a);
+                let ab_perpendicular = -perpendicular_towards(ab, ac);
+                let ac_perpendicular = -perpendicular_towards(ac, ab);
+                if ab_perpendicular.dot(-a) > S::ZERO {
+                    simplex = vec![b, a];
+                    dir = ab_perpendicular;
+                } else if ac_perpendicular.dot(-a) > S::ZERO {
+                    simplex = vec![c, a];
+                    dir = ac_perpendicular;
+                } else {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Distance between two convex shapes on any `QScalar`, see `algorithm::gjk_distance`.
+///
+/// # Examples
+/// ```
+/// use qgeometry::scalar::{ gjk_distance, QScalarCircle, QScalarVec2 };
+///
+/// let a = QScalarCircle::new(QScalarVec2::new(0.0, 0.0), 1.0);
+/// let b = QScalarCircle::new(QScalarVec2::new(3.0, 4.0), 1.0);
+/// assert!((gjk_distance(&a, &b) - 3.0).abs() < 1e-6);
+/// ```
+pub fn gjk_distance<S: QScalar>(shape_a: &impl QScalarShape<S>, shape_b: &impl QScalarShape<S>) -> S {
+    let mut simplex = vec![support_difference(shape_a, shape_b, QScalarVec2::X)];
+    let mut closest = simplex[0];
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        if closest == QScalarVec2::ZERO {
+            return S::ZERO;
+        }
+        let point = support_difference(shape_a, shape_b, -closest);
+        if simplex.contains(&point) || closest.dot(closest).saturating_sub(closest.dot(point)) <= S::EPS {
+            break;
+        }
+        simplex.push(point);
+        (closest, simplex) = closest_on_simplex(&simplex);
+    }
+    closest.length()
+}
+
+/// Point of a simplex closest to the origin, with the simplex reduced to the points spanning
+/// the feature it lies on.
+fn closest_on_simplex<S: QScalar>(simplex: &[QScalarVec2<S>]) -> (QScalarVec2<S>, Vec<QScalarVec2<S>>) {
+    match *simplex {
+        [a, b] => {
+            let ab = b.saturating_sub(a);
+            let t = S::ZERO.saturating_sub(a.dot(ab));
+            let length_squared = ab.length_squared();
+            if t <= S::ZERO {
+                (a, vec![a])
+            } else if t >= length_squared {
+                (b, vec![b])
+            } else {
+                (a.saturating_add(ab.saturating_mul_num(t.saturating_div(length_squared))), vec![a, b])
+            }
+        }
+        [a, b, c] => {
+            let origin = QScalarVec2::ZERO;
+            let sides = [orient(a, b, origin), orient(b, c, origin), orient(c, a, origin)];
+            let degenerate = sides.iter().all(|side| side.is_eq());
+            let enclosed = !sides.contains(&Ordering::Greater) || !sides.contains(&Ordering::Less);
+            if enclosed && !degenerate {
+                return (origin, simplex.to_vec());
+            }
+            [[a, b], [b, c], [c, a]]
+                .iter()
+                .map(|edge| closest_on_simplex(edge))
+                .min_by(|x, y| x.0.length_squared().partial_cmp(&y.0.length_squared()).unwrap_or(Ordering::Equal))
+                .expect("[scalar::closest_on_simplex] A triangle has three edges.")
+        }
+        _ => (simplex[0], vec![simplex[0]]),
+    }
+}
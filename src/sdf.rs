@@ -0,0 +1,350 @@
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use crate::shape::line::get_closest_point_on_chain;
+use crate::shape::{ QPoint, QLine, QBbox, QCircle, QPolygon, QPolyline, QShape, QCompound, QTransformedShape, QShapeCommon, QShapeType, QSupportMap };
+
+/// Signed distance field, negative inside the shape and positive outside.
+///
+/// Shapes without an interior (points, lines, polylines) return the unsigned distance.
+pub trait QSdf {
+    fn signed_distance(&self, point: &QPoint) -> Q64;
+
+    /// Union of both fields.
+    fn sdf_union<O: QSdf>(self, other: O) -> QSdfUnion<Self, O> where Self: Sized {
+        QSdfUnion::new(self, other)
+    }
+
+    /// Intersection of both fields.
+    fn sdf_intersection<O: QSdf>(self, other: O) -> QSdfIntersection<Self, O> where Self: Sized {
+        QSdfIntersection::new(self, other)
+    }
+
+    /// This field with `other` carved out of it.
+    fn sdf_subtraction<O: QSdf>(self, other: O) -> QSdfSubtraction<Self, O> where Self: Sized {
+        QSdfSubtraction::new(self, other)
+    }
+}
+
+/// Signed distance of a closed boundary through `points`, `inside` decides the sign.
+fn signed_distance_to_ring(points: &[QPoint], inside: bool, point: &QPoint) -> Q64 {
+    let distance = get_closest_point_on_chain(points, true, point).distance(point);
+    if inside { -distance } else { distance }
+}
+
+impl QSdf for QPoint {
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        self.distance(point)
+    }
+}
+
+impl QSdf for QLine {
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        self.get_closest_point_from_point(point).distance(point)
+    }
+}
+
+impl QSdf for QBbox {
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::sdf::QSdf;
+    ///
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(4.0, 4.0));
+    /// assert_eq!(bbox.signed_distance(&QPoint::new(qvec2!(1.0, 2.0))), q64!(-1));
+    /// assert_eq!(bbox.signed_distance(&QPoint::new(qvec2!(6.0, 2.0))), q64!(2));
+    /// ```
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        if !self.is_point_inside(point) {
+            return self.closest_point(point).distance(point);
+        }
+        let left_bottom = self.left_bottom();
+        let right_top = self.right_top();
+        let dx = point.x().saturating_sub(left_bottom.x()).min(right_top.x().saturating_sub(point.x()));
+        let dy = point.y().saturating_sub(left_bottom.y()).min(right_top.y().saturating_sub(point.y()));
+        -dx.min(dy)
+    }
+}
+
+impl QSdf for QCircle {
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        self.center().distance(point).saturating_sub(self.radius())
+    }
+}
+
+impl QSdf for QPolygon {
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        signed_distance_to_ring(self.points(), self.is_point_inside(point), point)
+    }
+}
+
+impl QSdf for QPolyline {
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        self.closest_point(point).distance(point)
+    }
+}
+
+impl QSdf for QShape {
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        match self {
+            QShape::QPoint(shape) => shape.signed_distance(point),
+            QShape::QLine(shape) => shape.signed_distance(point),
+            QShape::QBbox(shape) => shape.signed_distance(point),
+            QShape::QCircle(shape) => shape.signed_distance(point),
+            QShape::QPolygon(shape) => shape.signed_distance(point),
+            QShape::QPolyline(shape) => shape.signed_distance(point),
+        }
+    }
+}
+
+impl QSdf for QCompound {
+    /// Union of the fields of all children.
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        self.world_children()
+            .iter()
+            .map(|shape| shape.signed_distance(point))
+            .reduce(|a, b| a.min(b))
+            .expect("[QCompound::signed_distance] Children must not be empty.")
+    }
+}
+
+impl<S: QShapeCommon + QSupportMap + Clone> QSdf for QTransformedShape<S> {
+    /// Measured against the world space vertices, so curved base shapes are approximated.
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        match self.get_shape_type() {
+            QShapeType::QPoint | QShapeType::QLine | QShapeType::QPolyline => self.closest_point(point).distance(point),
+            _ => signed_distance_to_ring(&self.points(), self.is_point_inside(point), point),
+        }
+    }
+}
+
+/// Union of two fields, inside if inside either.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QSdfUnion<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: QSdf, B: QSdf> QSdfUnion<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+
+    pub fn a(&self) -> &A {
+        &self.a
+    }
+
+    pub fn b(&self) -> &B {
+        &self.b
+    }
+}
+
+impl<A: QSdf, B: QSdf> QSdf for QSdfUnion<A, B> {
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        self.a.signed_distance(point).min(self.b.signed_distance(point))
+    }
+}
+
+/// Intersection of two fields, inside if inside both.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QSdfIntersection<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: QSdf, B: QSdf> QSdfIntersection<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+
+    pub fn a(&self) -> &A {
+        &self.a
+    }
+
+    pub fn b(&self) -> &B {
+        &self.b
+    }
+}
+
+impl<A: QSdf, B: QSdf> QSdf for QSdfIntersection<A, B> {
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        self.a.signed_distance(point).max(self.b.signed_distance(point))
+    }
+}
+
+/// Field `a` with field `b` carved out of it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QSdfSubtraction<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: QSdf, B: QSdf> QSdfSubtraction<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+
+    pub fn a(&self) -> &A {
+        &self.a
+    }
+
+    pub fn b(&self) -> &B {
+        &self.b
+    }
+}
+
+impl<A: QSdf, B: QSdf> QSdf for QSdfSubtraction<A, B> {
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::sdf::QSdf;
+    ///
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(4.0, 4.0));
+    /// let hole = QCircle::new(QPoint::new(qvec2!(2.0, 2.0)), Q64::ONE);
+    /// let field = bbox.sdf_subtraction(hole);
+    /// assert!(field.signed_distance(&QPoint::new(qvec2!(2.0, 2.0))) > Q64::ZERO);
+    /// assert!(field.signed_distance(&QPoint::new(qvec2!(0.5, 0.5))) < Q64::ZERO);
+    /// ```
+    fn signed_distance(&self, point: &QPoint) -> Q64 {
+        self.a.signed_distance(point).max(-self.b.signed_distance(point))
+    }
+}
+
+/// Signed distances sampled on a regular grid, for fast approximate queries against static shapes.
+///
+/// Samples sit on the grid corners `origin + (i, j) * cell_size`, values between them are
+/// bilinearly interpolated and points outside the grid are clamped to its border.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QSdfGrid {
+    origin: QVec2,
+    cell_size: Q64,
+    columns: usize,
+    rows: usize,
+    values: Vec<Q64>,
+}
+
+impl QSdfGrid {
+    /// Create a grid from samples stored row by row, e.g. a density map or a loaded bake.
+    pub fn new(origin: QVec2, cell_size: Q64, columns: usize, rows: usize, values: Vec<Q64>) -> Self {
+        assert!(cell_size > Q64::ZERO, "[QSdfGrid::new] Cell size({cell_size:?}) must be positive.");
+        assert!(columns >= 2 && rows >= 2, "[QSdfGrid::new] Grid({columns}x{rows}) needs at least 2x2 samples.");
+        assert!(values.len() == columns * rows, "[QSdfGrid::new] Values({}) don't match the {columns}x{rows} grid.", values.len());
+        Self {
+            origin,
+            cell_size,
+            columns,
+            rows,
+            values,
+        }
+    }
+
+    pub fn origin(&self) -> QVec2 {
+        self.origin
+    }
+
+    pub fn cell_size(&self) -> Q64 {
+        self.cell_size
+    }
+
+    /// Number of samples along x.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Number of samples along y.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Get the sample at column `i` and row `j`.
+    pub fn value(&self, i: usize, j: usize) -> Q64 {
+        assert!(i < self.columns && j < self.rows, "[QSdfGrid::value] Sample ({i}, {j}) is out of the {}x{} grid.", self.columns, self.rows);
+        self.values[j * self.columns + i]
+    }
+
+    /// Bilinearly interpolated distance at `point`.
+    pub fn sample(&self, point: &QPoint) -> Q64 {
+        let (i, tx) = Self::locate(point.x().saturating_sub(self.origin.x), self.cell_size, self.columns);
+        let (j, ty) = Self::locate(point.y().saturating_sub(self.origin.y), self.cell_size, self.rows);
+        let bottom = lerp(self.value(i, j), self.value(i + 1, j), tx);
+        let top = lerp(self.value(i, j + 1), self.value(i + 1, j + 1), tx);
+        lerp(bottom, top, ty)
+    }
+
+    /// Gradient of the interpolated field at `point`, by central differences of one cell.
+    pub fn gradient(&self, point: &QPoint) -> QVec2 {
+        let h = self.cell_size.saturating_mul(Q64::HALF);
+        let dx = self.sample(&QPoint::new(point.pos().saturating_add(QVec2::new(h, Q64::ZERO))))
+            .saturating_sub(self.sample(&QPoint::new(point.pos().saturating_sub(QVec2::new(h, Q64::ZERO)))));
+        let dy = self.sample(&QPoint::new(point.pos().saturating_add(QVec2::new(Q64::ZERO, h))))
+            .saturating_sub(self.sample(&QPoint::new(point.pos().saturating_sub(QVec2::new(Q64::ZERO, h)))));
+        QVec2::new(dx.saturating_div(self.cell_size), dy.saturating_div(self.cell_size))
+    }
+
+    /// Direction of increasing distance at `point`, `None` where the field is flat.
+    pub fn normal(&self, point: &QPoint) -> Option<QDir> {
+        let gradient = self.gradient(point);
+        if gradient == QVec2::ZERO {
+            return None;
+        }
+        Some(QDir::new_from_vec(gradient))
+    }
+
+    /// Split an offset from the origin into a cell index and the fraction within that cell.
+    fn locate(offset: Q64, cell_size: Q64, samples: usize) -> (usize, Q64) {
+        let max_cell = samples - 2;
+        let cells = offset.saturating_div(cell_size).max(Q64::ZERO);
+        let index = cells.floor().to_num::<usize>().min(max_cell);
+        let fraction = cells.saturating_sub(q64!(index)).min(Q64::ONE);
+        (index, fraction)
+    }
+}
+
+fn lerp(a: Q64, b: Q64, t: Q64) -> Q64 {
+    a.saturating_add(b.saturating_sub(a).saturating_mul(t))
+}
+
+/// Sample the signed distance of `shape` over `bbox` every `cell_size`.
+///
+/// The grid covers at least the whole bbox, its last row and column may extend past it.
+///
+/// # Examples
+/// ```
+/// use qmath::prelude::*;
+/// use qmath::vec2::QVec2;
+/// use qgeometry::prelude::*;
+/// use qgeometry::sdf::{ self, QSdf };
+///
+/// let circle = QCircle::new(QPoint::ZERO, q64!(2));
+/// let grid = sdf::bake(&circle, &QBbox::new_from_parts(qvec2!(-4.0, -4.0), qvec2!(4.0, 4.0)), Q64::ONE);
+/// assert_eq!(grid.columns(), 9);
+/// assert_eq!(grid.sample(&QPoint::ZERO), q64!(-2));
+/// assert_eq!(grid.sample(&QPoint::new(qvec2!(3.0, 0.0))), circle.signed_distance(&QPoint::new(qvec2!(3.0, 0.0))));
+/// ```
+pub fn bake(shape: &impl QSdf, bbox: &QBbox, cell_size: Q64) -> QSdfGrid {
+    assert!(cell_size > Q64::ZERO, "[sdf::bake] Cell size({cell_size:?}) must be positive.");
+    let origin = bbox.left_bottom().pos();
+    let columns = bbox.width().saturating_div(cell_size).ceil().to_num::<usize>() + 1;
+    let rows = bbox.height().saturating_div(cell_size).ceil().to_num::<usize>() + 1;
+    let mut values = Vec::with_capacity(columns * rows);
+    for j in 0..rows {
+        let y = origin.y.saturating_add(cell_size.saturating_mul(q64!(j)));
+        for i in 0..columns {
+            let x = origin.x.saturating_add(cell_size.saturating_mul(q64!(i)));
+            values.push(shape.signed_distance(&QPoint::new(QVec2::new(x, y))));
+        }
+    }
+    QSdfGrid {
+        origin,
+        cell_size,
+        columns,
+        rows,
+        values,
+    }
+}
@@ -0,0 +1,360 @@
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use crate::generate::QRng;
+use crate::mesh::QMesh;
+use super::{ QPoint, QLine, QBbox, QCircle, QPolygon, QPolyline, QShapeCommon, QShapeType };
+
+/// Any of the concrete shapes, for storing shapes of different kinds together.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum QShape {
+    QPoint(QPoint),
+    QLine(QLine),
+    QBbox(QBbox),
+    QCircle(QCircle),
+    QPolygon(QPolygon),
+    QPolyline(QPolyline),
+}
+
+impl From<QPoint> for QShape {
+    fn from(shape: QPoint) -> Self {
+        QShape::QPoint(shape)
+    }
+}
+
+impl From<QLine> for QShape {
+    fn from(shape: QLine) -> Self {
+        QShape::QLine(shape)
+    }
+}
+
+impl From<QBbox> for QShape {
+    fn from(shape: QBbox) -> Self {
+        QShape::QBbox(shape)
+    }
+}
+
+impl From<QCircle> for QShape {
+    fn from(shape: QCircle) -> Self {
+        QShape::QCircle(shape)
+    }
+}
+
+impl From<QPolygon> for QShape {
+    fn from(shape: QPolygon) -> Self {
+        QShape::QPolygon(shape)
+    }
+}
+
+impl From<QPolyline> for QShape {
+    fn from(shape: QPolyline) -> Self {
+        QShape::QPolyline(shape)
+    }
+}
+
+/// Points of whichever variant a `QShape` holds, dispatched without boxing.
+enum QShapePointsIter<P, L, B, C, G, Y> {
+    QPoint(P),
+    QLine(L),
+    QBbox(B),
+    QCircle(C),
+    QPolygon(G),
+    QPolyline(Y),
+}
+
+impl<P, L, B, C, G, Y> Iterator for QShapePointsIter<P, L, B, C, G, Y>
+where
+    P: Iterator<Item = QPoint>,
+    L: Iterator<Item = QPoint>,
+    B: Iterator<Item = QPoint>,
+    C: Iterator<Item = QPoint>,
+    G: Iterator<Item = QPoint>,
+    Y: Iterator<Item = QPoint>,
+{
+    type Item = QPoint;
+
+    fn next(&mut self) -> Option<QPoint> {
+        match self {
+            QShapePointsIter::QPoint(iter) => iter.next(),
+            QShapePointsIter::QLine(iter) => iter.next(),
+            QShapePointsIter::QBbox(iter) => iter.next(),
+            QShapePointsIter::QCircle(iter) => iter.next(),
+            QShapePointsIter::QPolygon(iter) => iter.next(),
+            QShapePointsIter::QPolyline(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            QShapePointsIter::QPoint(iter) => iter.size_hint(),
+            QShapePointsIter::QLine(iter) => iter.size_hint(),
+            QShapePointsIter::QBbox(iter) => iter.size_hint(),
+            QShapePointsIter::QCircle(iter) => iter.size_hint(),
+            QShapePointsIter::QPolygon(iter) => iter.size_hint(),
+            QShapePointsIter::QPolyline(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl QShapeCommon for QShape {
+    type Rotated = QShape;
+
+    fn points(&self) -> Vec<QPoint> {
+        match self {
+            QShape::QPoint(shape) => shape.points(),
+            QShape::QLine(shape) => shape.points(),
+            QShape::QBbox(shape) => shape.points(),
+            QShape::QCircle(shape) => shape.points(),
+            QShape::QPolygon(shape) => QShapeCommon::points(shape),
+            QShape::QPolyline(shape) => QShapeCommon::points(shape),
+        }
+    }
+
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
+        match self {
+            QShape::QPoint(shape) => QShapePointsIter::QPoint(shape.points_iter()),
+            QShape::QLine(shape) => QShapePointsIter::QLine(shape.points_iter()),
+            QShape::QBbox(shape) => QShapePointsIter::QBbox(shape.points_iter()),
+            QShape::QCircle(shape) => QShapePointsIter::QCircle(shape.points_iter()),
+            QShape::QPolygon(shape) => QShapePointsIter::QPolygon(shape.points_iter()),
+            QShape::QPolyline(shape) => QShapePointsIter::QPolyline(shape.points_iter()),
+        }
+    }
+
+    fn points_slice(&self) -> Option<&[QPoint]> {
+        match self {
+            QShape::QPolygon(shape) => shape.points_slice(),
+            QShape::QPolyline(shape) => shape.points_slice(),
+            _ => None,
+        }
+    }
+
+    fn get_bbox(&self) -> QBbox {
+        match self {
+            QShape::QPoint(shape) => shape.get_bbox(),
+            QShape::QLine(shape) => shape.get_bbox(),
+            QShape::QBbox(shape) => shape.get_bbox(),
+            QShape::QCircle(shape) => shape.get_bbox(),
+            QShape::QPolygon(shape) => shape.get_bbox(),
+            QShape::QPolyline(shape) => shape.get_bbox(),
+        }
+    }
+
+    fn get_centroid(&self) -> QPoint {
+        match self {
+            QShape::QPoint(shape) => shape.get_centroid(),
+            QShape::QLine(shape) => shape.get_centroid(),
+            QShape::QBbox(shape) => shape.get_centroid(),
+            QShape::QCircle(shape) => shape.get_centroid(),
+            QShape::QPolygon(shape) => shape.get_centroid(),
+            QShape::QPolyline(shape) => shape.get_centroid(),
+        }
+    }
+
+    fn get_shape_type(&self) -> QShapeType {
+        match self {
+            QShape::QPoint(shape) => shape.get_shape_type(),
+            QShape::QLine(shape) => shape.get_shape_type(),
+            QShape::QBbox(shape) => shape.get_shape_type(),
+            QShape::QCircle(shape) => shape.get_shape_type(),
+            QShape::QPolygon(shape) => shape.get_shape_type(),
+            QShape::QPolyline(shape) => shape.get_shape_type(),
+        }
+    }
+
+    fn is_point_inside(&self, point: &QPoint) -> bool {
+        match self {
+            QShape::QPoint(shape) => shape.is_point_inside(point),
+            QShape::QLine(shape) => shape.is_point_inside(point),
+            QShape::QBbox(shape) => shape.is_point_inside(point),
+            QShape::QCircle(shape) => shape.is_point_inside(point),
+            QShape::QPolygon(shape) => shape.is_point_inside(point),
+            QShape::QPolyline(shape) => shape.is_point_inside(point),
+        }
+    }
+
+    fn is_collide(&self, other: &impl QShapeCommon) -> bool {
+        match self {
+            QShape::QPoint(shape) => shape.is_collide(other),
+            QShape::QLine(shape) => shape.is_collide(other),
+            QShape::QBbox(shape) => shape.is_collide(other),
+            QShape::QCircle(shape) => shape.is_collide(other),
+            QShape::QPolygon(shape) => shape.is_collide(other),
+            QShape::QPolyline(shape) => shape.is_collide(other),
+        }
+    }
+
+    fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
+        match self {
+            QShape::QPoint(shape) => shape.try_get_seperation_vector(other),
+            QShape::QLine(shape) => shape.try_get_seperation_vector(other),
+            QShape::QBbox(shape) => shape.try_get_seperation_vector(other),
+            QShape::QCircle(shape) => shape.try_get_seperation_vector(other),
+            QShape::QPolygon(shape) => shape.try_get_seperation_vector(other),
+            QShape::QPolyline(shape) => shape.try_get_seperation_vector(other),
+        }
+    }
+
+    fn translated(&self, offset: QVec2) -> Self {
+        match self {
+            QShape::QPoint(shape) => QShape::QPoint(shape.translated(offset)),
+            QShape::QLine(shape) => QShape::QLine(shape.translated(offset)),
+            QShape::QBbox(shape) => QShape::QBbox(shape.translated(offset)),
+            QShape::QCircle(shape) => QShape::QCircle(shape.translated(offset)),
+            QShape::QPolygon(shape) => QShape::QPolygon(shape.translated(offset)),
+            QShape::QPolyline(shape) => QShape::QPolyline(shape.translated(offset)),
+        }
+    }
+
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> QShape {
+        match self {
+            QShape::QPoint(shape) => QShape::QPoint(shape.rotated_around(pivot, angle)),
+            QShape::QLine(shape) => QShape::QLine(shape.rotated_around(pivot, angle)),
+            QShape::QBbox(shape) => QShape::QPolygon(shape.rotated_around(pivot, angle)),
+            QShape::QCircle(shape) => QShape::QCircle(shape.rotated_around(pivot, angle)),
+            QShape::QPolygon(shape) => QShape::QPolygon(shape.rotated_around(pivot, angle)),
+            QShape::QPolyline(shape) => QShape::QPolyline(shape.rotated_around(pivot, angle)),
+        }
+    }
+
+    fn scaled(&self, factor: Q64) -> Self {
+        match self {
+            QShape::QPoint(shape) => QShape::QPoint(shape.scaled(factor)),
+            QShape::QLine(shape) => QShape::QLine(shape.scaled(factor)),
+            QShape::QBbox(shape) => QShape::QBbox(shape.scaled(factor)),
+            QShape::QCircle(shape) => QShape::QCircle(shape.scaled(factor)),
+            QShape::QPolygon(shape) => QShape::QPolygon(shape.scaled(factor)),
+            QShape::QPolyline(shape) => QShape::QPolyline(shape.scaled(factor)),
+        }
+    }
+
+    fn closest_point(&self, point: &QPoint) -> QPoint {
+        match self {
+            QShape::QPoint(shape) => shape.closest_point(point),
+            QShape::QLine(shape) => shape.closest_point(point),
+            QShape::QBbox(shape) => shape.closest_point(point),
+            QShape::QCircle(shape) => shape.closest_point(point),
+            QShape::QPolygon(shape) => shape.closest_point(point),
+            QShape::QPolyline(shape) => shape.closest_point(point),
+        }
+    }
+
+    fn boundary_point(&self, t: Q64) -> QPoint {
+        match self {
+            QShape::QPoint(shape) => shape.boundary_point(t),
+            QShape::QLine(shape) => shape.boundary_point(t),
+            QShape::QBbox(shape) => shape.boundary_point(t),
+            QShape::QCircle(shape) => shape.boundary_point(t),
+            QShape::QPolygon(shape) => shape.boundary_point(t),
+            QShape::QPolyline(shape) => shape.boundary_point(t),
+        }
+    }
+
+    fn convex_hull(&self) -> QPolygon {
+        match self {
+            QShape::QPoint(shape) => shape.convex_hull(),
+            QShape::QLine(shape) => shape.convex_hull(),
+            QShape::QBbox(shape) => shape.convex_hull(),
+            QShape::QCircle(shape) => shape.convex_hull(),
+            QShape::QPolygon(shape) => shape.convex_hull(),
+            QShape::QPolyline(shape) => shape.convex_hull(),
+        }
+    }
+
+    fn tangent_at(&self, t: Q64) -> QDir {
+        match self {
+            QShape::QPoint(shape) => shape.tangent_at(t),
+            QShape::QLine(shape) => shape.tangent_at(t),
+            QShape::QBbox(shape) => shape.tangent_at(t),
+            QShape::QCircle(shape) => shape.tangent_at(t),
+            QShape::QPolygon(shape) => shape.tangent_at(t),
+            QShape::QPolyline(shape) => shape.tangent_at(t),
+        }
+    }
+
+    fn boundary_normal_at(&self, point: &QPoint) -> QDir {
+        match self {
+            QShape::QPoint(shape) => shape.boundary_normal_at(point),
+            QShape::QLine(shape) => shape.boundary_normal_at(point),
+            QShape::QBbox(shape) => shape.boundary_normal_at(point),
+            QShape::QCircle(shape) => shape.boundary_normal_at(point),
+            QShape::QPolygon(shape) => shape.boundary_normal_at(point),
+            QShape::QPolyline(shape) => shape.boundary_normal_at(point),
+        }
+    }
+
+    fn distance_to(&self, other: &impl QShapeCommon) -> Q64 {
+        match self {
+            QShape::QPoint(shape) => shape.distance_to(other),
+            QShape::QLine(shape) => shape.distance_to(other),
+            QShape::QBbox(shape) => shape.distance_to(other),
+            QShape::QCircle(shape) => shape.distance_to(other),
+            QShape::QPolygon(shape) => shape.distance_to(other),
+            QShape::QPolyline(shape) => shape.distance_to(other),
+        }
+    }
+
+    fn contains(&self, other: &impl QShapeCommon) -> bool {
+        match self {
+            QShape::QPoint(shape) => shape.contains(other),
+            QShape::QLine(shape) => shape.contains(other),
+            QShape::QBbox(shape) => shape.contains(other),
+            QShape::QCircle(shape) => shape.contains(other),
+            QShape::QPolygon(shape) => shape.contains(other),
+            QShape::QPolyline(shape) => shape.contains(other),
+        }
+    }
+
+    fn project_onto(&self, dir: QDir) -> (Q64, Q64) {
+        match self {
+            QShape::QPoint(shape) => shape.project_onto(dir),
+            QShape::QLine(shape) => shape.project_onto(dir),
+            QShape::QBbox(shape) => shape.project_onto(dir),
+            QShape::QCircle(shape) => shape.project_onto(dir),
+            QShape::QPolygon(shape) => shape.project_onto(dir),
+            QShape::QPolyline(shape) => shape.project_onto(dir),
+        }
+    }
+
+    fn get_support_point(&self, dir: QDir) -> QPoint {
+        match self {
+            QShape::QPoint(shape) => shape.get_support_point(dir),
+            QShape::QLine(shape) => shape.get_support_point(dir),
+            QShape::QBbox(shape) => shape.get_support_point(dir),
+            QShape::QCircle(shape) => shape.get_support_point(dir),
+            QShape::QPolygon(shape) => shape.get_support_point(dir),
+            QShape::QPolyline(shape) => shape.get_support_point(dir),
+        }
+    }
+
+    fn to_mesh(&self, resolution: usize) -> QMesh {
+        match self {
+            QShape::QPoint(shape) => shape.to_mesh(resolution),
+            QShape::QLine(shape) => shape.to_mesh(resolution),
+            QShape::QBbox(shape) => shape.to_mesh(resolution),
+            QShape::QCircle(shape) => shape.to_mesh(resolution),
+            QShape::QPolygon(shape) => shape.to_mesh(resolution),
+            QShape::QPolyline(shape) => shape.to_mesh(resolution),
+        }
+    }
+
+    fn precomputed_normals(&self) -> Option<&[QDir]> {
+        match self {
+            QShape::QPolygon(shape) => shape.precomputed_normals(),
+            _ => None,
+        }
+    }
+
+    fn sample_interior(&self, rng: &mut impl QRng) -> QPoint {
+        match self {
+            QShape::QPoint(shape) => shape.sample_interior(rng),
+            QShape::QLine(shape) => shape.sample_interior(rng),
+            QShape::QBbox(shape) => shape.sample_interior(rng),
+            QShape::QCircle(shape) => shape.sample_interior(rng),
+            QShape::QPolygon(shape) => shape.sample_interior(rng),
+            QShape::QPolyline(shape) => shape.sample_interior(rng),
+        }
+    }
+}
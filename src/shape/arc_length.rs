@@ -0,0 +1,134 @@
+use alloc::{ vec, vec::Vec };
+use qmath::prelude::*;
+use super::{ QPoint, QPolyline };
+
+/// Arc-length parameterization of a polyline.
+///
+/// Keeps a lookup table of cumulative lengths so points can be sampled by travelled distance
+/// instead of by vertex index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QArcLengthParam {
+    points: Vec<QPoint>,
+    cumulative_lengths: Vec<Q64>,
+}
+
+impl QArcLengthParam {
+    pub fn new(polyline: &QPolyline) -> Self {
+        let points = polyline.points().clone();
+        let mut cumulative_lengths = Vec::with_capacity(points.len());
+        let mut total = Q64::ZERO;
+        for (i, point) in points.iter().enumerate() {
+            if i > 0 {
+                total = total.saturating_add(points[i - 1].distance(point));
+            }
+            cumulative_lengths.push(total);
+        }
+        Self {
+            points,
+            cumulative_lengths,
+        }
+    }
+
+    pub fn points(&self) -> &Vec<QPoint> {
+        &self.points
+    }
+
+    /// Cumulative length at each vertex, the first one is always zero.
+    pub fn cumulative_lengths(&self) -> &Vec<Q64> {
+        &self.cumulative_lengths
+    }
+
+    /// Total length of the path.
+    pub fn length(&self) -> Q64 {
+        self.cumulative_lengths.last().copied().unwrap_or(Q64::ZERO)
+    }
+
+    /// Get the point after travelling `s` along the path, `s` is clamped to [0, length].
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(1.0, 3.0)]);
+    /// let param = QArcLengthParam::new(&polyline);
+    /// assert_eq!(param.length(), q64!(4));
+    /// assert_eq!(param.point_at_length(Q64::HALF).pos(), qvec2!(0.5, 0.0));
+    /// assert_eq!(param.point_at_length(q64!(2)).pos(), qvec2!(1.0, 1.0));
+    /// assert_eq!(param.point_at_length(q64!(10)).pos(), qvec2!(1.0, 3.0));
+    /// ```
+    pub fn point_at_length(&self, s: Q64) -> QPoint {
+        assert!(!self.points.is_empty(), "[QArcLengthParam::point_at_length] Points must not be empty.");
+        if s <= Q64::ZERO {
+            return self.points[0];
+        }
+        if s >= self.length() {
+            return self.points[self.points.len() - 1];
+        }
+
+        // First vertex whose cumulative length is not less than s.
+        let i = self.cumulative_lengths.partition_point(|&length| length < s).max(1);
+        let start_length = self.cumulative_lengths[i - 1];
+        let segment_length = self.cumulative_lengths[i].saturating_sub(start_length);
+        if segment_length == Q64::ZERO {
+            return self.points[i];
+        }
+
+        let t = s.saturating_sub(start_length).saturating_div(segment_length);
+        let start = self.points[i - 1].pos();
+        let end = self.points[i].pos();
+        QPoint::new(start.saturating_add(end.saturating_sub(start).saturating_mul_num(t)))
+    }
+
+    /// Resample the path with points evenly spaced by `spacing`.
+    ///
+    /// The last point of the path is always kept, so the final interval may be shorter.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(1.0, 3.0)]);
+    /// let resampled = QArcLengthParam::new(&polyline).resample(Q64::ONE);
+    /// assert_eq!(resampled.points().len(), 5);
+    /// assert_eq!(resampled.points()[2].pos(), qvec2!(1.0, 1.0));
+    /// ```
+    pub fn resample(&self, spacing: Q64) -> QPolyline {
+        assert!(spacing > Q64::ZERO, "[QArcLengthParam::resample] spacing({spacing:?}) should be larger than zero.");
+        if self.points.is_empty() {
+            return QPolyline::new(vec![]);
+        }
+
+        let length = self.length();
+        let mut points = vec![];
+        let mut s = Q64::ZERO;
+        while s < length {
+            points.push(self.point_at_length(s));
+            s = s.saturating_add(spacing);
+        }
+        points.push(self.points[self.points.len() - 1]);
+        QPolyline::new(points)
+    }
+
+    /// Resample the path with `count` points evenly spaced from start to end.
+    pub fn resample_count(&self, count: usize) -> QPolyline {
+        assert!(count >= 2, "[QArcLengthParam::resample_count] count({count}) should be at least 2.");
+        if self.points.is_empty() {
+            return QPolyline::new(vec![]);
+        }
+
+        let step = self.length().saturating_div(q64!(count - 1));
+        let points = (0..count)
+            .map(|i| {
+                if i == count - 1 {
+                    self.points[self.points.len() - 1]
+                } else {
+                    self.point_at_length(step.saturating_mul(q64!(i)))
+                }
+            })
+            .collect();
+        QPolyline::new(points)
+    }
+}
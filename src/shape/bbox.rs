@@ -1,10 +1,14 @@
+use alloc::vec::Vec;
 use qmath::prelude::*;
 use qmath::vec2::QVec2;
-use serde::{Deserialize, Serialize};
 use crate::algorithm::{epa, gjk};
-use super::{ QPoint, QPolygon, QShapeCommon, QShapeType };
+use crate::generate::QRng;
+use crate::transform::QTransform;
+use super::support_map::QShapeSupport;
+use super::{ QPoint, QPolygon, QShapeCommon, QShapeType, get_wrapped_fraction };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct QBbox {
     left_bottom: QPoint,
     right_top: QPoint,
@@ -26,6 +30,65 @@ impl QBbox {
         Self::new(QPoint::new(left_bottom), QPoint::new(right_top))
     }
 
+    /// Create a bbox from its center and half of its width and height.
+    pub fn from_center_half_extents(center: QVec2, half_extents: QVec2) -> Self {
+        Self::new_from_parts(center.saturating_sub(half_extents), center.saturating_add(half_extents))
+    }
+
+    /// Create a bbox from its left bottom corner and its width and height.
+    pub fn from_min_size(min: QVec2, size: QVec2) -> Self {
+        Self::new_from_parts(min, min.saturating_add(size))
+    }
+
+    /// Create a bbox from any two opposite corners, in any order.
+    ///
+    /// Axes without extent are grown by `Q64::EPS` like in `from_points`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::from_unordered_corners(qvec2!(3.0, -1.0), qvec2!(1.0, 2.0));
+    /// assert_eq!(bbox, QBbox::new_from_parts(qvec2!(1.0, -1.0), qvec2!(3.0, 2.0)));
+    /// assert_eq!(bbox, QBbox::from_center_half_extents(qvec2!(2.0, 0.5), qvec2!(1.0, 1.5)));
+    /// assert_eq!(bbox, QBbox::from_min_size(qvec2!(1.0, -1.0), qvec2!(2.0, 3.0)));
+    /// ```
+    pub fn from_unordered_corners(a: QVec2, b: QVec2) -> Self {
+        Self::from_points([QPoint::new(a), QPoint::new(b)]).expect("[QBbox::from_unordered_corners] Two corners always give a bbox.")
+    }
+
+    /// Get the smallest bbox containing every point, `None` if there are none.
+    ///
+    /// Axes without extent, e.g. for a single point, are grown by `Q64::EPS` on both sides.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let points = [QPoint::new(qvec2!(1.0, 4.0)), QPoint::new(qvec2!(-2.0, 0.0)), QPoint::new(qvec2!(3.0, 1.0))];
+    /// assert_eq!(QBbox::from_points(points), Some(QBbox::new_from_parts(qvec2!(-2.0, 0.0), qvec2!(3.0, 4.0))));
+    /// assert_eq!(QBbox::from_points(Vec::new()), None);
+    /// ```
+    pub fn from_points(points: impl IntoIterator<Item = QPoint>) -> Option<QBbox> {
+        let mut points = points.into_iter();
+        let first = points.next()?.pos();
+        let (mut min, mut max) = points.fold((first, first), |(min, max), point| (min.min(point.pos()), max.max(point.pos())));
+        if min.x == max.x {
+            (min.x, max.x) = (min.x.saturating_sub(Q64::EPS), max.x.saturating_add(Q64::EPS));
+        }
+        if min.y == max.y {
+            (min.y, max.y) = (min.y.saturating_sub(Q64::EPS), max.y.saturating_add(Q64::EPS));
+        }
+        Some(QBbox::new_from_parts(min, max))
+    }
+
+    /// Get the smallest bbox containing every shape, `None` if there are none.
+    pub fn enclosing(shapes: &[impl QShapeCommon]) -> Option<QBbox> {
+        shapes.iter().map(|shape| shape.get_bbox()).reduce(|acc, bbox| acc.union(&bbox))
+    }
+
     pub fn left_bottom(&self) -> QPoint {
         self.left_bottom
     }
@@ -50,6 +113,26 @@ impl QBbox {
         self.right_top = right_top;
     }
 
+    /// Create a bbox from float corners, see `QPoint::from_f64`.
+    pub fn from_f64(left_bottom: [f64; 2], right_top: [f64; 2]) -> Self {
+        Self::new(QPoint::from_f64(left_bottom), QPoint::from_f64(right_top))
+    }
+
+    /// Create a bbox from `f32` corners, see `QPoint::from_f64`.
+    pub fn from_f32(left_bottom: [f32; 2], right_top: [f32; 2]) -> Self {
+        Self::new(QPoint::from_f32(left_bottom), QPoint::from_f32(right_top))
+    }
+
+    /// Get the left bottom and right top corners rounded to the nearest `f64`.
+    pub fn to_f64(&self) -> [[f64; 2]; 2] {
+        [self.left_bottom.to_f64(), self.right_top.to_f64()]
+    }
+
+    /// Get the left bottom and right top corners rounded to the nearest `f32`.
+    pub fn to_f32(&self) -> [[f32; 2]; 2] {
+        [self.left_bottom.to_f32(), self.right_top.to_f32()]
+    }
+
     pub fn width(&self) -> Q64 {
         self.right_top.x().saturating_sub(self.left_bottom.x())
     }
@@ -57,11 +140,66 @@ impl QBbox {
     pub fn height(&self) -> Q64 {
         self.right_top.y().saturating_sub(self.left_bottom.y())
     }
-}
 
-impl QShapeCommon for QBbox {
-    fn points(&self) -> Vec<QPoint> {
-        vec![
+    /// Return true if the two bboxes overlap, touching edges count as overlap.
+    pub fn is_overlap(&self, other: &QBbox) -> bool {
+        self.left_bottom.x() <= other.right_top.x()
+            && other.left_bottom.x() <= self.right_top.x()
+            && self.left_bottom.y() <= other.right_top.y()
+            && other.left_bottom.y() <= self.right_top.y()
+    }
+
+    /// Return true if `other` is fully inside the bbox.
+    pub fn is_bbox_inside(&self, other: &QBbox) -> bool {
+        self.left_bottom.x() <= other.left_bottom.x()
+            && self.left_bottom.y() <= other.left_bottom.y()
+            && other.right_top.x() <= self.right_top.x()
+            && other.right_top.y() <= self.right_top.y()
+    }
+
+    /// Get a copy of the bbox grown by `margin` on every side.
+    pub fn inflated(&self, margin: Q64) -> QBbox {
+        QBbox::new_from_parts(
+            self.left_bottom.pos().saturating_sub_num(margin),
+            self.right_top.pos().saturating_add_num(margin)
+        )
+    }
+
+    /// Get the bbox covering this one at its start and after moving by `velocity`.
+    ///
+    /// Every position along the straight move is covered, for continuous collision
+    /// broadphases.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE);
+    /// assert_eq!(bbox.swept_by(qvec2!(2.0, -1.0)), QBbox::new_from_parts(qvec2!(0.0, -1.0), qvec2!(3.0, 1.0)));
+    /// ```
+    pub fn swept_by(&self, velocity: QVec2) -> QBbox {
+        QBbox::new_from_parts(
+            self.left_bottom.pos().min(self.left_bottom.pos().saturating_add(velocity)),
+            self.right_top.pos().max(self.right_top.pos().saturating_add(velocity))
+        )
+    }
+
+    /// Perimeter of the bbox, the surface area heuristic cost in 2D.
+    pub fn perimeter(&self) -> Q64 {
+        self.width().saturating_add(self.height()).saturating_mul(q64!(2))
+    }
+
+    /// Get the squared distance from a point to the bbox, zero if the point is inside.
+    pub fn get_distance_squared_from_point(&self, point: &QPoint) -> Q64 {
+        let dx = self.left_bottom.x().saturating_sub(point.x()).max(point.x().saturating_sub(self.right_top.x())).max(Q64::ZERO);
+        let dy = self.left_bottom.y().saturating_sub(point.y()).max(point.y().saturating_sub(self.right_top.y())).max(Q64::ZERO);
+        dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy))
+    }
+
+    /// Get the four corners, clockwise from the left bottom one.
+    pub fn corners(&self) -> [QPoint; 4] {
+        [
             self.left_bottom,
             QPoint::new_from_parts(self.left_bottom.x(), self.right_top.y()),
             self.right_top,
@@ -69,6 +207,26 @@ impl QShapeCommon for QBbox {
         ]
     }
 
+    /// Get the smallest bbox containing both bboxes.
+    pub fn union(&self, other: &QBbox) -> QBbox {
+        QBbox::new_from_parts(
+            self.left_bottom.pos().min(other.left_bottom.pos()),
+            self.right_top.pos().max(other.right_top.pos())
+        )
+    }
+}
+
+impl QShapeCommon for QBbox {
+    type Rotated = QPolygon;
+
+    fn points(&self) -> Vec<QPoint> {
+        self.corners().to_vec()
+    }
+
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
+        self.corners().into_iter()
+    }
+
     fn get_bbox(&self) -> QBbox {
         *self
     }
@@ -92,10 +250,9 @@ impl QShapeCommon for QBbox {
     fn is_collide(&self, other: &impl QShapeCommon) -> bool {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.is_collide(self),
             _ => {
-                let my_polygon = QPolygon::new(self.points());
-                let other_polygon = QPolygon::new(other.points());
-                gjk(&my_polygon, &other_polygon)
+                gjk(self, &QShapeSupport(other))
             }
         }
     }
@@ -103,11 +260,81 @@ impl QShapeCommon for QBbox {
     fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.try_get_seperation_vector(self).map(|vector| -vector),
             _ => {
-                let my_polygon = QPolygon::new(self.points());
-                let other_polygon = QPolygon::new(other.points());
-                epa(&my_polygon, &other_polygon)
+                epa(self, &QShapeSupport(other))
             }
         }
     }
+
+    fn translated(&self, offset: QVec2) -> Self {
+        QBbox::new(self.left_bottom.translated(offset), self.right_top.translated(offset))
+    }
+
+    /// A rotated bbox is no longer axis aligned, so it is promoted to a polygon.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE);
+    /// let rotated = bbox.rotated_around(&QPoint::ZERO, Q64::PI);
+    /// assert_eq!(rotated.get_shape_type(), QShapeType::QPolygon);
+    /// assert_eq!(rotated.points().len(), 4);
+    /// ```
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> QPolygon {
+        self.get_polygon().transformed(&QTransform::from_rotation_around(pivot.pos(), angle))
+    }
+
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 2.0));
+    /// let scaled = bbox.scaled(q64!(2));
+    /// assert_eq!(scaled.left_bottom().pos(), qvec2!(-1.0, -1.0));
+    /// assert_eq!(scaled.right_top().pos(), qvec2!(3.0, 3.0));
+    /// ```
+    fn scaled(&self, factor: Q64) -> Self {
+        assert!(factor > Q64::ZERO, "[QBbox::scaled] factor({factor:?}) should be larger than zero.");
+        let center = self.get_centroid().pos();
+        let half_size = QVec2::new(self.width(), self.height()).saturating_mul_num(Q64::HALF).saturating_mul_num(factor);
+        QBbox::new_from_parts(center.saturating_sub(half_size), center.saturating_add(half_size))
+    }
+
+    fn closest_point(&self, point: &QPoint) -> QPoint {
+        let x = point.x().max(self.left_bottom.x()).min(self.right_top.x());
+        let y = point.y().max(self.left_bottom.y()).min(self.right_top.y());
+        QPoint::new(QVec2::new(x, y))
+    }
+
+    /// Walk the sides in the order of `corners`, up the left side first.
+    fn boundary_point(&self, t: Q64) -> QPoint {
+        let (left, bottom, right, top) = (self.left_bottom.x(), self.left_bottom.y(), self.right_top.x(), self.right_top.y());
+        let (width, height) = (self.width(), self.height());
+        let mut s = self.perimeter().saturating_mul(get_wrapped_fraction(t));
+        if s <= height {
+            return QPoint::new_from_parts(left, bottom.saturating_add(s));
+        }
+        s = s.saturating_sub(height);
+        if s <= width {
+            return QPoint::new_from_parts(left.saturating_add(s), top);
+        }
+        s = s.saturating_sub(width);
+        if s <= height {
+            return QPoint::new_from_parts(right, top.saturating_sub(s));
+        }
+        s = s.saturating_sub(height);
+        QPoint::new_from_parts(right.saturating_sub(s.min(width)), bottom)
+    }
+
+    fn sample_interior(&self, rng: &mut impl QRng) -> QPoint {
+        QPoint::new_from_parts(
+            rng.next_in_range(self.left_bottom.x()..=self.right_top.x()),
+            rng.next_in_range(self.left_bottom.y()..=self.right_top.y()),
+        )
+    }
 }
\ No newline at end of file
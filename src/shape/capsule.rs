@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use crate::mesh::QMesh;
+use super::{ QPoint, QLine, QBbox, QShapeCommon, QSupportMap };
+
+/// A segment swept by a circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QCapsule {
+    segment: QLine,
+    radius: Q64,
+}
+
+impl QCapsule {
+    pub fn new(segment: QLine, radius: Q64) -> Self {
+        assert!(radius > Q64::ZERO, "[QCapsule::new] radius({radius:?}) should be larger than zero.");
+        Self {
+            segment,
+            radius,
+        }
+    }
+
+    pub fn new_from_parts(start: QVec2, end: QVec2, radius: Q64) -> Self {
+        Self::new(QLine::new_from_parts(start, end), radius)
+    }
+
+    pub fn segment(&self) -> QLine {
+        self.segment
+    }
+
+    pub fn radius(&self) -> Q64 {
+        self.radius
+    }
+
+    pub fn set_segment(&mut self, segment: QLine) {
+        self.segment = segment;
+    }
+
+    pub fn set_radius(&mut self, radius: Q64) {
+        self.radius = radius;
+    }
+
+    pub fn get_bbox(&self) -> QBbox {
+        let left_bottom = self.segment.start().pos().min(self.segment.end().pos());
+        let right_top = self.segment.start().pos().max(self.segment.end().pos());
+        QBbox::new_from_parts(left_bottom.saturating_sub_num(self.radius), right_top.saturating_add_num(self.radius))
+    }
+
+    /// Get the capsule moved by `offset`.
+    pub fn translated(&self, offset: QVec2) -> Self {
+        Self::new(self.segment.translated(offset), self.radius)
+    }
+
+    pub fn is_point_inside(&self, point: &QPoint) -> bool {
+        let closest = self.segment.get_closest_point_from_point(point);
+        closest.pos().distance_squared(point.pos()) <= self.radius.saturating_mul(self.radius)
+    }
+
+    /// Do the capsules overlap, touching counts.
+    pub fn is_overlapping(&self, other: &QCapsule) -> bool {
+        self.segment.distance_to_segment(&other.segment) <= self.radius.saturating_add(other.radius)
+    }
+
+    /// A triangle fan over both caps, `resolution` segments for the two of them together
+    /// and at least one per cap.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let capsule = QCapsule::new_from_parts(QVec2::ZERO, qvec2!(4.0, 0.0), Q64::ONE);
+    /// let mesh = capsule.to_mesh(16);
+    /// assert_eq!(mesh.positions().len(), 18);
+    /// assert!((mesh.positions()[0] - qvec2!(4.0, -1.0)).length() < q64!(0.001));
+    /// assert_eq!(mesh.indices().len(), 16);
+    /// ```
+    pub fn to_mesh(&self, resolution: usize) -> QMesh {
+        let steps = (resolution / 2).max(1);
+        let vector = self.segment.vector();
+        let dir = if vector == QVec2::ZERO { QVec2::X } else { QDir::new_from_vec(vector).to_vec() };
+        let normal = QVec2::new(-dir.y, dir.x);
+        // Each cap runs half a turn, starting a quarter turn before its own side.
+        let cap = |center: QPoint, start_angle: Q64| {
+            (0..=steps).map(move |k| {
+                let angle = start_angle.saturating_add(Q64::PI.saturating_mul(q64!(k as i64)).saturating_div(q64!(steps as i64)));
+                let (sin, cos) = angle.sin_cos();
+                let offset = dir.saturating_mul_num(cos).saturating_add(normal.saturating_mul_num(sin));
+                center.pos().saturating_add(offset.saturating_mul_num(self.radius))
+            })
+        };
+        let quarter = Q64::PI.saturating_mul(Q64::HALF);
+        let positions: Vec<QVec2> = cap(self.segment.end(), -quarter).chain(cap(self.segment.start(), quarter)).collect();
+        let indices = (1..positions.len() as u32 - 1).map(|i| [0, i, i + 1]).collect();
+        QMesh::new(positions, indices)
+    }
+}
+
+impl QSupportMap for QCapsule {
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let capsule = QCapsule::new_from_parts(QVec2::ZERO, qvec2!(4.0, 0.0), Q64::ONE);
+    /// assert_eq!(capsule.support(QDir::new_from_vec(QVec2::X)).pos(), qvec2!(5.0, 0.0));
+    /// assert_eq!(capsule.support(QDir::new_from_vec(QVec2::Y)).pos(), qvec2!(4.0, 1.0));
+    /// ```
+    fn support(&self, dir: QDir) -> QPoint {
+        let end = self.segment.support(dir);
+        QPoint::new(end.pos().saturating_add(dir.to_vec().saturating_mul_num(self.radius)))
+    }
+}
@@ -1,15 +1,44 @@
+use alloc::vec::Vec;
 use qmath::prelude::*;
 use qmath::vec2::QVec2;
-use serde::{Deserialize, Serialize};
-use crate::algorithm::{epa, gjk};
-use super::{ QPoint, QBbox, QPolygon, QShapeCommon, QShapeType };
+use qmath::dir::QDir;
+use smallvec::{ smallvec, SmallVec };
+use crate::algorithm::{epa, get_circumcenter, gjk, sqrt};
+use crate::transform::QTransform;
+use crate::generate::QRng;
+use crate::mesh::QMesh;
+use super::support_map::{ QShapeSupport, QSupportMap };
+use super::{ QPoint, QLine, QBbox, QShapeCommon, QShapeType, get_wrapped_fraction };
+use super::point::q64_from_f64;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct QCircle {
     center: QPoint,
     radius: Q64,
 }
 
+/// How the boundaries of two circles meet, see `QCircle::intersect_circle`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QCircleIntersection {
+    /// The circles are apart or one is nested in the other.
+    Disjoint,
+    /// One point for tangent circles, two for crossing ones.
+    Points(SmallVec<[QPoint; 2]>),
+    /// Same center and radius, the boundaries share every point.
+    Coincident,
+}
+
+impl QCircleIntersection {
+    /// Get the crossing points, empty for disjoint and coincident circles.
+    pub fn points(&self) -> &[QPoint] {
+        match self {
+            QCircleIntersection::Points(points) => points,
+            _ => &[],
+        }
+    }
+}
+
 impl QCircle {
     pub fn new(center: QPoint, radius: Q64) -> Self {
         assert!(radius > Q64::ZERO, "[QCircle::new] radius({radius:?}) should be larger than zero.");
@@ -42,22 +71,188 @@ impl QCircle {
     pub fn set_radius(&mut self, radius: Q64) {
         self.radius = radius;
     }
+
+    /// Create a circle from float values, see `QPoint::from_f64`.
+    pub fn from_f64(center: [f64; 2], radius: f64) -> Self {
+        Self::new(QPoint::from_f64(center), q64_from_f64(radius))
+    }
+
+    /// Create a circle from `f32` values, see `QPoint::from_f64`.
+    pub fn from_f32(center: [f32; 2], radius: f32) -> Self {
+        Self::from_f64([center[0] as f64, center[1] as f64], radius as f64)
+    }
+
+    /// Get the center and radius rounded to the nearest `f64`.
+    pub fn to_f64(&self) -> ([f64; 2], f64) {
+        (self.center.to_f64(), self.radius.to_num::<f64>())
+    }
+
+    /// Get the center and radius rounded to the nearest `f32`.
+    pub fn to_f32(&self) -> ([f32; 2], f32) {
+        (self.center.to_f32(), self.radius.to_num::<f32>())
+    }
+
+    /// Get the circle passing through three points, `None` if they are collinear.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let (a, b, c) = (QPoint::new(qvec2!(0.0, 0.0)), QPoint::new(qvec2!(4.0, 0.0)), QPoint::new(qvec2!(0.0, 2.0)));
+    /// let circle = QCircle::from_three_points(&a, &b, &c).unwrap();
+    /// assert_eq!(circle.center().pos(), qvec2!(2.0, 1.0));
+    /// assert!(QCircle::from_three_points(&a, &b, &QPoint::new(qvec2!(8.0, 0.0))).is_none());
+    /// ```
+    pub fn from_three_points(a: &QPoint, b: &QPoint, c: &QPoint) -> Option<Self> {
+        let center = get_circumcenter(a.pos(), b.pos(), c.pos())?;
+        let radius = center.distance(a.pos());
+        (radius > Q64::ZERO).then(|| QCircle::new(QPoint::new(center), radius))
+    }
+
+    /// Get the tangent lines from an outside point, each running from the point to where it touches the circle.
+    ///
+    /// Empty if the point is inside or on the circle.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::ZERO, q64!(3));
+    /// let tangents = circle.tangents_from_point(&QPoint::new(qvec2!(5.0, 0.0)));
+    /// assert_eq!(tangents.len(), 2);
+    /// assert!((tangents[0].end().pos() - qvec2!(1.8, -2.4)).length() < q64!(0.001));
+    /// assert!(circle.tangents_from_point(&QPoint::new(qvec2!(1.0, 1.0))).is_empty());
+    /// ```
+    pub fn tangents_from_point(&self, point: &QPoint) -> Vec<QLine> {
+        get_tangent_normals(self.center.pos(), self.radius, point.pos(), Q64::ZERO)
+            .into_iter()
+            .map(|normal| self.center.pos().saturating_sub(normal.saturating_mul_num(self.radius)))
+            .filter(|touch| *touch != point.pos())
+            .map(|touch| QLine::new_from_parts(point.pos(), touch))
+            .collect()
+    }
+
+    /// Get the points where the boundaries of two circles cross.
+    ///
+    /// Tangent circles give a single point. Circles that are apart or nested are disjoint.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::ZERO, q64!(5));
+    /// let points = circle.intersect_circle(&QCircle::new(QPoint::new(qvec2!(8.0, 0.0)), q64!(5)));
+    /// assert_eq!(points.points(), &[QPoint::new(qvec2!(4.0, 3.0)), QPoint::new(qvec2!(4.0, -3.0))]);
+    /// let tangent = circle.intersect_circle(&QCircle::new(QPoint::new(qvec2!(0.0, 7.0)), q64!(2)));
+    /// assert_eq!(tangent.points(), &[QPoint::new(qvec2!(0.0, 5.0))]);
+    /// assert_eq!(circle.intersect_circle(&QCircle::new(QPoint::ZERO, q64!(2))), QCircleIntersection::Disjoint);
+    /// assert_eq!(circle.intersect_circle(&circle), QCircleIntersection::Coincident);
+    /// ```
+    pub fn intersect_circle(&self, other: &QCircle) -> QCircleIntersection {
+        let offset = other.center.pos().saturating_sub(self.center.pos());
+        let distance_squared = offset.length_squared();
+        let radius_sum = self.radius.saturating_add(other.radius);
+        let radius_diff = self.radius.saturating_sub(other.radius);
+        if distance_squared == Q64::ZERO && radius_diff == Q64::ZERO {
+            return QCircleIntersection::Coincident;
+        }
+        if distance_squared == Q64::ZERO
+            || distance_squared > radius_sum.saturating_mul(radius_sum)
+            || distance_squared < radius_diff.saturating_mul(radius_diff) {
+            return QCircleIntersection::Disjoint;
+        }
+        let (self_squared, other_squared) = (self.radius.saturating_mul(self.radius), other.radius.saturating_mul(other.radius));
+        // The chord crosses the center line at `along`, as a fraction of the center distance.
+        let along = distance_squared
+            .saturating_add(self_squared)
+            .saturating_sub(other_squared)
+            .saturating_div(distance_squared.saturating_mul(q64!(2)));
+        let base = self.center.pos().saturating_add(offset.saturating_mul_num(along));
+        let across = sqrt(self_squared.saturating_div(distance_squared).saturating_sub(along.saturating_mul(along)));
+        if across == Q64::ZERO {
+            return QCircleIntersection::Points(smallvec![QPoint::new(base)]);
+        }
+        let chord = QVec2::new(-offset.y, offset.x).saturating_mul_num(across);
+        QCircleIntersection::Points(smallvec![QPoint::new(base.saturating_add(chord)), QPoint::new(base.saturating_sub(chord))])
+    }
+
+    /// Get the lines touching both circles, each running between the two touching points.
+    ///
+    /// The outer tangents come first, then the inner ones which exist only for separate circles.
+    /// Tangents touching both circles at the same point are skipped, as are all tangents of
+    /// concentric circles.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::ZERO, Q64::ONE);
+    /// let tangents = circle.common_tangents(&QCircle::new(QPoint::new(qvec2!(4.0, 0.0)), Q64::ONE));
+    /// assert_eq!(tangents.len(), 4);
+    /// assert!(tangents.iter().any(|line| line.start().pos() == qvec2!(0.0, 1.0) && line.end().pos() == qvec2!(4.0, 1.0)));
+    /// let inner = QCircle::new(QPoint::new(qvec2!(0.5, 0.0)), Q64::HALF);
+    /// assert!(circle.common_tangents(&inner).is_empty());
+    /// ```
+    pub fn common_tangents(&self, other: &QCircle) -> Vec<QLine> {
+        let mut rst = Vec::new();
+        for other_radius in [other.radius, -other.radius] {
+            for normal in get_tangent_normals(self.center.pos(), self.radius, other.center.pos(), other_radius) {
+                let start = self.center.pos().saturating_sub(normal.saturating_mul_num(self.radius));
+                let end = other.center.pos().saturating_sub(normal.saturating_mul_num(other_radius));
+                if start != end {
+                    rst.push(QLine::new_from_parts(start, end));
+                }
+            }
+        }
+        rst
+    }
+}
+
+/// Unit normals `n` of the lines with signed distance `radius_a` to `center_a` and `radius_b` to `center_b`.
+///
+/// Both circles touch such a line at `center - n * radius`, a single normal is returned when
+/// the two solutions coincide.
+fn get_tangent_normals(center_a: QVec2, radius_a: Q64, center_b: QVec2, radius_b: Q64) -> Vec<QVec2> {
+    let offset = center_b.saturating_sub(center_a);
+    let distance_squared = offset.length_squared();
+    let radius_diff = radius_b.saturating_sub(radius_a);
+    let height_squared = distance_squared.saturating_sub(radius_diff.saturating_mul(radius_diff));
+    if distance_squared == Q64::ZERO || height_squared < Q64::ZERO {
+        return Vec::new();
+    }
+    let height = sqrt(height_squared);
+    let along = offset.saturating_mul_num(radius_diff);
+    let across = QVec2::new(-offset.y, offset.x).saturating_mul_num(height);
+    let mut rst = Vec::new();
+    for normal in [along.saturating_add(across), along.saturating_sub(across)] {
+        let normal = QVec2::new(normal.x.saturating_div(distance_squared), normal.y.saturating_div(distance_squared));
+        if !rst.contains(&normal) {
+            rst.push(normal);
+        }
+    }
+    rst
 }
 
 impl QShapeCommon for QCircle {
+    type Rotated = QCircle;
+
     fn points(&self) -> Vec<QPoint> {
-        let mut points = Vec::new();
+        self.points_iter().collect()
+    }
+
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
         let num_points = 16;
-        
-        for i in 0..num_points {
+        (0..num_points).map(move |i| {
             let angle: Q64 = q64!(i) / q64!(num_points) * Q64::TAU;
             let (sin, cos) = angle.sin_cos();
             let x = self.center.x().saturating_add(self.radius.saturating_mul(cos));
             let y = self.center.y().saturating_add(self.radius.saturating_mul(sin));
-            points.push(QPoint::new(QVec2::new(x, y)));
-        }
-
-        points
+            QPoint::new(QVec2::new(x, y))
+        })
     }
 
     fn get_bbox(&self) -> QBbox {
@@ -79,10 +274,9 @@ impl QShapeCommon for QCircle {
     fn is_collide(&self, other: &impl QShapeCommon) -> bool {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.is_collide(self),
             _ => {
-                let my_polygon = QPolygon::new(self.points());
-                let other_polygon = QPolygon::new(other.points());
-                gjk(&my_polygon, &other_polygon)
+                gjk(self, &QShapeSupport(other))
             }
         }
     }
@@ -90,10 +284,159 @@ impl QShapeCommon for QCircle {
     fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.try_get_seperation_vector(self).map(|vector| -vector),
             _ => {
-                let my_polygon = QPolygon::new(self.points());
-                let other_polygon = QPolygon::new(other.points());
-                epa(&my_polygon, &other_polygon)
+                epa(self, &QShapeSupport(other))
+            }
+        }
+    }
+
+    fn translated(&self, offset: QVec2) -> Self {
+        QCircle::new(self.center.translated(offset), self.radius)
+    }
+
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> QCircle {
+        let center = QTransform::from_rotation_around(pivot.pos(), angle).transform_point(&self.center);
+        QCircle::new(center, self.radius)
+    }
+
+    fn scaled(&self, factor: Q64) -> Self {
+        assert!(factor > Q64::ZERO, "[QCircle::scaled] factor({factor:?}) should be larger than zero.");
+        QCircle::new(self.center, self.radius.saturating_mul(factor))
+    }
+
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::ZERO, q64!(2));
+    /// assert_eq!(circle.closest_point(&QPoint::new(qvec2!(0.0, 5.0))).pos(), qvec2!(0.0, 2.0));
+    /// assert_eq!(circle.closest_point(&QPoint::new(QVec2::ONE)).pos(), QVec2::ONE);
+    /// ```
+    fn closest_point(&self, point: &QPoint) -> QPoint {
+        if self.is_point_inside(point) {
+            return *point;
+        }
+        let dir = QDir::new_from_vec(point.pos().saturating_sub(self.center.pos()));
+        QPoint::new(self.center.pos().saturating_add(dir.to_vec().saturating_mul_num(self.radius)))
+    }
+
+    /// Counter-clockwise from the rightmost point, like `points`.
+    fn boundary_point(&self, t: Q64) -> QPoint {
+        let (sin, cos) = get_wrapped_fraction(t).saturating_mul(Q64::TAU).sin_cos();
+        QPoint::new_from_parts(
+            self.center.x().saturating_add(self.radius.saturating_mul(cos)),
+            self.center.y().saturating_add(self.radius.saturating_mul(sin)),
+        )
+    }
+
+    fn tangent_at(&self, t: Q64) -> QDir {
+        let (sin, cos) = get_wrapped_fraction(t).saturating_mul(Q64::TAU).sin_cos();
+        QDir::new_from_vec(QVec2::new(-sin, cos))
+    }
+
+    /// Away from the center, `QVec2::X` at the center itself.
+    fn boundary_normal_at(&self, point: &QPoint) -> QDir {
+        let offset = point.pos().saturating_sub(self.center.pos());
+        QDir::new_from_vec(if offset == QVec2::ZERO { QVec2::X } else { offset })
+    }
+
+    /// Distance from the center to the other shape, minus the radius.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::ZERO, Q64::ONE);
+    /// let other = QCircle::new(QPoint::new(qvec2!(5.0, 0.0)), q64!(2));
+    /// assert_eq!(circle.distance_to(&other), q64!(2));
+    /// let bbox = QBbox::new_from_parts(qvec2!(0.5, -1.0), qvec2!(3.0, 1.0));
+    /// assert_eq!(circle.distance_to(&bbox), Q64::ZERO);
+    /// ```
+    fn distance_to(&self, other: &impl QShapeCommon) -> Q64 {
+        let center_distance = other.closest_point(&self.center).distance(&self.center);
+        center_distance.saturating_sub(self.radius).max(Q64::ZERO)
+    }
+
+    /// A circle is convex, so containing every vertex is enough, other circles are checked exactly.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::ZERO, q64!(4));
+    /// assert!(circle.contains(&QCircle::new(QPoint::new(qvec2!(1.0, 0.0)), q64!(3))));
+    /// assert!(!circle.contains(&QCircle::new(QPoint::new(qvec2!(2.0, 0.0)), q64!(3))));
+    /// assert!(circle.contains(&QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE)));
+    /// ```
+    fn contains(&self, other: &impl QShapeCommon) -> bool {
+        if other.get_shape_type() == QShapeType::QCircle {
+            // The far end of the other circle along the line of centers, from its own radius.
+            let offset = other.get_centroid().pos().saturating_sub(self.center.pos());
+            let dir = QDir::new_from_vec(if offset == QVec2::ZERO { QVec2::X } else { offset });
+            let (_, far) = other.project_onto(dir);
+            return far.saturating_sub(self.center.pos().dot(dir.to_vec())) <= self.radius;
+        }
+        let points = other.points();
+        !points.is_empty() && points.iter().all(|point| self.is_point_inside(point))
+    }
+
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::new(qvec2!(3.0, 1.0)), q64!(2));
+    /// assert_eq!(circle.project_onto(QDir::new_from_vec(QVec2::X)), (q64!(1), q64!(5)));
+    /// ```
+    fn project_onto(&self, dir: QDir) -> (Q64, Q64) {
+        let center = self.center.pos().dot(dir.to_vec());
+        (center.saturating_sub(self.radius), center.saturating_add(self.radius))
+    }
+
+    /// The exact point on the circle, not the farthest of its vertices.
+    fn get_support_point(&self, dir: QDir) -> QPoint {
+        self.support(dir)
+    }
+
+    /// A triangle fan over `resolution` boundary vertices, at least 3.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::ZERO, Q64::ONE);
+    /// let mesh = circle.to_mesh(32);
+    /// assert_eq!(mesh.positions().len(), 32);
+    /// assert_eq!(mesh.indices().len(), 30);
+    /// ```
+    fn to_mesh(&self, resolution: usize) -> QMesh {
+        let count = resolution.max(3);
+        let positions = (0..count)
+            .map(|i| {
+                let angle: Q64 = q64!(i as i64) / q64!(count as i64) * Q64::TAU;
+                let (sin, cos) = angle.sin_cos();
+                self.center.pos().saturating_add(QVec2::new(cos, sin).saturating_mul_num(self.radius))
+            })
+            .collect();
+        let indices = (1..count as u32 - 1).map(|i| [0, i, i + 1]).collect();
+        QMesh::new(positions, indices)
+    }
+
+    /// Rejection sampling in the bounding square, exact and 1.27 draws on average.
+    fn sample_interior(&self, rng: &mut impl QRng) -> QPoint {
+        let radius_squared = self.radius.saturating_mul(self.radius);
+        loop {
+            let offset = QVec2::new(rng.next_in_range(-self.radius..=self.radius), rng.next_in_range(-self.radius..=self.radius));
+            if offset.length_squared() <= radius_squared {
+                return QPoint::new(self.center.pos().saturating_add(offset));
             }
         }
     }
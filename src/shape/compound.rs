@@ -0,0 +1,211 @@
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use crate::algorithm::hull_of;
+use crate::mesh::QMesh;
+use super::support_map::support_of_points;
+use super::{ QPoint, QBbox, QPolygon, QShape, QShapeCommon, QShapeType };
+
+/// A shape made of several child shapes, each placed at a local offset from the compound origin.
+///
+/// Concave objects are usually modeled as a compound of convex parts.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QCompound {
+    origin: QVec2,
+    children: Vec<(QVec2, QShape)>,
+}
+
+impl QCompound {
+    pub fn new(origin: QVec2, children: Vec<(QVec2, QShape)>) -> Self {
+        Self {
+            origin,
+            children,
+        }
+    }
+
+    pub fn origin(&self) -> QVec2 {
+        self.origin
+    }
+
+    pub fn set_origin(&mut self, origin: QVec2) {
+        self.origin = origin;
+    }
+
+    /// Children with their local offsets.
+    pub fn children(&self) -> &Vec<(QVec2, QShape)> {
+        &self.children
+    }
+
+    pub fn children_mut(&mut self) -> &mut Vec<(QVec2, QShape)> {
+        &mut self.children
+    }
+
+    pub fn add_child(&mut self, offset: QVec2, shape: impl Into<QShape>) {
+        self.children.push((offset, shape.into()));
+    }
+
+    /// Get the children placed in world space.
+    pub fn world_children(&self) -> Vec<QShape> {
+        self.children
+            .iter()
+            .map(|(offset, shape)| shape.translated(self.origin.saturating_add(*offset)))
+            .collect()
+    }
+
+    /// Store world space children back as local children of a compound at `origin`.
+    fn from_world_children(origin: QVec2, offsets: Vec<QVec2>, world_children: Vec<QShape>) -> Self {
+        let children = offsets
+            .into_iter()
+            .zip(world_children)
+            .map(|(offset, shape)| (offset, shape.translated(-origin.saturating_add(offset))))
+            .collect();
+        Self::new(origin, children)
+    }
+}
+
+impl QShapeCommon for QCompound {
+    type Rotated = QCompound;
+
+    fn points(&self) -> Vec<QPoint> {
+        self.world_children().iter().flat_map(|shape| shape.points()).collect()
+    }
+
+    /// Union of the bboxes of all children.
+    fn get_bbox(&self) -> QBbox {
+        self.world_children()
+            .iter()
+            .map(|shape| shape.get_bbox())
+            .reduce(|a, b| a.union(&b))
+            .expect("[QCompound::get_bbox] Children must not be empty.")
+    }
+
+    /// Average of the centroids of all children.
+    fn get_centroid(&self) -> QPoint {
+        let centroids: Vec<QPoint> = self.world_children().iter().map(|shape| shape.get_centroid()).collect();
+        if centroids.is_empty() { return QPoint::new(self.origin); }
+        QPolygon::new(centroids).get_centroid()
+    }
+
+    fn get_shape_type(&self) -> QShapeType {
+        QShapeType::QCompound
+    }
+
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let mut compound = QCompound::new(qvec2!(10.0, 0.0), vec![]);
+    /// compound.add_child(QVec2::ZERO, QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE));
+    /// compound.add_child(qvec2!(3.0, 0.0), QCircle::new(QPoint::ZERO, Q64::ONE));
+    /// assert!(compound.is_point_inside(&QPoint::new(qvec2!(10.5, 0.5))));
+    /// assert!(compound.is_point_inside(&QPoint::new(qvec2!(13.0, 0.5))));
+    /// assert!(!compound.is_point_inside(&QPoint::new(qvec2!(11.5, 0.5))));
+    /// ```
+    fn is_point_inside(&self, point: &QPoint) -> bool {
+        self.world_children().iter().any(|shape| shape.is_point_inside(point))
+    }
+
+    /// Every child is tested on its own, the other shapes hand compounds back here so a
+    /// concave compound never collides through the gaps of its vertex hull.
+    fn is_collide(&self, other: &impl QShapeCommon) -> bool {
+        self.world_children().iter().any(|shape| shape.is_collide(other))
+    }
+
+    /// The longest separation vector among all colliding children.
+    fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
+        self.world_children()
+            .iter()
+            .filter_map(|shape| shape.try_get_seperation_vector(other))
+            .max_by(|a, b| a.length_squared().partial_cmp(&b.length_squared()).unwrap_or(core::cmp::Ordering::Equal))
+    }
+
+    fn translated(&self, offset: QVec2) -> Self {
+        Self::new(self.origin.saturating_add(offset), self.children.clone())
+    }
+
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> Self {
+        let origin = QPoint::new(self.origin).rotated_around(pivot, angle).pos();
+        let offsets = self.children
+            .iter()
+            .map(|(offset, _)| QPoint::new(*offset).rotated_around(&QPoint::ZERO, angle).pos())
+            .collect();
+        let world_children = self.world_children().iter().map(|shape| shape.rotated_around(pivot, angle)).collect();
+        Self::from_world_children(origin, offsets, world_children)
+    }
+
+    /// Scale every child around the compound centroid.
+    fn scaled(&self, factor: Q64) -> Self {
+        assert!(factor > Q64::ZERO, "[QCompound::scaled] factor({factor:?}) should be larger than zero.");
+        let center = self.get_centroid().pos();
+        let scale_pos = |pos: QVec2| center.saturating_add(pos.saturating_sub(center).saturating_mul_num(factor));
+        let origin = scale_pos(self.origin);
+        let offsets = self.children.iter().map(|(offset, _)| offset.saturating_mul_num(factor)).collect();
+        let world_children = self.world_children()
+            .iter()
+            .map(|shape| {
+                let child_center = shape.get_centroid().pos();
+                shape.scaled(factor).translated(scale_pos(child_center).saturating_sub(child_center))
+            })
+            .collect();
+        Self::from_world_children(origin, offsets, world_children)
+    }
+
+    /// The nearest of the closest points of all children.
+    fn closest_point(&self, point: &QPoint) -> QPoint {
+        self.world_children()
+            .iter()
+            .map(|shape| shape.closest_point(point))
+            .min_by(|a, b| {
+                a.pos().distance_squared(point.pos())
+                    .partial_cmp(&b.pos().distance_squared(point.pos()))
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })
+            .expect("[QCompound::closest_point] Children must not be empty.")
+    }
+
+    /// The smallest distance from any child.
+    fn distance_to(&self, other: &impl QShapeCommon) -> Q64 {
+        self.world_children()
+            .iter()
+            .map(|shape| shape.distance_to(other))
+            .reduce(|a, b| a.min(b))
+            .expect("[QCompound::distance_to] Children must not be empty.")
+    }
+
+    /// Only shapes entirely inside a single child are contained.
+    fn contains(&self, other: &impl QShapeCommon) -> bool {
+        self.world_children().iter().any(|shape| shape.contains(other))
+    }
+
+    /// Union of the extents of all children.
+    fn project_onto(&self, dir: QDir) -> (Q64, Q64) {
+        self.world_children()
+            .iter()
+            .map(|shape| shape.project_onto(dir))
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+            .expect("[QCompound::project_onto] Children must not be empty.")
+    }
+
+    /// The farthest of the support points of all children.
+    fn get_support_point(&self, dir: QDir) -> QPoint {
+        support_of_points(self.world_children().iter().map(|shape| shape.get_support_point(dir)), dir)
+    }
+
+    /// Meshes of all children merged into one.
+    fn to_mesh(&self, resolution: usize) -> QMesh {
+        let mut mesh = QMesh::default();
+        for child in self.world_children() {
+            mesh.append(child.to_mesh(resolution));
+        }
+        mesh
+    }
+
+    /// Hull of all children, see `algorithm::hull_of`.
+    fn convex_hull(&self) -> QPolygon {
+        hull_of(&self.world_children())
+    }
+}
@@ -0,0 +1,110 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use crate::mesh::QMesh;
+use super::{ QPoint, QBbox, QSupportMap };
+
+/// Axis aligned ellipse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QEllipse {
+    center: QPoint,
+    radii: QVec2,
+}
+
+impl QEllipse {
+    pub fn new(center: QPoint, radii: QVec2) -> Self {
+        assert!(
+            radii.x > Q64::ZERO && radii.y > Q64::ZERO,
+            "[QEllipse::new] radii({radii:?}) should be larger than zero."
+        );
+        Self {
+            center,
+            radii,
+        }
+    }
+
+    pub fn center(&self) -> QPoint {
+        self.center
+    }
+
+    /// Semi-axes along x and y.
+    pub fn radii(&self) -> QVec2 {
+        self.radii
+    }
+
+    pub fn set_center(&mut self, center: QPoint) {
+        self.center = center;
+    }
+
+    pub fn set_radii(&mut self, radii: QVec2) {
+        self.radii = radii;
+    }
+
+    pub fn get_bbox(&self) -> QBbox {
+        QBbox::new_from_parts(self.center.pos().saturating_sub(self.radii), self.center.pos().saturating_add(self.radii))
+    }
+
+    /// Get the ellipse moved by `offset`.
+    pub fn translated(&self, offset: QVec2) -> Self {
+        Self::new(QPoint::new(self.center.pos().saturating_add(offset)), self.radii)
+    }
+
+    pub fn is_point_inside(&self, point: &QPoint) -> bool {
+        let local = point.pos().saturating_sub(self.center.pos());
+        let x = local.x.saturating_div(self.radii.x);
+        let y = local.y.saturating_div(self.radii.y);
+        x.saturating_mul(x).saturating_add(y.saturating_mul(y)) <= Q64::ONE
+    }
+
+    /// A triangle fan over `resolution` boundary vertices, at least 3.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let ellipse = QEllipse::new(QPoint::ZERO, qvec2!(3.0, 1.0));
+    /// let mesh = ellipse.to_mesh(16);
+    /// assert_eq!(mesh.positions().len(), 16);
+    /// assert!((mesh.positions()[0] - qvec2!(3.0, 0.0)).length() < q64!(0.001));
+    /// assert_eq!(mesh.indices().len(), 14);
+    /// ```
+    pub fn to_mesh(&self, resolution: usize) -> QMesh {
+        let count = resolution.max(3);
+        let positions = (0..count)
+            .map(|i| {
+                let angle: Q64 = q64!(i as i64) / q64!(count as i64) * Q64::TAU;
+                let (sin, cos) = angle.sin_cos();
+                self.center.pos().saturating_add(QVec2::new(cos.saturating_mul(self.radii.x), sin.saturating_mul(self.radii.y)))
+            })
+            .collect();
+        let indices = (1..count as u32 - 1).map(|i| [0, i, i + 1]).collect();
+        QMesh::new(positions, indices)
+    }
+}
+
+impl QSupportMap for QEllipse {
+    /// The ellipse is the unit circle scaled by the radii, so the normal direction is scaled once more.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let ellipse = QEllipse::new(QPoint::ZERO, qvec2!(3.0, 1.0));
+    /// assert_eq!(ellipse.support(QDir::new_from_vec(QVec2::X)).pos(), qvec2!(3.0, 0.0));
+    /// assert_eq!(ellipse.support(QDir::new_from_vec(QVec2::NEG_Y)).pos(), qvec2!(0.0, -1.0));
+    /// ```
+    fn support(&self, dir: QDir) -> QPoint {
+        let axis = dir.to_vec();
+        let scaled = QVec2::new(self.radii.x.saturating_mul(axis.x), self.radii.y.saturating_mul(axis.y));
+        let length = scaled.length();
+        let offset = QVec2::new(
+            self.radii.x.saturating_mul(scaled.x).saturating_div(length),
+            self.radii.y.saturating_mul(scaled.y).saturating_div(length),
+        );
+        QPoint::new(self.center.pos().saturating_add(offset))
+    }
+}
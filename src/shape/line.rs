@@ -1,11 +1,16 @@
+use alloc::{ vec, vec::Vec };
 use qmath::prelude::*;
 use qmath::vec2::QVec2;
 use qmath::dir::QDir;
-use serde::{Deserialize, Serialize};
-use crate::algorithm::{epa, gjk};
-use super::{ QPoint, QBbox, QPolygon, QShapeCommon, QShapeType };
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+use core::cmp::Ordering;
+use crate::algorithm::{cross_sign, epa, gjk, orient2d};
+use crate::tolerance::QTolerance;
+use crate::transform::QTransform;
+use super::support_map::QShapeSupport;
+use super::{ QPoint, QBbox, QShapeCommon, QShapeType };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct QLine {
     start: QPoint,
     end: QPoint,
@@ -59,6 +64,55 @@ impl QLine {
         self.end.pos().saturating_sub(self.start.pos())
     }
 
+    /// Get the point at parameter `t`, the start at 0 and the end at 1.
+    ///
+    /// `t` outside [0, 1] extrapolates along the line.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let line = QLine::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 2.0));
+    /// assert_eq!(line.point_at(q64!(0.25)).pos(), qvec2!(1.0, 0.5));
+    /// assert_eq!(line.midpoint().pos(), qvec2!(2.0, 1.0));
+    /// let (head, tail) = line.split_at(q64!(0.25));
+    /// assert_eq!((head.end(), tail.start()), (line.point_at(q64!(0.25)), line.point_at(q64!(0.25))));
+    /// assert_eq!(line.subdivide(4)[3].start().pos(), qvec2!(3.0, 1.5));
+    /// ```
+    pub fn point_at(&self, t: Q64) -> QPoint {
+        QPoint::new(self.start.pos().saturating_add(self.vector().saturating_mul_num(t)))
+    }
+
+    pub fn midpoint(&self) -> QPoint {
+        QPoint::new(self.start.pos().midpoint(self.end.pos()))
+    }
+
+    /// Split the line at parameter `t`, which must be strictly between 0 and 1.
+    pub fn split_at(&self, t: Q64) -> (QLine, QLine) {
+        assert!(t > Q64::ZERO && t < Q64::ONE, "[QLine::split_at] t({t}) should be between 0 and 1.");
+        let point = self.point_at(t);
+        (QLine::new(self.start, point), QLine::new(point, self.end))
+    }
+
+    /// Split the line into `n` pieces of equal length, in order from the start.
+    pub fn subdivide(&self, n: usize) -> Vec<QLine> {
+        assert!(n > 0, "[QLine::subdivide] n should be positive.");
+        let count = q64!(n as i64);
+        let points: Vec<QPoint> = (0..=n)
+            .map(|i| if i == n { self.end } else { self.point_at(q64!(i as i64).saturating_div(count)) })
+            .collect();
+        points.windows(2).map(|w| QLine::new(w[0], w[1])).collect()
+    }
+
+    /// Get the mirror image about `axis`, the axis is treated as an infinite line.
+    ///
+    /// Start and end keep their roles, so the direction of the line is reflected too.
+    pub fn mirrored(&self, axis: &QLine) -> QLine {
+        let transform = QTransform::from_reflection(axis);
+        QLine::new(transform.transform_point(&self.start), transform.transform_point(&self.end))
+    }
+
     /// Is the angle between pa and pb greater than 90 degrees.
     /// # Examples
     /// ```
@@ -144,26 +198,77 @@ impl QLine {
     /// assert!(lab.get_distance_from_point(&pb) == Q64::ZERO);
     /// ```
     pub fn get_distance_from_point(&self, point: &QPoint) -> Q64 {
-        if self.start.pos() == self.end.pos() {
-            return self.start.distance(point);
+        let (closest, t) = self.closest_point_to(point);
+        if t > Q64::ZERO && t < Q64::ONE {
+            self.get_perpendicular_distance_from_point(point)
+        } else {
+            closest.distance(point)
         }
+    }
 
+    /// Create a segment from float end points, see `QPoint::from_f64`.
+    pub fn from_f64(start: [f64; 2], end: [f64; 2]) -> Self {
+        Self::new(QPoint::from_f64(start), QPoint::from_f64(end))
+    }
+
+    /// Create a segment from `f32` end points, see `QPoint::from_f64`.
+    pub fn from_f32(start: [f32; 2], end: [f32; 2]) -> Self {
+        Self::new(QPoint::from_f32(start), QPoint::from_f32(end))
+    }
+
+    /// Get the end points rounded to the nearest `f64`.
+    pub fn to_f64(&self) -> [[f64; 2]; 2] {
+        [self.start.to_f64(), self.end.to_f64()]
+    }
+
+    /// Get the end points rounded to the nearest `f32`.
+    pub fn to_f32(&self) -> [[f32; 2]; 2] {
+        [self.start.to_f32(), self.end.to_f32()]
+    }
+
+    /// Get the point of the segment nearest to `point`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let line = QLine::new_from_parts(QVec2::ZERO, qvec2!(2.0, 0.0));
+    /// assert_eq!(line.get_closest_point_from_point(&QPoint::new(qvec2!(1.0, 3.0))).pos(), qvec2!(1.0, 0.0));
+    /// assert_eq!(line.get_closest_point_from_point(&QPoint::new(qvec2!(-1.0, 3.0))).pos(), QVec2::ZERO);
+    /// assert_eq!(line.get_closest_point_from_point(&QPoint::new(qvec2!(5.0, -1.0))).pos(), qvec2!(2.0, 0.0));
+    /// ```
+    pub fn get_closest_point_from_point(&self, point: &QPoint) -> QPoint {
+        self.closest_point_to(point).0
+    }
+
+    /// Get the point of the segment nearest to `point` and its parameter `t` in [0, 1].
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let line = QLine::new_from_parts(QVec2::ZERO, qvec2!(4.0, 0.0));
+    /// assert_eq!(line.closest_point_to(&QPoint::new(qvec2!(1.0, 3.0))), (QPoint::new(qvec2!(1.0, 0.0)), q64!(0.25)));
+    /// assert_eq!(line.closest_point_to(&QPoint::new(qvec2!(-1.0, 3.0))), (line.start(), Q64::ZERO));
+    /// assert_eq!(line.closest_point_to(&QPoint::new(qvec2!(9.0, 3.0))), (line.end(), Q64::ONE));
+    /// ```
+    pub fn closest_point_to(&self, point: &QPoint) -> (QPoint, Q64) {
         let segment_vector = self.vector();
         let segment_length_squared = segment_vector.length_squared();
-        if segment_length_squared == 0 {
-            return self.start.distance(point);
+        if segment_length_squared == Q64::ZERO {
+            return (self.start, Q64::ZERO);
         }
 
         let point_vector = point.pos().saturating_sub(self.start.pos());
-        let projection = point_vector.dot(segment_vector);
-        let t = projection / segment_length_squared;
-
-        if t < Q64::ZERO {
-            self.start.distance(point)
-        } else if t > Q64::ONE {
-            self.end.distance(point)
+        let t = point_vector.dot(segment_vector).saturating_div(segment_length_squared);
+        if t <= Q64::ZERO {
+            (self.start, Q64::ZERO)
+        } else if t >= Q64::ONE {
+            (self.end, Q64::ONE)
         } else {
-            self.get_perpendicular_distance_from_point(point)
+            (self.point_at(t), t)
         }
     }
 
@@ -203,15 +308,96 @@ impl QLine {
     /// assert!(!lab.is_point_on_line(&ph));
     /// ```
     pub fn is_point_on_line(&self, point: &QPoint) -> bool {
-        let ab = self.end.pos().saturating_sub(self.start.pos());
-        let ap = point.pos().saturating_sub(self.start.pos());
+        self.is_point_on_line_with_tolerance(point, QTolerance::EXACT)
+    }
+
+    /// Is `point` within `tolerance` of the segment.
+    pub fn is_point_on_line_with_tolerance(&self, point: &QPoint, tolerance: QTolerance) -> bool {
+        tolerance.is_point_on_segment(self.start.pos(), self.end.pos(), point.pos())
+    }
+
+    /// Get the segment with both ends snapped to the grid, `None` if they snap together.
+    pub fn snap_to_grid(&self, cell: Q64) -> Option<QLine> {
+        let (start, end) = (self.start.snap_to_grid(cell), self.end.snap_to_grid(cell));
+        (start != end).then(|| QLine::new(start, end))
+    }
+
+    /// Do the segments cross at a single point interior to both, touching doesn't count.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let a = QLine::new_from_parts(QVec2::ZERO, qvec2!(2.0, 2.0));
+    /// assert!(a.is_crossing(&QLine::new_from_parts(qvec2!(0.0, 2.0), qvec2!(2.0, 0.0))));
+    /// assert!(!a.is_crossing(&QLine::new_from_parts(qvec2!(1.0, 1.0), qvec2!(2.0, 0.0))));
+    /// ```
+    pub fn is_crossing(&self, other: &QLine) -> bool {
+        let (a, b, c, d) = (self.start.pos(), self.end.pos(), other.start.pos(), other.end.pos());
+        let is_opposite = |p: Ordering, q: Ordering| p != Ordering::Equal && q == p.reverse();
+        is_opposite(orient2d(a, b, c), orient2d(a, b, d)) && is_opposite(orient2d(c, d, a), orient2d(c, d, b))
+    }
+
+    /// Do the segments point along the same or opposite directions, exactly.
+    pub fn is_parallel_to(&self, other: &QLine) -> bool {
+        cross_sign(self.start.pos(), self.end.pos(), other.start.pos(), other.end.pos()) == Ordering::Equal
+    }
 
-        let cross = ap.cross(ab);
-        if cross != 0 { return false; }
+    /// Does `other` lie on the infinite line through this segment, exactly.
+    pub fn is_collinear_with(&self, other: &QLine) -> bool {
+        let (a, b) = (self.start.pos(), self.end.pos());
+        orient2d(a, b, other.start.pos()) == Ordering::Equal && orient2d(a, b, other.end.pos()) == Ordering::Equal
+    }
 
-        let dot_product = ap.dot(ab);
-        let squared_length = ab.length_squared();
-        dot_product >= 0 && dot_product <= squared_length
+    /// Do the segments share at least one point, touching and overlapping count.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let a = QLine::new_from_parts(qvec2!(0.0, 0.0), qvec2!(2.0, 0.0));
+    /// assert!(a.intersects(&QLine::new_from_parts(qvec2!(1.0, 0.0), qvec2!(1.0, 1.0))));
+    /// assert!(a.intersects(&QLine::new_from_parts(qvec2!(1.0, 0.0), qvec2!(3.0, 0.0))));
+    /// assert!(!a.intersects(&QLine::new_from_parts(qvec2!(3.0, 0.0), qvec2!(4.0, 0.0))));
+    /// assert!(!a.intersects(&QLine::new_from_parts(qvec2!(1.0, 0.5), qvec2!(1.0, 1.0))));
+    /// ```
+    pub fn intersects(&self, other: &QLine) -> bool {
+        let (a, b, c, d) = (self.start.pos(), self.end.pos(), other.start.pos(), other.end.pos());
+        let (side_c, side_d) = (orient2d(a, b, c), orient2d(a, b, d));
+        let (side_a, side_b) = (orient2d(c, d, a), orient2d(c, d, b));
+        if side_c != side_d && side_a != side_b && side_c != Ordering::Equal && side_d != Ordering::Equal
+            && side_a != Ordering::Equal && side_b != Ordering::Equal {
+            return true;
+        }
+        self.is_point_on_line(&other.start) || self.is_point_on_line(&other.end)
+            || other.is_point_on_line(&self.start) || other.is_point_on_line(&self.end)
+    }
+
+    /// Get the minimum distance between the segments, zero if they intersect.
+    ///
+    /// Without an intersection the closest pair always involves an end point, so the
+    /// distance is the smallest of the four end point to segment distances.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let a = QLine::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 0.0));
+    /// assert_eq!(a.distance_to_segment(&QLine::new_from_parts(qvec2!(2.0, 1.0), qvec2!(2.0, 3.0))), Q64::ONE);
+    /// assert_eq!(a.distance_to_segment(&QLine::new_from_parts(qvec2!(7.0, 4.0), qvec2!(9.0, 4.0))), q64!(5));
+    /// assert_eq!(a.distance_to_segment(&QLine::new_from_parts(qvec2!(2.0, -1.0), qvec2!(2.0, 1.0))), Q64::ZERO);
+    /// ```
+    pub fn distance_to_segment(&self, other: &QLine) -> Q64 {
+        if self.intersects(other) {
+            return Q64::ZERO;
+        }
+        self.get_distance_from_point(&other.start)
+            .min(self.get_distance_from_point(&other.end))
+            .min(other.get_distance_from_point(&self.start))
+            .min(other.get_distance_from_point(&self.end))
     }
 
     pub fn get_x_at_y(&self, y: Q64) -> Q64 {
@@ -234,10 +420,16 @@ impl QLine {
 }
 
 impl QShapeCommon for QLine {
+    type Rotated = QLine;
+
     fn points(&self) -> Vec<QPoint> {
         vec![self.start, self.end]
     }
 
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
+        [self.start, self.end].into_iter()
+    }
+
     fn get_bbox(&self) -> QBbox {
         let mut left_bottom = self.start.pos().min(self.end.pos());
         let mut right_top = self.start.pos().max(self.end.pos());
@@ -267,10 +459,9 @@ impl QShapeCommon for QLine {
     fn is_collide(&self, other: &impl QShapeCommon) -> bool {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.is_collide(self),
             _ => {
-                let my_polygon = QPolygon::new(self.points());
-                let other_polygon = QPolygon::new(other.points());
-                gjk(&my_polygon, &other_polygon)
+                gjk(self, &QShapeSupport(other))
             }
         }
     }
@@ -278,11 +469,56 @@ impl QShapeCommon for QLine {
     fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.try_get_seperation_vector(self).map(|vector| -vector),
             _ => {
-                let my_polygon = QPolygon::new(self.points());
-                let other_polygon = QPolygon::new(other.points());
-                epa(&my_polygon, &other_polygon)
+                epa(self, &QShapeSupport(other))
             }
         }
     }
-}
\ No newline at end of file
+
+    fn translated(&self, offset: QVec2) -> Self {
+        QLine::new(self.start.translated(offset), self.end.translated(offset))
+    }
+
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> QLine {
+        let transform = QTransform::from_rotation_around(pivot.pos(), angle);
+        QLine::new(transform.transform_point(&self.start), transform.transform_point(&self.end))
+    }
+
+    fn scaled(&self, factor: Q64) -> Self {
+        assert!(factor > Q64::ZERO, "[QLine::scaled] factor({factor:?}) should be larger than zero.");
+        let center = self.get_centroid().pos();
+        let scale = |point: QPoint| QPoint::new(center.saturating_add(point.pos().saturating_sub(center).saturating_mul_num(factor)));
+        QLine::new(scale(self.start), scale(self.end))
+    }
+
+    fn closest_point(&self, point: &QPoint) -> QPoint {
+        self.get_closest_point_from_point(point)
+    }
+
+    /// Interpolate from start to end, `t` is clamped to [0, 1].
+    fn boundary_point(&self, t: Q64) -> QPoint {
+        let t = t.max(Q64::ZERO).min(Q64::ONE);
+        QPoint::new(self.start.pos().saturating_add(self.vector().saturating_mul_num(t)))
+    }
+}
+/// Get the point nearest to `point` on a chain of segments through `points`.
+///
+/// The chain is closed back to the first point when `is_closed` is true.
+pub(crate) fn get_closest_point_on_chain(points: &[QPoint], is_closed: bool, point: &QPoint) -> QPoint {
+    assert!(!points.is_empty(), "[line::get_closest_point_on_chain] Points must not be empty.");
+    let edge_count = if is_closed && points.len() > 2 { points.len() } else { points.len() - 1 };
+    let mut rst = points[0];
+    let mut min_dist = rst.pos().distance_squared(point.pos());
+    for i in 0..edge_count {
+        let (start, end) = (points[i], points[(i + 1) % points.len()]);
+        if start == end { continue; }
+        let candidate = QLine::new(start, end).get_closest_point_from_point(point);
+        let dist = candidate.pos().distance_squared(point.pos());
+        if dist < min_dist {
+            min_dist = dist;
+            rst = candidate;
+        }
+    }
+    rst
+}
@@ -3,44 +3,369 @@ pub mod line;
 pub mod bbox;
 pub mod circle;
 pub mod polygon;
+pub mod polyline;
+pub mod spline;
+pub mod arc_length;
+pub mod transformed;
+pub mod any;
+pub mod compound;
+pub mod ray;
+pub mod support_map;
+pub mod capsule;
+pub mod ellipse;
+pub mod obb;
+pub mod point_cloud;
 
 pub use point::QPoint;
 pub use line::QLine;
 pub use bbox::QBbox;
-pub use circle::QCircle;
+pub use circle::{ QCircle, QCircleIntersection };
 pub use polygon::QPolygon;
+pub use polyline::QPolyline;
+pub use spline::QSpline;
+pub use arc_length::QArcLengthParam;
+pub use transformed::QTransformedShape;
+pub use any::QShape;
+pub use compound::QCompound;
+pub use ray::{ QRay, QRayHit };
+pub use support_map::QSupportMap;
+pub use capsule::QCapsule;
+pub use ellipse::QEllipse;
+pub use obb::QObb;
+pub use point_cloud::QPointCloud;
+use alloc::vec::Vec;
+use qmath::prelude::*;
 use qmath::vec2::QVec2;
-use serde::{Deserialize, Serialize};
+use qmath::dir::QDir;
+use crate::algorithm::{ andrew_graham_scan, gjk_distance, signed_double_area };
+use crate::generate::{ sample_chain, sample_triangles, QRng };
+use crate::mesh::QMesh;
+use crate::tolerance::QTolerance;
+use line::get_closest_point_on_chain;
+use support_map::{ support_of_points, QShapeSupport };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+/// Segments used to flatten curved boundaries in `sample_interior`.
+pub const SAMPLE_RESOLUTION: usize = 32;
+
+/// Wrap a boundary fraction into [0, 1).
+pub(crate) fn get_wrapped_fraction(t: Q64) -> Q64 {
+    t.saturating_sub(t.floor())
+}
+
+/// Arc-length table of the boundary of `shape`, closed back to its first point unless it is
+/// a polyline, and the length travelled at `t`.
+fn get_boundary_param(shape: &(impl QShapeCommon + ?Sized), t: Q64) -> (QArcLengthParam, Q64) {
+    let is_closed = shape.get_shape_type() != QShapeType::QPolyline && shape.points_iter().nth(2).is_some();
+    let first = shape.points_iter().next().filter(|_| is_closed);
+    let param = QArcLengthParam::new(&QPolyline::new(shape.points_iter().chain(first).collect()));
+    let t = if is_closed { get_wrapped_fraction(t) } else { t.max(Q64::ZERO).min(Q64::ONE) };
+    let s = param.length().saturating_mul(t);
+    (param, s)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum QShapeType {
     QPoint,
     QLine,
     QBbox,
     QCircle,
     QPolygon,
+    QPolyline,
+    QCompound,
 }
 
 pub trait QShapeCommon {
+    /// Shape kind produced by `rotated_around`.
+    type Rotated: QShapeCommon;
+
     fn points(&self) -> Vec<QPoint>;
 
+    /// Iterate over the vertices, shapes override it to avoid allocating.
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
+        self.points().into_iter()
+    }
+
+    /// Get the stored vertices, `None` for shapes that compute them on demand.
+    fn points_slice(&self) -> Option<&[QPoint]> {
+        None
+    }
+
+    /// Get the outward edge normals if the shape stores them, see `QPolygon::with_precomputed_normals`.
+    fn precomputed_normals(&self) -> Option<&[QDir]> {
+        None
+    }
+
+    /// Iterate over the boundary edges, closed unless the shape is a polyline.
+    ///
+    /// Zero length edges are skipped and shapes with less than 3 points are not closed.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE);
+    /// assert_eq!(bbox.edges_iter().count(), 4);
+    /// let polyline = QPolyline::new_from_parts(vec![QVec2::ZERO, QVec2::X, QVec2::ONE]);
+    /// assert_eq!(polyline.edges_iter().count(), 2);
+    /// ```
+    fn edges_iter(&self) -> impl Iterator<Item = QLine> + '_ {
+        let is_closed = self.get_shape_type() != QShapeType::QPolyline && self.points_iter().nth(2).is_some();
+        let first = self.points_iter().next().filter(|_| is_closed);
+        self.points_iter()
+            .zip(self.points_iter().skip(1).chain(first))
+            .filter(|(start, end)| start != end)
+            .map(|(start, end)| QLine::new(start, end))
+    }
+
     fn get_bbox(&self) -> QBbox;
 
+    /// Get the bbox grown by `margin`, broadphases store these fat bboxes so small moves
+    /// don't need a reinsertion.
+    fn get_fat_bbox(&self, margin: Q64) -> QBbox {
+        self.get_bbox().inflated(margin)
+    }
+
+    /// Get the bbox covering the shape along a move by `velocity`.
+    fn get_swept_bbox(&self, velocity: QVec2) -> QBbox {
+        self.get_bbox().swept_by(velocity)
+    }
+
     fn get_centroid(&self) -> QPoint;
 
     fn get_shape_type(&self) -> QShapeType;
 
     fn is_point_inside(&self, point: &QPoint) -> bool;
 
+    /// Is `point` inside the shape or within `tolerance` of it.
+    fn is_point_inside_with_tolerance(&self, point: &QPoint, tolerance: QTolerance) -> bool {
+        if tolerance.is_exact() {
+            return self.is_point_inside(point);
+        }
+        self.is_point_inside(point) || self.closest_point(point).distance(point) <= tolerance.distance()
+    }
+
     fn is_collide(&self, other: &impl QShapeCommon) -> bool;
 
     fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2>;
 
+    /// Get the point of the shape nearest to `point`, the point itself if it is inside the shape.
+    fn closest_point(&self, point: &QPoint) -> QPoint;
+
+    /// Get the minimum distance to another shape, zero if they overlap.
+    ///
+    /// Points, circles and compounds have exact formulas, other pairs run GJK on
+    /// `get_support_point` of both shapes.
+    fn distance_to(&self, other: &impl QShapeCommon) -> Q64 where Self: Sized {
+        match other.get_shape_type() {
+            QShapeType::QPoint | QShapeType::QCircle | QShapeType::QCompound => other.distance_to(self),
+            _ => gjk_distance(&QShapeSupport(self), &QShapeSupport(other)),
+        }
+    }
+
+    /// Is `other` entirely inside the shape.
+    ///
+    /// Every vertex of `other` must be inside and no edges of the two boundaries may cross.
+    /// Circles inside shapes with a polygonal boundary are checked exactly.
+    fn contains(&self, other: &impl QShapeCommon) -> bool where Self: Sized {
+        if other.points_iter().next().is_none() || !other.points_iter().all(|point| self.is_point_inside(&point)) {
+            return false;
+        }
+
+        let my_is_closed = self.get_shape_type() != QShapeType::QPolyline;
+        if other.get_shape_type() == QShapeType::QCircle && my_is_closed && self.points_iter().nth(2).is_some() {
+            let center = other.get_centroid();
+            let (_, right) = other.project_onto(QDir::new_from_vec(QVec2::X));
+            let radius = right.saturating_sub(center.x());
+            return get_closest_point_on_chain(&self.points(), true, &center).distance(&center) >= radius;
+        }
+
+        let other_edges: Vec<QLine> = other.edges_iter().collect();
+        !self.edges_iter().any(|a| other_edges.iter().any(|b| a.is_crossing(b)))
+    }
+
+    /// Get the point at the fraction `t` of the boundary length, walking the points in order.
+    ///
+    /// Closed boundaries wrap `t` into [0, 1), open chains clamp it to [0, 1]. Polygons are
+    /// walked by arc length along their edges, circles, bboxes and lines are exact.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let triangle = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0)]);
+    /// assert_eq!(triangle.boundary_point(Q64::ZERO).pos(), qvec2!(0.0, 0.0));
+    /// assert_eq!(triangle.boundary_point(q64!(0.25)).pos(), qvec2!(3.0, 0.0));
+    /// assert_eq!(triangle.boundary_point(Q64::HALF).pos(), qvec2!(4.0, 2.0));
+    /// assert_eq!(triangle.boundary_point(q64!(1.25)).pos(), qvec2!(3.0, 0.0));
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 2.0));
+    /// assert_eq!(bbox.boundary_point(q64!(0.375)).pos(), qvec2!(1.0, 2.0));
+    /// let circle = QCircle::new(QPoint::ZERO, q64!(2));
+    /// assert_eq!(circle.boundary_point(Q64::ZERO).pos(), qvec2!(2.0, 0.0));
+    /// ```
+    fn boundary_point(&self, t: Q64) -> QPoint {
+        let (param, s) = get_boundary_param(self, t);
+        param.point_at_length(s)
+    }
+
+    /// Get the direction of travel along the boundary at `t`, see `boundary_point`.
+    ///
+    /// At a vertex the edge leaving it is used, shapes without edges give `QVec2::X`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let triangle = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0)]);
+    /// assert_eq!(triangle.tangent_at(q64!(0.1)).to_vec(), QVec2::X);
+    /// assert_eq!(triangle.tangent_at(q64!(0.4)).to_vec(), QVec2::Y);
+    /// let circle = QCircle::new(QPoint::ZERO, q64!(2));
+    /// assert_eq!(circle.tangent_at(Q64::ZERO).to_vec(), QVec2::Y);
+    /// ```
+    fn tangent_at(&self, t: Q64) -> QDir {
+        let (param, s) = get_boundary_param(self, t);
+        let (points, lengths) = (param.points(), param.cumulative_lengths());
+        let mut edges = (1..points.len()).filter(|&i| points[i - 1] != points[i]);
+        edges
+            .clone()
+            .find(|&i| lengths[i] > s)
+            .or_else(|| edges.next_back())
+            .map_or(QDir::new_from_vec(QVec2::X), |i| QDir::new_from_vec(points[i].pos().saturating_sub(points[i - 1].pos())))
+    }
+
+    /// Get the outward normal of the boundary edge nearest to `point`.
+    ///
+    /// Closed shapes use the outward side for either winding. Open chains have no outside,
+    /// so the normal faces the side of `point`, the right side when it is on the chain.
+    /// A single point faces away from itself.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 2.0));
+    /// assert_eq!(bbox.boundary_normal_at(&QPoint::new(qvec2!(1.0, -1.0))).to_vec(), QVec2::NEG_Y);
+    /// assert_eq!(bbox.boundary_normal_at(&QPoint::new(qvec2!(0.5, 1.0))).to_vec(), -QVec2::X);
+    /// let line = QLine::new_from_parts(QVec2::ZERO, QVec2::X);
+    /// assert_eq!(line.boundary_normal_at(&QPoint::new(qvec2!(0.5, 1.0))).to_vec(), QVec2::Y);
+    /// let circle = QCircle::new(QPoint::ZERO, q64!(2));
+    /// assert_eq!(circle.boundary_normal_at(&QPoint::new(qvec2!(0.0, 5.0))).to_vec(), QVec2::Y);
+    /// ```
+    fn boundary_normal_at(&self, point: &QPoint) -> QDir {
+        let nearest = self
+            .edges_iter()
+            .min_by_key(|edge| edge.get_closest_point_from_point(point).pos().distance_squared(point.pos()));
+        let Some(edge) = nearest else {
+            let offset = self.points_iter().next().map_or(QVec2::ZERO, |other| point.pos().saturating_sub(other.pos()));
+            return QDir::new_from_vec(if offset == QVec2::ZERO { QVec2::X } else { offset });
+        };
+        let right = edge.get_perpendicular_dir();
+        let positions: Vec<QVec2> = self.points_iter().map(|other| other.pos()).collect();
+        let is_closed = self.get_shape_type() != QShapeType::QPolyline && positions.len() >= 3;
+        if is_closed {
+            return if signed_double_area(&positions) >= Q64::ZERO { right } else { -right };
+        }
+        let side = edge.vector().cross(point.pos().saturating_sub(edge.start().pos()));
+        if side > Q64::ZERO { -right } else { right }
+    }
+
+    /// Get the farthest point of the shape along `dir`, collisions and distances run GJK on it.
+    ///
+    /// Defaults to the farthest vertex, curved shapes return the exact point.
+    fn get_support_point(&self, dir: QDir) -> QPoint {
+        support_of_points(self.points_iter(), dir)
+    }
+
+    /// Get the min and max extent of the shape along `dir`.
+    fn project_onto(&self, dir: QDir) -> (Q64, Q64) {
+        let axis = dir.to_vec();
+        self.points_iter()
+            .map(|point| point.pos().dot(axis))
+            .fold(None, |extent: Option<(Q64, Q64)>, value| match extent {
+                Some((min, max)) => Some((min.min(value), max.max(value))),
+                None => Some((value, value)),
+            })
+            .expect("[QShapeCommon::project_onto] Shape must have points.")
+    }
+
+    /// Get a copy of the shape moved by `offset`.
+    fn translated(&self, offset: QVec2) -> Self where Self: Sized;
+
+    /// Get a copy of the shape rotated counter-clockwise around `pivot`, `angle` is in radians.
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> Self::Rotated;
+
+    /// Get a copy of the shape scaled by `factor` around its centroid.
+    ///
+    /// Every shape asserts that `factor` is larger than zero, so no shape is mirrored or collapsed.
+    fn scaled(&self, factor: Q64) -> Self where Self: Sized;
+
     fn get_polygon(&self) -> QPolygon {
-        QPolygon::new(self.points().to_vec())
+        QPolygon::new(self.points())
+    }
+
+    /// Get the smallest convex polygon around the points, counter-clockwise.
+    ///
+    /// Points and lines give a hull of one or two points. Circles are hulled by their vertices,
+    /// see `algorithm::hull_of` for exact tangents between several shapes.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let l_shape = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 2.0), qvec2!(2.0, 2.0), qvec2!(2.0, 4.0), qvec2!(0.0, 4.0)]);
+    /// let hull = l_shape.convex_hull();
+    /// assert_eq!(hull.points().len(), 5);
+    /// assert!(hull.is_ccw());
+    /// assert!(hull.is_point_inside(&QPoint::new(qvec2!(3.0, 2.5))));
+    /// ```
+    fn convex_hull(&self) -> QPolygon {
+        let positions: Vec<QVec2> = self.points_iter().map(|point| point.pos()).collect();
+        QPolygon::new_from_parts(andrew_graham_scan(&positions))
     }
 
     fn ear_clipping_triangulation(&self) -> Vec<usize> {
         self.get_polygon().ear_clipping_triangulation()
     }
+
+    /// Draw a point uniformly at random inside the shape.
+    ///
+    /// Shapes with area are triangulated and a triangle is picked with a probability
+    /// proportional to its area, curved boundaries are flattened with `SAMPLE_RESOLUTION`
+    /// segments. Shapes without area are sampled by length along their points.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::generate::QSplitMix64;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 4.0), qvec2!(2.0, 1.0), qvec2!(0.0, 4.0)]);
+    /// let mut rng = QSplitMix64::new(1);
+    /// assert!((0..100).all(|_| polygon.is_point_inside(&polygon.sample_interior(&mut rng))));
+    /// ```
+    fn sample_interior(&self, rng: &mut impl QRng) -> QPoint {
+        let mesh = self.to_mesh(SAMPLE_RESOLUTION);
+        sample_triangles(mesh.positions(), mesh.indices(), rng).unwrap_or_else(|| sample_chain(&self.points(), rng))
+    }
+
+    /// Triangulate the shape into a render mesh without UVs.
+    ///
+    /// `resolution` is the number of segments used for curved boundaries, polygonal shapes
+    /// ignore it. Shapes without area give a mesh without triangles.
+    fn to_mesh(&self, _resolution: usize) -> QMesh {
+        let positions: Vec<QVec2> = self.points_iter().map(|point| point.pos()).collect();
+        let has_area = !matches!(self.get_shape_type(), QShapeType::QPoint | QShapeType::QLine | QShapeType::QPolyline)
+            && positions.len() >= 3
+            && signed_double_area(&positions) != Q64::ZERO;
+        if !has_area {
+            return QMesh::new(positions, Vec::new());
+        }
+        let triangles = self.ear_clipping_triangulation();
+        QMesh::from_triangulation(positions, &triangles)
+    }
 }
\ No newline at end of file
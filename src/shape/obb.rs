@@ -0,0 +1,105 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use super::{ QPoint, QBbox, QPolygon, QSupportMap };
+use super::support_map::support_of_points;
+
+/// Oriented box, a rectangle rotated so its first side runs along `axis`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QObb {
+    center: QPoint,
+    axis: QVec2,
+    half_extents: QVec2,
+}
+
+impl QObb {
+    /// Half extents may be zero, a box of flat point sets has no width.
+    pub fn new(center: QPoint, axis: QDir, half_extents: QVec2) -> Self {
+        assert!(
+            half_extents.x >= Q64::ZERO && half_extents.y >= Q64::ZERO,
+            "[QObb::new] half_extents({half_extents:?}) should not be negative."
+        );
+        Self {
+            center,
+            axis: axis.to_vec(),
+            half_extents,
+        }
+    }
+
+    pub fn center(&self) -> QPoint {
+        self.center
+    }
+
+    /// Unit direction of the side measured by `half_extents.x`.
+    pub fn axis(&self) -> QVec2 {
+        self.axis
+    }
+
+    /// Unit direction of the side measured by `half_extents.y`, the axis turned by 90°.
+    pub fn perpendicular_axis(&self) -> QVec2 {
+        QVec2::new(-self.axis.y, self.axis.x)
+    }
+
+    pub fn half_extents(&self) -> QVec2 {
+        self.half_extents
+    }
+
+    pub fn set_center(&mut self, center: QPoint) {
+        self.center = center;
+    }
+
+    pub fn area(&self) -> Q64 {
+        self.half_extents.x.saturating_mul(self.half_extents.y).saturating_mul(q64!(4))
+    }
+
+    /// Get the corners counter-clockwise, starting at the one behind both axes.
+    pub fn corners(&self) -> [QPoint; 4] {
+        let u = self.axis.saturating_mul_num(self.half_extents.x);
+        let v = self.perpendicular_axis().saturating_mul_num(self.half_extents.y);
+        let center = self.center.pos();
+        [
+            QPoint::new(center.saturating_sub(u).saturating_sub(v)),
+            QPoint::new(center.saturating_add(u).saturating_sub(v)),
+            QPoint::new(center.saturating_add(u).saturating_add(v)),
+            QPoint::new(center.saturating_sub(u).saturating_add(v)),
+        ]
+    }
+
+    pub fn get_polygon(&self) -> QPolygon {
+        QPolygon::new(self.corners().to_vec())
+    }
+
+    pub fn get_bbox(&self) -> QBbox {
+        QBbox::from_points(self.corners()).expect("[QObb::get_bbox] Corners are never empty.")
+    }
+
+    /// Get the box moved by `offset`.
+    pub fn translated(&self, offset: QVec2) -> Self {
+        Self {
+            center: self.center.translated(offset),
+            ..*self
+        }
+    }
+
+    pub fn is_point_inside(&self, point: &QPoint) -> bool {
+        let offset = point.pos().saturating_sub(self.center.pos());
+        offset.dot(self.axis).abs() <= self.half_extents.x && offset.dot(self.perpendicular_axis()).abs() <= self.half_extents.y
+    }
+}
+
+impl QSupportMap for QObb {
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let obb = QObb::new(QPoint::ZERO, QDir::new_from_vec(QVec2::Y), qvec2!(2.0, 1.0));
+    /// assert_eq!(obb.support(QDir::new_from_vec(qvec2!(1.0, 1.0))).pos(), qvec2!(1.0, 2.0));
+    /// ```
+    fn support(&self, dir: QDir) -> QPoint {
+        support_of_points(self.corners(), dir)
+    }
+}
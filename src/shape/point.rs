@@ -1,10 +1,13 @@
+use alloc::{ vec, vec::Vec };
 use qmath::prelude::*;
 use qmath::vec2::QVec2;
-use serde::{Deserialize, Serialize};
 use crate::algorithm::{epa, gjk};
-use super::{ QBbox, QPolygon, QShapeCommon, QShapeType };
+use crate::transform::QTransform;
+use super::support_map::QShapeSupport;
+use super::{ QLine, QBbox, QShapeCommon, QShapeType };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct QPoint {
     pos: QVec2,
 }
@@ -61,13 +64,119 @@ impl QPoint {
     pub fn distance(&self, other: &QPoint) -> Q64 {
         self.pos.distance(other.pos)
     }
+
+    /// Round the position to the nearest multiple of `cell`, halves round up.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let point = QPoint::new(qvec2!(1.3, -0.6));
+    /// assert_eq!(point.snap_to_grid(Q64::HALF).pos(), qvec2!(1.5, -0.5));
+    /// ```
+    pub fn snap_to_grid(&self, cell: Q64) -> QPoint {
+        assert!(cell > Q64::ZERO, "[QPoint::snap_to_grid] cell({cell}) should be positive.");
+        QPoint::new(snap_pos(self.pos, cell))
+    }
+
+    /// Get the mirror image about `axis`, the axis is treated as an infinite line.
+    pub fn mirrored(&self, axis: &QLine) -> QPoint {
+        QTransform::from_reflection(axis).transform_point(self)
+    }
+
+    /// Create a point from float coordinates, rounded to the nearest `Q64`.
+    ///
+    /// Panics if a coordinate is not finite or doesn't fit in `Q64`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let point = QPoint::from_f64([1.5, -2.0]);
+    /// assert_eq!(point.pos(), qvec2!(1.5, -2.0));
+    /// assert_eq!(point.to_f64(), [1.5, -2.0]);
+    /// assert_eq!(point.to_f32(), [1.5f32, -2.0f32]);
+    /// ```
+    pub fn from_f64(pos: [f64; 2]) -> Self {
+        Self::new(vec2_from_f64(pos))
+    }
+
+    /// Create a point from `f32` coordinates, see `from_f64`.
+    pub fn from_f32(pos: [f32; 2]) -> Self {
+        Self::from_f64([pos[0] as f64, pos[1] as f64])
+    }
+
+    /// Get the coordinates rounded to the nearest `f64`.
+    pub fn to_f64(&self) -> [f64; 2] {
+        vec2_to_f64(self.pos)
+    }
+
+    /// Get the coordinates rounded to the nearest `f32`.
+    pub fn to_f32(&self) -> [f32; 2] {
+        [self.pos.x.to_num::<f32>(), self.pos.y.to_num::<f32>()]
+    }
+
+    /// Convert a whole slice of float coordinates, see `from_f64`.
+    pub fn from_f64_slice(positions: &[[f64; 2]]) -> Vec<QPoint> {
+        positions.iter().map(|pos| Self::from_f64(*pos)).collect()
+    }
+
+    /// Convert a whole slice of `f32` coordinates, see `from_f64`.
+    pub fn from_f32_slice(positions: &[[f32; 2]]) -> Vec<QPoint> {
+        positions.iter().map(|pos| Self::from_f32(*pos)).collect()
+    }
+
+    /// Convert a whole slice of points to `f64` coordinates.
+    pub fn to_f64_vec(points: &[QPoint]) -> Vec<[f64; 2]> {
+        points.iter().map(|point| point.to_f64()).collect()
+    }
+
+    /// Convert a whole slice of points to `f32` coordinates.
+    pub fn to_f32_vec(points: &[QPoint]) -> Vec<[f32; 2]> {
+        points.iter().map(|point| point.to_f32()).collect()
+    }
+}
+
+/// Round a float to the nearest `Q64`, `None` if it is not finite or out of range.
+pub(crate) fn try_q64_from_f64(value: f64) -> Option<Q64> {
+    if !value.is_finite() || value < Q64::MIN.to_num::<f64>() || value > Q64::MAX.to_num::<f64>() {
+        return None;
+    }
+    Some(q64!(value))
+}
+
+/// Round a float to the nearest `Q64`, panics if it is not finite or out of range.
+pub(crate) fn q64_from_f64(value: f64) -> Q64 {
+    try_q64_from_f64(value).unwrap_or_else(|| panic!("[q64_from_f64] value({value}) doesn't fit in Q64."))
+}
+
+pub(crate) fn vec2_from_f64(pos: [f64; 2]) -> QVec2 {
+    QVec2::new(q64_from_f64(pos[0]), q64_from_f64(pos[1]))
+}
+
+/// Round `pos` to the nearest multiple of `cell`, `cell` must be positive.
+pub(crate) fn snap_pos(pos: QVec2, cell: Q64) -> QVec2 {
+    let snap = |value: Q64| value.saturating_div(cell).saturating_add(Q64::HALF).floor().saturating_mul(cell);
+    QVec2::new(snap(pos.x), snap(pos.y))
+}
+
+pub(crate) fn vec2_to_f64(pos: QVec2) -> [f64; 2] {
+    [pos.x.to_num::<f64>(), pos.y.to_num::<f64>()]
 }
 
 impl QShapeCommon for QPoint {
+    type Rotated = QPoint;
+
     fn points(&self) -> Vec<QPoint> {
         vec![*self]
     }
 
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
+        core::iter::once(*self)
+    }
+
     fn get_bbox(&self) -> QBbox {
         QBbox::new_from_parts(self.pos - QVec2::EPS, self.pos + QVec2::EPS)
     }
@@ -87,10 +196,9 @@ impl QShapeCommon for QPoint {
     fn is_collide(&self, other: &impl QShapeCommon) -> bool {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.is_collide(self),
             _ => {
-                let my_polygon = QPolygon::new(self.points());
-                let other_polygon = QPolygon::new(other.points());
-                gjk(&my_polygon, &other_polygon)
+                gjk(self, &QShapeSupport(other))
             }
         }
     }
@@ -98,11 +206,32 @@ impl QShapeCommon for QPoint {
     fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.try_get_seperation_vector(self).map(|vector| -vector),
             _ => {
-                let my_polygon = QPolygon::new(self.points());
-                let other_polygon = QPolygon::new(other.points());
-                epa(&my_polygon, &other_polygon)
+                epa(self, &QShapeSupport(other))
             }
         }
     }
+
+    fn translated(&self, offset: QVec2) -> Self {
+        QPoint::new(self.pos.saturating_add(offset))
+    }
+
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> QPoint {
+        QTransform::from_rotation_around(pivot.pos(), angle).transform_point(self)
+    }
+
+    /// A point has no extent, scaling around its centroid leaves it unchanged.
+    fn scaled(&self, factor: Q64) -> Self {
+        assert!(factor > Q64::ZERO, "[QPoint::scaled] factor({factor:?}) should be larger than zero.");
+        *self
+    }
+
+    fn closest_point(&self, _point: &QPoint) -> QPoint {
+        *self
+    }
+
+    fn distance_to(&self, other: &impl QShapeCommon) -> Q64 {
+        other.closest_point(self).distance(self)
+    }
 }
\ No newline at end of file
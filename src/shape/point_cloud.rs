@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::andrew_graham_scan;
+use crate::spatial::QKdTree;
+use super::{ QPoint, QBbox, QPolygon, QPolyline };
+
+/// Unordered set of points, e.g. a sensor scan or sampled positions.
+///
+/// Queries scan every point, build a `QKdTree` with `to_kd_tree` for repeated lookups.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QPointCloud {
+    points: Vec<QPoint>,
+}
+
+impl QPointCloud {
+    pub fn new(points: Vec<QPoint>) -> Self {
+        Self {
+            points,
+        }
+    }
+
+    pub fn new_from_parts(points: Vec<QVec2>) -> Self {
+        Self::new(points.into_iter().map(QPoint::new).collect())
+    }
+
+    pub fn points(&self) -> &Vec<QPoint> {
+        &self.points
+    }
+
+    pub fn points_mut(&mut self) -> &mut Vec<QPoint> {
+        &mut self.points
+    }
+
+    /// Get the positions, as taken by the `Vec<QVec2>` based functions in `algorithm`.
+    pub fn positions(&self) -> Vec<QVec2> {
+        self.points.iter().map(|point| point.pos()).collect()
+    }
+
+    pub fn push(&mut self, point: QPoint) {
+        self.points.push(point);
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Get the bbox of the points, see `QBbox::from_points`.
+    pub fn get_bbox(&self) -> Option<QBbox> {
+        QBbox::from_points(self.points.iter().copied())
+    }
+
+    /// Get the mean of the points, `None` if there are none.
+    pub fn get_centroid(&self) -> Option<QPoint> {
+        let origin = self.points.first()?.pos();
+        let count = q64!(self.points.len() as i64);
+        let sum = self.points.iter().fold(QVec2::ZERO, |acc, point| acc.saturating_add(point.pos().saturating_sub(origin)));
+        Some(QPoint::new(origin.saturating_add(QVec2::new(sum.x.saturating_div(count), sum.y.saturating_div(count)))))
+    }
+
+    /// Get the convex hull, `None` if the points are all on one line.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let cloud: QPointCloud = [qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(1.0, 1.0), qvec2!(0.0, 2.0), qvec2!(2.0, 2.0)]
+    ///     .into_iter()
+    ///     .map(QPoint::new)
+    ///     .collect();
+    /// assert_eq!(cloud.convex_hull().unwrap().points().len(), 4);
+    /// assert_eq!(cloud.get_centroid(), Some(QPoint::new(qvec2!(1.0, 1.0))));
+    /// assert_eq!(cloud.nearest(&QPoint::new(qvec2!(1.9, 2.5))), Some(4));
+    /// assert_eq!(cloud.within_radius(&QPoint::new(qvec2!(0.0, 0.0)), q64!(2)), vec![0, 1, 2, 3]);
+    /// ```
+    pub fn convex_hull(&self) -> Option<QPolygon> {
+        let hull = andrew_graham_scan(&self.positions());
+        (hull.len() >= 3).then(|| QPolygon::new_from_parts(hull))
+    }
+
+    /// Get the index of the point nearest to `point`, ties are broken by the smaller index.
+    pub fn nearest(&self, point: &QPoint) -> Option<usize> {
+        self.points
+            .iter()
+            .enumerate()
+            .min_by_key(|(index, other)| (other.pos().distance_squared(point.pos()), *index))
+            .map(|(index, _)| index)
+    }
+
+    /// Get the indices of all points within `radius` of `point`, sorted by index.
+    pub fn within_radius(&self, point: &QPoint, radius: Q64) -> Vec<usize> {
+        let radius_squared = radius.saturating_mul(radius);
+        (0..self.points.len())
+            .filter(|&index| self.points[index].pos().distance_squared(point.pos()) <= radius_squared)
+            .collect()
+    }
+
+    /// Build a tree over the points, indices are shared with the cloud.
+    pub fn to_kd_tree(&self) -> QKdTree {
+        QKdTree::new(self.points.clone())
+    }
+
+    /// Connect the points in their order into a closed ring.
+    pub fn to_polygon(&self) -> QPolygon {
+        QPolygon::new(self.points.clone())
+    }
+
+    /// Connect the points in their order into an open chain.
+    pub fn to_polyline(&self) -> QPolyline {
+        QPolyline::new(self.points.clone())
+    }
+}
+
+impl From<Vec<QPoint>> for QPointCloud {
+    fn from(points: Vec<QPoint>) -> Self {
+        Self::new(points)
+    }
+}
+
+impl FromIterator<QPoint> for QPointCloud {
+    fn from_iter<I: IntoIterator<Item = QPoint>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
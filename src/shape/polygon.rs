@@ -1,34 +1,1260 @@
+use alloc::{ collections::BTreeMap, vec, vec::Vec };
+use core::hash::{ Hash, Hasher };
+use core::ops::{ Deref, DerefMut };
 use qmath::prelude::*;
 use qmath::vec2::QVec2;
 use qmath::dir::QDir;
-use serde::{Deserialize, Serialize};
-use crate::algorithm::{epa, gjk};
-use super::{ QPoint, QLine, QBbox, QShapeCommon, QShapeType };
+use crate::algorithm::{andrew_graham_scan, atan2, epa, get_minkowski_sum, gjk, orient2d, signed_double_area};
+use crate::tolerance::QTolerance;
+use crate::transform::QTransform;
+use super::support_map::QShapeSupport;
+use super::{ QPoint, QLine, QBbox, QPolyline, QArcLengthParam, QShapeCommon, QShapeType, SAMPLE_RESOLUTION };
+use super::line::get_closest_point_on_chain;
+use super::point::snap_pos;
+use crate::raster::get_crossings_at_y;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+/// Equality and hashing only look at the points, the bbox is derived from them.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct QPolygon {
     points: Vec<QPoint>,
+    /// Cached `get_bbox` result, `None` while there are no points.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bbox: Option<QBbox>,
+    /// Outward edge normals, only stored after `with_precomputed_normals`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    normals: Option<Vec<QDir>>,
+    /// Ear clipping triangle indices, only stored after `with_cached_triangulation`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    triangulation: Option<Vec<usize>>,
+    /// Convex hull vertices, stored along with the triangulation.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hull: Option<Vec<QPoint>>,
+    /// Set when the points change while the triangulation is cached, the cache is rebuilt
+    /// on the next `cached_triangulation` or `cached_convex_hull`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    is_triangulation_dirty: bool,
+    /// Bumped every time the points are changed through `points_mut`, kept by serde so
+    /// buffers built before saving still see later changes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    version: u64,
+}
+
+impl PartialEq for QPolygon {
+    fn eq(&self, other: &Self) -> bool {
+        self.points == other.points
+    }
+}
+
+impl Eq for QPolygon {}
+
+impl Hash for QPolygon {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.points.hash(state);
+    }
 }
 
 impl QPolygon {
     pub fn new(points: Vec<QPoint>) -> Self {
+        let bbox = get_points_bbox(&points);
         Self {
             points,
+            bbox,
+            normals: None,
+            triangulation: None,
+            hull: None,
+            is_triangulation_dirty: false,
+            version: 0,
         }
     }
 
     pub fn new_from_parts(points: Vec<QVec2>) -> Self {
-        Self {
-            points: points.into_iter().map(|pos| QPoint::new(pos)).collect(),
+        Self::new(points.into_iter().map(|pos| QPoint::new(pos)).collect())
+    }
+
+    /// Create a regular polygon with `n` vertices on the circle of `radius` around `center`.
+    ///
+    /// The first vertex is at angle `rotation` from the x axis and the others follow
+    /// counter-clockwise.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let square = QPolygon::regular(QPoint::ZERO, q64!(2), 4, Q64::ZERO);
+    /// assert_eq!(square.points()[0].pos(), qvec2!(2.0, 0.0));
+    /// assert!((square.points()[1].pos() - qvec2!(0.0, 2.0)).length() < q64!(0.001));
+    /// assert!(square.is_ccw());
+    /// ```
+    pub fn regular(center: QPoint, radius: Q64, n: usize, rotation: Q64) -> Self {
+        assert!(n >= 3, "[QPolygon::regular] n({n}) should be at least 3.");
+        assert!(radius > Q64::ZERO, "[QPolygon::regular] radius({radius}) should be positive.");
+        Self::new((0..n).map(|i| get_point_on_circle(center, radius, rotation, i, n)).collect())
+    }
+
+    /// Create a star with `points` tips on the circle of `outer_radius`, alternating with
+    /// notches on the circle of `inner_radius`, counter-clockwise from the first tip at
+    /// the top.
+    pub fn star(center: QPoint, outer_radius: Q64, inner_radius: Q64, points: usize) -> Self {
+        assert!(points >= 2, "[QPolygon::star] points({points}) should be at least 2.");
+        assert!(
+            inner_radius > Q64::ZERO && inner_radius < outer_radius,
+            "[QPolygon::star] inner_radius({inner_radius}) should be between zero and outer_radius({outer_radius})."
+        );
+        let rotation = Q64::PI.saturating_mul(Q64::HALF);
+        Self::new(
+            (0..points * 2)
+                .map(|i| {
+                    let radius = if i % 2 == 0 { outer_radius } else { inner_radius };
+                    get_point_on_circle(center, radius, rotation, i, points * 2)
+                })
+                .collect()
+        )
+    }
+
+    /// Create a polygon from imported vertices, removing duplicates and collinear vertices.
+    ///
+    /// Vertices within `tolerance` of their predecessor are dropped, then vertices within
+    /// `tolerance` of the line through their neighbours, until none is left. Spikes that
+    /// double back are removed too. Returns `None` if fewer than 3 vertices remain.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let points = QPoint::from_f64_slice(&[[0.0, 0.0], [0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [0.0, 0.0]]);
+    /// let polygon = QPolygon::new_cleaned(points, QTolerance::EXACT).unwrap();
+    /// assert_eq!(polygon.points().len(), 4);
+    /// assert_eq!(QPolygon::new_cleaned(QPoint::from_f64_slice(&[[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]]), QTolerance::EXACT), None);
+    /// ```
+    pub fn new_cleaned(points: Vec<QPoint>, tolerance: QTolerance) -> Option<Self> {
+        let mut rst: Vec<QPoint> = Vec::with_capacity(points.len());
+        for point in points {
+            if rst.last().is_none_or(|last: &QPoint| !tolerance.is_equal(last.pos(), point.pos())) {
+                rst.push(point);
+            }
+        }
+        while rst.len() > 1 && tolerance.is_equal(rst[0].pos(), rst[rst.len() - 1].pos()) {
+            rst.pop();
         }
+
+        let mut i = 0;
+        let mut kept = 0;
+        // Stop after a full lap without removals.
+        while rst.len() >= 3 && kept < rst.len() {
+            let m = rst.len();
+            let (prev, cur, next) = (rst[(i + m - 1) % m].pos(), rst[i].pos(), rst[(i + 1) % m].pos());
+            if tolerance.is_collinear(prev, next, cur) || tolerance.is_equal(prev, next) {
+                rst.remove(i);
+                kept = 0;
+                i = (i + m - 2) % (m - 1);
+            } else {
+                kept += 1;
+                i = (i + 1) % m;
+            }
+        }
+        (rst.len() >= 3).then(|| Self::new(rst))
     }
 
     pub fn points(&self) -> &Vec<QPoint> {
         &self.points
     }
 
-    pub fn points_mut(&mut self) -> &mut Vec<QPoint> {
-        &mut self.points
+    /// Get mutable access to the points, the cached bbox is refreshed when the guard drops.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let mut polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(0.0, 1.0)]);
+    /// polygon.points_mut().push(QPoint::new(qvec2!(5.0, 5.0)));
+    /// assert!(polygon.get_bbox().is_point_inside(&QPoint::new(qvec2!(5.0, 5.0))));
+    /// ```
+    pub fn points_mut(&mut self) -> QPolygonPointsMut<'_> {
+        QPolygonPointsMut { polygon: self }
+    }
+
+    /// Insert a vertex before `index`, `index` may be the number of points to append.
+    pub fn insert_vertex(&mut self, index: usize, point: QPoint) {
+        assert!(index <= self.points.len(), "[QPolygon::insert_vertex] index({index}) is out of {} points.", self.points.len());
+        self.points_mut().insert(index, point);
+    }
+
+    /// Remove the vertex at `index` and return it.
+    pub fn remove_vertex(&mut self, index: usize) -> QPoint {
+        assert!(index < self.points.len(), "[QPolygon::remove_vertex] index({index}) is out of {} points.", self.points.len());
+        self.points_mut().remove(index)
+    }
+
+    /// Move the vertex at `index` to `point` and return its previous value.
+    pub fn set_vertex(&mut self, index: usize, point: QPoint) -> QPoint {
+        assert!(index < self.points.len(), "[QPolygon::set_vertex] index({index}) is out of {} points.", self.points.len());
+        core::mem::replace(&mut self.points_mut()[index], point)
+    }
+
+    /// Apply `edit` to the points and keep it only if `is_valid` accepts the result.
+    ///
+    /// Returns whether the edit was kept, a rejected edit leaves the polygon unchanged.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let mut polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)]);
+    /// // Dragging a corner across the opposite edge would make the polygon self-intersect.
+    /// assert!(!polygon.try_edit(|points| points[1] = QPoint::new(qvec2!(-1.0, 3.0)), QPolygon::is_simple));
+    /// assert_eq!(polygon.points()[1].pos(), qvec2!(2.0, 0.0));
+    /// assert!(polygon.try_edit(|points| points[1] = QPoint::new(qvec2!(3.0, -1.0)), QPolygon::is_simple));
+    /// ```
+    pub fn try_edit(&mut self, edit: impl FnOnce(&mut Vec<QPoint>), is_valid: impl Fn(&QPolygon) -> bool) -> bool {
+        let backup = self.points.clone();
+        edit(&mut *self.points_mut());
+        if is_valid(self) {
+            return true;
+        }
+        *self.points_mut() = backup;
+        false
+    }
+
+    /// Does the boundary have at least 3 edges and no edge touch another except its
+    /// neighbours at their shared vertices.
+    pub fn is_simple(&self) -> bool {
+        let edges: Vec<QLine> = self.edges_iter().collect();
+        let n = edges.len();
+        if n < 3 {
+            return false;
+        }
+        for i in 0..n {
+            // Neighbours fold back onto each other when they are collinear and opposed.
+            let (edge, next) = (edges[i], edges[(i + 1) % n]);
+            if edge.is_parallel_to(&next) && edge.vector().dot(next.vector()) < Q64::ZERO {
+                return false;
+            }
+            for j in i + 2..n {
+                if (i == 0 && j == n - 1) || !edges[i].intersects(&edges[j]) {
+                    continue;
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Store the outward edge normals so SAT queries don't recompute them.
+    ///
+    /// `closest_point` also uses them to skip the edges facing away. EPA works on the
+    /// Minkowski difference and gains nothing from them. Worth it for static polygons that
+    /// are queried many times. The normals are kept up to date through `points_mut`,
+    /// transformed copies don't carry them.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(0.0, 1.0)]).with_precomputed_normals();
+    /// assert_eq!(polygon.precomputed_normals().unwrap()[0].to_vec(), QVec2::NEG_Y);
+    /// ```
+    pub fn with_precomputed_normals(mut self) -> Self {
+        self.normals = Some(self.get_edge_normals());
+        self
+    }
+
+    /// Store the triangulation and the convex hull so drawing a static polygon every frame
+    /// doesn't re-run ear clipping.
+    ///
+    /// `ear_clipping_triangulation` and `to_mesh` then use the stored triangles. Changing the
+    /// points through `points_mut` drops both and bumps `triangulation_version`, they are
+    /// recomputed on the next `cached_triangulation` or `cached_convex_hull`. Until then the
+    /// `&self` queries compute them on the fly. Transformed copies don't carry them.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let mut polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(1.0, 0.5), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)])
+    ///     .with_cached_triangulation();
+    /// assert_eq!(polygon.cached_triangulation().unwrap().len(), 9);
+    /// assert_eq!(polygon.cached_convex_hull().unwrap().len(), 4);
+    /// let version = polygon.triangulation_version();
+    /// polygon.remove_vertex(2);
+    /// assert_eq!(polygon.cached_triangulation().unwrap().len(), 6);
+    /// assert_ne!(polygon.triangulation_version(), version);
+    /// ```
+    pub fn with_cached_triangulation(mut self) -> Self {
+        self.refresh_triangulation();
+        self
+    }
+
+    /// Get the stored triangle indices, rebuilt first if the points changed, see
+    /// `with_cached_triangulation`.
+    pub fn cached_triangulation(&mut self) -> Option<&[usize]> {
+        self.refresh_dirty_triangulation();
+        self.triangulation.as_deref()
+    }
+
+    /// Get the stored convex hull, counter-clockwise, rebuilt first if the points changed,
+    /// see `with_cached_triangulation`.
+    pub fn cached_convex_hull(&mut self) -> Option<&[QPoint]> {
+        self.refresh_dirty_triangulation();
+        self.hull.as_deref()
+    }
+
+    /// Number of changes to the points, so buffers built from the triangulation know when
+    /// to be rebuilt. Wraps around on overflow.
+    pub fn triangulation_version(&self) -> u64 {
+        self.version
+    }
+
+    fn refresh_dirty_triangulation(&mut self) {
+        if self.is_triangulation_dirty {
+            self.refresh_triangulation();
+        }
+    }
+
+    fn refresh_triangulation(&mut self) {
+        let ring: Vec<QVec2> = self.points.iter().map(|point| point.pos()).collect();
+        // Ear clipping needs a ring with area, anything else has no triangles.
+        self.triangulation = Some(if ring.len() >= 3 && signed_double_area(&ring) != Q64::ZERO {
+            self.triangulate()
+        } else {
+            vec![]
+        });
+        self.hull = Some(andrew_graham_scan(&ring).into_iter().map(QPoint::new).collect());
+        self.is_triangulation_dirty = false;
+    }
+
+    /// Ear clipping, see `ear_clipping_triangulation`.
+    ///
+    /// An ear must turn the same way as the ring, so reflex vertices are never clipped.
+    /// Rings without an ear, self-intersecting ones for example, keep the triangles found so far.
+    fn triangulate(&self) -> Vec<usize> {
+        let shape = &self.points;
+        let ring: Vec<QVec2> = shape.iter().map(|point| point.pos()).collect();
+        let winding = signed_double_area(&ring).cmp(&Q64::ZERO);
+        let is_valid_ear = |a: &QPoint, b: &QPoint, c: &QPoint| {
+            if winding == core::cmp::Ordering::Equal || orient2d(a.pos(), b.pos(), c.pos()) != winding {
+                return false;
+            }
+            let ear = QPolygon::new(vec![*a, *b, *c]);
+            shape.iter().all(|point| point == a || point == b || point == c || !ear.is_point_inside(point))
+        };
+
+        let mut points = shape.to_vec();
+        let mut triangles_indices = Vec::new();
+        let get_index = |v| shape.iter().position(|&p| p == v).unwrap();
+        // Triangles are clockwise whatever the winding of the ring.
+        let mut push_triangle = |a: QPoint, b: QPoint, c: QPoint| {
+            let triangle = if winding == core::cmp::Ordering::Greater { [c, b, a] } else { [a, b, c] };
+            triangles_indices.extend(triangle.map(|point| get_index(point)));
+        };
+
+        while points.len() > 3 {
+            let n = points.len();
+            let Some(i) = (0..n).find(|&i| is_valid_ear(&points[i], &points[(i + 1) % n], &points[(i + 2) % n])) else {
+                return triangles_indices;
+            };
+            push_triangle(points[i], points[(i + 1) % n], points[(i + 2) % n]);
+            points.remove((i + 1) % n);
+        }
+
+        if points.len() == 3 && orient2d(points[0].pos(), points[1].pos(), points[2].pos()) == winding {
+            push_triangle(points[0], points[1], points[2]);
+        }
+        triangles_indices
+    }
+
+    /// Get the outward normal of every non-degenerate edge, in edge order.
+    pub fn get_edge_normals(&self) -> Vec<QDir> {
+        let is_ccw = self.is_ccw();
+        self.edges_iter()
+            .map(|edge| if is_ccw { edge.get_perpendicular_dir() } else { -edge.get_perpendicular_dir() })
+            .collect()
+    }
+
+    /// Create a polygon from float vertices, see `QPoint::from_f64`.
+    pub fn from_f64(points: &[[f64; 2]]) -> Self {
+        Self::new(QPoint::from_f64_slice(points))
+    }
+
+    /// Create a polygon from `f32` vertices, see `QPoint::from_f64`.
+    pub fn from_f32(points: &[[f32; 2]]) -> Self {
+        Self::new(QPoint::from_f32_slice(points))
+    }
+
+    /// Get the vertices rounded to the nearest `f64`.
+    pub fn to_f64(&self) -> Vec<[f64; 2]> {
+        QPoint::to_f64_vec(&self.points)
+    }
+
+    /// Get the vertices rounded to the nearest `f32`.
+    pub fn to_f32(&self) -> Vec<[f32; 2]> {
+        QPoint::to_f32_vec(&self.points)
+    }
+
+    /// Point in polygon test in O(log n) for convex polygons, the boundary counts as inside.
+    ///
+    /// Binary searches the triangle fan around the first vertex, so the answer is only
+    /// meaningful if the polygon is convex. Both windings work.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let hexagon = QPolygon::regular(QPoint::ZERO, q64!(4), 6, Q64::ZERO);
+    /// assert!(hexagon.contains_point_convex(&QPoint::new(qvec2!(2.0, 1.0))));
+    /// assert!(hexagon.contains_point_convex(&hexagon.points()[3]));
+    /// assert!(!hexagon.contains_point_convex(&QPoint::new(qvec2!(3.5, 3.0))));
+    /// ```
+    pub fn contains_point_convex(&self, point: &QPoint) -> bool {
+        let n = self.points.len();
+        if n < 3 {
+            return false;
+        }
+        let (first, q) = (self.points[0].pos(), point.pos());
+        let is_ccw = (1..n - 1)
+            .map(|i| orient2d(first, self.points[i].pos(), self.points[i + 1].pos()))
+            .find(|turn| *turn != core::cmp::Ordering::Equal)
+            .unwrap_or(core::cmp::Ordering::Greater) == core::cmp::Ordering::Greater;
+        // Is `q` on the left of or on the line from `a` to `b`, in counter-clockwise terms.
+        let is_left = |a: QVec2, b: QVec2| {
+            let turn = orient2d(a, b, q);
+            if is_ccw { turn != core::cmp::Ordering::Less } else { turn != core::cmp::Ordering::Greater }
+        };
+        let last = self.points[n - 1].pos();
+        if !is_left(first, self.points[1].pos()) || !is_left(last, first) {
+            return false;
+        }
+        let (mut low, mut high) = (1, n - 1);
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if is_left(first, self.points[mid].pos()) { low = mid; } else { high = mid; }
+        }
+        is_left(self.points[low].pos(), self.points[low + 1].pos())
+    }
+
+    /// Test many points at once, gives the same answers as `is_point_inside` for each point.
+    ///
+    /// Points outside the bbox are rejected first, the rest are swept in y order while only
+    /// the edges spanning the current y are kept active, so each edge is visited by the
+    /// queries in its own y range instead of by every query.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 4.0), qvec2!(2.0, 1.0), qvec2!(0.0, 4.0)]);
+    /// let queries = [QPoint::new(qvec2!(1.0, 1.0)), QPoint::new(qvec2!(2.0, 3.0)), QPoint::new(qvec2!(4.0, 2.0)), QPoint::new(qvec2!(9.0, 1.0))];
+    /// assert_eq!(polygon.contains_points(&queries), vec![true, false, true, false]);
+    /// ```
+    pub fn contains_points(&self, points: &[QPoint]) -> Vec<bool> {
+        let mut rst = vec![false; points.len()];
+        if self.points.len() < 3 {
+            return rst;
+        }
+
+        let bbox = self.get_bbox();
+        let mut order: Vec<usize> = (0..points.len()).filter(|&i| bbox.is_point_inside(&points[i])).collect();
+        order.sort_by(|&a, &b| points[a].y().partial_cmp(&points[b].y()).unwrap_or(core::cmp::Ordering::Equal));
+
+        // Same edges as `is_point_inside`.
+        let mut edges: Vec<QLine> = self.edges_iter().collect();
+        edges.sort_by(|a, b| a.start().y().min(a.end().y()).partial_cmp(&b.start().y().min(b.end().y())).unwrap_or(core::cmp::Ordering::Equal));
+
+        let mut active: Vec<QLine> = vec![];
+        let mut next_edge = 0;
+        for index in order {
+            let point = &points[index];
+            let py = point.y();
+            while next_edge < edges.len() && edges[next_edge].start().y().min(edges[next_edge].end().y()) <= py {
+                active.push(edges[next_edge]);
+                next_edge += 1;
+            }
+            active.retain(|edge| edge.start().y().max(edge.end().y()) >= py);
+
+            let mut is_inside = false;
+            for edge in &active {
+                if edge.is_point_on_line(point) {
+                    is_inside = true;
+                    break;
+                }
+                if (edge.start().y() > py) != (edge.end().y() > py) && point.x() < edge.get_x_at_y(py) {
+                    is_inside = !is_inside;
+                }
+            }
+            rst[index] = is_inside;
+        }
+        rst
+    }
+
+    /// Snap every vertex to the grid and drop the vertices that land on their predecessor.
+    ///
+    /// This is plain quantization: edges may cross after snapping and the result may have
+    /// fewer than 3 vertices. Use `snap_rounded` when the topology has to survive.
+    pub fn snap_to_grid(&self, cell: Q64) -> QPolygon {
+        let mut points: Vec<QPoint> = self.points.iter().map(|point| point.snap_to_grid(cell)).collect();
+        points.dedup();
+        while points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        QPolygon::new(points)
+    }
+
+    /// Snap round the polygon onto the grid without creating new crossings.
+    ///
+    /// Every cell holding a vertex or an edge crossing is a hot pixel, and each edge is
+    /// rerouted through the centers of the hot pixels it passes, in order along the edge.
+    /// Edges can then meet or overlap but never cross where they didn't before, so no
+    /// slivers or flipped triangles appear. Spikes left by overlapping edges are removed.
+    /// Returns `None` if the polygon collapses to less than 3 vertices or no area.
+    ///
+    /// Runs in O(n * h) for `n` edges and `h` hot pixels, plus O(n²) to find the crossings.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// // The thin spike would be flattened onto the bottom edge by plain snapping.
+    /// let polygon = QPolygon::new_from_parts(vec![
+    ///     qvec2!(0.0, 0.0), qvec2!(4.1, 0.0), qvec2!(4.1, 2.0), qvec2!(2.0, 0.4), qvec2!(0.0, 2.0),
+    /// ]);
+    /// let snapped = polygon.snap_rounded(Q64::ONE).unwrap();
+    /// assert!(snapped.points().iter().all(|point| point.snap_to_grid(Q64::ONE) == *point));
+    /// assert!(snapped.points().contains(&QPoint::new(qvec2!(2.0, 0.0))));
+    /// ```
+    pub fn snap_rounded(&self, cell: Q64) -> Option<QPolygon> {
+        assert!(cell > Q64::ZERO, "[QPolygon::snap_rounded] cell({cell}) should be positive.");
+        let ring: Vec<QVec2> = self.points.iter().map(|point| point.pos()).collect();
+        let n = ring.len();
+        if n < 3 {
+            return None;
+        }
+
+        let mut hot_pixels: Vec<QVec2> = ring.iter().map(|pos| snap_pos(*pos, cell)).collect();
+        for i in 0..n {
+            for j in i + 1..n {
+                let (a, b, c, d) = (ring[i], ring[(i + 1) % n], ring[j], ring[(j + 1) % n]);
+                if a == b || c == d || !QLine::new_from_parts(a, b).is_crossing(&QLine::new_from_parts(c, d)) {
+                    continue;
+                }
+                let (ab, cd) = (b.saturating_sub(a), d.saturating_sub(c));
+                let t = c.saturating_sub(a).cross(cd).saturating_div(ab.cross(cd));
+                hot_pixels.push(snap_pos(a.saturating_add(ab.saturating_mul_num(t)), cell));
+            }
+        }
+        hot_pixels.sort_by(|a, b| a.x.cmp(&b.x).then(a.y.cmp(&b.y)));
+        hot_pixels.dedup();
+
+        let half = cell.saturating_mul(Q64::HALF);
+        let mut rst: Vec<QVec2> = vec![];
+        for i in 0..n {
+            let (start, end) = (ring[i], ring[(i + 1) % n]);
+            let dir = end.saturating_sub(start);
+            let mut passed: Vec<QVec2> = hot_pixels
+                .iter()
+                .copied()
+                .filter(|center| is_segment_touching_square(start, end, *center, half))
+                .collect();
+            passed.sort_by(|a, b| a.saturating_sub(start).dot(dir).cmp(&b.saturating_sub(start).dot(dir)));
+            rst.extend(passed);
+        }
+
+        // Remove repeated vertices and the spikes of edges folded back onto themselves.
+        let mut changed = true;
+        while changed && rst.len() >= 3 {
+            changed = false;
+            rst.dedup();
+            while rst.len() > 1 && rst.first() == rst.last() {
+                rst.pop();
+                changed = true;
+            }
+            let m = rst.len();
+            if let Some(i) = (0..m).find(|&i| m >= 3 && rst[(i + m - 1) % m] == rst[(i + 1) % m]) {
+                let (first, second) = if i + 1 < m { (i, i + 1) } else { (0, i) };
+                rst.remove(second);
+                rst.remove(first);
+                changed = true;
+            }
+        }
+        (rst.len() >= 3 && signed_double_area(&rst) != Q64::ZERO).then(|| QPolygon::new_from_parts(rst))
+    }
+
+    /// Get the interior spans `(y, x_start, x_end)` of rows `row_height` high.
+    ///
+    /// Rows are aligned to multiples of `row_height` like the cells of `raster` and sampled
+    /// at their middle, spans come bottom row first and left to right. Crossings pair up
+    /// with the even-odd rule, so self-overlapping parts count as outside.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 2.0), qvec2!(2.0, 1.0), qvec2!(0.0, 2.0)]);
+    /// let spans: Vec<_> = polygon.scanlines(Q64::ONE).collect();
+    /// assert_eq!(spans.len(), 3);
+    /// assert_eq!(spans[0], (Q64::HALF, Q64::ZERO, q64!(4)));
+    /// ```
+    pub fn scanlines(&self, row_height: Q64) -> impl Iterator<Item = (Q64, Q64, Q64)> + '_ {
+        assert!(row_height > Q64::ZERO, "[QPolygon::scanlines] row_height({row_height}) should be positive.");
+        let mut edges: Vec<QLine> = self.edges_iter().collect();
+        edges.sort_by(|a, b| a.start().y().min(a.end().y()).cmp(&b.start().y().min(b.end().y())));
+        let (row_min, row_max) = match (self.points.len() >= 3).then(|| self.get_bbox()) {
+            Some(bbox) => (
+                bbox.left_bottom().y().saturating_div(row_height).floor().to_num::<i64>(),
+                bbox.right_top().y().saturating_div(row_height).floor().to_num::<i64>(),
+            ),
+            _ => (0, -1),
+        };
+
+        let mut active: Vec<QLine> = vec![];
+        let mut next_edge = 0;
+        (row_min..=row_max).flat_map(move |row| {
+            let y = q64!(row).saturating_add(Q64::HALF).saturating_mul(row_height);
+            while next_edge < edges.len() && edges[next_edge].start().y().min(edges[next_edge].end().y()) <= y {
+                active.push(edges[next_edge]);
+                next_edge += 1;
+            }
+            active.retain(|edge| edge.start().y().max(edge.end().y()) >= y);
+            let mut crossings = get_crossings_at_y(&active, y);
+            crossings.sort();
+            let spans: Vec<(Q64, Q64, Q64)> = crossings.chunks_exact(2).map(|pair| (y, pair[0], pair[1])).filter(|span| span.1 < span.2).collect();
+            spans
+        })
+    }
+
+    /// Resample the boundary with `count` vertices evenly spaced by arc length.
+    ///
+    /// The first vertex is kept, the others are placed along the closed boundary so the
+    /// original corners are generally cut.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)]);
+    /// let resampled = square.resample_n(8);
+    /// assert_eq!(resampled.points()[1].pos(), qvec2!(1.0, 0.0));
+    /// assert_eq!(resampled.points()[5].pos(), qvec2!(1.0, 2.0));
+    /// assert_eq!(square.resample(Q64::HALF).points().len(), 16);
+    /// ```
+    pub fn resample_n(&self, count: usize) -> QPolygon {
+        assert!(count >= 3, "[QPolygon::resample_n] count({count}) should be at least 3.");
+        assert!(!self.points.is_empty(), "[QPolygon::resample_n] Points must not be empty.");
+        let mut ring = self.points.clone();
+        ring.push(self.points[0]);
+        let param = QArcLengthParam::new(&QPolyline::new(ring));
+        let step = param.length().saturating_div(q64!(count as i64));
+        QPolygon::new((0..count).map(|i| param.point_at_length(step.saturating_mul(q64!(i as i64)))).collect())
+    }
+
+    /// Resample the boundary with vertices about `spacing` apart, see `resample_n`.
+    ///
+    /// The spacing is adjusted so the vertices divide the perimeter evenly, at least 3 are kept.
+    pub fn resample(&self, spacing: Q64) -> QPolygon {
+        assert!(spacing > Q64::ZERO, "[QPolygon::resample] spacing({spacing}) should be positive.");
+        let perimeter = self.edges_iter().fold(Q64::ZERO, |acc, edge| acc.saturating_add(edge.vector().length()));
+        let count = perimeter.saturating_div(spacing).saturating_add(Q64::HALF).floor().to_num::<i64>().max(3);
+        self.resample_n(count as usize)
+    }
+
+    /// Find a large axis aligned rectangle inside the polygon, `None` if there is no room.
+    ///
+    /// Rectangle sides are taken from the vertex coordinates plus `resolution` even steps
+    /// across the bbox, so the result is the best one on that grid and a higher resolution
+    /// gets closer to the true largest rectangle.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let l_shape = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 2.0), qvec2!(2.0, 2.0), qvec2!(2.0, 4.0), qvec2!(0.0, 4.0)]);
+    /// assert_eq!(l_shape.largest_inscribed_rect(0), Some(QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(6.0, 2.0))));
+    /// let triangle = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(0.0, 4.0)]);
+    /// assert_eq!(triangle.largest_inscribed_rect(8), Some(QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(2.0, 2.0))));
+    /// ```
+    pub fn largest_inscribed_rect(&self, resolution: usize) -> Option<QBbox> {
+        let bbox = (self.points.len() >= 3).then(|| self.get_bbox())?;
+        let get_grid = |coords: Vec<Q64>, min: Q64, size: Q64| {
+            let mut rst = coords;
+            rst.extend((1..resolution).map(|i| min.saturating_add(size.saturating_mul(q64!(i as i64)).saturating_div(q64!(resolution as i64)))));
+            rst.sort();
+            rst.dedup();
+            rst
+        };
+        let min = bbox.left_bottom().pos();
+        let xs = get_grid(self.points.iter().map(|point| point.x()).collect(), min.x, bbox.width());
+        let ys = get_grid(self.points.iter().map(|point| point.y()).collect(), min.y, bbox.height());
+        let edges: Vec<QLine> = self.edges_iter().collect();
+
+        // Every vertex is on a grid line, so an edge entering a cell has corners strictly on both sides.
+        let inside: Vec<Vec<bool>> = ys
+            .windows(2)
+            .map(|row| {
+                xs.windows(2)
+                    .map(|col| {
+                        let corners = [QVec2::new(col[0], row[0]), QVec2::new(col[1], row[0]), QVec2::new(col[1], row[1]), QVec2::new(col[0], row[1])];
+                        let is_crossed = edges.iter().any(|edge| {
+                            let (a, b) = (edge.start().pos(), edge.end().pos());
+                            let is_overlapping = a.x.min(b.x) < col[1] && a.x.max(b.x) > col[0] && a.y.min(b.y) < row[1] && a.y.max(b.y) > row[0];
+                            let sides: Vec<core::cmp::Ordering> = corners.iter().map(|corner| orient2d(a, b, *corner)).collect();
+                            is_overlapping && sides.contains(&core::cmp::Ordering::Greater) && sides.contains(&core::cmp::Ordering::Less)
+                        });
+                        let center = QPoint::new(corners[0].midpoint(corners[2]));
+                        !is_crossed && self.is_point_inside(&center)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut best: Option<(Q64, QBbox)> = None;
+        for bottom in 0..inside.len() {
+            let mut is_column_inside = vec![true; xs.len() - 1];
+            for top in bottom..inside.len() {
+                is_column_inside.iter_mut().zip(&inside[top]).for_each(|(column, cell)| *column &= *cell);
+                let height = ys[top + 1].saturating_sub(ys[bottom]);
+                let mut start = 0;
+                while start < is_column_inside.len() {
+                    if !is_column_inside[start] {
+                        start += 1;
+                        continue;
+                    }
+                    let end = (start..is_column_inside.len()).find(|&i| !is_column_inside[i]).unwrap_or(is_column_inside.len());
+                    let area = xs[end].saturating_sub(xs[start]).saturating_mul(height);
+                    if best.is_none_or(|(best_area, _)| area > best_area) {
+                        let rect = QBbox::new_from_parts(QVec2::new(xs[start], ys[bottom]), QVec2::new(xs[end], ys[top + 1]));
+                        best = Some((area, rect));
+                    }
+                    start = end;
+                }
+            }
+        }
+        best.map(|(_, rect)| rect)
+    }
+
+    /// Get the points within `radius` of the polygon, its Minkowski sum with a disk.
+    ///
+    /// The disk is the regular `SAMPLE_RESOLUTION`-gon inscribed in the circle of `radius`,
+    /// and the sum is exact for it: the polygon joined with `algorithm::get_minkowski_sum` of
+    /// every edge and the disk. Nearby parts merge and enclosed gaps become holes. Every
+    /// outer boundary is counter-clockwise and comes with the clockwise holes inside it.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)]);
+    /// let dilated = square.dilate(Q64::ONE);
+    /// assert_eq!(dilated.len(), 1);
+    /// let (outer, holes) = &dilated[0];
+    /// assert!(holes.is_empty());
+    /// assert!(outer.is_point_inside(&QPoint::new(qvec2!(3.0, 1.0))));
+    /// assert!(outer.is_point_inside(&QPoint::new(qvec2!(2.5, 2.5))));
+    /// assert!(!outer.is_point_inside(&QPoint::new(qvec2!(2.9, 2.9))));
+    /// ```
+    pub fn dilate(&self, radius: Q64) -> Vec<(QPolygon, Vec<QPolygon>)> {
+        assert!(radius >= Q64::ZERO, "[QPolygon::dilate] radius({radius:?}) should not be negative.");
+        if radius == Q64::ZERO {
+            return if self.points.len() >= 3 { vec![(self.clone(), vec![])] } else { vec![] };
+        }
+        let (outers, holes): (Vec<QPolygon>, Vec<QPolygon>) = self
+            .get_offset_rings(radius, true)
+            .into_iter()
+            .partition(|ring| ring.is_ccw());
+        let mut rst: Vec<(QPolygon, Vec<QPolygon>)> = outers.into_iter().map(|outer| (outer, vec![])).collect();
+        for hole in holes {
+            // A hole belongs to the smallest outer boundary around it.
+            let owner = rst
+                .iter_mut()
+                .filter(|(outer, _)| outer.is_point_inside(&hole.points[0]))
+                .min_by_key(|(outer, _)| outer.area());
+            if let Some((_, owned)) = owner {
+                owned.push(hole);
+            }
+        }
+        rst
+    }
+
+    /// Get the points at least `radius` deep inside the polygon, its Minkowski difference
+    /// with a disk.
+    ///
+    /// Exact for the same disk polygon as `dilate`, the polygon minus the Minkowski sum of
+    /// every edge and the disk. Narrow necks split the polygon into counter-clockwise parts
+    /// and parts thinner than `2 * radius` disappear, eroding a simple polygon leaves no holes.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 4.0), qvec2!(0.0, 4.0)]);
+    /// let eroded = square.erode(Q64::ONE);
+    /// assert_eq!(eroded.len(), 1);
+    /// assert!((eroded[0].area() - q64!(4)).abs() < q64!(0.001));
+    /// assert!(!eroded[0].is_point_inside(&QPoint::new(qvec2!(0.5, 2.0))));
+    /// assert!(square.erode(q64!(3)).is_empty());
+    /// ```
+    pub fn erode(&self, radius: Q64) -> Vec<QPolygon> {
+        assert!(radius >= Q64::ZERO, "[QPolygon::erode] radius({radius:?}) should not be negative.");
+        if radius == Q64::ZERO {
+            return if self.points.len() >= 3 { vec![self.clone()] } else { vec![] };
+        }
+        self.get_offset_rings(radius, false).into_iter().filter(|ring| ring.is_ccw()).collect()
+    }
+
+    /// Cut the polygon along an open path into the parts on either side of it.
+    ///
+    /// Every stretch of `cut` through the inside from one boundary point to another splits
+    /// the part it runs through in two, so a winding path may cut many times. Stretches ending
+    /// inside the polygon cut nothing and the path must not cross itself. The parts are
+    /// counter-clockwise, the polygon itself is returned when nothing is cut.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 4.0), qvec2!(0.0, 4.0)]);
+    /// let parts = square.slice(&QPolyline::new_from_parts(vec![qvec2!(-2.0, 1.0), qvec2!(2.0, 3.0), qvec2!(6.0, 1.0)]));
+    /// assert_eq!(parts.len(), 2);
+    /// assert_eq!(parts.iter().map(|part| part.area()).collect::<Vec<_>>(), vec![q64!(10), q64!(6)]);
+    /// assert!(parts.iter().any(|part| part.is_point_inside(&QPoint::new(qvec2!(2.0, 1.0)))));
+    /// let dangling = square.slice(&QPolyline::new_from_parts(vec![qvec2!(-1.0, 2.0), qvec2!(2.0, 2.0)]));
+    /// assert_eq!(dangling, vec![square.clone()]);
+    /// ```
+    pub fn slice(&self, cut: &QPolyline) -> Vec<QPolygon> {
+        let mut ring: Vec<QVec2> = self.points.iter().map(|point| point.pos()).collect();
+        ring.dedup();
+        if ring.len() > 1 && ring[0] == ring[ring.len() - 1] {
+            ring.pop();
+        }
+        let mut path: Vec<QVec2> = cut.points().iter().map(|point| point.pos()).collect();
+        path.dedup();
+        if ring.len() < 3 || path.len() < 2 || signed_double_area(&ring) == Q64::ZERO {
+            return vec![self.clone()];
+        }
+        if signed_double_area(&ring) < Q64::ZERO {
+            ring.reverse();
+        }
+
+        let n = ring.len();
+        let mut edge_hits: Vec<Vec<QVec2>> = vec![vec![]; n];
+        let mut path_hits: Vec<Vec<QVec2>> = vec![vec![]; path.len() - 1];
+        for j in 0..path.len() - 1 {
+            for i in 0..n {
+                if let Some(hit) = get_slice_hit(path[j], path[j + 1], ring[i], ring[(i + 1) % n]) {
+                    edge_hits[i].push(hit);
+                    path_hits[j].push(hit);
+                }
+            }
+        }
+
+        // Nodes are shared by position, so a boundary point met by the path has one id.
+        let mut positions: Vec<QVec2> = vec![];
+        let mut ids: BTreeMap<(Q64, Q64), usize> = BTreeMap::new();
+        let mut boundary: Vec<usize> = vec![];
+        for (i, hits) in edge_hits.iter_mut().enumerate() {
+            hits.sort_by_key(|hit| hit.distance_squared(ring[i]));
+            for pos in core::iter::once(ring[i]).chain(hits.iter().copied()) {
+                let id = get_slice_node(&mut positions, &mut ids, pos);
+                if boundary.last() != Some(&id) {
+                    boundary.push(id);
+                }
+            }
+        }
+        while boundary.len() > 1 && boundary.first() == boundary.last() {
+            boundary.pop();
+        }
+        let mut path_nodes: Vec<usize> = vec![];
+        for (j, hits) in path_hits.iter_mut().enumerate() {
+            hits.sort_by_key(|hit| hit.distance_squared(path[j]));
+            for pos in core::iter::once(path[j]).chain(hits.iter().copied()) {
+                let id = get_slice_node(&mut positions, &mut ids, pos);
+                if path_nodes.last() != Some(&id) {
+                    path_nodes.push(id);
+                }
+            }
+        }
+        let id = get_slice_node(&mut positions, &mut ids, path[path.len() - 1]);
+        if path_nodes.last() != Some(&id) {
+            path_nodes.push(id);
+        }
+
+        let mut ring_index = vec![None; positions.len()];
+        for (k, &id) in boundary.iter().enumerate() {
+            ring_index[id] = Some(k);
+        }
+        let m = boundary.len();
+        let is_adjacent = |u: usize, v: usize| match (ring_index[u], ring_index[v]) {
+            (Some(x), Some(y)) => (x + 1) % m == y || (y + 1) % m == x,
+            _ => false,
+        };
+
+        // Split the path at the boundary, the stretches between two boundary nodes are chords.
+        let mut chords: Vec<Vec<usize>> = vec![];
+        let mut current: Option<Vec<usize>> = None;
+        for &id in &path_nodes {
+            if let Some(chord) = current.as_mut() {
+                chord.push(id);
+            }
+            if ring_index[id].is_some() {
+                chords.extend(current.take());
+                current = Some(vec![id]);
+            }
+        }
+
+        let mut parts: Vec<Vec<usize>> = vec![boundary];
+        let mut is_cut = false;
+        for chord in chords {
+            let (first, last) = (chord[0], chord[chord.len() - 1]);
+            if chord.len() < 2 || first == last || (chord.len() == 2 && is_adjacent(first, last)) {
+                continue;
+            }
+            // Inner nodes are off the boundary, a single segment is tested at its midpoint.
+            let probe = QPoint::new(if chord.len() > 2 { positions[chord[1]] } else { positions[first].midpoint(positions[last]) });
+            if !self.is_point_inside(&probe) {
+                continue;
+            }
+            let found = parts.iter().position(|part| {
+                part.contains(&first) && part.contains(&last)
+                    && QPolygon::new_from_parts(part.iter().map(|&id| positions[id]).collect()).is_point_inside(&probe)
+            });
+            let Some(index) = found else {
+                continue;
+            };
+            let part = parts.remove(index);
+            let k = part.len();
+            let ia = part.iter().position(|&id| id == first).unwrap_or(0);
+            let ib = part.iter().position(|&id| id == last).unwrap_or(0);
+            let inner = &chord[1..chord.len() - 1];
+            let mut left: Vec<usize> = (0..=(ib + k - ia) % k).map(|s| part[(ia + s) % k]).collect();
+            left.extend(inner.iter().rev());
+            let mut right: Vec<usize> = (0..=(ia + k - ib) % k).map(|s| part[(ib + s) % k]).collect();
+            right.extend(inner.iter());
+            parts.extend([left, right].into_iter().filter(|part| part.len() >= 3));
+            is_cut = true;
+        }
+
+        if !is_cut {
+            return vec![self.clone()];
+        }
+        parts
+            .into_iter()
+            .map(|part| QPolygon::new_from_parts(part.into_iter().map(|id| positions[id]).collect()))
+            .collect()
+    }
+
+    /// Boundary rings of the polygon joined with, or without, the capsules around its edges.
+    ///
+    /// Only capsule boundaries can be on the result, every edge is inside its own capsule.
+    /// A piece of a capsule edge between two crossings is kept if it is outside the polygon
+    /// when joining or inside it when cutting, and outside all other capsules. Crossings
+    /// are computed once per pair of edges, so the kept pieces meet exactly.
+    fn get_offset_rings(&self, radius: Q64, is_dilation: bool) -> Vec<QPolygon> {
+        let mut ring: Vec<QVec2> = self.points.iter().map(|point| point.pos()).collect();
+        ring.dedup();
+        if ring.len() > 1 && ring[0] == ring[ring.len() - 1] {
+            ring.pop();
+        }
+        if ring.len() < 3 || signed_double_area(&ring) == Q64::ZERO {
+            return vec![];
+        }
+        if signed_double_area(&ring) < Q64::ZERO {
+            ring.reverse();
+        }
+
+        let n = ring.len();
+        let disk = QPolygon::regular(QPoint::ZERO, radius, SAMPLE_RESOLUTION, Q64::ZERO);
+        let capsules: Vec<Vec<QVec2>> = (0..n)
+            .map(|i| {
+                let edge = QPolygon::new_from_parts(vec![ring[i], ring[(i + 1) % n]]);
+                get_minkowski_sum(&edge, &disk).points.iter().map(|point| point.pos()).collect()
+            })
+            .collect();
+        let capsule_bboxes: Vec<QBbox> = capsules
+            .iter()
+            .map(|capsule| get_points_bbox(&capsule.iter().map(|pos| QPoint::new(*pos)).collect::<Vec<_>>()).expect("[QPolygon::get_offset_rings] Capsules are not empty."))
+            .collect();
+        let edges: Vec<(usize, QVec2, QVec2)> = capsules
+            .iter()
+            .enumerate()
+            .flat_map(|(k, capsule)| (0..capsule.len()).map(move |i| (k, capsule[i], capsule[(i + 1) % capsule.len()])))
+            .collect();
+
+        let mut cuts: Vec<Vec<QVec2>> = edges.iter().map(|&(_, a, b)| vec![a, b]).collect();
+        for e in 0..edges.len() {
+            let (k, a, b) = edges[e];
+            for f in e + 1..edges.len() {
+                let (other, p, q) = edges[f];
+                if other == k || !capsule_bboxes[other].is_overlap(&capsule_bboxes[k]) {
+                    continue;
+                }
+                if let Some(hit) = get_slice_hit(a, b, p, q) {
+                    cuts[e].push(hit);
+                    cuts[f].push(hit);
+                }
+                // Overlapping collinear edges are split at each other's ends.
+                for (target, from, to, end) in [(e, a, b, p), (e, a, b, q), (f, p, q, a), (f, p, q, b)] {
+                    let along = to.saturating_sub(from);
+                    let offset = end.saturating_sub(from);
+                    if orient2d(from, to, end) == core::cmp::Ordering::Equal
+                        && offset.dot(along) > Q64::ZERO && offset.dot(along) < along.dot(along)
+                    {
+                        cuts[target].push(end);
+                    }
+                }
+            }
+        }
+
+        let mut pieces: Vec<(QVec2, QVec2)> = vec![];
+        for (e, &(k, a, _)) in edges.iter().enumerate() {
+            let mut stops = core::mem::take(&mut cuts[e]);
+            stops.sort_by_key(|stop| stop.distance_squared(a));
+            stops.dedup();
+            for window in stops.windows(2) {
+                let (start, end) = (window[0], window[1]);
+                let mid = start.midpoint(end);
+                let side = get_ring_side(&ring, mid);
+                if side != if is_dilation { core::cmp::Ordering::Less } else { core::cmp::Ordering::Greater } {
+                    continue;
+                }
+                let is_covered = (0..n).any(|other| {
+                    if other == k || !capsule_bboxes[other].is_point_inside(&QPoint::new(mid)) {
+                        return false;
+                    }
+                    match get_ring_side(&capsules[other], mid) {
+                        core::cmp::Ordering::Greater => true,
+                        // On the other boundary, covered unless it runs the same way and comes later.
+                        core::cmp::Ordering::Equal => {
+                            let is_same_way = capsules[other]
+                                .iter()
+                                .map(|pos| orient2d(start, end, *pos))
+                                .find(|side| *side != core::cmp::Ordering::Equal)
+                                == Some(core::cmp::Ordering::Greater);
+                            !is_same_way || other < k
+                        }
+                        core::cmp::Ordering::Less => false,
+                    }
+                });
+                if !is_covered {
+                    pieces.push(if is_dilation { (start, end) } else { (end, start) });
+                }
+            }
+        }
+        get_piece_rings(&pieces)
+            .into_iter()
+            .filter_map(|ring| QPolygon::new_cleaned(ring.into_iter().map(QPoint::new).collect(), QTolerance::EXACT))
+            .collect()
+    }
+
+    /// Get a copy of the polygon with every vertex transformed.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(1.0, 1.0)]);
+    /// let moved = polygon.transformed(&QTransform::from_translation(qvec2!(2.0, 3.0)));
+    /// assert_eq!(moved.points()[2].pos(), qvec2!(3.0, 4.0));
+    /// ```
+    pub fn transformed(&self, transform: &QTransform) -> QPolygon {
+        QPolygon::new(self.points.iter().map(|point| transform.transform_point(point)).collect())
+    }
+
+    /// Get the mirror image about `axis`, the axis is treated as an infinite line.
+    ///
+    /// A reflection flips the winding, so the vertex order is reversed afterwards to keep
+    /// CCW polygons CCW.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(1.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 1.0)]);
+    /// let y_axis = QLine::new_from_parts(QVec2::ZERO, QVec2::Y);
+    /// let mirrored = polygon.mirrored(&y_axis);
+    /// assert_eq!(mirrored.points()[0].pos(), qvec2!(-2.0, 1.0));
+    /// assert_eq!(mirrored.points()[2].pos(), qvec2!(-1.0, 0.0));
+    /// ```
+    pub fn mirrored(&self, axis: &QLine) -> QPolygon {
+        let mut rst = self.transformed(&QTransform::from_reflection(axis));
+        rst.points.reverse();
+        rst
+    }
+
+    /// Transform every vertex in place.
+    pub fn transform(&mut self, transform: &QTransform) {
+        self.points_mut().iter_mut().for_each(|point| *point = transform.transform_point(point));
+    }
+
+    /// Get the interior angle at each vertex in radians, in (0, 2π).
+    ///
+    /// Works for both windings, angles above π are at reflex vertices.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 4.0), qvec2!(2.0, 1.0), qvec2!(0.0, 4.0)]);
+    /// let angles = polygon.interior_angles();
+    /// assert!((angles[0] - Q64::PI * Q64::HALF).abs() < q64!(0.001));
+    /// assert!(angles[3] > Q64::PI);
+    /// assert_eq!(polygon.reflex_vertices(), vec![3]);
+    /// assert_eq!(polygon.convex_vertices(), vec![0, 1, 2, 4]);
+    /// ```
+    pub fn interior_angles(&self) -> Vec<Q64> {
+        let n = self.points.len();
+        let sign = if self.is_ccw() { Q64::ONE } else { Q64::NEG_ONE };
+        (0..n)
+            .map(|i| {
+                let (prev, cur, next) = (self.points[(i + n - 1) % n].pos(), self.points[i].pos(), self.points[(i + 1) % n].pos());
+                let (incoming, outgoing) = (cur.saturating_sub(prev), next.saturating_sub(cur));
+                let turn = atan2(incoming.cross(outgoing), incoming.dot(outgoing));
+                Q64::PI.saturating_sub(turn.saturating_mul(sign))
+            })
+            .collect()
+    }
+
+    /// Get the indices of the vertices with an interior angle above π, exactly.
+    pub fn reflex_vertices(&self) -> Vec<usize> {
+        let turn = if self.is_ccw() { core::cmp::Ordering::Less } else { core::cmp::Ordering::Greater };
+        self.get_vertices_turning(turn)
+    }
+
+    /// Get the indices of the vertices with an interior angle below π, exactly.
+    ///
+    /// Vertices in the middle of a straight run are neither convex nor reflex.
+    pub fn convex_vertices(&self) -> Vec<usize> {
+        let turn = if self.is_ccw() { core::cmp::Ordering::Greater } else { core::cmp::Ordering::Less };
+        self.get_vertices_turning(turn)
+    }
+
+    fn get_vertices_turning(&self, turn: core::cmp::Ordering) -> Vec<usize> {
+        let n = self.points.len();
+        (0..n)
+            .filter(|&i| orient2d(self.points[(i + n - 1) % n].pos(), self.points[i].pos(), self.points[(i + 1) % n].pos()) == turn)
+            .collect()
+    }
+
+    /// Are the vertices in counter-clockwise order, by the sign of the area.
+    pub fn is_ccw(&self) -> bool {
+        let ring: Vec<QVec2> = self.points.iter().map(|point| point.pos()).collect();
+        signed_double_area(&ring) >= Q64::ZERO
+    }
+
+    /// Get the enclosed area, positive for either winding.
+    pub fn area(&self) -> Q64 {
+        let ring: Vec<QVec2> = self.points.iter().map(|point| point.pos()).collect();
+        signed_double_area(&ring).abs().saturating_mul(Q64::HALF)
+    }
+
+    /// Get the center of mass of the enclosed area, unlike `get_centroid` which averages the vertices.
+    ///
+    /// Falls back to `get_centroid` for polygons without area.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 2.0), qvec2!(3.0, 2.0), qvec2!(2.0, 2.0), qvec2!(1.0, 2.0), qvec2!(0.0, 2.0)]);
+    /// assert_eq!(polygon.area(), q64!(8));
+    /// assert_eq!(polygon.area_centroid().pos(), qvec2!(2.0, 1.0));
+    /// assert_ne!(polygon.get_centroid().pos(), qvec2!(2.0, 1.0));
+    /// ```
+    pub fn area_centroid(&self) -> QPoint {
+        let Some(origin) = self.points.first().map(|point| point.pos()) else {
+            return self.get_centroid();
+        };
+        let ring: Vec<QVec2> = self.points.iter().map(|point| point.pos().saturating_sub(origin)).collect();
+        let double_area = signed_double_area(&ring);
+        if double_area == Q64::ZERO {
+            return self.get_centroid();
+        }
+        let n = ring.len();
+        let sum = (0..n).fold(QVec2::ZERO, |acc, i| {
+            let (p, q) = (ring[i], ring[(i + 1) % n]);
+            acc.saturating_add(p.saturating_add(q).saturating_mul_num(p.cross(q)))
+        });
+        let divisor = double_area.saturating_mul(q64!(3));
+        QPoint::new(origin.saturating_add(QVec2::new(sum.x.saturating_div(divisor), sum.y.saturating_div(divisor))))
+    }
+
+    /// Get the second moments of area `(Ixx, Iyy, Ixy)` about the area centroid.
+    ///
+    /// `Ixx` integrates `y²`, `Iyy` integrates `x²` and `Ixy` integrates `xy` over the area,
+    /// with coordinates relative to `area_centroid`. Both windings give the same result.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let rect = QPolygon::new_from_parts(vec![qvec2!(1.0, 1.0), qvec2!(7.0, 1.0), qvec2!(7.0, 3.0), qvec2!(1.0, 3.0)]);
+    /// assert_eq!(rect.second_moments(), (q64!(4), q64!(36), Q64::ZERO));
+    /// assert_eq!(rect.moment_of_inertia(Q64::HALF), q64!(20));
+    /// ```
+    pub fn second_moments(&self) -> (Q64, Q64, Q64) {
+        let centroid = self.area_centroid().pos();
+        let ring: Vec<QVec2> = self.points.iter().map(|point| point.pos().saturating_sub(centroid)).collect();
+        let n = ring.len();
+        let (mut ixx, mut iyy, mut ixy) = (Q64::ZERO, Q64::ZERO, Q64::ZERO);
+        for i in 0..n {
+            let (p, q) = (ring[i], ring[(i + 1) % n]);
+            let cross = p.cross(q);
+            let yy = p.y.saturating_mul(p.y).saturating_add(p.y.saturating_mul(q.y)).saturating_add(q.y.saturating_mul(q.y));
+            let xx = p.x.saturating_mul(p.x).saturating_add(p.x.saturating_mul(q.x)).saturating_add(q.x.saturating_mul(q.x));
+            let xy = p.x.saturating_mul(q.y)
+                .saturating_add(p.x.saturating_mul(p.y).saturating_mul(q64!(2)))
+                .saturating_add(q.x.saturating_mul(q.y).saturating_mul(q64!(2)))
+                .saturating_add(q.x.saturating_mul(p.y));
+            ixx = ixx.saturating_add(cross.saturating_mul(yy));
+            iyy = iyy.saturating_add(cross.saturating_mul(xx));
+            ixy = ixy.saturating_add(cross.saturating_mul(xy));
+        }
+        let sign = if signed_double_area(&ring) < Q64::ZERO { Q64::NEG_ONE } else { Q64::ONE };
+        (
+            ixx.saturating_div(q64!(12)).saturating_mul(sign),
+            iyy.saturating_div(q64!(12)).saturating_mul(sign),
+            ixy.saturating_div(q64!(24)).saturating_mul(sign),
+        )
+    }
+
+    /// Get the moment of inertia about the area centroid for a uniform `density` per unit area.
+    pub fn moment_of_inertia(&self, density: Q64) -> Q64 {
+        let (ixx, iyy, _) = self.second_moments();
+        ixx.saturating_add(iyy).saturating_mul(density)
+    }
+
+    /// Iterate over the boundary edges, from each vertex to the next and from the last
+    /// back to the first. Edges between repeated vertices are skipped, see `edges_iter`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(1.0, 0.0), qvec2!(0.0, 1.0)]);
+    /// let edges: Vec<QLine> = polygon.edges().collect();
+    /// assert_eq!(edges.len(), 3);
+    /// assert_eq!(edges[2], QLine::new_from_parts(qvec2!(0.0, 1.0), qvec2!(0.0, 0.0)));
+    /// ```
+    pub fn edges(&self) -> impl Iterator<Item = QLine> + '_ {
+        self.edges_iter()
     }
 
     /// Get the cloest line to the origin.
@@ -63,8 +1289,13 @@ impl QPolygon {
         for i in 0..points_count {
             let vj = self.points[j].pos();
             let vi = self.points[i].pos();
+            // Skip the edges between repeated vertices like `edges_iter` does.
+            if vi == vj {
+                j = i;
+                continue;
+            }
             let line = QLine::new(QPoint::new(vj), QPoint::new(vi));
-            
+
             let line_distance = line.get_distance_from_point(point);
             if line_distance < min_dist {
                 min_dist = line_distance;
@@ -103,17 +1334,217 @@ impl QPolygon {
             .max_by(|a, b| {
                 let dot_a: Q64 = a.pos().dot(dir.to_vec());
                 let dot_b: Q64 = b.pos().dot(dir.to_vec());
-                dot_a.partial_cmp(&dot_b).unwrap_or(std::cmp::Ordering::Equal)
+                dot_a.partial_cmp(&dot_b).unwrap_or(core::cmp::Ordering::Equal)
             })
             .expect("[get_farest_point_in_direction] Shape must not be empty.")
     }
 }
 
+/// Mutable access to the points of a `QPolygon`, refreshes its cached bbox on drop and
+/// marks a cached triangulation as dirty.
+pub struct QPolygonPointsMut<'a> {
+    polygon: &'a mut QPolygon,
+}
+
+impl Deref for QPolygonPointsMut<'_> {
+    type Target = Vec<QPoint>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.polygon.points
+    }
+}
+
+impl DerefMut for QPolygonPointsMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.polygon.points
+    }
+}
+
+impl Drop for QPolygonPointsMut<'_> {
+    fn drop(&mut self) {
+        self.polygon.bbox = get_points_bbox(&self.polygon.points);
+        if self.polygon.normals.is_some() {
+            self.polygon.normals = Some(self.polygon.get_edge_normals());
+        }
+        if self.polygon.triangulation.is_some() || self.polygon.is_triangulation_dirty {
+            self.polygon.triangulation = None;
+            self.polygon.hull = None;
+            self.polygon.is_triangulation_dirty = true;
+        }
+        self.polygon.version = self.polygon.version.wrapping_add(1);
+    }
+}
+
+/// Get the `i`-th of `n` points evenly spaced on a circle, starting at angle `rotation`.
+fn get_point_on_circle(center: QPoint, radius: Q64, rotation: Q64, i: usize, n: usize) -> QPoint {
+    let angle = rotation.saturating_add(q64!(i as i64).saturating_div(q64!(n as i64)).saturating_mul(Q64::TAU));
+    let (sin, cos) = angle.sin_cos();
+    QPoint::new(center.pos().saturating_add(QVec2::new(cos, sin).saturating_mul_num(radius)))
+}
+
+/// Does the segment touch the closed square of half size `half` around `center`.
+fn is_segment_touching_square(start: QVec2, end: QVec2, center: QVec2, half: Q64) -> bool {
+    let dir = end.saturating_sub(start);
+    let (min, max) = (center.saturating_sub(QVec2::splat(half)), center.saturating_add(QVec2::splat(half)));
+    let (mut t_min, mut t_max) = (Q64::ZERO, Q64::ONE);
+    for (p, q) in [
+        (-dir.x, start.x.saturating_sub(min.x)),
+        (dir.x, max.x.saturating_sub(start.x)),
+        (-dir.y, start.y.saturating_sub(min.y)),
+        (dir.y, max.y.saturating_sub(start.y)),
+    ] {
+        if p == Q64::ZERO {
+            if q < Q64::ZERO {
+                return false;
+            }
+            continue;
+        }
+        let t = q.saturating_div(p);
+        if p < Q64::ZERO {
+            t_min = t_min.max(t);
+        } else {
+            t_max = t_max.min(t);
+        }
+        if t_min > t_max {
+            return false;
+        }
+    }
+    true
+}
+
+/// Point where the path segment `a`-`b` meets the edge `p`-`q`, `None` if they don't meet
+/// or run along each other.
+fn get_slice_hit(a: QVec2, b: QVec2, p: QVec2, q: QVec2) -> Option<QVec2> {
+    let (side_p, side_q) = (orient2d(a, b, p), orient2d(a, b, q));
+    let (side_a, side_b) = (orient2d(p, q, a), orient2d(p, q, b));
+    if side_p == side_q || side_a == side_b {
+        return None;
+    }
+    if side_p == core::cmp::Ordering::Equal { return Some(p); }
+    if side_q == core::cmp::Ordering::Equal { return Some(q); }
+    if side_a == core::cmp::Ordering::Equal { return Some(a); }
+    if side_b == core::cmp::Ordering::Equal { return Some(b); }
+    // Multiply before dividing so cuts at representable points are exact.
+    let direction = b.saturating_sub(a);
+    let (d_p, d_q) = (direction.cross(p.saturating_sub(a)), direction.cross(q.saturating_sub(a)));
+    let (delta, denominator) = (q.saturating_sub(p), d_p.saturating_sub(d_q));
+    Some(p.saturating_add(QVec2::new(
+        delta.x.saturating_mul(d_p).saturating_div(denominator),
+        delta.y.saturating_mul(d_p).saturating_div(denominator),
+    )))
+}
+
+/// Whether `pos` is inside (`Greater`), on (`Equal`) or outside (`Less`) a closed ring.
+fn get_ring_side(ring: &[QVec2], pos: QVec2) -> core::cmp::Ordering {
+    let n = ring.len();
+    let mut is_inside = false;
+    for i in 0..n {
+        let (a, b) = (ring[i], ring[(i + 1) % n]);
+        if orient2d(a, b, pos) == core::cmp::Ordering::Equal
+            && pos.x >= a.x.min(b.x) && pos.x <= a.x.max(b.x) && pos.y >= a.y.min(b.y) && pos.y <= a.y.max(b.y)
+        {
+            return core::cmp::Ordering::Equal;
+        }
+        if (a.y > pos.y) != (b.y > pos.y) {
+            // The crossing is right of `pos` if `pos` is left of the upward edge.
+            let (low, high) = if a.y < b.y { (a, b) } else { (b, a) };
+            if orient2d(low, high, pos) == core::cmp::Ordering::Greater {
+                is_inside = !is_inside;
+            }
+        }
+    }
+    if is_inside { core::cmp::Ordering::Greater } else { core::cmp::Ordering::Less }
+}
+
+/// Chain directed pieces meeting end to start into closed rings.
+///
+/// Where several pieces leave one point the sharpest left turn is taken, so rings touching
+/// at a vertex stay apart. Open chains are dropped.
+fn get_piece_rings(pieces: &[(QVec2, QVec2)]) -> Vec<Vec<QVec2>> {
+    let mut outgoing: BTreeMap<(Q64, Q64), Vec<usize>> = BTreeMap::new();
+    for (i, (start, _)) in pieces.iter().enumerate() {
+        outgoing.entry((start.x, start.y)).or_default().push(i);
+    }
+    let mut is_used = vec![false; pieces.len()];
+    let mut rst = vec![];
+    for first in 0..pieces.len() {
+        if is_used[first] {
+            continue;
+        }
+        is_used[first] = true;
+        let mut ring = vec![pieces[first].0];
+        let mut current = first;
+        loop {
+            let (start, end) = pieces[current];
+            if end == ring[0] {
+                rst.push(ring);
+                break;
+            }
+            let incoming = end.saturating_sub(start);
+            let next = outgoing
+                .get(&(end.x, end.y))
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|&i| !is_used[i])
+                .max_by_key(|&i| {
+                    let dir = pieces[i].1.saturating_sub(pieces[i].0);
+                    atan2(incoming.cross(dir), incoming.dot(dir))
+                });
+            let Some(next) = next else {
+                break;
+            };
+            is_used[next] = true;
+            ring.push(end);
+            current = next;
+        }
+    }
+    rst
+}
+
+/// Id of the slice node at `pos`, added if it is new.
+fn get_slice_node(positions: &mut Vec<QVec2>, ids: &mut BTreeMap<(Q64, Q64), usize>, pos: QVec2) -> usize {
+    *ids.entry((pos.x, pos.y)).or_insert_with(|| {
+        positions.push(pos);
+        positions.len() - 1
+    })
+}
+
+/// Bbox of `points` grown by `Q64::EPS`, `None` if there are no points.
+fn get_points_bbox(points: &[QPoint]) -> Option<QBbox> {
+    let first = points.first()?;
+    let mut min_x = first.x().saturating_sub(Q64::EPS);
+    let mut max_x = first.x().saturating_add(Q64::EPS);
+    let mut min_y = first.y().saturating_sub(Q64::EPS);
+    let mut max_y = first.y().saturating_add(Q64::EPS);
+
+    for point in points {
+        min_x = min_x.min(point.x());
+        max_x = max_x.max(point.x());
+        min_y = min_y.min(point.y());
+        max_y = max_y.max(point.y());
+    }
+
+    let left_bottom = QPoint::new_from_parts(min_x, min_y);
+    let right_top = QPoint::new_from_parts(max_x, max_y);
+    Some(QBbox::new(left_bottom, right_top))
+}
+
 impl QShapeCommon for QPolygon {
+    type Rotated = QPolygon;
+
     fn points(&self) -> Vec<QPoint> {
         self.points.clone()
     }
 
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
+        self.points.iter().copied()
+    }
+
+    fn points_slice(&self) -> Option<&[QPoint]> {
+        Some(&self.points)
+    }
+
     fn get_shape_type(&self) -> QShapeType {
         QShapeType::QPolygon
     }
@@ -210,26 +1641,19 @@ impl QShapeCommon for QPolygon {
         }
 
         let mut rst = false;
-        let mut j = self.points.len() - 1;
-
-        for i in 0..self.points.len() {
-            let vi = self.points[i].pos();
-            let vj = self.points[j].pos();
-
-            let line = QLine::new(QPoint::new(vi), QPoint::new(vj));
+        for line in self.edges_iter() {
             if line.is_point_on_line(point) {
                 return true;
             }
 
             let py: Q64 = point.y();
-            if (vi.y > py) != (vj.y > py) {
+            if (line.start().y() > py) != (line.end().y() > py) {
                 let intersect_x: Q64 = line.get_x_at_y(py);
                 let px: Q64 = point.x();
                 if px < intersect_x {
                     rst = !rst;
                 }
             }
-            j = i;
         }
         rst
     }
@@ -255,74 +1679,25 @@ impl QShapeCommon for QPolygon {
     /// assert!(triangles.len() == 6);
     /// ```
     fn ear_clipping_triangulation(&self) -> Vec<usize> {
-        let shape = &self.points;
-        fn is_valid_ear(shape: &Vec<QPoint>, a: &QPoint, b: &QPoint, c: &QPoint) -> bool {
-            let cross_product: Q64 = (b.pos() - a.pos()).cross(c.pos() - b.pos());
-            // Ear triangle's vertices need to be in CCW order.
-            if cross_product == Q64::ZERO { return false; }
-            for point in shape.iter() {
-                if point != a && point != b && point != c && QPolygon::new(vec![*a, *b, *c]).is_point_inside(point) { return false; }
-            }
-            true
-        }
-
-        let mut points = shape.to_vec();
-        let mut triangles_indices = Vec::new();
-        let get_index = |v| shape.iter().position(|&p| p == v).unwrap();
-
-        while points.len() > 3 {
-            for i in 0..points.len() {
-                let j = (i + 1) % points.len();
-                let k = (i + 2) % points.len();
-
-                let a = points[i];
-                let b = points[j];
-                let c = points[k];
-
-                if is_valid_ear(shape, &a, &b, &c) {
-                    triangles_indices.push(get_index(c));
-                    triangles_indices.push(get_index(b));
-                    triangles_indices.push(get_index(a));
-                    points.remove(j);
-                    break;
-                }
-            }
+        match &self.triangulation {
+            Some(triangulation) => triangulation.clone(),
+            None => self.triangulate(),
         }
-
-        assert!(points.len() == 3);
-        triangles_indices.push(get_index(points[2]));
-        triangles_indices.push(get_index(points[1]));
-        triangles_indices.push(get_index(points[0]));
-
-        triangles_indices
     }
 
+    /// Cached, so it is O(1) unless the polygon was deserialized.
     fn get_bbox(&self) -> QBbox {
-        assert!(!self.points.is_empty(), "[QPolygon::get_bbox] Points must not be empty.");
-
-        let mut min_x = self.points[0].x().saturating_sub(Q64::EPS);
-        let mut max_x = self.points[0].x().saturating_add(Q64::EPS);
-        let mut min_y = self.points[0].y().saturating_sub(Q64::EPS);
-        let mut max_y = self.points[0].y().saturating_add(Q64::EPS);
-
-        for point in &self.points {
-            min_x = min_x.min(point.x());
-            max_x = max_x.max(point.x());
-            min_y = min_y.min(point.y());
-            max_y = max_y.max(point.y());
-        }
-
-        let left_bottom = QPoint::new_from_parts(min_x, min_y);
-        let right_top = QPoint::new_from_parts(max_x, max_y);
-        QBbox::new(left_bottom, right_top)
+        self.bbox
+            .or_else(|| get_points_bbox(&self.points))
+            .expect("[QPolygon::get_bbox] Points must not be empty.")
     }
 
     fn is_collide(&self, other: &impl QShapeCommon) -> bool {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.is_collide(self),
             _ => {
-                let other_polygon = QPolygon::new(other.points());
-                gjk(self, &other_polygon)
+                gjk(self, &QShapeSupport(other))
             }
         }
     }
@@ -330,10 +1705,80 @@ impl QShapeCommon for QPolygon {
     fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
         let other_shape_type = other.get_shape_type();
         match other_shape_type {
+            QShapeType::QCompound => other.try_get_seperation_vector(self).map(|vector| -vector),
             _ => {
-                let other_polygon = QPolygon::new(other.points());
-                epa(self, &other_polygon)
+                epa(self, &QShapeSupport(other))
             }
         }
     }
+
+    fn translated(&self, offset: QVec2) -> Self {
+        self.transformed(&QTransform::from_translation(offset))
+    }
+
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> QPolygon {
+        self.transformed(&QTransform::from_rotation_around(pivot.pos(), angle))
+    }
+
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)]);
+    /// let scaled = polygon.scaled(Q64::HALF);
+    /// assert_eq!(scaled.points()[0].pos(), qvec2!(0.5, 0.5));
+    /// assert_eq!(scaled.points()[2].pos(), qvec2!(1.5, 1.5));
+    /// ```
+    fn scaled(&self, factor: Q64) -> Self {
+        assert!(factor > Q64::ZERO, "[QPolygon::scaled] factor({factor:?}) should be larger than zero.");
+        let center = self.get_centroid().pos();
+        let transform = QTransform::from_translation(center)
+            * QTransform::from_scale(QVec2::splat(factor))
+            * QTransform::from_translation(-center);
+        self.transformed(&transform)
+    }
+
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(0.0, 2.0)]);
+    /// assert_eq!(polygon.closest_point(&QPoint::new(qvec2!(2.0, 2.0))).pos(), qvec2!(1.0, 1.0));
+    /// assert_eq!(polygon.closest_point(&QPoint::new(qvec2!(0.5, 0.5))).pos(), qvec2!(0.5, 0.5));
+    /// ```
+    fn closest_point(&self, point: &QPoint) -> QPoint {
+        if self.is_point_inside(point) {
+            return *point;
+        }
+        match &self.normals {
+            // The nearest boundary point of an outside point is on an edge facing it.
+            Some(normals) => self.edges_iter()
+                .zip(normals)
+                .filter(|(edge, normal)| point.pos().saturating_sub(edge.start().pos()).dot(normal.to_vec()) >= Q64::ZERO)
+                .map(|(edge, _)| edge.get_closest_point_from_point(point))
+                .min_by(|a, b| {
+                    a.pos().distance_squared(point.pos())
+                        .partial_cmp(&b.pos().distance_squared(point.pos()))
+                        .unwrap_or(core::cmp::Ordering::Equal)
+                })
+                .unwrap_or_else(|| get_closest_point_on_chain(&self.points, true, point)),
+            None => get_closest_point_on_chain(&self.points, true, point),
+        }
+    }
+
+    fn precomputed_normals(&self) -> Option<&[QDir]> {
+        self.normals.as_deref()
+    }
+
+    /// Uses the stored hull of `with_cached_triangulation` if there is one.
+    fn convex_hull(&self) -> QPolygon {
+        match &self.hull {
+            Some(hull) => QPolygon::new(hull.clone()),
+            None => QPolygon::new_from_parts(andrew_graham_scan(&self.points.iter().map(|point| point.pos()).collect())),
+        }
+    }
 }
\ No newline at end of file
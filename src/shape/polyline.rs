@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::{epa, gjk};
+use crate::transform::QTransform;
+use super::support_map::QShapeSupport;
+use super::{ QPoint, QLine, QBbox, QPolygon, QArcLengthParam, QShapeCommon, QShapeType };
+use super::line::get_closest_point_on_chain;
+
+/// An open chain of connected line segments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QPolyline {
+    points: Vec<QPoint>,
+}
+
+impl QPolyline {
+    pub fn new(points: Vec<QPoint>) -> Self {
+        Self {
+            points,
+        }
+    }
+
+    pub fn new_from_parts(points: Vec<QVec2>) -> Self {
+        Self {
+            points: points.into_iter().map(|pos| QPoint::new(pos)).collect(),
+        }
+    }
+
+    pub fn points(&self) -> &Vec<QPoint> {
+        &self.points
+    }
+
+    pub fn points_mut(&mut self) -> &mut Vec<QPoint> {
+        &mut self.points
+    }
+
+    /// Get the segments of the polyline, degenerate segments are skipped.
+    pub fn segments(&self) -> Vec<QLine> {
+        self.points
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .map(|pair| QLine::new(pair[0], pair[1]))
+            .collect()
+    }
+
+    /// Get the total length of the polyline.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(3.0, 0.0), qvec2!(3.0, 4.0)]);
+    /// assert_eq!(polyline.length(), q64!(7));
+    /// ```
+    pub fn length(&self) -> Q64 {
+        self.points
+            .windows(2)
+            .fold(Q64::ZERO, |acc, pair| acc.saturating_add(pair[0].distance(&pair[1])))
+    }
+
+    /// Get a copy of the polyline with every vertex transformed.
+    pub fn transformed(&self, transform: &QTransform) -> QPolyline {
+        QPolyline::new(self.points.iter().map(|point| transform.transform_point(point)).collect())
+    }
+
+    /// Build the arc-length lookup table of the polyline.
+    pub fn arc_length_param(&self) -> QArcLengthParam {
+        QArcLengthParam::new(self)
+    }
+}
+
+impl QShapeCommon for QPolyline {
+    type Rotated = QPolyline;
+
+    fn points(&self) -> Vec<QPoint> {
+        self.points.clone()
+    }
+
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
+        self.points.iter().copied()
+    }
+
+    fn points_slice(&self) -> Option<&[QPoint]> {
+        Some(&self.points)
+    }
+
+    fn get_bbox(&self) -> QBbox {
+        assert!(!self.points.is_empty(), "[QPolyline::get_bbox] Points must not be empty.");
+
+        let mut min_x = self.points[0].x().saturating_sub(Q64::EPS);
+        let mut max_x = self.points[0].x().saturating_add(Q64::EPS);
+        let mut min_y = self.points[0].y().saturating_sub(Q64::EPS);
+        let mut max_y = self.points[0].y().saturating_add(Q64::EPS);
+
+        for point in &self.points {
+            min_x = min_x.min(point.x());
+            max_x = max_x.max(point.x());
+            min_y = min_y.min(point.y());
+            max_y = max_y.max(point.y());
+        }
+
+        let left_bottom = QPoint::new_from_parts(min_x, min_y);
+        let right_top = QPoint::new_from_parts(max_x, max_y);
+        QBbox::new(left_bottom, right_top)
+    }
+
+    fn get_centroid(&self) -> QPoint {
+        QPolygon::new(self.points.clone()).get_centroid()
+    }
+
+    fn get_shape_type(&self) -> QShapeType {
+        QShapeType::QPolyline
+    }
+
+    /// Return true if the point lies on any segment of the polyline.
+    fn is_point_inside(&self, point: &QPoint) -> bool {
+        if self.points.len() == 1 {
+            return self.points[0] == *point;
+        }
+        self.segments().iter().any(|segment| segment.is_point_on_line(point))
+    }
+
+    fn is_collide(&self, other: &impl QShapeCommon) -> bool {
+        let other_shape_type = other.get_shape_type();
+        match other_shape_type {
+            QShapeType::QCompound => other.is_collide(self),
+            _ => {
+                gjk(self, &QShapeSupport(other))
+            }
+        }
+    }
+
+    fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
+        let other_shape_type = other.get_shape_type();
+        match other_shape_type {
+            QShapeType::QCompound => other.try_get_seperation_vector(self).map(|vector| -vector),
+            _ => {
+                epa(self, &QShapeSupport(other))
+            }
+        }
+    }
+
+    fn translated(&self, offset: QVec2) -> Self {
+        self.transformed(&QTransform::from_translation(offset))
+    }
+
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> QPolyline {
+        self.transformed(&QTransform::from_rotation_around(pivot.pos(), angle))
+    }
+
+    fn scaled(&self, factor: Q64) -> Self {
+        assert!(factor > Q64::ZERO, "[QPolyline::scaled] factor({factor:?}) should be larger than zero.");
+        let center = self.get_centroid().pos();
+        let transform = QTransform::from_translation(center)
+            * QTransform::from_scale(QVec2::splat(factor))
+            * QTransform::from_translation(-center);
+        self.transformed(&transform)
+    }
+
+    fn closest_point(&self, point: &QPoint) -> QPoint {
+        get_closest_point_on_chain(&self.points, false, point)
+    }
+}
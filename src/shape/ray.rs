@@ -0,0 +1,418 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use alloc::{ vec, vec::Vec };
+use crate::algorithm::sqrt;
+use super::{ QPoint, QLine, QBbox, QCircle, QPolygon, QPolyline, QShape, QShapeCommon, QShapeType };
+
+/// Half-infinite ray, `t` along the ray is the travelled distance from the origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QRay {
+    origin: QPoint,
+    dir: QDir,
+}
+
+/// Where a ray first touches a shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QRayHit {
+    t: Q64,
+    point: QPoint,
+    normal: QDir,
+    edge_index: Option<usize>,
+}
+
+impl QRayHit {
+    pub fn new(t: Q64, point: QPoint, normal: QDir, edge_index: Option<usize>) -> Self {
+        Self {
+            t,
+            point,
+            normal,
+            edge_index,
+        }
+    }
+
+    /// Distance along the ray.
+    pub fn t(&self) -> Q64 {
+        self.t
+    }
+
+    pub fn point(&self) -> QPoint {
+        self.point
+    }
+
+    /// Surface normal at the hit, facing back against the ray.
+    pub fn normal(&self) -> QDir {
+        self.normal
+    }
+
+    /// Index of the edge that was hit, `None` for curved shapes and rays starting inside.
+    pub fn edge_index(&self) -> Option<usize> {
+        self.edge_index
+    }
+}
+
+impl QRay {
+    pub fn new(origin: QPoint, dir: QDir) -> Self {
+        Self {
+            origin,
+            dir,
+        }
+    }
+
+    /// Create a ray from `origin` towards `target`.
+    pub fn new_towards(origin: QPoint, target: QPoint) -> Self {
+        assert!(origin != target, "[QRay::new_towards] origin({origin:?}) should not be equal to target({target:?}).");
+        Self::new(origin, QDir::new_from_vec(target.pos().saturating_sub(origin.pos())))
+    }
+
+    pub fn origin(&self) -> QPoint {
+        self.origin
+    }
+
+    pub fn dir(&self) -> QDir {
+        self.dir
+    }
+
+    pub fn set_origin(&mut self, origin: QPoint) {
+        self.origin = origin;
+    }
+
+    pub fn set_dir(&mut self, dir: QDir) {
+        self.dir = dir;
+    }
+
+    /// Get the point at distance `t` along the ray.
+    pub fn point_at(&self, t: Q64) -> QPoint {
+        QPoint::new(self.origin.pos().saturating_add(self.dir.to_vec().saturating_mul_num(t)))
+    }
+
+    /// Slab test against a bbox, return the distance where the ray enters the bbox.
+    ///
+    /// Return zero if the origin is already inside the bbox.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(qvec2!(2.0, -1.0), qvec2!(3.0, 1.0));
+    /// let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::X));
+    /// assert_eq!(ray.intersect_bbox(&bbox), Some(q64!(2)));
+    /// let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::Y));
+    /// assert_eq!(ray.intersect_bbox(&bbox), None);
+    /// ```
+    pub fn intersect_bbox(&self, bbox: &QBbox) -> Option<Q64> {
+        let origin = self.origin.pos();
+        let dir = self.dir.to_vec();
+        let min = bbox.left_bottom().pos();
+        let max = bbox.right_top().pos();
+
+        let mut t_min = Q64::ZERO;
+        let mut t_max = Q64::MAX;
+        for (o, d, lo, hi) in [(origin.x, dir.x, min.x, max.x), (origin.y, dir.y, min.y, max.y)] {
+            if d == Q64::ZERO {
+                if o < lo || o > hi { return None; }
+                continue;
+            }
+            let t1 = lo.saturating_sub(o).saturating_div(d);
+            let t2 = hi.saturating_sub(o).saturating_div(d);
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max { return None; }
+        }
+        Some(t_min)
+    }
+
+    /// Get the distance where the ray first touches a segment.
+    ///
+    /// A collinear overlapping segment is hit where the ray reaches its nearer end.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::X));
+    /// let crossing = QLine::new_from_parts(qvec2!(3.0, -1.0), qvec2!(3.0, 1.0));
+    /// assert_eq!(ray.intersect_segment(&crossing), Some(q64!(3)));
+    /// let collinear = QLine::new_from_parts(qvec2!(5.0, 0.0), qvec2!(2.0, 0.0));
+    /// assert_eq!(ray.intersect_segment(&collinear), Some(q64!(2)));
+    /// let behind = QLine::new_from_parts(qvec2!(-3.0, -1.0), qvec2!(-3.0, 1.0));
+    /// assert_eq!(ray.intersect_segment(&behind), None);
+    /// ```
+    pub fn intersect_segment(&self, segment: &QLine) -> Option<Q64> {
+        let origin = self.origin.pos();
+        let dir = self.dir.to_vec();
+        let edge = segment.vector();
+        let to_start = segment.start().pos().saturating_sub(origin);
+        let denom = dir.cross(edge);
+
+        if denom == Q64::ZERO {
+            if to_start.cross(dir) != Q64::ZERO { return None; }
+            let t_start = to_start.dot(dir);
+            let t_end = segment.end().pos().saturating_sub(origin).dot(dir);
+            if t_start.max(t_end) < Q64::ZERO { return None; }
+            return Some(t_start.min(t_end).max(Q64::ZERO));
+        }
+
+        let t = to_start.cross(edge).saturating_div(denom);
+        let u = to_start.cross(dir).saturating_div(denom);
+        if t < Q64::ZERO || u < Q64::ZERO || u > Q64::ONE { return None; }
+        Some(t)
+    }
+
+    /// Get the distance where the ray first touches the boundary of a shape.
+    ///
+    /// Shapes with at least 3 points other than polylines are treated as closed,
+    /// a ray starting inside one hits at zero.
+    pub fn intersect_shape(&self, shape: &impl QShapeCommon) -> Option<Q64> {
+        let is_closed = shape.points_iter().nth(2).is_some() && shape.get_shape_type() != QShapeType::QPolyline;
+        if is_closed && shape.is_point_inside(&self.origin) {
+            return Some(Q64::ZERO);
+        }
+        let mut points = shape.points_iter();
+        if let (Some(point), None) = (points.next(), points.next()) {
+            let t = point.pos().saturating_sub(self.origin.pos()).dot(self.dir.to_vec());
+            return (t >= Q64::ZERO && self.point_at(t) == point).then_some(t);
+        }
+
+        shape.edges_iter()
+            .filter_map(|edge| self.intersect_segment(&edge))
+            .reduce(|a, b| a.min(b))
+    }
+
+    /// Cast against a polygon, edge `i` runs from vertex `i` to the next one.
+    ///
+    /// A ray starting inside or on the boundary hits at zero with the normal against the
+    /// ray, like `intersect_shape`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)]);
+    /// let hit = QRay::new(QPoint::new(qvec2!(-1.0, 1.0)), QDir::new_from_vec(QVec2::X)).cast_polygon(&square).unwrap();
+    /// assert_eq!(hit.t(), q64!(1));
+    /// assert_eq!(hit.point().pos(), qvec2!(0.0, 1.0));
+    /// assert_eq!(hit.normal().to_vec(), qvec2!(-1.0, 0.0));
+    /// assert_eq!(hit.edge_index(), Some(3));
+    /// ```
+    pub fn cast_polygon(&self, polygon: &QPolygon) -> Option<QRayHit> {
+        let points = polygon.points();
+        if points.len() >= 3 && polygon.is_point_inside(&self.origin) {
+            return Some(self.get_inside_hit());
+        }
+        self.cast_edges(get_ring_edges(points).into_iter())
+    }
+
+    /// Cast against a bbox, edges are numbered like `QBbox::corners`, starting with the left side.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(qvec2!(2.0, -1.0), qvec2!(3.0, 1.0));
+    /// let hit = QRay::new(QPoint::new(qvec2!(2.5, 4.0)), QDir::new_from_vec(-QVec2::Y)).cast_bbox(&bbox).unwrap();
+    /// assert_eq!(hit.t(), q64!(3));
+    /// assert_eq!(hit.normal().to_vec(), QVec2::Y);
+    /// assert_eq!(hit.edge_index(), Some(1));
+    /// ```
+    pub fn cast_bbox(&self, bbox: &QBbox) -> Option<QRayHit> {
+        if bbox.is_point_inside(&self.origin) {
+            return Some(self.get_inside_hit());
+        }
+        self.cast_edges(get_ring_edges(&bbox.corners()).into_iter())
+    }
+
+    /// Cast against a circle, the normal points away from the center.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let circle = QCircle::new(QPoint::new(qvec2!(5.0, 0.0)), q64!(2));
+    /// let hit = QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::X)).cast_circle(&circle).unwrap();
+    /// assert_eq!(hit.point().pos(), qvec2!(3.0, 0.0));
+    /// assert_eq!(hit.normal().to_vec(), -QVec2::X);
+    /// assert_eq!(QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::Y)).cast_circle(&circle), None);
+    /// ```
+    pub fn cast_circle(&self, circle: &QCircle) -> Option<QRayHit> {
+        let offset = self.origin.pos().saturating_sub(circle.center().pos());
+        let b = offset.dot(self.dir.to_vec());
+        let c = offset.length_squared().saturating_sub(circle.radius().saturating_mul(circle.radius()));
+        if c <= Q64::ZERO {
+            return Some(self.get_inside_hit());
+        }
+        let discriminant = b.saturating_mul(b).saturating_sub(c);
+        if b > Q64::ZERO || discriminant < Q64::ZERO {
+            return None;
+        }
+        let t = (-b).saturating_sub(sqrt(discriminant)).max(Q64::ZERO);
+        let point = self.point_at(t);
+        let outward = point.pos().saturating_sub(circle.center().pos());
+        let normal = if outward == QVec2::ZERO { -self.dir.to_vec() } else { outward };
+        Some(QRayHit::new(t, point, QDir::new_from_vec(normal), None))
+    }
+
+    /// Cast against any shape, polylines and lines number their edges from the first point.
+    pub fn cast_shape(&self, shape: &QShape) -> Option<QRayHit> {
+        match shape {
+            QShape::QPoint(point) => {
+                let t = self.intersect_shape(point)?;
+                Some(QRayHit::new(t, *point, QDir::new_from_vec(-self.dir.to_vec()), None))
+            }
+            QShape::QBbox(bbox) => self.cast_bbox(bbox),
+            QShape::QCircle(circle) => self.cast_circle(circle),
+            QShape::QPolygon(polygon) => self.cast_polygon(polygon),
+            QShape::QLine(_) | QShape::QPolyline(_) => self.cast_edges(get_numbered_edges(shape).into_iter()),
+        }
+    }
+
+    /// Get the ray bounced off the surface at `hit`, starting at the hit point.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let wall = QBbox::new_from_parts(qvec2!(2.0, -4.0), qvec2!(3.0, 4.0));
+    /// let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::X));
+    /// let bounced = ray.reflect(&ray.cast_bbox(&wall).unwrap());
+    /// assert_eq!(bounced.origin().pos(), qvec2!(2.0, 0.0));
+    /// assert_eq!(bounced.vector(), -QVec2::X);
+    /// ```
+    pub fn reflect(&self, hit: &QRayHit) -> QRay {
+        let (dir, normal) = (self.dir.to_vec(), hit.normal().to_vec());
+        let reflected = dir.saturating_sub(normal.saturating_mul_num(dir.dot(normal).saturating_mul(q64!(2))));
+        QRay::new(hit.point(), QDir::new_from_vec(reflected))
+    }
+
+    /// Follow the ray as it bounces between shapes, at most `max_bounces` times and for
+    /// at most `max_length` in total.
+    ///
+    /// Shapes act as mirrors on both sides of their boundary, so a ray can bounce around
+    /// inside a room. The path ends at the hit after the last bounce, or after `max_length`
+    /// if the ray escapes first.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let room = QShape::QBbox(QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 4.0)));
+    /// let ray = QRay::new(QPoint::new(qvec2!(1.0, 1.0)), QDir::new_from_vec(QVec2::X));
+    /// let path = ray.trace(&[room.clone()], 1, Q64::MAX);
+    /// assert_eq!(path.points(), &vec![QPoint::new(qvec2!(1.0, 1.0)), QPoint::new(qvec2!(4.0, 1.0)), QPoint::new(qvec2!(0.0, 1.0))]);
+    /// let path = ray.trace(&[room], 5, q64!(5));
+    /// assert_eq!(path.points().last().unwrap().pos(), qvec2!(2.0, 1.0));
+    /// ```
+    pub fn trace(&self, shapes: &[QShape], max_bounces: usize, max_length: Q64) -> QPolyline {
+        let (mut ray, mut remaining) = (*self, max_length);
+        let mut points = vec![self.origin];
+        // The shape and edge the ray currently starts on.
+        let mut start: Option<(usize, Option<usize>)> = None;
+        for _ in 0..=max_bounces {
+            let hit = shapes
+                .iter()
+                .enumerate()
+                .filter_map(|(i, shape)| {
+                    let from = start.filter(|(j, _)| *j == i).map(|(_, edge)| edge);
+                    ray.cast_boundary(shape, from).map(|hit| (i, hit))
+                })
+                .min_by_key(|(i, hit)| (hit.t(), *i));
+            match hit {
+                Some((i, hit)) if hit.t() <= remaining => {
+                    points.push(hit.point());
+                    remaining = remaining.saturating_sub(hit.t());
+                    ray = ray.reflect(&hit);
+                    start = Some((i, hit.edge_index()));
+                }
+                _ => {
+                    points.push(ray.point_at(remaining));
+                    break;
+                }
+            }
+        }
+        QPolyline::new(points)
+    }
+
+    /// Nearest hit on the boundary of `shape` from either side, `from` is the edge the
+    /// ray starts on, `Some(None)` for a curved one.
+    fn cast_boundary(&self, shape: &QShape, from: Option<Option<usize>>) -> Option<QRayHit> {
+        let QShape::QCircle(circle) = shape else {
+            let skip = from.flatten();
+            return self.cast_edges(get_numbered_edges(shape).into_iter().filter(|(i, _)| Some(*i) != skip));
+        };
+        let dir = self.dir.to_vec();
+        let offset = self.origin.pos().saturating_sub(circle.center().pos());
+        let b = offset.dot(dir);
+        let t = if from.is_some() {
+            // Starting on the circle, the ray only meets it again across a chord.
+            (b < Q64::ZERO).then(|| b.saturating_mul(q64!(-2)))?
+        } else {
+            let c = offset.length_squared().saturating_sub(circle.radius().saturating_mul(circle.radius()));
+            let discriminant = b.saturating_mul(b).saturating_sub(c);
+            if discriminant < Q64::ZERO {
+                return None;
+            }
+            let root = sqrt(discriminant);
+            let (near, far) = ((-b).saturating_sub(root), (-b).saturating_add(root));
+            if near >= Q64::ZERO { near } else if far >= Q64::ZERO { far } else { return None; }
+        };
+        let point = self.point_at(t);
+        let outward = point.pos().saturating_sub(circle.center().pos());
+        let normal = if outward.dot(dir) > Q64::ZERO { -outward } else { outward };
+        Some(QRayHit::new(t, point, QDir::new_from_vec(normal), None))
+    }
+
+    /// Nearest hit among numbered edges, the first edge wins ties.
+    fn cast_edges(&self, edges: impl Iterator<Item = (usize, QLine)>) -> Option<QRayHit> {
+        let dir = self.dir.to_vec();
+        let (index, edge, t) = edges
+            .filter(|(_, edge)| edge.start() != edge.end())
+            .filter_map(|(index, edge)| self.intersect_segment(&edge).map(|t| (index, edge, t)))
+            .min_by_key(|(index, _, t)| (*t, *index))?;
+        let vector = edge.vector();
+        let normal = QVec2::new(-vector.y, vector.x);
+        let normal = if normal.dot(dir) > Q64::ZERO { -normal } else { normal };
+        Some(QRayHit::new(t, self.point_at(t), QDir::new_from_vec(normal), Some(index)))
+    }
+
+    fn get_inside_hit(&self) -> QRayHit {
+        QRayHit::new(Q64::ZERO, self.origin, QDir::new_from_vec(-self.dir.to_vec()), None)
+    }
+
+    /// Direction of the ray as a unit vector.
+    pub fn vector(&self) -> QVec2 {
+        self.dir.to_vec()
+    }
+}
+
+/// Edges of a closed ring, edge `i` runs from point `i` to the next one.
+fn get_ring_edges(points: &[QPoint]) -> Vec<(usize, QLine)> {
+    let n = points.len();
+    (0..n).map(|i| (i, QLine::new(points[i], points[(i + 1) % n]))).collect()
+}
+
+/// Edges of a shape numbered like `QRay::cast_shape`, circles and points have none.
+fn get_numbered_edges(shape: &QShape) -> Vec<(usize, QLine)> {
+    match shape {
+        QShape::QLine(line) => vec![(0, *line)],
+        QShape::QBbox(bbox) => get_ring_edges(&bbox.corners()),
+        QShape::QPolygon(polygon) => get_ring_edges(polygon.points()),
+        QShape::QPolyline(polyline) => polyline.points().windows(2).enumerate().map(|(i, pair)| (i, QLine::new(pair[0], pair[1]))).collect(),
+        QShape::QPoint(_) | QShape::QCircle(_) => vec![],
+    }
+}
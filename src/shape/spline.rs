@@ -0,0 +1,184 @@
+use alloc::{ vec, vec::Vec };
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::mesh::QMesh;
+use super::{ QPoint, QPolyline };
+
+/// Cardinal spline passing through every control point.
+///
+/// A tension of zero gives the Catmull-Rom spline, a tension of one gives straight segments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QSpline {
+    control_points: Vec<QPoint>,
+    tension: Q64,
+}
+
+impl QSpline {
+    /// Max recursion depth of `flatten` for each segment.
+    const MAX_FLATTEN_DEPTH: usize = 16;
+
+    /// Create a Catmull-Rom spline.
+    pub fn new(control_points: Vec<QPoint>) -> Self {
+        Self::new_cardinal(control_points, Q64::ZERO)
+    }
+
+    pub fn new_from_parts(control_points: Vec<QVec2>) -> Self {
+        Self::new(control_points.into_iter().map(|pos| QPoint::new(pos)).collect())
+    }
+
+    pub fn new_cardinal(control_points: Vec<QPoint>, tension: Q64) -> Self {
+        assert!(
+            tension >= Q64::ZERO && tension <= Q64::ONE,
+            "[QSpline::new_cardinal] tension({tension:?}) should be in [0, 1]."
+        );
+        Self {
+            control_points,
+            tension,
+        }
+    }
+
+    pub fn control_points(&self) -> &Vec<QPoint> {
+        &self.control_points
+    }
+
+    pub fn control_points_mut(&mut self) -> &mut Vec<QPoint> {
+        &mut self.control_points
+    }
+
+    pub fn tension(&self) -> Q64 {
+        self.tension
+    }
+
+    pub fn set_tension(&mut self, tension: Q64) {
+        assert!(
+            tension >= Q64::ZERO && tension <= Q64::ONE,
+            "[QSpline::set_tension] tension({tension:?}) should be in [0, 1]."
+        );
+        self.tension = tension;
+    }
+
+    /// Number of curve segments, one between each pair of adjacent control points.
+    pub fn segment_count(&self) -> usize {
+        self.control_points.len().saturating_sub(1)
+    }
+
+    /// Get the point on the segment starting at control point `segment`, `t` is in [0, 1].
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let spline = QSpline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 1.0), qvec2!(2.0, 0.0)]);
+    /// assert_eq!(spline.point_at(0, Q64::ZERO).pos(), qvec2!(0.0, 0.0));
+    /// assert_eq!(spline.point_at(0, Q64::ONE).pos(), qvec2!(1.0, 1.0));
+    /// assert_eq!(spline.point_at(1, Q64::ONE).pos(), qvec2!(2.0, 0.0));
+    /// ```
+    pub fn point_at(&self, segment: usize, t: Q64) -> QPoint {
+        assert!(segment < self.segment_count(), "[QSpline::point_at] segment({segment}) out of range.");
+        let last = self.control_points.len() - 1;
+        let p0 = self.control_points[segment.saturating_sub(1)].pos();
+        let p1 = self.control_points[segment].pos();
+        let p2 = self.control_points[segment + 1].pos();
+        let p3 = self.control_points[(segment + 2).min(last)].pos();
+
+        // Tangents of the hermite form.
+        let scale = Q64::ONE.saturating_sub(self.tension).saturating_mul(Q64::HALF);
+        let m1 = p2.saturating_sub(p0).saturating_mul_num(scale);
+        let m2 = p3.saturating_sub(p1).saturating_mul_num(scale);
+
+        let t2 = t.saturating_mul(t);
+        let t3 = t2.saturating_mul(t);
+        let two = q64!(2);
+        let three = q64!(3);
+        let h00 = two.saturating_mul(t3).saturating_sub(three.saturating_mul(t2)).saturating_add(Q64::ONE);
+        let h10 = t3.saturating_sub(two.saturating_mul(t2)).saturating_add(t);
+        let h01 = three.saturating_mul(t2).saturating_sub(two.saturating_mul(t3));
+        let h11 = t3.saturating_sub(t2);
+
+        QPoint::new(
+            p1.saturating_mul_num(h00)
+                .saturating_add(m1.saturating_mul_num(h10))
+                .saturating_add(p2.saturating_mul_num(h01))
+                .saturating_add(m2.saturating_mul_num(h11))
+        )
+    }
+
+    /// A mesh of the curve sampled with `resolution` steps per segment, at least one.
+    ///
+    /// The curve has no area, so the mesh has positions but no triangles.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let spline = QSpline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 1.0), qvec2!(2.0, 0.0)]);
+    /// let mesh = spline.to_mesh(8);
+    /// assert_eq!(mesh.positions().len(), 17);
+    /// assert_eq!(mesh.positions()[8], qvec2!(1.0, 1.0));
+    /// assert!(mesh.is_empty());
+    /// ```
+    pub fn to_mesh(&self, resolution: usize) -> QMesh {
+        let steps = resolution.max(1);
+        let mut positions: Vec<QVec2> = self.control_points.iter().take(1).map(|point| point.pos()).collect();
+        for segment in 0..self.segment_count() {
+            positions.extend((1..=steps).map(|k| self.point_at(segment, q64!(k as i64).saturating_div(q64!(steps as i64))).pos()));
+        }
+        QMesh::new(positions, vec![])
+    }
+
+    /// Flatten the spline to a polyline.
+    ///
+    /// Each segment is subdivided until the curve deviates from its chord by no more than `tolerance`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let spline = QSpline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 1.0), qvec2!(2.0, 0.0)]);
+    /// let polyline = spline.flatten(q64!(0.01));
+    /// assert!(polyline.points().len() > 3);
+    /// assert_eq!(polyline.points().first().unwrap().pos(), qvec2!(0.0, 0.0));
+    /// assert_eq!(polyline.points().last().unwrap().pos(), qvec2!(2.0, 0.0));
+    /// ```
+    pub fn flatten(&self, tolerance: Q64) -> QPolyline {
+        assert!(tolerance > Q64::ZERO, "[QSpline::flatten] tolerance({tolerance:?}) should be larger than zero.");
+        let mut points = vec![];
+        if self.control_points.len() < 2 {
+            points.extend(self.control_points.iter().copied());
+            return QPolyline::new(points);
+        }
+
+        points.push(self.control_points[0]);
+        for segment in 0..self.segment_count() {
+            let start = self.point_at(segment, Q64::ZERO);
+            let end = self.point_at(segment, Q64::ONE);
+            self.flatten_segment(segment, (Q64::ZERO, start), (Q64::ONE, end), tolerance, 0, &mut points);
+        }
+        QPolyline::new(points)
+    }
+
+    /// Recursively subdivide `[t0, t1]` of a segment, pushing every point except `p0`.
+    fn flatten_segment(
+        &self,
+        segment: usize,
+        (t0, p0): (Q64, QPoint),
+        (t1, p1): (Q64, QPoint),
+        tolerance: Q64,
+        depth: usize,
+        points: &mut Vec<QPoint>,
+    ) {
+        let t_mid = t0.saturating_add(t1.saturating_sub(t0).saturating_mul(Q64::HALF));
+        let p_mid = self.point_at(segment, t_mid);
+        let chord_mid = QPoint::new(p0.pos().midpoint(p1.pos()));
+        if depth >= Self::MAX_FLATTEN_DEPTH || p_mid.distance(&chord_mid) <= tolerance {
+            points.push(p1);
+            return;
+        }
+        self.flatten_segment(segment, (t0, p0), (t_mid, p_mid), tolerance, depth + 1, points);
+        self.flatten_segment(segment, (t_mid, p_mid), (t1, p1), tolerance, depth + 1, points);
+    }
+}
@@ -0,0 +1,83 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use super::{ QPoint, QLine, QBbox, QCircle, QPolygon, QPolyline, QShape, QShapeCommon };
+
+/// Convex shape described by its support function, used by GJK and EPA.
+///
+/// Implicit shapes like circles are used exactly instead of as a vertex polygon.
+pub trait QSupportMap {
+    /// Get the point of the shape farthest along `dir`.
+    fn support(&self, dir: QDir) -> QPoint;
+}
+
+/// Farthest of `points` along `dir`.
+pub(crate) fn support_of_points(points: impl IntoIterator<Item = QPoint>, dir: QDir) -> QPoint {
+    let axis = dir.to_vec();
+    points
+        .into_iter()
+        .max_by(|a, b| a.pos().dot(axis).partial_cmp(&b.pos().dot(axis)).unwrap_or(core::cmp::Ordering::Equal))
+        .expect("[support_map::support_of_points] Points must not be empty.")
+}
+
+/// Support map of any shape, read through `QShapeCommon::get_support_point`.
+pub(crate) struct QShapeSupport<'a, S>(pub(crate) &'a S);
+
+impl<S: QShapeCommon> QSupportMap for QShapeSupport<'_, S> {
+    fn support(&self, dir: QDir) -> QPoint {
+        self.0.get_support_point(dir)
+    }
+}
+
+impl QSupportMap for QPoint {
+    fn support(&self, _dir: QDir) -> QPoint {
+        *self
+    }
+}
+
+impl QSupportMap for QLine {
+    fn support(&self, dir: QDir) -> QPoint {
+        support_of_points([self.start(), self.end()], dir)
+    }
+}
+
+impl QSupportMap for QBbox {
+    fn support(&self, dir: QDir) -> QPoint {
+        let axis = dir.to_vec();
+        let x = if axis.x >= Q64::ZERO { self.right_top().x() } else { self.left_bottom().x() };
+        let y = if axis.y >= Q64::ZERO { self.right_top().y() } else { self.left_bottom().y() };
+        QPoint::new(QVec2::new(x, y))
+    }
+}
+
+impl QSupportMap for QCircle {
+    fn support(&self, dir: QDir) -> QPoint {
+        QPoint::new(self.center().pos().saturating_add(dir.to_vec().saturating_mul_num(self.radius())))
+    }
+}
+
+impl QSupportMap for QPolygon {
+    fn support(&self, dir: QDir) -> QPoint {
+        self.get_farest_point_in_direction(dir)
+    }
+}
+
+/// The convex hull of the polyline.
+impl QSupportMap for QPolyline {
+    fn support(&self, dir: QDir) -> QPoint {
+        support_of_points(self.points().iter().copied(), dir)
+    }
+}
+
+impl QSupportMap for QShape {
+    fn support(&self, dir: QDir) -> QPoint {
+        match self {
+            QShape::QPoint(shape) => shape.support(dir),
+            QShape::QLine(shape) => shape.support(dir),
+            QShape::QBbox(shape) => shape.support(dir),
+            QShape::QCircle(shape) => shape.support(dir),
+            QShape::QPolygon(shape) => shape.support(dir),
+            QShape::QPolyline(shape) => shape.support(dir),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use crate::algorithm::{epa, gjk, gjk_distance};
+use crate::transform::QTransform;
+use super::support_map::{ QShapeSupport, QSupportMap };
+use super::{ QPoint, QBbox, QPolygon, QShapeCommon, QShapeType };
+use super::line::get_closest_point_on_chain;
+
+/// A base shape with a lazily applied transform.
+///
+/// Moving the shape only updates the transform. Collisions and bboxes go through the
+/// support map of the base shape, only `points` transforms every vertex.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QTransformedShape<S> {
+    shape: S,
+    transform: QTransform,
+}
+
+impl<S: QShapeCommon + QSupportMap + Clone> QTransformedShape<S> {
+    pub fn new(shape: S, transform: QTransform) -> Self {
+        Self {
+            shape,
+            transform,
+        }
+    }
+
+    pub fn shape(&self) -> &S {
+        &self.shape
+    }
+
+    pub fn transform(&self) -> QTransform {
+        self.transform
+    }
+
+    pub fn shape_mut(&mut self) -> &mut S {
+        &mut self.shape
+    }
+
+    pub fn transform_mut(&mut self) -> &mut QTransform {
+        &mut self.transform
+    }
+
+    pub fn set_transform(&mut self, transform: QTransform) {
+        self.transform = transform;
+    }
+
+    /// Get the shape as an explicit polygon in world space.
+    pub fn to_polygon(&self) -> QPolygon {
+        QPolygon::new(self.points())
+    }
+
+    fn with_transform(&self, transform: QTransform) -> Self {
+        Self::new(self.shape.clone(), transform)
+    }
+
+    /// Does the transform keep angles and only scale lengths uniformly.
+    fn is_similarity(&self) -> bool {
+        let (x_axis, y_axis) = (self.transform.x_axis(), self.transform.y_axis());
+        x_axis.dot(y_axis) == Q64::ZERO && x_axis.length_squared() == y_axis.length_squared()
+    }
+}
+
+/// The base support is queried along the direction pulled back into local space.
+impl<S: QSupportMap> QSupportMap for QTransformedShape<S> {
+    fn support(&self, dir: QDir) -> QPoint {
+        let axis = dir.to_vec();
+        let local = QVec2::new(self.transform.x_axis().dot(axis), self.transform.y_axis().dot(axis));
+        let local_dir = if local == QVec2::ZERO { dir } else { QDir::new_from_vec(local) };
+        self.transform.transform_point(&self.shape.support(local_dir))
+    }
+}
+
+impl<S: QShapeCommon + QSupportMap + Clone> QShapeCommon for QTransformedShape<S> {
+    type Rotated = QTransformedShape<S>;
+
+    fn points(&self) -> Vec<QPoint> {
+        self.points_iter().collect()
+    }
+
+    fn points_iter(&self) -> impl Iterator<Item = QPoint> + '_ {
+        self.shape.points_iter().map(|point| self.transform.transform_point(&point))
+    }
+
+    /// The support point of the base shape, so a transformed circle stays exact.
+    fn get_support_point(&self, dir: QDir) -> QPoint {
+        self.support(dir)
+    }
+
+    /// The extremes along the axes, found with four support queries.
+    fn get_bbox(&self) -> QBbox {
+        let axes = [QVec2::X, QVec2::Y, -QVec2::X, -QVec2::Y].map(|axis| self.support(QDir::new_from_vec(axis)).pos());
+        let (min, max) = (QVec2::new(axes[2].x, axes[3].y), QVec2::new(axes[0].x, axes[1].y));
+        if min.x < max.x && min.y < max.y {
+            QBbox::new_from_parts(min, max)
+        } else {
+            QPolygon::new(self.points()).get_bbox()
+        }
+    }
+
+    /// Affine transforms preserve centroids, so only the base centroid is transformed.
+    fn get_centroid(&self) -> QPoint {
+        self.transform.transform_point(&self.shape.get_centroid())
+    }
+
+    /// Bboxes and circles are no longer axis aligned or round once transformed, so they
+    /// report `QPolygon`, the other kinds keep the type of the base shape.
+    fn get_shape_type(&self) -> QShapeType {
+        match self.shape.get_shape_type() {
+            QShapeType::QBbox | QShapeType::QCircle => QShapeType::QPolygon,
+            shape_type => shape_type,
+        }
+    }
+
+    /// The point is brought into the local space of the base shape instead of transforming the shape.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let bbox = QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE);
+    /// let moved = QTransformedShape::new(bbox, QTransform::from_translation(qvec2!(5.0, 0.0)));
+    /// assert!(moved.is_point_inside(&QPoint::new(qvec2!(5.5, 0.5))));
+    /// assert!(!moved.is_point_inside(&QPoint::new(qvec2!(0.5, 0.5))));
+    /// ```
+    fn is_point_inside(&self, point: &QPoint) -> bool {
+        match self.transform.inverse() {
+            Some(inverse) => self.shape.is_point_inside(&inverse.transform_point(point)),
+            None => self.to_polygon().is_point_inside(point),
+        }
+    }
+
+    fn is_collide(&self, other: &impl QShapeCommon) -> bool {
+        let other_shape_type = other.get_shape_type();
+        match other_shape_type {
+            QShapeType::QCompound => other.is_collide(self),
+            _ => {
+                gjk(self, &QShapeSupport(other))
+            }
+        }
+    }
+
+    fn try_get_seperation_vector(&self, other: &impl QShapeCommon) -> Option<QVec2> {
+        let other_shape_type = other.get_shape_type();
+        match other_shape_type {
+            QShapeType::QCompound => other.try_get_seperation_vector(self).map(|vector| -vector),
+            _ => {
+                epa(self, &QShapeSupport(other))
+            }
+        }
+    }
+
+    fn translated(&self, offset: QVec2) -> Self {
+        self.with_transform(QTransform::from_translation(offset) * self.transform)
+    }
+
+    fn rotated_around(&self, pivot: &QPoint, angle: Q64) -> Self {
+        self.with_transform(QTransform::from_rotation_around(pivot.pos(), angle) * self.transform)
+    }
+
+    fn scaled(&self, factor: Q64) -> Self {
+        assert!(factor > Q64::ZERO, "[QTransformedShape::scaled] factor({factor:?}) should be larger than zero.");
+        let center = self.get_centroid().pos();
+        let scale = QTransform::from_translation(center)
+            * QTransform::from_scale(QVec2::splat(factor))
+            * QTransform::from_translation(-center);
+        self.with_transform(scale * self.transform)
+    }
+
+    /// Similarities keep closest points, so the point is brought into local space. Other
+    /// transforms don't, and the shape is placed in world space first.
+    fn closest_point(&self, point: &QPoint) -> QPoint {
+        if self.is_similarity() {
+            if let Some(inverse) = self.transform.inverse() {
+                return self.transform.transform_point(&self.shape.closest_point(&inverse.transform_point(point)));
+            }
+        }
+        if self.is_point_inside(point) {
+            return *point;
+        }
+        let points = self.points();
+        let is_closed = points.len() > 2 && self.get_shape_type() != QShapeType::QPolyline;
+        get_closest_point_on_chain(&points, is_closed, point)
+    }
+
+    /// A transformed circle is no longer a circle, so GJK runs on the transformed support map.
+    fn distance_to(&self, other: &impl QShapeCommon) -> Q64 {
+        gjk_distance(self, &QShapeSupport(other))
+    }
+}
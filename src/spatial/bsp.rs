@@ -0,0 +1,245 @@
+use alloc::{ vec, vec::Vec };
+use core::cmp::Ordering;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::orient2d;
+use crate::shape::{ QPoint, QLine, QPolygon, QShapeCommon };
+
+/// Side of a splitting line, or of the solid described by a tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum QBspSide {
+    /// Right of the splitter, outside the solid.
+    Front,
+    /// Left of the splitter, inside the solid.
+    Back,
+    /// On the splitter, or on the boundary of the solid.
+    On,
+}
+
+#[derive(Debug, Clone)]
+struct QBspNode {
+    /// The splitter first, then the other segments on its line.
+    segments: Vec<QLine>,
+    front: Option<usize>,
+    back: Option<usize>,
+}
+
+/// Pending step of the front to back walk.
+#[derive(Debug, Clone, Copy)]
+enum QBspVisit {
+    /// Order the near side, the node's own segments and the far side.
+    Split(usize),
+    /// Output the node's own segments.
+    Emit(usize),
+}
+
+/// Binary space partition of the plane by segments.
+///
+/// Every node splits by the line through its first segment, segments crossing it are cut
+/// in two. The left of a segment is its back, so counter-clockwise polygon edges describe
+/// a solid whose inside is at the back. The first remaining segment is always the next
+/// splitter, so the same input gives the same tree. Sorted input can make the tree as deep
+/// as it has segments, so every walk keeps its own stack instead of recursing.
+#[derive(Debug, Clone, Default)]
+pub struct QBspTree {
+    nodes: Vec<QBspNode>,
+    root: Option<usize>,
+}
+
+/// Side of `point` relative to the line through `splitter`.
+fn get_side(splitter: &QLine, point: QVec2) -> QBspSide {
+    match orient2d(splitter.start().pos(), splitter.end().pos(), point) {
+        Ordering::Less => QBspSide::Front,
+        Ordering::Greater => QBspSide::Back,
+        Ordering::Equal => QBspSide::On,
+    }
+}
+
+/// Cut `segment` by the line through `splitter` into its front and back parts.
+///
+/// A segment on the line goes to the front if it runs the same way as the splitter.
+fn split_segment(splitter: &QLine, segment: &QLine) -> (Option<QLine>, Option<QLine>) {
+    let (start, end) = (segment.start().pos(), segment.end().pos());
+    match (get_side(splitter, start), get_side(splitter, end)) {
+        (QBspSide::On, QBspSide::On) => {
+            if splitter.vector().dot(segment.vector()) > Q64::ZERO { (Some(*segment), None) } else { (None, Some(*segment)) }
+        }
+        (QBspSide::Front, QBspSide::Back) | (QBspSide::Back, QBspSide::Front) => {
+            let origin = splitter.start().pos();
+            let (d_start, d_end) = (splitter.vector().cross(start.saturating_sub(origin)), splitter.vector().cross(end.saturating_sub(origin)));
+            // Multiply before dividing so cuts at representable points are exact.
+            let (delta, denominator) = (end.saturating_sub(start), d_start.saturating_sub(d_end));
+            let cut = start.saturating_add(QVec2::new(
+                delta.x.saturating_mul(d_start).saturating_div(denominator),
+                delta.y.saturating_mul(d_start).saturating_div(denominator),
+            ));
+            let (first, second) = (
+                (cut != start).then(|| QLine::new_from_parts(start, cut)),
+                (cut != end).then(|| QLine::new_from_parts(cut, end)),
+            );
+            if get_side(splitter, start) == QBspSide::Front { (first, second) } else { (second, first) }
+        }
+        (QBspSide::Front, _) | (_, QBspSide::Front) => (Some(*segment), None),
+        _ => (None, Some(*segment)),
+    }
+}
+
+impl QBspTree {
+    /// Build a tree from segments, splitting them in input order.
+    pub fn new(segments: Vec<QLine>) -> Self {
+        let mut rst = Self::default();
+        rst.build(segments);
+        rst
+    }
+
+    /// Build a solid from the boundaries of `polygons`, reoriented counter-clockwise so
+    /// their inside is at the back.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::spatial::{ QBspSide, QBspTree };
+    ///
+    /// let l_shape = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 2.0), qvec2!(2.0, 2.0), qvec2!(2.0, 4.0), qvec2!(0.0, 4.0)]);
+    /// let tree = QBspTree::from_polygons(&[l_shape]);
+    /// assert_eq!(tree.classify_point(&QPoint::new(qvec2!(1.0, 3.0))), QBspSide::Back);
+    /// assert_eq!(tree.classify_point(&QPoint::new(qvec2!(3.0, 3.0))), QBspSide::Front);
+    /// assert_eq!(tree.classify_point(&QPoint::new(qvec2!(2.0, 3.0))), QBspSide::On);
+    /// let (outside, inside) = tree.clip_segment(&QLine::new_from_parts(qvec2!(-1.0, 1.0), qvec2!(5.0, 1.0)));
+    /// assert_eq!(inside, vec![QLine::new_from_parts(qvec2!(0.0, 1.0), qvec2!(4.0, 1.0))]);
+    /// assert_eq!(outside.len(), 2);
+    /// ```
+    pub fn from_polygons(polygons: &[QPolygon]) -> Self {
+        let segments = polygons
+            .iter()
+            .flat_map(|polygon| {
+                let is_ccw = polygon.is_ccw();
+                polygon.edges_iter().map(move |edge| if is_ccw { edge } else { QLine::new(edge.end(), edge.start()) })
+            })
+            .collect();
+        Self::new(segments)
+    }
+
+    fn build(&mut self, segments: Vec<QLine>) {
+        // Each entry is a set of segments and the parent slot its node goes into.
+        let mut stack: Vec<(Vec<QLine>, Option<(usize, QBspSide)>)> = vec![(segments, None)];
+        while let Some((segments, parent)) = stack.pop() {
+            let Some((&splitter, rest)) = segments.split_first() else {
+                continue;
+            };
+            let (mut on_line, mut front, mut back) = (vec![splitter], vec![], vec![]);
+            for segment in rest {
+                let (start_side, end_side) = (get_side(&splitter, segment.start().pos()), get_side(&splitter, segment.end().pos()));
+                if start_side == QBspSide::On && end_side == QBspSide::On {
+                    on_line.push(*segment);
+                    continue;
+                }
+                let (front_part, back_part) = split_segment(&splitter, segment);
+                front.extend(front_part);
+                back.extend(back_part);
+            }
+            let index = self.nodes.len();
+            self.nodes.push(QBspNode { segments: on_line, front: None, back: None });
+            match parent {
+                None => self.root = Some(index),
+                Some((parent, QBspSide::Back)) => self.nodes[parent].back = Some(index),
+                Some((parent, _)) => self.nodes[parent].front = Some(index),
+            }
+            stack.push((back, Some((index, QBspSide::Back))));
+            stack.push((front, Some((index, QBspSide::Front))));
+        }
+    }
+
+    /// Number of segments after splitting.
+    pub fn len(&self) -> usize {
+        self.nodes.iter().map(|node| node.segments.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Number of nodes on the longest path from the root.
+    pub fn height(&self) -> usize {
+        let mut rst = 0;
+        let mut stack: Vec<(usize, usize)> = self.root.map(|root| (root, 1)).into_iter().collect();
+        while let Some((index, depth)) = stack.pop() {
+            rst = rst.max(depth);
+            let node = &self.nodes[index];
+            stack.extend([node.front, node.back].into_iter().flatten().map(|child| (child, depth + 1)));
+        }
+        rst
+    }
+
+    /// Classify a point against the solid, `On` for points on its boundary.
+    ///
+    /// A point on a splitter is pushed down both sides, it is `On` as soon as the leaves
+    /// it reaches disagree.
+    pub fn classify_point(&self, point: &QPoint) -> QBspSide {
+        let point = point.pos();
+        let mut rst = None;
+        let mut stack = vec![(self.root, QBspSide::Front)];
+        while let Some((node, leaf)) = stack.pop() {
+            let Some(index) = node else {
+                match rst {
+                    None => rst = Some(leaf),
+                    Some(side) if side != leaf => return QBspSide::On,
+                    _ => {}
+                }
+                continue;
+            };
+            let node = &self.nodes[index];
+            match get_side(&node.segments[0], point) {
+                QBspSide::Front => stack.push((node.front, QBspSide::Front)),
+                QBspSide::Back => stack.push((node.back, QBspSide::Back)),
+                QBspSide::On => stack.extend([(node.back, QBspSide::Back), (node.front, QBspSide::Front)]),
+            }
+        }
+        rst.unwrap_or(QBspSide::Front)
+    }
+
+    /// Cut a segment into its parts outside and inside the solid, as `(front, back)`.
+    ///
+    /// Parts along the boundary count as outside when they run with the boundary.
+    pub fn clip_segment(&self, segment: &QLine) -> (Vec<QLine>, Vec<QLine>) {
+        let (mut front, mut back) = (vec![], vec![]);
+        let mut stack = vec![(self.root, *segment, QBspSide::Front)];
+        while let Some((node, segment, leaf)) = stack.pop() {
+            let Some(index) = node else {
+                if leaf == QBspSide::Back { back.push(segment) } else { front.push(segment) }
+                continue;
+            };
+            let node = &self.nodes[index];
+            let (front_part, back_part) = split_segment(&node.segments[0], &segment);
+            // The back part is pushed first so the front part is cut first.
+            stack.extend(back_part.map(|part| (node.back, part, QBspSide::Back)));
+            stack.extend(front_part.map(|part| (node.front, part, QBspSide::Front)));
+        }
+        (front, back)
+    }
+
+    /// Get every segment ordered so nearer ones to `viewpoint` come first, nothing later
+    /// in the list can hide something earlier.
+    pub fn front_to_back(&self, viewpoint: &QPoint) -> Vec<QLine> {
+        let mut rst = Vec::with_capacity(self.len());
+        let mut stack: Vec<QBspVisit> = self.root.map(QBspVisit::Split).into_iter().collect();
+        while let Some(visit) = stack.pop() {
+            match visit {
+                QBspVisit::Split(index) => {
+                    let node = &self.nodes[index];
+                    let (near, far) = match get_side(&node.segments[0], viewpoint.pos()) {
+                        QBspSide::Back => (node.back, node.front),
+                        _ => (node.front, node.back),
+                    };
+                    stack.extend(far.map(QBspVisit::Split));
+                    stack.push(QBspVisit::Emit(index));
+                    stack.extend(near.map(QBspVisit::Split));
+                }
+                QBspVisit::Emit(index) => rst.extend(self.nodes[index].segments.iter().copied()),
+            }
+        }
+        rst
+    }
+}
@@ -0,0 +1,405 @@
+use alloc::{ vec, vec::Vec };
+use qmath::prelude::*;
+use crate::shape::{ QPoint, QBbox, QCircle, QRay, QShapeCommon };
+
+#[derive(Debug, Clone)]
+struct QTreeNode<T> {
+    /// Fat bbox for leaves, union of the children for internal nodes.
+    bbox: QBbox,
+    parent: Option<usize>,
+    children: Option<(usize, usize)>,
+    /// Only leaves carry data, `None` for internal and freed nodes.
+    data: Option<T>,
+}
+
+impl<T> QTreeNode<T> {
+    fn is_leaf(&self) -> bool {
+        self.children.is_none()
+    }
+}
+
+/// Dynamic bounding volume hierarchy for moving objects.
+///
+/// Leaves store fat bboxes grown by `margin`, so small movements don't touch the tree.
+/// New leaves are placed by the surface area heuristic and ancestors are refitted on the way up.
+/// Proxies returned by `insert` stay valid until removed.
+#[derive(Debug, Clone)]
+pub struct QDynamicTree<T> {
+    nodes: Vec<QTreeNode<T>>,
+    free_nodes: Vec<usize>,
+    root: Option<usize>,
+    margin: Q64,
+    leaf_count: usize,
+}
+
+impl<T> QDynamicTree<T> {
+    pub fn new(margin: Q64) -> Self {
+        assert!(margin >= Q64::ZERO, "[QDynamicTree::new] margin({margin:?}) should not be negative.");
+        Self {
+            nodes: vec![],
+            free_nodes: vec![],
+            root: None,
+            margin,
+            leaf_count: 0,
+        }
+    }
+
+    pub fn margin(&self) -> Q64 {
+        self.margin
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaf_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaf_count == 0
+    }
+
+    /// Get the fat bbox of a proxy.
+    pub fn fat_bbox(&self, proxy: usize) -> QBbox {
+        assert!(self.is_proxy(proxy), "[QDynamicTree::fat_bbox] proxy({proxy}) is not a leaf.");
+        self.nodes[proxy].bbox
+    }
+
+    pub fn data(&self, proxy: usize) -> Option<&T> {
+        self.nodes.get(proxy).and_then(|node| node.data.as_ref())
+    }
+
+    pub fn data_mut(&mut self, proxy: usize) -> Option<&mut T> {
+        self.nodes.get_mut(proxy).and_then(|node| node.data.as_mut())
+    }
+
+    /// Insert an object with its tight bbox, return the proxy of the new leaf.
+    pub fn insert(&mut self, bbox: QBbox, data: T) -> usize {
+        let leaf = self.allocate_node(QTreeNode {
+            bbox: bbox.inflated(self.margin),
+            parent: None,
+            children: None,
+            data: Some(data),
+        });
+        self.insert_leaf(leaf);
+        self.leaf_count += 1;
+        leaf
+    }
+
+    /// Remove a proxy, return its data.
+    pub fn remove(&mut self, proxy: usize) -> T {
+        assert!(self.is_proxy(proxy), "[QDynamicTree::remove] proxy({proxy}) is not a leaf.");
+        self.remove_leaf(proxy);
+        self.leaf_count -= 1;
+        let data = self.nodes[proxy].data.take().expect("[QDynamicTree::remove] Leaf must carry data.");
+        self.free_nodes.push(proxy);
+        data
+    }
+
+    /// Update the tight bbox of a proxy.
+    ///
+    /// Return true if the leaf was reinserted, false if the bbox still fits in the fat bbox.
+    pub fn update(&mut self, proxy: usize, bbox: QBbox) -> bool {
+        assert!(self.is_proxy(proxy), "[QDynamicTree::update] proxy({proxy}) is not a leaf.");
+        if self.nodes[proxy].bbox.is_bbox_inside(&bbox) {
+            return false;
+        }
+        self.remove_leaf(proxy);
+        self.nodes[proxy].bbox = bbox.inflated(self.margin);
+        self.insert_leaf(proxy);
+        true
+    }
+
+    /// Get proxies whose fat bbox overlaps `bbox`.
+    pub fn query_bbox(&self, bbox: &QBbox) -> Vec<usize> {
+        self.query(|node_bbox| node_bbox.is_overlap(bbox))
+    }
+
+    /// Get proxies whose fat bbox contains `point`.
+    pub fn query_point(&self, point: &QPoint) -> Vec<usize> {
+        self.query(|node_bbox| node_bbox.is_point_inside(point))
+    }
+
+    /// Get proxies whose fat bbox is hit by the ray within `max_t`, sorted by entry distance.
+    pub fn query_ray(&self, ray: &QRay, max_t: Q64) -> Vec<usize> {
+        self.ray_candidates(ray, max_t).into_iter().map(|(_, proxy)| proxy).collect()
+    }
+
+    /// Cast a ray against the objects, `hit` returns the exact hit distance of a candidate.
+    ///
+    /// Return all hits within `max_t` as `(proxy, t)` sorted by `t`.
+    pub fn raycast_all(&self, ray: &QRay, max_t: Q64, hit: impl Fn(usize, &T) -> Option<Q64>) -> Vec<(usize, Q64)> {
+        let mut rst: Vec<(usize, Q64)> = self
+            .ray_candidates(ray, max_t)
+            .into_iter()
+            .filter_map(|(_, proxy)| self.hit_proxy(proxy, max_t, &hit))
+            .collect();
+        rst.sort_by(|a, b| (a.1, a.0).partial_cmp(&(b.1, b.0)).unwrap_or(core::cmp::Ordering::Equal));
+        rst
+    }
+
+    /// Cast a ray against the objects, return the nearest hit within `max_t` as `(proxy, t)`.
+    ///
+    /// Candidates are visited by entry distance of their fat bbox and the search stops once
+    /// no remaining candidate can beat the best hit.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qmath::dir::QDir;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::spatial::QDynamicTree;
+    ///
+    /// let mut tree = QDynamicTree::new(Q64::ZERO);
+    /// let circles = [QCircle::new(QPoint::new(qvec2!(3.0, 0.0)), Q64::ONE), QCircle::new(QPoint::new(qvec2!(9.0, 0.0)), Q64::ONE)];
+    /// let near = tree.insert(circles[0].get_bbox(), circles[0]);
+    /// let far = tree.insert(circles[1].get_bbox(), circles[1]);
+    /// let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::X));
+    /// let (proxy, _) = tree.raycast_first(&ray, Q64::MAX, |_, circle| ray.intersect_shape(circle)).unwrap();
+    /// assert_eq!(proxy, near);
+    /// let hits = tree.raycast_all(&ray, Q64::MAX, |_, circle| ray.intersect_shape(circle));
+    /// assert_eq!(hits.iter().map(|(proxy, _)| *proxy).collect::<Vec<_>>(), vec![near, far]);
+    /// ```
+    pub fn raycast_first(&self, ray: &QRay, max_t: Q64, hit: impl Fn(usize, &T) -> Option<Q64>) -> Option<(usize, Q64)> {
+        let mut best: Option<(usize, Q64)> = None;
+        for (entry_t, proxy) in self.ray_candidates(ray, max_t) {
+            if best.is_some_and(|(_, best_t)| entry_t > best_t) { break; }
+            if let Some((proxy, t)) = self.hit_proxy(proxy, max_t, &hit) {
+                let is_better = match best {
+                    None => true,
+                    Some((best_proxy, best_t)) => t < best_t || (t == best_t && proxy < best_proxy),
+                };
+                if is_better { best = Some((proxy, t)); }
+            }
+        }
+        best
+    }
+
+    /// Leaves whose fat bbox is hit within `max_t` as `(entry_t, proxy)`, sorted.
+    fn ray_candidates(&self, ray: &QRay, max_t: Q64) -> Vec<(Q64, usize)> {
+        let hit = |node_bbox: &QBbox| ray.intersect_bbox(node_bbox).filter(|t| *t <= max_t);
+        let mut rst: Vec<(Q64, usize)> = self
+            .query(|node_bbox| hit(node_bbox).is_some())
+            .into_iter()
+            .filter_map(|proxy| hit(&self.nodes[proxy].bbox).map(|t| (t, proxy)))
+            .collect();
+        rst.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+        rst
+    }
+
+    fn hit_proxy(&self, proxy: usize, max_t: Q64, hit: &impl Fn(usize, &T) -> Option<Q64>) -> Option<(usize, Q64)> {
+        let data = self.nodes[proxy].data.as_ref()?;
+        hit(proxy, data).filter(|t| *t <= max_t).map(|t| (proxy, t))
+    }
+
+    /// Enumerate all pairs of proxies whose fat bboxes overlap, each pair is `(smaller, larger)`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::spatial::QDynamicTree;
+    ///
+    /// let mut tree = QDynamicTree::new(Q64::ZERO);
+    /// let a = tree.insert(QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 2.0)), "a");
+    /// let b = tree.insert(QBbox::new_from_parts(QVec2::ONE, qvec2!(3.0, 3.0)), "b");
+    /// let c = tree.insert(QBbox::new_from_parts(qvec2!(5.0, 5.0), qvec2!(6.0, 6.0)), "c");
+    /// assert_eq!(tree.query_pairs(), vec![(a, b)]);
+    /// tree.update(c, QBbox::new_from_parts(qvec2!(2.5, 2.5), qvec2!(4.0, 4.0)));
+    /// assert_eq!(tree.query_pairs(), vec![(a, b), (b, c)]);
+    /// ```
+    pub fn query_pairs(&self) -> Vec<(usize, usize)> {
+        let mut rst = vec![];
+        for proxy in 0..self.nodes.len() {
+            if !self.is_proxy(proxy) { continue; }
+            let bbox = self.nodes[proxy].bbox;
+            let mut others: Vec<usize> = self
+                .query_bbox(&bbox)
+                .into_iter()
+                .filter(|other| *other > proxy)
+                .collect();
+            others.sort_unstable();
+            rst.extend(others.into_iter().map(|other| (proxy, other)));
+        }
+        rst
+    }
+
+    /// Height of the tree, zero when empty.
+    pub fn height(&self) -> usize {
+        fn height_of<T>(nodes: &[QTreeNode<T>], index: usize) -> usize {
+            match nodes[index].children {
+                None => 1,
+                Some((left, right)) => 1 + height_of(nodes, left).max(height_of(nodes, right)),
+            }
+        }
+        self.root.map(|root| height_of(&self.nodes, root)).unwrap_or(0)
+    }
+
+    fn is_proxy(&self, proxy: usize) -> bool {
+        self.nodes.get(proxy).is_some_and(|node| node.is_leaf() && node.data.is_some())
+    }
+
+    /// Traverse nodes accepted by `filter`, return the leaves reached.
+    fn query(&self, filter: impl Fn(&QBbox) -> bool) -> Vec<usize> {
+        let mut rst = vec![];
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !filter(&node.bbox) { continue; }
+            match node.children {
+                None => rst.push(index),
+                Some((left, right)) => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+        rst
+    }
+
+    fn allocate_node(&mut self, node: QTreeNode<T>) -> usize {
+        match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn free_node(&mut self, index: usize) {
+        self.nodes[index].parent = None;
+        self.nodes[index].children = None;
+        self.nodes[index].data = None;
+        self.free_nodes.push(index);
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        self.nodes[leaf].parent = None;
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            return;
+        };
+
+        // Find the best sibling by the surface area heuristic.
+        let leaf_bbox = self.nodes[leaf].bbox;
+        let mut index = root;
+        while let Some((left, right)) = self.nodes[index].children {
+            let perimeter = self.nodes[index].bbox.perimeter();
+            let combined_perimeter = self.nodes[index].bbox.union(&leaf_bbox).perimeter();
+
+            // Cost of creating a new parent for this node and the leaf.
+            let cost = combined_perimeter.saturating_mul(q64!(2));
+            // Minimum cost of pushing the leaf further down the tree.
+            let inheritance_cost = combined_perimeter.saturating_sub(perimeter).saturating_mul(q64!(2));
+            let child_cost = |child: usize| {
+                let child_bbox = self.nodes[child].bbox;
+                let new_perimeter = child_bbox.union(&leaf_bbox).perimeter();
+                if self.nodes[child].is_leaf() {
+                    new_perimeter.saturating_add(inheritance_cost)
+                } else {
+                    new_perimeter.saturating_sub(child_bbox.perimeter()).saturating_add(inheritance_cost)
+                }
+            };
+            let cost_left = child_cost(left);
+            let cost_right = child_cost(right);
+
+            if cost < cost_left && cost < cost_right { break; }
+            index = if cost_left <= cost_right { left } else { right };
+        }
+
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate_node(QTreeNode {
+            bbox: self.nodes[sibling].bbox.union(&leaf_bbox),
+            parent: old_parent,
+            children: Some((sibling, leaf)),
+            data: None,
+        });
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+
+        match old_parent {
+            Some(old_parent) => {
+                self.replace_child(old_parent, sibling, new_parent);
+                self.refit_from(old_parent);
+            }
+            None => self.root = Some(new_parent),
+        }
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        if self.root == Some(leaf) {
+            self.root = None;
+            return;
+        }
+
+        let parent = self.nodes[leaf].parent.expect("[QDynamicTree::remove_leaf] Non-root leaf must have a parent.");
+        let (left, right) = self.nodes[parent].children.expect("[QDynamicTree::remove_leaf] Parent must have children.");
+        let sibling = if left == leaf { right } else { left };
+        let grand_parent = self.nodes[parent].parent;
+
+        self.nodes[sibling].parent = grand_parent;
+        match grand_parent {
+            Some(grand_parent) => {
+                self.replace_child(grand_parent, parent, sibling);
+                self.refit_from(grand_parent);
+            }
+            None => self.root = Some(sibling),
+        }
+        self.free_node(parent);
+        self.nodes[leaf].parent = None;
+    }
+
+    fn replace_child(&mut self, parent: usize, old_child: usize, new_child: usize) {
+        let (left, right) = self.nodes[parent].children.expect("[QDynamicTree::replace_child] Parent must have children.");
+        self.nodes[parent].children = Some(if left == old_child { (new_child, right) } else { (left, new_child) });
+    }
+
+    /// Recompute the bboxes of `index` and all its ancestors.
+    fn refit_from(&mut self, index: usize) {
+        let mut current = Some(index);
+        while let Some(index) = current {
+            if let Some((left, right)) = self.nodes[index].children {
+                self.nodes[index].bbox = self.nodes[left].bbox.union(&self.nodes[right].bbox);
+            }
+            current = self.nodes[index].parent;
+        }
+    }
+}
+
+impl<T: QShapeCommon> QDynamicTree<T> {
+    /// Get proxies whose shape intersects `region`, not just its bbox.
+    pub fn query_region(&self, region: &QBbox) -> Vec<usize> {
+        self.query_shape(region, region)
+    }
+
+    /// Get proxies whose shape intersects `circle`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::spatial::QDynamicTree;
+    ///
+    /// let mut tree = QDynamicTree::new(Q64::ZERO);
+    /// let line = QLine::new_from_parts(qvec2!(0.0, 4.0), qvec2!(4.0, 0.0));
+    /// tree.insert(line.get_bbox(), line);
+    /// // The bboxes overlap but the geometries don't
+    /// assert!(tree.query_circle(&QCircle::new(QPoint::ZERO, Q64::ONE)).is_empty());
+    /// assert_eq!(tree.query_circle(&QCircle::new(QPoint::new(qvec2!(2.0, 2.0)), Q64::ONE)).len(), 1);
+    /// ```
+    pub fn query_circle(&self, circle: &QCircle) -> Vec<usize> {
+        self.query_shape(&circle.get_bbox(), circle)
+    }
+
+    fn query_shape(&self, bbox: &QBbox, region: &impl QShapeCommon) -> Vec<usize> {
+        let mut rst: Vec<usize> = self
+            .query_bbox(bbox)
+            .into_iter()
+            .filter(|proxy| self.nodes[*proxy].data.as_ref().is_some_and(|shape| shape.is_collide(region)))
+            .collect();
+        rst.sort_unstable();
+        rst
+    }
+}
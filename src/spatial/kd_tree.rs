@@ -0,0 +1,125 @@
+use alloc::{ vec, vec::Vec };
+use qmath::prelude::*;
+use crate::shape::QPoint;
+
+/// Static 2D tree over points for nearest neighbor and radius queries.
+///
+/// The tree is stored implicitly: every range of `order` keeps its median point at the middle,
+/// with smaller coordinates on the left. Points are identified by their index in the input.
+#[derive(Debug, Clone)]
+pub struct QKdTree {
+    points: Vec<QPoint>,
+    order: Vec<usize>,
+}
+
+impl QKdTree {
+    pub fn new(points: Vec<QPoint>) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        Self::build(&points, &mut order, 0);
+        Self {
+            points,
+            order,
+        }
+    }
+
+    pub fn points(&self) -> &Vec<QPoint> {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Get the index of the point nearest to `point`, ties are broken by the smaller index.
+    pub fn nearest(&self, point: &QPoint) -> Option<usize> {
+        self.k_nearest(point, 1).first().copied()
+    }
+
+    /// Get the indices of the `k` points nearest to `point`, sorted by distance.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::spatial::QKdTree;
+    ///
+    /// let points = (0..10).map(|i| QPoint::new(qvec2!(i, 0))).collect();
+    /// let tree = QKdTree::new(points);
+    /// assert_eq!(tree.nearest(&QPoint::new(qvec2!(3.2, 1.0))), Some(3));
+    /// assert_eq!(tree.k_nearest(&QPoint::new(qvec2!(6.4, 0.0)), 3), vec![6, 7, 5]);
+    /// assert_eq!(tree.within_radius(&QPoint::new(qvec2!(0.0, 0.0)), q64!(2)), vec![0, 1, 2]);
+    /// ```
+    pub fn k_nearest(&self, point: &QPoint, k: usize) -> Vec<usize> {
+        if k == 0 { return vec![]; }
+        let mut best: Vec<(Q64, usize)> = Vec::with_capacity(k + 1);
+        self.k_nearest_in(0, self.order.len(), 0, point, k, &mut best);
+        best.into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// Get the indices of all points within `radius` of `point`, sorted by index.
+    pub fn within_radius(&self, point: &QPoint, radius: Q64) -> Vec<usize> {
+        let radius_squared = radius.saturating_mul(radius);
+        let mut rst = vec![];
+        self.within_radius_in(0, self.order.len(), 0, point, radius_squared, &mut rst);
+        rst.sort_unstable();
+        rst
+    }
+
+    fn build(points: &[QPoint], order: &mut [usize], depth: usize) {
+        if order.len() <= 1 { return; }
+        let mid = order.len() / 2;
+        order.select_nth_unstable_by(mid, |a, b| {
+            let (pa, pb) = (axis_value(&points[*a], depth), axis_value(&points[*b], depth));
+            pa.partial_cmp(&pb).unwrap_or(core::cmp::Ordering::Equal).then(a.cmp(b))
+        });
+        let (left, right) = order.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    fn k_nearest_in(&self, lo: usize, hi: usize, depth: usize, point: &QPoint, k: usize, best: &mut Vec<(Q64, usize)>) {
+        if lo >= hi { return; }
+        let mid = lo + (hi - lo) / 2;
+        let index = self.order[mid];
+        let candidate = (self.points[index].pos().distance_squared(point.pos()), index);
+        let position = best.partition_point(|entry| entry < &candidate);
+        if position < k {
+            best.insert(position, candidate);
+            best.truncate(k);
+        }
+
+        let diff = axis_value(point, depth).saturating_sub(axis_value(&self.points[index], depth));
+        let (near, far) = if diff < Q64::ZERO { ((lo, mid), (mid + 1, hi)) } else { ((mid + 1, hi), (lo, mid)) };
+        self.k_nearest_in(near.0, near.1, depth + 1, point, k, best);
+        let is_full = best.len() == k;
+        if !is_full || diff.saturating_mul(diff) <= best[k - 1].0 {
+            self.k_nearest_in(far.0, far.1, depth + 1, point, k, best);
+        }
+    }
+
+    fn within_radius_in(&self, lo: usize, hi: usize, depth: usize, point: &QPoint, radius_squared: Q64, rst: &mut Vec<usize>) {
+        if lo >= hi { return; }
+        let mid = lo + (hi - lo) / 2;
+        let index = self.order[mid];
+        if self.points[index].pos().distance_squared(point.pos()) <= radius_squared {
+            rst.push(index);
+        }
+
+        let diff = axis_value(point, depth).saturating_sub(axis_value(&self.points[index], depth));
+        let diff_squared = diff.saturating_mul(diff);
+        if diff < Q64::ZERO || diff_squared <= radius_squared {
+            self.within_radius_in(lo, mid, depth + 1, point, radius_squared, rst);
+        }
+        if diff >= Q64::ZERO || diff_squared <= radius_squared {
+            self.within_radius_in(mid + 1, hi, depth + 1, point, radius_squared, rst);
+        }
+    }
+}
+
+fn axis_value(point: &QPoint, depth: usize) -> Q64 {
+    if depth % 2 == 0 { point.x() } else { point.y() }
+}
@@ -0,0 +1,21 @@
+#[cfg(feature = "std")]
+pub mod spatial_hash;
+pub mod dynamic_tree;
+pub mod rtree;
+pub mod kd_tree;
+pub mod trapezoidal_map;
+pub mod bsp;
+
+#[cfg(feature = "std")]
+pub use spatial_hash::QSpatialHash;
+pub use dynamic_tree::QDynamicTree;
+pub use rtree::QRTree;
+pub use kd_tree::QKdTree;
+pub use trapezoidal_map::QTrapezoidalMap;
+pub use bsp::{ QBspSide, QBspTree };
+use qmath::prelude::*;
+
+/// Index of the grid cell containing `value` for cells of `cell_size`.
+pub(crate) fn cell_index(value: Q64, cell_size: Q64) -> i64 {
+    value.saturating_div(cell_size).floor().to_num::<i64>()
+}
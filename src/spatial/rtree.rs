@@ -0,0 +1,263 @@
+use alloc::{ vec, vec::Vec };
+use qmath::prelude::*;
+use crate::shape::{ QPoint, QBbox, QCircle, QRay, QShapeCommon };
+
+#[derive(Debug, Clone)]
+struct QRTreeNode {
+    bbox: QBbox,
+    /// Item indices for leaves, node indices otherwise.
+    children: Vec<usize>,
+    is_leaf: bool,
+}
+
+/// Static R-tree bulk loaded with Sort-Tile-Recursive packing.
+///
+/// Built once from all items, items are identified by their index in the input.
+#[derive(Debug, Clone)]
+pub struct QRTree<T> {
+    items: Vec<(QBbox, T)>,
+    nodes: Vec<QRTreeNode>,
+    root: Option<usize>,
+}
+
+impl<T> QRTree<T> {
+    pub const DEFAULT_NODE_CAPACITY: usize = 16;
+
+    pub fn new(items: Vec<(QBbox, T)>) -> Self {
+        Self::new_with_capacity(items, Self::DEFAULT_NODE_CAPACITY)
+    }
+
+    /// Bulk load the tree, every node holds at most `node_capacity` children.
+    pub fn new_with_capacity(items: Vec<(QBbox, T)>, node_capacity: usize) -> Self {
+        assert!(node_capacity >= 2, "[QRTree::new_with_capacity] node_capacity({node_capacity}) should be at least 2.");
+        let mut nodes = vec![];
+        let mut level: Vec<(QBbox, usize)> = items.iter().enumerate().map(|(i, (bbox, _))| (*bbox, i)).collect();
+        let mut is_leaf = true;
+
+        while !level.is_empty() {
+            let mut next_level = vec![];
+            for group in str_pack(level, node_capacity) {
+                let bbox = group
+                    .iter()
+                    .map(|(bbox, _)| *bbox)
+                    .reduce(|a, b| a.union(&b))
+                    .expect("[QRTree::new_with_capacity] Group must not be empty.");
+                nodes.push(QRTreeNode {
+                    bbox,
+                    children: group.into_iter().map(|(_, index)| index).collect(),
+                    is_leaf,
+                });
+                next_level.push((bbox, nodes.len() - 1));
+            }
+            is_leaf = false;
+            if next_level.len() == 1 { break; }
+            level = next_level;
+        }
+
+        let root = if nodes.is_empty() { None } else { Some(nodes.len() - 1) };
+        Self {
+            items,
+            nodes,
+            root,
+        }
+    }
+
+    /// Build a tree over shapes, keyed by their bboxes.
+    pub fn new_from_shapes(shapes: &[T]) -> Self where T: QShapeCommon + Clone {
+        Self::new(shapes.iter().map(|shape| (shape.get_bbox(), shape.clone())).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index).map(|(_, item)| item)
+    }
+
+    pub fn get_bbox(&self, index: usize) -> Option<QBbox> {
+        self.items.get(index).map(|(bbox, _)| *bbox)
+    }
+
+    /// Get indices of items whose bbox overlaps `bbox`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::spatial::QRTree;
+    ///
+    /// let items = (0..100)
+    ///     .map(|i| (QBbox::new_from_parts(qvec2!(i, i), qvec2!(i, i) + QVec2::splat(Q64::HALF)), i))
+    ///     .collect();
+    /// let tree = QRTree::new_with_capacity(items, 4);
+    /// let mut hits = tree.query_bbox(&QBbox::new_from_parts(qvec2!(10.0, 10.0), qvec2!(12.0, 12.0)));
+    /// hits.sort();
+    /// assert_eq!(hits, vec![10, 11, 12]);
+    /// assert_eq!(tree.nearest(&QPoint::new(qvec2!(50.2, 49.0))), Some(49));
+    /// ```
+    pub fn query_bbox(&self, bbox: &QBbox) -> Vec<usize> {
+        self.query(|node_bbox| node_bbox.is_overlap(bbox))
+    }
+
+    /// Get indices of items whose bbox contains `point`.
+    pub fn query_point(&self, point: &QPoint) -> Vec<usize> {
+        self.query(|node_bbox| node_bbox.is_point_inside(point))
+    }
+
+    /// Cast a ray against the items, `hit` returns the exact hit distance of a candidate.
+    ///
+    /// Return all hits within `max_t` as `(index, t)` sorted by `t`.
+    pub fn raycast_all(&self, ray: &QRay, max_t: Q64, hit: impl Fn(usize, &T) -> Option<Q64>) -> Vec<(usize, Q64)> {
+        let mut rst: Vec<(usize, Q64)> = self
+            .query(|bbox| ray.intersect_bbox(bbox).is_some_and(|t| t <= max_t))
+            .into_iter()
+            .filter_map(|index| hit(index, &self.items[index].1).filter(|t| *t <= max_t).map(|t| (index, t)))
+            .collect();
+        rst.sort_by(|a, b| (a.1, a.0).partial_cmp(&(b.1, b.0)).unwrap_or(core::cmp::Ordering::Equal));
+        rst
+    }
+
+    /// Cast a ray against the items, return the nearest hit within `max_t` as `(index, t)`.
+    ///
+    /// Nodes are visited by entry distance and pruned once they can't beat the best hit.
+    pub fn raycast_first(&self, ray: &QRay, max_t: Q64, hit: impl Fn(usize, &T) -> Option<Q64>) -> Option<(usize, Q64)> {
+        let root = self.root?;
+        let mut best: Option<(usize, Q64)> = None;
+        self.raycast_first_in(root, ray, max_t, &hit, &mut best);
+        best
+    }
+
+    fn raycast_first_in(
+        &self,
+        node_index: usize,
+        ray: &QRay,
+        max_t: Q64,
+        hit: &impl Fn(usize, &T) -> Option<Q64>,
+        best: &mut Option<(usize, Q64)>,
+    ) {
+        let limit = |best: &Option<(usize, Q64)>| best.map(|(_, t)| t.min(max_t)).unwrap_or(max_t);
+        let node = &self.nodes[node_index];
+        let mut children: Vec<(Q64, usize)> = node.children
+            .iter()
+            .filter_map(|&child| {
+                let bbox = if node.is_leaf { self.items[child].0 } else { self.nodes[child].bbox };
+                ray.intersect_bbox(&bbox).filter(|t| *t <= max_t).map(|t| (t, child))
+            })
+            .collect();
+        children.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+
+        for (entry_t, child) in children {
+            if entry_t > limit(best) { break; }
+            if !node.is_leaf {
+                self.raycast_first_in(child, ray, max_t, hit, best);
+                continue;
+            }
+            let Some(t) = hit(child, &self.items[child].1).filter(|t| *t <= max_t) else { continue; };
+            let is_better = match *best {
+                None => true,
+                Some((best_index, best_t)) => t < best_t || (t == best_t && child < best_index),
+            };
+            if is_better { *best = Some((child, t)); }
+        }
+    }
+
+    /// Get the index of the item whose bbox is nearest to `point`.
+    ///
+    /// Ties are broken by the smaller index.
+    pub fn nearest(&self, point: &QPoint) -> Option<usize> {
+        let root = self.root?;
+        let mut best: Option<(Q64, usize)> = None;
+        self.nearest_in(root, point, &mut best);
+        best.map(|(_, index)| index)
+    }
+
+    fn nearest_in(&self, node_index: usize, point: &QPoint, best: &mut Option<(Q64, usize)>) {
+        let node = &self.nodes[node_index];
+        if node.is_leaf {
+            for &index in &node.children {
+                let dist = self.items[index].0.get_distance_squared_from_point(point);
+                let is_better = match *best {
+                    None => true,
+                    Some((best_dist, best_index)) => dist < best_dist || (dist == best_dist && index < best_index),
+                };
+                if is_better { *best = Some((dist, index)); }
+            }
+            return;
+        }
+
+        // Visit closer children first so farther ones are more likely pruned.
+        let mut children: Vec<(Q64, usize)> = node.children
+            .iter()
+            .map(|&child| (self.nodes[child].bbox.get_distance_squared_from_point(point), child))
+            .collect();
+        children.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+        for (dist, child) in children {
+            if best.is_some_and(|(best_dist, _)| dist > best_dist) { break; }
+            self.nearest_in(child, point, best);
+        }
+    }
+
+    /// Traverse nodes accepted by `filter`, return the items reached.
+    fn query(&self, filter: impl Fn(&QBbox) -> bool) -> Vec<usize> {
+        let mut rst = vec![];
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            if !filter(&node.bbox) { continue; }
+            if node.is_leaf {
+                rst.extend(node.children.iter().copied().filter(|&index| filter(&self.items[index].0)));
+            } else {
+                stack.extend(node.children.iter().rev().copied());
+            }
+        }
+        rst
+    }
+}
+
+impl<T: QShapeCommon> QRTree<T> {
+    /// Get indices of items whose shape intersects `region`, not just its bbox.
+    pub fn query_region(&self, region: &QBbox) -> Vec<usize> {
+        self.query_shape(region, region)
+    }
+
+    /// Get indices of items whose shape intersects `circle`.
+    pub fn query_circle(&self, circle: &QCircle) -> Vec<usize> {
+        self.query_shape(&circle.get_bbox(), circle)
+    }
+
+    fn query_shape(&self, bbox: &QBbox, region: &impl QShapeCommon) -> Vec<usize> {
+        let mut rst: Vec<usize> = self
+            .query_bbox(bbox)
+            .into_iter()
+            .filter(|index| self.items[*index].1.is_collide(region))
+            .collect();
+        rst.sort_unstable();
+        rst
+    }
+}
+
+/// Sort-Tile-Recursive grouping of entries into runs of at most `capacity`.
+fn str_pack(mut entries: Vec<(QBbox, usize)>, capacity: usize) -> Vec<Vec<(QBbox, usize)>> {
+    let group_count = entries.len().div_ceil(capacity);
+    let mut slice_count = 1;
+    while slice_count * slice_count < group_count {
+        slice_count += 1;
+    }
+
+    // Twice the center, enough for ordering.
+    let center_x = |bbox: &QBbox| bbox.left_bottom().x().saturating_add(bbox.right_top().x());
+    let center_y = |bbox: &QBbox| bbox.left_bottom().y().saturating_add(bbox.right_top().y());
+    entries.sort_by(|a, b| center_x(&a.0).partial_cmp(&center_x(&b.0)).unwrap_or(core::cmp::Ordering::Equal));
+
+    let mut groups = vec![];
+    for slice in entries.chunks_mut(slice_count * capacity) {
+        slice.sort_by(|a, b| center_y(&a.0).partial_cmp(&center_y(&b.0)).unwrap_or(core::cmp::Ordering::Equal));
+        groups.extend(slice.chunks(capacity).map(|group| group.to_vec()));
+    }
+    groups
+}
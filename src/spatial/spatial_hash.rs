@@ -0,0 +1,132 @@
+use alloc::{ vec, vec::Vec };
+use std::collections::{HashMap, HashSet};
+use core::hash::Hash;
+use qmath::prelude::*;
+use crate::shape::{ QPoint, QBbox };
+use super::cell_index;
+
+/// Uniform grid broadphase.
+///
+/// Every entry is stored in all cells its bbox overlaps, queries only visit the cells they touch
+/// and return candidate ids whose bbox overlaps the query.
+#[derive(Debug, Clone)]
+pub struct QSpatialHash<T> {
+    cell_size: Q64,
+    cells: HashMap<(i64, i64), Vec<T>>,
+    entries: HashMap<T, QBbox>,
+}
+
+impl<T: Copy + Eq + Hash> QSpatialHash<T> {
+    pub fn new(cell_size: Q64) -> Self {
+        assert!(cell_size > Q64::ZERO, "[QSpatialHash::new] cell_size({cell_size:?}) should be larger than zero.");
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> Q64 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+    }
+
+    pub fn get_bbox(&self, id: &T) -> Option<QBbox> {
+        self.entries.get(id).copied()
+    }
+
+    /// Insert an entry, an existing entry with the same id is replaced.
+    pub fn insert(&mut self, id: T, bbox: QBbox) {
+        self.remove(&id);
+        for cell in self.cells_of(&bbox) {
+            self.cells.entry(cell).or_default().push(id);
+        }
+        self.entries.insert(id, bbox);
+    }
+
+    /// Remove an entry, return its bbox if it existed.
+    pub fn remove(&mut self, id: &T) -> Option<QBbox> {
+        let bbox = self.entries.remove(id)?;
+        for cell in self.cells_of(&bbox) {
+            if let Some(ids) = self.cells.get_mut(&cell) {
+                ids.retain(|other| other != id);
+                if ids.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        Some(bbox)
+    }
+
+    /// Move an entry to a new bbox, cells are only touched when the covered cells change.
+    pub fn update(&mut self, id: T, bbox: QBbox) {
+        match self.entries.get(&id) {
+            Some(old_bbox) if self.cells_of(old_bbox) == self.cells_of(&bbox) => {
+                self.entries.insert(id, bbox);
+            }
+            _ => self.insert(id, bbox),
+        }
+    }
+
+    /// Get ids whose bbox overlaps `bbox`, in a deterministic order.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::spatial::QSpatialHash;
+    ///
+    /// let mut grid = QSpatialHash::new(q64!(4));
+    /// grid.insert(1, QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE));
+    /// grid.insert(2, QBbox::new_from_parts(qvec2!(10.0, 10.0), qvec2!(11.0, 11.0)));
+    /// assert_eq!(grid.query_bbox(&QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 2.0))), vec![1]);
+    /// grid.update(1, QBbox::new_from_parts(qvec2!(10.0, 10.0), qvec2!(12.0, 12.0)));
+    /// assert_eq!(grid.query_point(&QPoint::new(qvec2!(10.5, 10.5))), vec![2, 1]);
+    /// ```
+    pub fn query_bbox(&self, bbox: &QBbox) -> Vec<T> {
+        let mut visited = HashSet::new();
+        let mut rst = vec![];
+        for cell in self.cells_of(bbox) {
+            let Some(ids) = self.cells.get(&cell) else { continue; };
+            for id in ids {
+                if visited.insert(*id) && self.entries[id].is_overlap(bbox) {
+                    rst.push(*id);
+                }
+            }
+        }
+        rst
+    }
+
+    /// Get ids whose bbox contains `point`.
+    pub fn query_point(&self, point: &QPoint) -> Vec<T> {
+        let cell = (cell_index(point.x(), self.cell_size), cell_index(point.y(), self.cell_size));
+        let Some(ids) = self.cells.get(&cell) else { return vec![]; };
+        ids.iter().copied().filter(|id| self.entries[id].is_point_inside(point)).collect()
+    }
+
+    fn cells_of(&self, bbox: &QBbox) -> Vec<(i64, i64)> {
+        let min_x = cell_index(bbox.left_bottom().x(), self.cell_size);
+        let min_y = cell_index(bbox.left_bottom().y(), self.cell_size);
+        let max_x = cell_index(bbox.right_top().x(), self.cell_size);
+        let max_y = cell_index(bbox.right_top().y(), self.cell_size);
+        let mut rst = vec![];
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                rst.push((x, y));
+            }
+        }
+        rst
+    }
+}
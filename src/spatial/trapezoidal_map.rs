@@ -0,0 +1,359 @@
+use alloc::{ vec, vec::Vec };
+use alloc::collections::BTreeMap;
+use core::cmp::Ordering;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::{ orient2d, signed_double_area };
+use crate::generate::{ shuffle, QSplitMix64 };
+use crate::shape::{ QPoint, QLine, QPolygon, QShapeCommon };
+
+/// Seed of the insertion order, fixed so the same input always builds the same map.
+const INSERTION_SEED: u64 = 0x5472_6170_657A_6F69;
+
+/// Distinct input segment with its lexicographically smaller endpoint first.
+#[derive(Debug, Clone)]
+struct QMapSegment {
+    left: QVec2,
+    right: QVec2,
+    /// Index of its first occurrence among the given segments or polygon edges.
+    index: usize,
+    /// Polygons bounded by the segment and whether their interior is above it.
+    polygons: Vec<(usize, bool)>,
+}
+
+/// Trapezoid between a top and a bottom segment and the vertical walls through two
+/// endpoints, `None` on the sides where it is unbounded.
+#[derive(Debug, Clone, Copy)]
+struct QTrapezoid {
+    top: Option<usize>,
+    bottom: Option<usize>,
+    leftp: Option<QVec2>,
+    rightp: Option<QVec2>,
+}
+
+/// Node of the search structure.
+#[derive(Debug, Clone, Copy)]
+enum QMapNode {
+    /// Endpoint and a segment ending there, then the children left and right of it.
+    Point(QVec2, usize, usize, usize),
+    /// Segment, then the children above and below it.
+    Segment(usize, usize, usize),
+    Leaf(usize),
+}
+
+/// Static point location structure over non-crossing segments.
+///
+/// The segments and the vertical walls through their endpoints cut the plane into
+/// trapezoids. Segments are inserted in a shuffled order and a search structure records
+/// every split, so the map holds at most 3n + 1 trapezoids and builds in O(n log n)
+/// expected time, a query takes O(log n) expected time. The shuffle uses a fixed seed
+/// so the same input always builds the same map.
+///
+/// Points sharing an x coordinate are ordered by y, as if the plane were slightly sheared,
+/// so vertical segments need no special case. Segments may touch at their ends but must
+/// not cross, polygons may be nested or share edges but must not overlap otherwise.
+#[derive(Debug, Clone)]
+pub struct QTrapezoidalMap {
+    segments: Vec<QMapSegment>,
+    /// Number of segments or polygon edges given, repeated ones included.
+    input_count: usize,
+    trapezoids: Vec<QTrapezoid>,
+    /// Innermost polygon around every trapezoid.
+    regions: Vec<Option<usize>>,
+    /// Search structure, the first node is the root.
+    nodes: Vec<QMapNode>,
+    /// Absolute double area of every polygon, a nested polygon is smaller than the one around it.
+    areas: Vec<Q64>,
+}
+
+impl QTrapezoidalMap {
+    /// Build the map from the boundaries of `polygons`, `locate` then gives the polygon index.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::spatial::QTrapezoidalMap;
+    ///
+    /// let outer = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(10.0, 0.0), qvec2!(10.0, 10.0), qvec2!(0.0, 10.0)]);
+    /// let inner = QPolygon::new_from_parts(vec![qvec2!(2.0, 2.0), qvec2!(5.0, 3.0), qvec2!(3.0, 6.0)]);
+    /// let map = QTrapezoidalMap::new(&[outer, inner]);
+    /// assert_eq!(map.locate(&QPoint::new(qvec2!(3.0, 3.0))), Some(1));
+    /// assert_eq!(map.locate(&QPoint::new(qvec2!(8.0, 3.0))), Some(0));
+    /// assert_eq!(map.locate(&QPoint::new(qvec2!(10.0, 5.0))), Some(0));
+    /// assert_eq!(map.locate(&QPoint::new(qvec2!(11.0, 3.0))), None);
+    /// ```
+    pub fn new(polygons: &[QPolygon]) -> Self {
+        let mut lines = vec![];
+        let mut areas = vec![];
+        for (index, polygon) in polygons.iter().enumerate() {
+            let ring: Vec<QVec2> = polygon.points_iter().map(|point| point.pos()).collect();
+            let area = signed_double_area(&ring);
+            areas.push(area.abs());
+            lines.extend(polygon.edges_iter().map(|edge| (edge, Some((index, area > Q64::ZERO)))));
+        }
+        Self::build(lines, areas)
+    }
+
+    /// Build the map from loose segments, every trapezoid is then outside any region.
+    pub fn from_segments(segments: &[QLine]) -> Self {
+        Self::build(segments.iter().map(|segment| (*segment, None)).collect(), vec![])
+    }
+
+    fn build(lines: Vec<(QLine, Option<(usize, bool)>)>, areas: Vec<Q64>) -> Self {
+        let input_count = lines.len();
+        let mut segments: Vec<QMapSegment> = vec![];
+        let mut ids: BTreeMap<(Q64, Q64, Q64, Q64), usize> = BTreeMap::new();
+        for (index, (line, polygon)) in lines.into_iter().enumerate() {
+            let (start, end) = (line.start().pos(), line.end().pos());
+            if start == end {
+                continue;
+            }
+            let is_forward = is_left_of(start, end);
+            let (left, right) = if is_forward { (start, end) } else { (end, start) };
+            // Edges shared by two polygons are stored once.
+            let id = *ids.entry((left.x, left.y, right.x, right.y)).or_insert_with(|| {
+                segments.push(QMapSegment { left, right, index, polygons: vec![] });
+                segments.len() - 1
+            });
+            if let Some((polygon, is_ccw)) = polygon {
+                // A counter-clockwise ring has its interior on the left of every edge.
+                segments[id].polygons.push((polygon, is_forward == is_ccw));
+            }
+        }
+
+        let mut map = Self {
+            segments,
+            input_count,
+            trapezoids: vec![QTrapezoid { top: None, bottom: None, leftp: None, rightp: None }],
+            regions: vec![],
+            nodes: vec![QMapNode::Leaf(0)],
+            areas,
+        };
+        let mut order: Vec<usize> = (0..map.segments.len()).collect();
+        shuffle(&mut order, &mut QSplitMix64::new(INSERTION_SEED));
+        let mut leaves = vec![0];
+        for segment in order {
+            map.insert(segment, &mut leaves);
+        }
+        map.remove_replaced(&leaves);
+        map.regions = map.get_regions();
+        map
+    }
+
+    /// Number of segments or polygon edges the map was built from.
+    pub fn len(&self) -> usize {
+        self.input_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input_count == 0
+    }
+
+    /// Number of trapezoids, including the unbounded ones, at most 3n + 1 for n distinct segments.
+    pub fn trapezoid_count(&self) -> usize {
+        self.trapezoids.len()
+    }
+
+    /// Get the index of the innermost polygon containing `point`, the boundary counts as inside.
+    pub fn locate(&self, point: &QPoint) -> Option<usize> {
+        match self.get_position(point.pos()) {
+            (_, Some(segment)) => self.get_innermost(self.segments[segment].polygons.iter().map(|(polygon, _)| *polygon)),
+            (trapezoid, None) => self.regions[trapezoid],
+        }
+    }
+
+    /// Get the index of the nearest segment below `point` or through it, in the order
+    /// the segments or polygon edges were given.
+    pub fn segment_below(&self, point: &QPoint) -> Option<usize> {
+        let (trapezoid, on_segment) = self.get_position(point.pos());
+        on_segment.or(self.trapezoids[trapezoid].bottom).map(|segment| self.segments[segment].index)
+    }
+
+    /// Trapezoid containing `q` and a segment through it, if any. Points on a segment are
+    /// placed above it.
+    fn get_position(&self, q: QVec2) -> (usize, Option<usize>) {
+        let mut on_segment = None;
+        let mut node = 0;
+        loop {
+            node = match self.nodes[node] {
+                QMapNode::Leaf(trapezoid) => return (trapezoid, on_segment),
+                QMapNode::Point(point, segment, left, right) => {
+                    if point == q {
+                        on_segment = on_segment.or(Some(segment));
+                    }
+                    if is_left_of(q, point) { left } else { right }
+                }
+                QMapNode::Segment(segment, above, below) => {
+                    let QMapSegment { left, right, .. } = self.segments[segment];
+                    match orient2d(left, right, q) {
+                        Ordering::Less => below,
+                        Ordering::Equal => {
+                            on_segment = on_segment.or(Some(segment));
+                            above
+                        }
+                        Ordering::Greater => above,
+                    }
+                }
+            };
+        }
+    }
+
+    /// Trapezoid containing the points of `segment` just right of `from`, just below the
+    /// segment itself if it is already in the map.
+    fn locate_along(&self, segment: usize, from: QVec2) -> usize {
+        let mut node = 0;
+        loop {
+            node = match self.nodes[node] {
+                QMapNode::Leaf(trapezoid) => return trapezoid,
+                QMapNode::Point(point, _, left, right) => if is_left_of(from, point) { left } else { right },
+                QMapNode::Segment(other, above, below) => {
+                    if other != segment && self.is_above(segment, other) { above } else { below }
+                }
+            };
+        }
+    }
+
+    /// Is segment `a` above segment `b` where both span the same x, they must not cross.
+    fn is_above(&self, a: usize, b: usize) -> bool {
+        let (p, q) = (self.segments[a].left, self.segments[a].right);
+        let (r, s) = (self.segments[b].left, self.segments[b].right);
+        // Compare at the later left endpoint, or at the right ends when both start together.
+        let side = if is_left_of(r, p) {
+            orient2d(r, s, p).then(orient2d(r, s, q))
+        } else if is_left_of(p, r) {
+            orient2d(p, q, r).then(orient2d(p, q, s)).reverse()
+        } else {
+            orient2d(r, s, q)
+        };
+        side == Ordering::Greater
+    }
+
+    fn insert(&mut self, segment: usize, leaves: &mut Vec<usize>) {
+        let (p, q) = (self.segments[segment].left, self.segments[segment].right);
+        let mut crossed = vec![self.locate_along(segment, p)];
+        while let Some(wall) = self.trapezoids[crossed[crossed.len() - 1]].rightp.filter(|wall| is_left_of(*wall, q)) {
+            crossed.push(self.locate_along(segment, wall));
+        }
+
+        let first = self.trapezoids[crossed[0]];
+        let last = self.trapezoids[crossed[crossed.len() - 1]];
+        let left = (first.leftp != Some(p)).then(|| self.add_trapezoid(QTrapezoid { rightp: Some(p), ..first }, leaves));
+        let right = (last.rightp != Some(q)).then(|| self.add_trapezoid(QTrapezoid { leftp: Some(q), ..last }, leaves));
+
+        // A wall above the segment splits the trapezoids above it, the others are merged,
+        // and the same below.
+        let mut upper = self.add_trapezoid(QTrapezoid { top: first.top, bottom: Some(segment), leftp: Some(p), rightp: Some(q) }, leaves);
+        let mut lower = self.add_trapezoid(QTrapezoid { top: Some(segment), bottom: first.bottom, leftp: Some(p), rightp: Some(q) }, leaves);
+        let mut pieces = vec![(upper, lower)];
+        for pair in crossed.windows(2) {
+            let wall = self.trapezoids[pair[0]].rightp.expect("[QTrapezoidalMap::insert] A crossed trapezoid must end at a wall.");
+            let next = self.trapezoids[pair[1]];
+            if orient2d(p, q, wall) == Ordering::Greater {
+                self.trapezoids[upper].rightp = Some(wall);
+                upper = self.add_trapezoid(QTrapezoid { top: next.top, bottom: Some(segment), leftp: Some(wall), rightp: Some(q) }, leaves);
+            } else {
+                self.trapezoids[lower].rightp = Some(wall);
+                lower = self.add_trapezoid(QTrapezoid { top: Some(segment), bottom: next.bottom, leftp: Some(wall), rightp: Some(q) }, leaves);
+            }
+            pieces.push((upper, lower));
+        }
+
+        // Every crossed leaf becomes the subtree telling its new trapezoids apart.
+        let last_index = crossed.len() - 1;
+        for (i, (&old, &(upper, lower))) in crossed.iter().zip(&pieces).enumerate() {
+            let mut node = QMapNode::Segment(segment, leaves[upper], leaves[lower]);
+            if let Some(right) = right.filter(|_| i == last_index) {
+                node = QMapNode::Point(q, segment, self.add_node(node), leaves[right]);
+            }
+            if let Some(left) = left.filter(|_| i == 0) {
+                node = QMapNode::Point(p, segment, leaves[left], self.add_node(node));
+            }
+            self.nodes[leaves[old]] = node;
+            leaves[old] = usize::MAX;
+        }
+    }
+
+    fn add_node(&mut self, node: QMapNode) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    fn add_trapezoid(&mut self, trapezoid: QTrapezoid, leaves: &mut Vec<usize>) -> usize {
+        self.trapezoids.push(trapezoid);
+        let leaf = self.add_node(QMapNode::Leaf(self.trapezoids.len() - 1));
+        leaves.push(leaf);
+        self.trapezoids.len() - 1
+    }
+
+    /// Drop the trapezoids whose leaves were replaced during the build.
+    fn remove_replaced(&mut self, leaves: &[usize]) {
+        let mut ids = vec![usize::MAX; self.trapezoids.len()];
+        let mut trapezoids = vec![];
+        for (old, trapezoid) in self.trapezoids.iter().enumerate() {
+            if leaves[old] != usize::MAX {
+                ids[old] = trapezoids.len();
+                trapezoids.push(*trapezoid);
+            }
+        }
+        for node in &mut self.nodes {
+            if let QMapNode::Leaf(trapezoid) = node {
+                *trapezoid = ids[*trapezoid];
+            }
+        }
+        self.trapezoids = trapezoids;
+    }
+
+    /// Innermost polygon around every trapezoid.
+    ///
+    /// The polygons around a trapezoid are those around the trapezoid below its bottom
+    /// segment, less the ones left and plus the ones entered through it. They are kept as
+    /// persistent stacks, so trapezoids only share the polygons they have in common.
+    fn get_regions(&self) -> Vec<Option<usize>> {
+        // Stack entries as a polygon and the entry below it.
+        let mut entries: Vec<(usize, Option<usize>)> = vec![];
+        let mut stacks: Vec<Option<Option<usize>>> = vec![None; self.trapezoids.len()];
+        for start in 0..self.trapezoids.len() {
+            let mut pending = vec![start];
+            while let Some(&trapezoid) = pending.last() {
+                if stacks[trapezoid].is_some() {
+                    pending.pop();
+                    continue;
+                }
+                let QTrapezoid { bottom, leftp, .. } = self.trapezoids[trapezoid];
+                let Some(bottom) = bottom else {
+                    stacks[trapezoid] = Some(None);
+                    continue;
+                };
+                let segment = &self.segments[bottom];
+                let below = self.locate_along(bottom, leftp.unwrap_or(segment.left));
+                let Some(mut stack) = stacks[below] else {
+                    pending.push(below);
+                    continue;
+                };
+                let is_left = |polygon: usize| segment.polygons.iter().any(|&(other, is_inner_above)| other == polygon && !is_inner_above);
+                while let Some(entry) = stack.filter(|&entry| is_left(entries[entry].0)) {
+                    stack = entries[entry].1;
+                }
+                let mut entered: Vec<usize> = segment.polygons.iter().filter(|(_, is_inner_above)| *is_inner_above).map(|(polygon, _)| *polygon).collect();
+                entered.sort_by(|a, b| self.areas[*b].cmp(&self.areas[*a]));
+                for polygon in entered {
+                    entries.push((polygon, stack));
+                    stack = Some(entries.len() - 1);
+                }
+                stacks[trapezoid] = Some(stack);
+            }
+        }
+        stacks.into_iter().map(|stack| stack.flatten().map(|entry| entries[entry].0)).collect()
+    }
+
+    /// The smallest of `polygons`, a nested polygon is smaller than the one around it.
+    fn get_innermost(&self, polygons: impl Iterator<Item = usize>) -> Option<usize> {
+        polygons.min_by_key(|polygon| self.areas[*polygon])
+    }
+}
+
+/// Order points by x, then by y for points on the same vertical line.
+fn is_left_of(a: QVec2, b: QVec2) -> bool {
+    (a.x, a.y) < (b.x, b.y)
+}
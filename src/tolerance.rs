@@ -0,0 +1,88 @@
+//! Tolerance shared by the geometric predicates.
+//!
+//! Predicates without a tolerance argument are exact. Their `*_with_tolerance` variants
+//! take a `QTolerance` so one slack can be applied consistently to point on segment,
+//! collinearity, containment and hull checks.
+
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::algorithm::orient2d;
+use crate::shape::QPoint;
+use crate::shape::line::get_closest_point_on_chain;
+
+/// Largest distance at which two features are still considered touching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QTolerance {
+    distance: Q64,
+}
+
+impl QTolerance {
+    /// No slack, predicates only accept exact results.
+    pub const EXACT: Self = Self { distance: Q64::ZERO };
+    /// The smallest step of `Q64`.
+    pub const EPS: Self = Self { distance: Q64::EPS };
+
+    pub fn new(distance: Q64) -> Self {
+        assert!(distance >= Q64::ZERO, "[QTolerance::new] distance({distance}) should not be negative.");
+        Self { distance }
+    }
+
+    pub fn distance(&self) -> Q64 {
+        self.distance
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.distance == Q64::ZERO
+    }
+
+    /// Is `value` within the tolerance of zero.
+    pub fn is_zero(&self, value: Q64) -> bool {
+        value.abs() <= self.distance
+    }
+
+    /// Are `a` and `b` within the tolerance of each other.
+    pub fn is_equal(&self, a: QVec2, b: QVec2) -> bool {
+        a.distance(b) <= self.distance
+    }
+
+    /// Is `c` within the tolerance of the infinite line through `a` and `b`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let c = qvec2!(1.0, 0.01);
+    /// assert!(!QTolerance::EXACT.is_collinear(QVec2::ZERO, QVec2::X, c));
+    /// assert!(QTolerance::new(q64!(0.1)).is_collinear(QVec2::ZERO, QVec2::X, c));
+    /// ```
+    pub fn is_collinear(&self, a: QVec2, b: QVec2, c: QVec2) -> bool {
+        if self.is_exact() {
+            return orient2d(a, b, c) == core::cmp::Ordering::Equal;
+        }
+        if a == b {
+            return self.is_equal(a, c);
+        }
+        let cross = b.saturating_sub(a).cross(c.saturating_sub(a)).abs();
+        cross <= self.distance.saturating_mul(a.distance(b))
+    }
+
+    /// Is `point` within the tolerance of the segment from `start` to `end`.
+    pub fn is_point_on_segment(&self, start: QVec2, end: QVec2, point: QVec2) -> bool {
+        if self.is_exact() {
+            let (min, max) = (start.min(end), start.max(end));
+            let is_in_range = min.x <= point.x && point.x <= max.x && min.y <= point.y && point.y <= max.y;
+            return is_in_range && orient2d(start, end, point) == core::cmp::Ordering::Equal;
+        }
+        let point = QPoint::new(point);
+        get_closest_point_on_chain(&[QPoint::new(start), QPoint::new(end)], false, &point).distance(&point) <= self.distance
+    }
+}
+
+impl Default for QTolerance {
+    fn default() -> Self {
+        Self::EXACT
+    }
+}
@@ -0,0 +1,164 @@
+use core::ops::Mul;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::shape::{ QPoint, QLine };
+
+/// 2D affine transform stored as a 2x3 matrix.
+///
+/// A point `p` is mapped to `x_axis * p.x + y_axis * p.y + translation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QTransform {
+    x_axis: QVec2,
+    y_axis: QVec2,
+    translation: QVec2,
+}
+
+impl QTransform {
+    pub const IDENTITY: Self = Self::new(QVec2::X, QVec2::Y, QVec2::ZERO);
+
+    pub const fn new(x_axis: QVec2, y_axis: QVec2, translation: QVec2) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            translation,
+        }
+    }
+
+    pub fn from_translation(translation: QVec2) -> Self {
+        Self::new(QVec2::X, QVec2::Y, translation)
+    }
+
+    /// Counter-clockwise rotation around the origin, `angle` is in radians.
+    pub fn from_rotation(angle: Q64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(QVec2::new(cos, sin), QVec2::new(-sin, cos), QVec2::ZERO)
+    }
+
+    /// Counter-clockwise rotation around `pivot`, `angle` is in radians.
+    pub fn from_rotation_around(pivot: QVec2, angle: Q64) -> Self {
+        Self::from_translation(pivot) * Self::from_rotation(angle) * Self::from_translation(-pivot)
+    }
+
+    /// Reflection about `axis`, the axis is treated as an infinite line.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let axis = QLine::new_from_parts(QVec2::ZERO, QVec2::ONE);
+    /// let transform = QTransform::from_reflection(&axis);
+    /// assert_eq!(transform.transform_point(&QPoint::new(qvec2!(2.0, 0.0))).pos(), qvec2!(0.0, 2.0));
+    /// ```
+    pub fn from_reflection(axis: &QLine) -> Self {
+        let dir = axis.vector();
+        let length_squared = dir.length_squared();
+        let a = dir.x.saturating_mul(dir.x).saturating_sub(dir.y.saturating_mul(dir.y)).saturating_div(length_squared);
+        let b = q64!(2).saturating_mul(dir.x).saturating_mul(dir.y).saturating_div(length_squared);
+        let pivot = axis.start().pos();
+        Self::from_translation(pivot)
+            * Self::new(QVec2::new(a, b), QVec2::new(b, -a), QVec2::ZERO)
+            * Self::from_translation(-pivot)
+    }
+
+    pub fn from_scale(scale: QVec2) -> Self {
+        Self::new(QVec2::new(scale.x, Q64::ZERO), QVec2::new(Q64::ZERO, scale.y), QVec2::ZERO)
+    }
+
+    /// Scale first, then rotate, then translate.
+    pub fn from_scale_rotation_translation(scale: QVec2, angle: Q64, translation: QVec2) -> Self {
+        Self::from_translation(translation) * Self::from_rotation(angle) * Self::from_scale(scale)
+    }
+
+    pub fn x_axis(&self) -> QVec2 {
+        self.x_axis
+    }
+
+    pub fn y_axis(&self) -> QVec2 {
+        self.y_axis
+    }
+
+    pub fn translation(&self) -> QVec2 {
+        self.translation
+    }
+
+    pub fn set_translation(&mut self, translation: QVec2) {
+        self.translation = translation;
+    }
+
+    /// Transform a vector, translation is ignored.
+    pub fn transform_vector(&self, vector: QVec2) -> QVec2 {
+        self.x_axis.saturating_mul_num(vector.x).saturating_add(self.y_axis.saturating_mul_num(vector.y))
+    }
+
+    /// Transform a point.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let transform = QTransform::from_translation(qvec2!(1.0, 2.0)) * QTransform::from_scale(qvec2!(2.0, 3.0));
+    /// let point = transform.transform_point(&QPoint::new(QVec2::ONE));
+    /// assert_eq!(point.pos(), qvec2!(3.0, 5.0));
+    /// ```
+    pub fn transform_point(&self, point: &QPoint) -> QPoint {
+        QPoint::new(self.transform_vector(point.pos()).saturating_add(self.translation))
+    }
+
+    /// Get the transform that applies `self` first and then `other`.
+    pub fn then(&self, other: &QTransform) -> QTransform {
+        *other * *self
+    }
+
+    /// Determinant of the linear part.
+    pub fn determinant(&self) -> Q64 {
+        self.x_axis.cross(self.y_axis)
+    }
+
+    /// Get the inverse transform, `None` if the transform is degenerate.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let transform = QTransform::from_translation(qvec2!(1.0, 2.0)) * QTransform::from_scale(qvec2!(2.0, 4.0));
+    /// let inverse = transform.inverse().unwrap();
+    /// let point = QPoint::new(qvec2!(3.0, -1.0));
+    /// assert_eq!(inverse.transform_point(&transform.transform_point(&point)), point);
+    /// assert!(QTransform::from_scale(QVec2::ZERO).inverse().is_none());
+    /// ```
+    pub fn inverse(&self) -> Option<QTransform> {
+        let det = self.determinant();
+        if det == Q64::ZERO {
+            return None;
+        }
+
+        let x_axis = QVec2::new(self.y_axis.y.saturating_div(det), -self.x_axis.y.saturating_div(det));
+        let y_axis = QVec2::new(-self.y_axis.x.saturating_div(det), self.x_axis.x.saturating_div(det));
+        let linear = QTransform::new(x_axis, y_axis, QVec2::ZERO);
+        let translation = -linear.transform_vector(self.translation);
+        Some(QTransform::new(x_axis, y_axis, translation))
+    }
+}
+
+impl Default for QTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for QTransform {
+    type Output = QTransform;
+
+    /// Compose two transforms, `rhs` is applied first.
+    fn mul(self, rhs: QTransform) -> QTransform {
+        QTransform::new(
+            self.transform_vector(rhs.x_axis),
+            self.transform_vector(rhs.y_axis),
+            self.transform_vector(rhs.translation).saturating_add(self.translation),
+        )
+    }
+}
@@ -0,0 +1,360 @@
+use alloc::{ vec, vec::Vec, string::{String, ToString} };
+use core::fmt;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::shape::{ QPoint, QLine, QBbox, QCircle, QPolygon, QPolyline, QShape, QCompound, QShapeCommon };
+use crate::shape::point::try_q64_from_f64;
+
+const WKB_POINT: u32 = 1;
+const WKB_LINE_STRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTI_POLYGON: u32 = 6;
+
+/// Byte order of a WKB buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QByteOrder {
+    BigEndian,
+    LittleEndian,
+}
+
+/// Error produced while decoding WKB.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QWkbError {
+    /// The buffer ended before the geometry was complete.
+    UnexpectedEnd,
+    /// The byte order flag is neither 0 nor 1.
+    InvalidByteOrder(u8),
+    /// A geometry type code this crate doesn't read.
+    UnsupportedType(u32),
+    /// A valid WKB geometry that can't be represented by the requested type.
+    Unsupported(String),
+    /// A coordinate that is not finite or doesn't fit in `Q64`.
+    OutOfRange(f64),
+    /// Bytes left over after the geometry.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for QWkbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QWkbError::UnexpectedEnd => write!(f, "unexpected end of WKB"),
+            QWkbError::InvalidByteOrder(flag) => write!(f, "invalid WKB byte order flag {flag}"),
+            QWkbError::UnsupportedType(code) => write!(f, "unsupported WKB geometry type {code}"),
+            QWkbError::Unsupported(geometry) => write!(f, "unsupported WKB geometry: {geometry}"),
+            QWkbError::OutOfRange(value) => write!(f, "WKB coordinate {value} doesn't fit in Q64"),
+            QWkbError::TrailingBytes(count) => write!(f, "{count} trailing bytes after WKB geometry"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QWkbError {}
+
+/// Shapes that can be written as Well-Known Binary.
+///
+/// Coordinates are IEEE doubles, so `Q64` values that need more than 53 bits of precision
+/// are rounded to the nearest double.
+pub trait QToWkb {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8>;
+}
+
+/// Shapes that can be read from Well-Known Binary, in either byte order.
+///
+/// Doubles are converted to `Q64` exactly when representable, non-finite or out of range
+/// values are rejected.
+pub trait QFromWkb: Sized {
+    fn from_wkb(bytes: &[u8]) -> Result<Self, QWkbError>;
+}
+
+struct QWkbWriter {
+    byte_order: QByteOrder,
+    bytes: Vec<u8>,
+}
+
+impl QWkbWriter {
+    fn new(byte_order: QByteOrder) -> Self {
+        Self { byte_order, bytes: vec![] }
+    }
+
+    fn header(&mut self, geometry_type: u32) {
+        self.bytes.push(match self.byte_order {
+            QByteOrder::BigEndian => 0,
+            QByteOrder::LittleEndian => 1,
+        });
+        self.u32(geometry_type);
+    }
+
+    fn u32(&mut self, value: u32) {
+        match self.byte_order {
+            QByteOrder::BigEndian => self.bytes.extend_from_slice(&value.to_be_bytes()),
+            QByteOrder::LittleEndian => self.bytes.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn coordinate(&mut self, pos: QVec2) {
+        for value in [pos.x.to_num::<f64>(), pos.y.to_num::<f64>()] {
+            match self.byte_order {
+                QByteOrder::BigEndian => self.bytes.extend_from_slice(&value.to_be_bytes()),
+                QByteOrder::LittleEndian => self.bytes.extend_from_slice(&value.to_le_bytes()),
+            }
+        }
+    }
+
+    fn coordinates(&mut self, points: &[QPoint], is_closed: bool) {
+        let count = if is_closed && !points.is_empty() { points.len() + 1 } else { points.len() };
+        self.u32(count as u32);
+        for i in 0..count {
+            self.coordinate(points[i % points.len()].pos());
+        }
+    }
+
+    fn polygon(&mut self, points: &[QPoint]) {
+        self.header(WKB_POLYGON);
+        self.u32(1);
+        self.coordinates(points, true);
+    }
+}
+
+impl QToWkb for QPoint {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8> {
+        let mut writer = QWkbWriter::new(byte_order);
+        writer.header(WKB_POINT);
+        writer.coordinate(self.pos());
+        writer.bytes
+    }
+}
+
+impl QToWkb for QLine {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8> {
+        QPolyline::new(vec![self.start(), self.end()]).to_wkb(byte_order)
+    }
+}
+
+impl QToWkb for QPolyline {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8> {
+        let mut writer = QWkbWriter::new(byte_order);
+        writer.header(WKB_LINE_STRING);
+        writer.coordinates(self.points(), false);
+        writer.bytes
+    }
+}
+
+impl QToWkb for QPolygon {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8> {
+        let mut writer = QWkbWriter::new(byte_order);
+        writer.polygon(self.points());
+        writer.bytes
+    }
+}
+
+impl QToWkb for QBbox {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8> {
+        self.get_polygon().to_wkb(byte_order)
+    }
+}
+
+/// Circles are written as their vertex polygon.
+impl QToWkb for QCircle {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8> {
+        self.get_polygon().to_wkb(byte_order)
+    }
+}
+
+impl QToWkb for QShape {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8> {
+        match self {
+            QShape::QPoint(shape) => shape.to_wkb(byte_order),
+            QShape::QLine(shape) => shape.to_wkb(byte_order),
+            QShape::QBbox(shape) => shape.to_wkb(byte_order),
+            QShape::QCircle(shape) => shape.to_wkb(byte_order),
+            QShape::QPolygon(shape) => shape.to_wkb(byte_order),
+            QShape::QPolyline(shape) => shape.to_wkb(byte_order),
+        }
+    }
+}
+
+/// Children are written as the polygons of a multipolygon in world space.
+impl QToWkb for QCompound {
+    fn to_wkb(&self, byte_order: QByteOrder) -> Vec<u8> {
+        let world_children = self.world_children();
+        let mut writer = QWkbWriter::new(byte_order);
+        writer.header(WKB_MULTI_POLYGON);
+        writer.u32(world_children.len() as u32);
+        for child in world_children.iter() {
+            writer.polygon(&child.points());
+        }
+        writer.bytes
+    }
+}
+
+/// Decoded WKB geometry before it is turned into a shape.
+enum QWkbGeometry {
+    Point(QVec2),
+    LineString(Vec<QVec2>),
+    Polygon(Vec<Vec<QVec2>>),
+    MultiPolygon(Vec<Vec<Vec<QVec2>>>),
+}
+
+struct QWkbReader<'a> {
+    bytes: &'a [u8],
+    cursor: usize,
+}
+
+impl QWkbReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], QWkbError> {
+        let end = self.cursor + N;
+        let slice = self.bytes.get(self.cursor..end).ok_or(QWkbError::UnexpectedEnd)?;
+        self.cursor = end;
+        Ok(slice.try_into().expect("[QWkbReader::take] Slice length is checked above."))
+    }
+
+    fn byte_order(&mut self) -> Result<QByteOrder, QWkbError> {
+        match self.take::<1>()?[0] {
+            0 => Ok(QByteOrder::BigEndian),
+            1 => Ok(QByteOrder::LittleEndian),
+            flag => Err(QWkbError::InvalidByteOrder(flag)),
+        }
+    }
+
+    fn u32(&mut self, byte_order: QByteOrder) -> Result<u32, QWkbError> {
+        let bytes = self.take::<4>()?;
+        Ok(match byte_order {
+            QByteOrder::BigEndian => u32::from_be_bytes(bytes),
+            QByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+        })
+    }
+
+    fn number(&mut self, byte_order: QByteOrder) -> Result<Q64, QWkbError> {
+        let bytes = self.take::<8>()?;
+        let value = match byte_order {
+            QByteOrder::BigEndian => f64::from_be_bytes(bytes),
+            QByteOrder::LittleEndian => f64::from_le_bytes(bytes),
+        };
+        try_q64_from_f64(value).ok_or(QWkbError::OutOfRange(value))
+    }
+
+    fn coordinate(&mut self, byte_order: QByteOrder) -> Result<QVec2, QWkbError> {
+        let x = self.number(byte_order)?;
+        let y = self.number(byte_order)?;
+        Ok(QVec2::new(x, y))
+    }
+
+    fn coordinates(&mut self, byte_order: QByteOrder) -> Result<Vec<QVec2>, QWkbError> {
+        let count = self.u32(byte_order)? as usize;
+        // Every coordinate takes 16 bytes, don't trust the count for the allocation.
+        let mut rst = Vec::with_capacity(count.min(self.bytes.len() / 16));
+        for _ in 0..count {
+            rst.push(self.coordinate(byte_order)?);
+        }
+        Ok(rst)
+    }
+
+    fn ring(&mut self, byte_order: QByteOrder) -> Result<Vec<QVec2>, QWkbError> {
+        let mut ring = self.coordinates(byte_order)?;
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        Ok(ring)
+    }
+
+    fn rings(&mut self, byte_order: QByteOrder) -> Result<Vec<Vec<QVec2>>, QWkbError> {
+        let count = self.u32(byte_order)?;
+        (0..count).map(|_| self.ring(byte_order)).collect()
+    }
+
+    fn geometry(&mut self) -> Result<QWkbGeometry, QWkbError> {
+        let byte_order = self.byte_order()?;
+        match self.u32(byte_order)? {
+            WKB_POINT => Ok(QWkbGeometry::Point(self.coordinate(byte_order)?)),
+            WKB_LINE_STRING => Ok(QWkbGeometry::LineString(self.coordinates(byte_order)?)),
+            WKB_POLYGON => Ok(QWkbGeometry::Polygon(self.rings(byte_order)?)),
+            WKB_MULTI_POLYGON => {
+                let count = self.u32(byte_order)?;
+                let mut polygons = vec![];
+                for _ in 0..count {
+                    // Members are read inline, nested collections would otherwise recurse.
+                    let member_order = self.byte_order()?;
+                    if self.u32(member_order)? != WKB_POLYGON {
+                        return Err(QWkbError::Unsupported("MULTIPOLYGON member is not a polygon".to_string()));
+                    }
+                    polygons.push(self.rings(member_order)?);
+                }
+                Ok(QWkbGeometry::MultiPolygon(polygons))
+            }
+            code => Err(QWkbError::UnsupportedType(code)),
+        }
+    }
+}
+
+fn decode_wkb(bytes: &[u8]) -> Result<QWkbGeometry, QWkbError> {
+    let mut reader = QWkbReader { bytes, cursor: 0 };
+    let geometry = reader.geometry()?;
+    if reader.cursor != bytes.len() {
+        return Err(QWkbError::TrailingBytes(bytes.len() - reader.cursor));
+    }
+    Ok(geometry)
+}
+
+/// Only the exterior ring is kept, holes are rejected.
+fn polygon_from_rings(mut rings: Vec<Vec<QVec2>>) -> Result<QPolygon, QWkbError> {
+    if rings.len() != 1 {
+        return Err(QWkbError::Unsupported("POLYGON must have exactly one ring".to_string()));
+    }
+    Ok(QPolygon::new_from_parts(rings.remove(0)))
+}
+
+impl QFromWkb for QPoint {
+    fn from_wkb(bytes: &[u8]) -> Result<Self, QWkbError> {
+        match decode_wkb(bytes)? {
+            QWkbGeometry::Point(pos) => Ok(QPoint::new(pos)),
+            _ => Err(QWkbError::Unsupported("expected POINT".to_string())),
+        }
+    }
+}
+
+impl QFromWkb for QPolyline {
+    fn from_wkb(bytes: &[u8]) -> Result<Self, QWkbError> {
+        match decode_wkb(bytes)? {
+            QWkbGeometry::LineString(points) => Ok(QPolyline::new_from_parts(points)),
+            _ => Err(QWkbError::Unsupported("expected LINESTRING".to_string())),
+        }
+    }
+}
+
+impl QFromWkb for QPolygon {
+    fn from_wkb(bytes: &[u8]) -> Result<Self, QWkbError> {
+        match decode_wkb(bytes)? {
+            QWkbGeometry::Polygon(rings) => polygon_from_rings(rings),
+            _ => Err(QWkbError::Unsupported("expected POLYGON".to_string())),
+        }
+    }
+}
+
+/// Each polygon of a multipolygon becomes a child at the compound origin.
+impl QFromWkb for QCompound {
+    fn from_wkb(bytes: &[u8]) -> Result<Self, QWkbError> {
+        match decode_wkb(bytes)? {
+            QWkbGeometry::MultiPolygon(polygons) => {
+                let mut compound = QCompound::new(QVec2::ZERO, vec![]);
+                for rings in polygons {
+                    compound.add_child(QVec2::ZERO, polygon_from_rings(rings)?);
+                }
+                Ok(compound)
+            }
+            _ => Err(QWkbError::Unsupported("expected MULTIPOLYGON".to_string())),
+        }
+    }
+}
+
+/// A two point linestring becomes a `QLine`, longer ones a `QPolyline`.
+impl QFromWkb for QShape {
+    fn from_wkb(bytes: &[u8]) -> Result<Self, QWkbError> {
+        match decode_wkb(bytes)? {
+            QWkbGeometry::Point(pos) => Ok(QShape::QPoint(QPoint::new(pos))),
+            QWkbGeometry::LineString(points) if points.len() == 2 => Ok(QShape::QLine(QLine::new_from_parts(points[0], points[1]))),
+            QWkbGeometry::LineString(points) => Ok(QShape::QPolyline(QPolyline::new_from_parts(points))),
+            QWkbGeometry::Polygon(rings) => Ok(QShape::QPolygon(polygon_from_rings(rings)?)),
+            QWkbGeometry::MultiPolygon(_) => Err(QWkbError::Unsupported("MULTIPOLYGON, decode it as a QCompound".to_string())),
+        }
+    }
+}
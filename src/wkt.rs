@@ -0,0 +1,350 @@
+use alloc::{ vec, vec::Vec, string::{String, ToString}, format };
+use core::fmt;
+use core::str::FromStr;
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use crate::shape::{ QPoint, QLine, QBbox, QCircle, QPolygon, QPolyline, QShape, QCompound, QShapeCommon };
+use crate::shape::point::try_q64_from_f64;
+
+/// Error produced while parsing WKT text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QWktError {
+    /// The text ended before the geometry was complete.
+    UnexpectedEnd,
+    /// A token that doesn't fit the grammar at this position.
+    UnexpectedToken(String),
+    /// A coordinate that isn't a number.
+    InvalidNumber(String),
+    /// A coordinate that is not finite or doesn't fit in `Q64`.
+    OutOfRange(String),
+    /// A valid WKT geometry that can't be represented by the requested type.
+    Unsupported(String),
+}
+
+impl fmt::Display for QWktError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QWktError::UnexpectedEnd => write!(f, "unexpected end of WKT"),
+            QWktError::UnexpectedToken(token) => write!(f, "unexpected token `{token}` in WKT"),
+            QWktError::InvalidNumber(token) => write!(f, "invalid number `{token}` in WKT"),
+            QWktError::OutOfRange(token) => write!(f, "WKT coordinate `{token}` doesn't fit in Q64"),
+            QWktError::Unsupported(geometry) => write!(f, "unsupported WKT geometry: {geometry}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QWktError {}
+
+/// Shapes that can be written as Well-Known Text.
+///
+/// Coordinates are written as decimals through `f64`, so values that need more than
+/// 53 bits of precision are rounded.
+pub trait QToWkt {
+    fn to_wkt(&self) -> String;
+}
+
+fn write_coordinate(out: &mut String, pos: QVec2) {
+    out.push_str(&format!("{} {}", pos.x.to_num::<f64>(), pos.y.to_num::<f64>()));
+}
+
+fn write_coordinates(out: &mut String, points: &[QPoint], is_closed: bool) {
+    out.push('(');
+    let count = if is_closed && !points.is_empty() { points.len() + 1 } else { points.len() };
+    for i in 0..count {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write_coordinate(out, points[i % points.len()].pos());
+    }
+    out.push(')');
+}
+
+fn write_polygon_body(out: &mut String, points: &[QPoint]) {
+    out.push('(');
+    write_coordinates(out, points, true);
+    out.push(')');
+}
+
+impl QToWkt for QPoint {
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::wkt::QToWkt;
+    ///
+    /// assert_eq!(QPoint::new(qvec2!(1.5, -2.0)).to_wkt(), "POINT (1.5 -2)");
+    /// ```
+    fn to_wkt(&self) -> String {
+        let mut out = String::from("POINT (");
+        write_coordinate(&mut out, self.pos());
+        out.push(')');
+        out
+    }
+}
+
+impl QToWkt for QLine {
+    fn to_wkt(&self) -> String {
+        let mut out = String::from("LINESTRING ");
+        write_coordinates(&mut out, &[self.start(), self.end()], false);
+        out
+    }
+}
+
+impl QToWkt for QPolyline {
+    fn to_wkt(&self) -> String {
+        let mut out = String::from("LINESTRING ");
+        write_coordinates(&mut out, self.points(), false);
+        out
+    }
+}
+
+impl QToWkt for QPolygon {
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::wkt::QToWkt;
+    ///
+    /// let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(0.0, 1.0)]);
+    /// assert_eq!(polygon.to_wkt(), "POLYGON ((0 0, 1 0, 0 1, 0 0))");
+    /// ```
+    fn to_wkt(&self) -> String {
+        let mut out = String::from("POLYGON ");
+        write_polygon_body(&mut out, self.points());
+        out
+    }
+}
+
+impl QToWkt for QBbox {
+    fn to_wkt(&self) -> String {
+        self.get_polygon().to_wkt()
+    }
+}
+
+/// Circles are written as their vertex polygon.
+impl QToWkt for QCircle {
+    fn to_wkt(&self) -> String {
+        self.get_polygon().to_wkt()
+    }
+}
+
+impl QToWkt for QShape {
+    fn to_wkt(&self) -> String {
+        match self {
+            QShape::QPoint(shape) => shape.to_wkt(),
+            QShape::QLine(shape) => shape.to_wkt(),
+            QShape::QBbox(shape) => shape.to_wkt(),
+            QShape::QCircle(shape) => shape.to_wkt(),
+            QShape::QPolygon(shape) => shape.to_wkt(),
+            QShape::QPolyline(shape) => shape.to_wkt(),
+        }
+    }
+}
+
+/// Children are written as the polygons of a `MULTIPOLYGON` in world space.
+impl QToWkt for QCompound {
+    fn to_wkt(&self) -> String {
+        let mut out = String::from("MULTIPOLYGON (");
+        for (i, child) in self.world_children().iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write_polygon_body(&mut out, &child.points());
+        }
+        out.push(')');
+        out
+    }
+}
+
+/// Parsed WKT geometry before it is turned into a shape.
+enum QWktGeometry {
+    Point(QVec2),
+    LineString(Vec<QVec2>),
+    Polygon(Vec<Vec<QVec2>>),
+    MultiPolygon(Vec<Vec<Vec<QVec2>>>),
+}
+
+struct QWktParser<'a> {
+    tokens: core::iter::Peekable<alloc::vec::IntoIter<&'a str>>,
+}
+
+impl<'a> QWktParser<'a> {
+    fn new(text: &'a str) -> Self {
+        let mut tokens = vec![];
+        let mut start = None;
+        for (i, c) in text.char_indices() {
+            let is_separator = c.is_whitespace() || c == '(' || c == ')' || c == ',';
+            match (is_separator, start) {
+                (true, Some(s)) => {
+                    tokens.push(&text[s..i]);
+                    start = None;
+                }
+                (false, None) => start = Some(i),
+                _ => {}
+            }
+            if c == '(' || c == ')' || c == ',' {
+                tokens.push(&text[i..i + 1]);
+            }
+        }
+        if let Some(s) = start {
+            tokens.push(&text[s..]);
+        }
+        Self { tokens: tokens.into_iter().peekable() }
+    }
+
+    fn next(&mut self) -> Result<&'a str, QWktError> {
+        self.tokens.next().ok_or(QWktError::UnexpectedEnd)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), QWktError> {
+        let token = self.next()?;
+        if token != expected {
+            return Err(QWktError::UnexpectedToken(token.to_string()));
+        }
+        Ok(())
+    }
+
+    fn number(&mut self) -> Result<Q64, QWktError> {
+        let token = self.next()?;
+        let value: f64 = token.parse().map_err(|_| QWktError::InvalidNumber(token.to_string()))?;
+        try_q64_from_f64(value).ok_or_else(|| QWktError::OutOfRange(token.to_string()))
+    }
+
+    fn coordinate(&mut self) -> Result<QVec2, QWktError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok(QVec2::new(x, y))
+    }
+
+    /// Parse `( item, item, ... )`.
+    fn list<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T, QWktError>) -> Result<Vec<T>, QWktError> {
+        self.expect("(")?;
+        let mut rst = vec![item(self)?];
+        loop {
+            match self.next()? {
+                "," => rst.push(item(self)?),
+                ")" => return Ok(rst),
+                token => return Err(QWktError::UnexpectedToken(token.to_string())),
+            }
+        }
+    }
+
+    fn ring(&mut self) -> Result<Vec<QVec2>, QWktError> {
+        let mut ring = self.list(Self::coordinate)?;
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        Ok(ring)
+    }
+
+    fn geometry(&mut self) -> Result<QWktGeometry, QWktError> {
+        let tag = self.next()?.to_ascii_uppercase();
+        let geometry = match tag.as_str() {
+            "POINT" => {
+                self.expect("(")?;
+                let point = self.coordinate()?;
+                self.expect(")")?;
+                QWktGeometry::Point(point)
+            }
+            "LINESTRING" => QWktGeometry::LineString(self.list(Self::coordinate)?),
+            "POLYGON" => QWktGeometry::Polygon(self.list(Self::ring)?),
+            "MULTIPOLYGON" => QWktGeometry::MultiPolygon(self.list(|parser| parser.list(Self::ring))?),
+            _ => return Err(QWktError::Unsupported(tag)),
+        };
+        match self.tokens.next() {
+            Some(token) => Err(QWktError::UnexpectedToken(token.to_string())),
+            None => Ok(geometry),
+        }
+    }
+}
+
+fn parse_wkt(text: &str) -> Result<QWktGeometry, QWktError> {
+    QWktParser::new(text).geometry()
+}
+
+/// Only the exterior ring is kept, holes are rejected.
+fn polygon_from_rings(mut rings: Vec<Vec<QVec2>>) -> Result<QPolygon, QWktError> {
+    if rings.len() > 1 {
+        return Err(QWktError::Unsupported("POLYGON with holes".to_string()));
+    }
+    Ok(QPolygon::new_from_parts(rings.remove(0)))
+}
+
+impl FromStr for QPoint {
+    type Err = QWktError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match parse_wkt(text)? {
+            QWktGeometry::Point(pos) => Ok(QPoint::new(pos)),
+            _ => Err(QWktError::Unsupported("expected POINT".to_string())),
+        }
+    }
+}
+
+impl FromStr for QPolyline {
+    type Err = QWktError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match parse_wkt(text)? {
+            QWktGeometry::LineString(points) => Ok(QPolyline::new_from_parts(points)),
+            _ => Err(QWktError::Unsupported("expected LINESTRING".to_string())),
+        }
+    }
+}
+
+impl FromStr for QPolygon {
+    type Err = QWktError;
+
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    ///
+    /// let polygon: QPolygon = "POLYGON ((0 0, 2 0, 2 2, 0 0))".parse().unwrap();
+    /// assert_eq!(polygon.points().len(), 3);
+    /// assert_eq!(polygon.points()[2].pos(), qvec2!(2.0, 2.0));
+    /// ```
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match parse_wkt(text)? {
+            QWktGeometry::Polygon(rings) => polygon_from_rings(rings),
+            _ => Err(QWktError::Unsupported("expected POLYGON".to_string())),
+        }
+    }
+}
+
+/// Each polygon of a `MULTIPOLYGON` becomes a child at the compound origin.
+impl FromStr for QCompound {
+    type Err = QWktError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match parse_wkt(text)? {
+            QWktGeometry::MultiPolygon(polygons) => {
+                let mut compound = QCompound::new(QVec2::ZERO, vec![]);
+                for rings in polygons {
+                    compound.add_child(QVec2::ZERO, polygon_from_rings(rings)?);
+                }
+                Ok(compound)
+            }
+            _ => Err(QWktError::Unsupported("expected MULTIPOLYGON".to_string())),
+        }
+    }
+}
+
+/// A two point `LINESTRING` becomes a `QLine`, longer ones a `QPolyline`.
+impl FromStr for QShape {
+    type Err = QWktError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match parse_wkt(text)? {
+            QWktGeometry::Point(pos) => Ok(QShape::QPoint(QPoint::new(pos))),
+            QWktGeometry::LineString(points) if points.len() == 2 => Ok(QShape::QLine(QLine::new_from_parts(points[0], points[1]))),
+            QWktGeometry::LineString(points) => Ok(QShape::QPolyline(QPolyline::new_from_parts(points))),
+            QWktGeometry::Polygon(rings) => Ok(QShape::QPolygon(polygon_from_rings(rings)?)),
+            QWktGeometry::MultiPolygon(_) => Err(QWktError::Unsupported("MULTIPOLYGON, parse it as a QCompound".to_string())),
+        }
+    }
+}
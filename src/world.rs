@@ -0,0 +1,383 @@
+use alloc::{ vec, vec::Vec };
+use core::fmt;
+use alloc::sync::Arc;
+use qmath::prelude::*;
+use crate::shape::{ QPoint, QBbox, QCircle, QRay, QShape, QShapeCommon, QTransformedShape };
+use crate::spatial::QDynamicTree;
+use crate::transform::QTransform;
+
+/// Generational handle of a shape in a `QCollisionWorld`.
+///
+/// A handle becomes stale when its shape is removed, even if the slot is reused later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QShapeHandle {
+    index: u32,
+    generation: u32,
+}
+
+impl QShapeHandle {
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// Layer and mask bitsets deciding which shapes may interact.
+///
+/// Two filters interact when each one's layers overlap the other's mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QCollisionFilter {
+    layers: u32,
+    mask: u32,
+}
+
+impl QCollisionFilter {
+    /// Belongs to every layer and interacts with every layer.
+    pub const ALL: Self = Self::new(u32::MAX, u32::MAX);
+    /// Interacts with nothing.
+    pub const NONE: Self = Self::new(0, 0);
+
+    pub const fn new(layers: u32, mask: u32) -> Self {
+        Self {
+            layers,
+            mask,
+        }
+    }
+
+    pub fn layers(&self) -> u32 {
+        self.layers
+    }
+
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    pub fn set_layers(&mut self, layers: u32) {
+        self.layers = layers;
+    }
+
+    pub fn set_mask(&mut self, mask: u32) {
+        self.mask = mask;
+    }
+
+    /// # Examples
+    /// ```
+    /// use qgeometry::world::QCollisionFilter;
+    ///
+    /// const TERRAIN: u32 = 1 << 0;
+    /// const PROJECTILE: u32 = 1 << 1;
+    /// let terrain = QCollisionFilter::new(TERRAIN, PROJECTILE);
+    /// let projectile = QCollisionFilter::new(PROJECTILE, TERRAIN);
+    /// assert!(terrain.can_interact(&projectile));
+    /// assert!(!projectile.can_interact(&projectile));
+    /// assert!(!QCollisionFilter::NONE.can_interact(&QCollisionFilter::ALL));
+    /// ```
+    pub fn can_interact(&self, other: &QCollisionFilter) -> bool {
+        (self.layers & other.mask) != 0 && (other.layers & self.mask) != 0
+    }
+}
+
+impl Default for QCollisionFilter {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// User callback deciding whether two shapes may interact, checked after the layer filters.
+pub type QPairFilter = Arc<dyn Fn(QShapeHandle, QShapeHandle) -> bool + Send + Sync>;
+
+#[derive(Debug, Clone)]
+struct QCollisionObject {
+    shape: QTransformedShape<QShape>,
+    filter: QCollisionFilter,
+    proxy: usize,
+}
+
+#[derive(Debug, Clone)]
+struct QWorldSlot {
+    generation: u32,
+    object: Option<QCollisionObject>,
+}
+
+/// Owns shapes behind handles and keeps a dynamic tree broadphase up to date.
+///
+/// Queries run the broadphase first and then the narrowphase of `QShapeCommon`.
+/// Results are sorted by handle so they are deterministic.
+#[derive(Clone)]
+pub struct QCollisionWorld {
+    slots: Vec<QWorldSlot>,
+    free_slots: Vec<u32>,
+    broadphase: QDynamicTree<QShapeHandle>,
+    pair_filter: Option<QPairFilter>,
+}
+
+impl fmt::Debug for QCollisionWorld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QCollisionWorld")
+            .field("slots", &self.slots)
+            .field("free_slots", &self.free_slots)
+            .field("broadphase", &self.broadphase)
+            .field("pair_filter", &self.pair_filter.is_some())
+            .finish()
+    }
+}
+
+impl QCollisionWorld {
+    /// Create a world, `margin` is the fat bbox margin of the broadphase.
+    pub fn new(margin: Q64) -> Self {
+        Self {
+            slots: vec![],
+            free_slots: vec![],
+            broadphase: QDynamicTree::new(margin),
+            pair_filter: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.broadphase.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.broadphase.is_empty()
+    }
+
+    pub fn contains(&self, handle: QShapeHandle) -> bool {
+        self.object(handle).is_some()
+    }
+
+    /// Get all live handles, sorted.
+    pub fn handles(&self) -> Vec<QShapeHandle> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.object.is_some())
+            .map(|(index, slot)| QShapeHandle { index: index as u32, generation: slot.generation })
+            .collect()
+    }
+
+    /// Insert a shape in local space placed by `transform`, it interacts with everything.
+    pub fn insert(&mut self, shape: impl Into<QShape>, transform: QTransform) -> QShapeHandle {
+        self.insert_with_filter(shape, transform, QCollisionFilter::ALL)
+    }
+
+    /// Insert a shape in local space placed by `transform` with a collision filter.
+    pub fn insert_with_filter(&mut self, shape: impl Into<QShape>, transform: QTransform, filter: QCollisionFilter) -> QShapeHandle {
+        let index = match self.free_slots.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(QWorldSlot { generation: 0, object: None });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let handle = QShapeHandle { index, generation: self.slots[index as usize].generation };
+        let shape = QTransformedShape::new(shape.into(), transform);
+        let proxy = self.broadphase.insert(shape.get_bbox(), handle);
+        self.slots[index as usize].object = Some(QCollisionObject { shape, filter, proxy });
+        handle
+    }
+
+    /// Remove a shape, return it in local space. Return `None` for a stale handle.
+    pub fn remove(&mut self, handle: QShapeHandle) -> Option<QShape> {
+        self.object(handle)?;
+        let slot = &mut self.slots[handle.index as usize];
+        let object = slot.object.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free_slots.push(handle.index);
+        self.broadphase.remove(object.proxy);
+        Some(object.shape.shape().clone())
+    }
+
+    /// Get the shape in local space.
+    pub fn shape(&self, handle: QShapeHandle) -> Option<&QShape> {
+        self.object(handle).map(|object| object.shape.shape())
+    }
+
+    /// Get the shape placed in world space.
+    pub fn world_shape(&self, handle: QShapeHandle) -> Option<&QTransformedShape<QShape>> {
+        self.object(handle).map(|object| &object.shape)
+    }
+
+    pub fn transform(&self, handle: QShapeHandle) -> Option<QTransform> {
+        self.object(handle).map(|object| object.shape.transform())
+    }
+
+    pub fn filter(&self, handle: QShapeHandle) -> Option<QCollisionFilter> {
+        self.object(handle).map(|object| object.filter)
+    }
+
+    /// Change the collision filter of a shape, return false for a stale handle.
+    pub fn set_filter(&mut self, handle: QShapeHandle, filter: QCollisionFilter) -> bool {
+        if !self.contains(handle) { return false; }
+        self.slots[handle.index as usize].object.as_mut().expect("[QCollisionWorld::set_filter] Object must exist.").filter = filter;
+        true
+    }
+
+    /// Set the callback deciding whether two shapes may interact, `None` to clear it.
+    pub fn set_pair_filter(&mut self, pair_filter: Option<QPairFilter>) {
+        self.pair_filter = pair_filter;
+    }
+
+    /// Move a shape, return false for a stale handle.
+    pub fn set_transform(&mut self, handle: QShapeHandle, transform: QTransform) -> bool {
+        if !self.contains(handle) { return false; }
+        let object = self.slots[handle.index as usize].object.as_mut().expect("[QCollisionWorld::set_transform] Object must exist.");
+        object.shape.set_transform(transform);
+        let bbox = object.shape.get_bbox();
+        let proxy = object.proxy;
+        self.broadphase.update(proxy, bbox);
+        true
+    }
+
+    /// Get all shapes colliding with the shape of `handle`.
+    pub fn intersections_with(&self, handle: QShapeHandle) -> Vec<QShapeHandle> {
+        let Some(object) = self.object(handle) else { return vec![]; };
+        let mut rst: Vec<QShapeHandle> = self
+            .broadphase
+            .query_bbox(&object.shape.get_bbox())
+            .into_iter()
+            .map(|proxy| self.handle_of(proxy))
+            .filter(|other| *other != handle)
+            .filter(|other| self.is_pair_colliding(handle, *other))
+            .collect();
+        rst.sort_unstable();
+        rst
+    }
+
+    /// Get all pairs of colliding shapes, each pair is `(smaller, larger)`.
+    /// # Examples
+    /// ```
+    /// use qmath::prelude::*;
+    /// use qmath::vec2::QVec2;
+    /// use qgeometry::prelude::*;
+    /// use qgeometry::world::QCollisionWorld;
+    ///
+    /// let mut world = QCollisionWorld::new(Q64::ZERO);
+    /// let unit = QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE);
+    /// let a = world.insert(unit, QTransform::IDENTITY);
+    /// let b = world.insert(unit, QTransform::from_translation(qvec2!(0.5, 0.5)));
+    /// let c = world.insert(unit, QTransform::from_translation(qvec2!(5.0, 0.0)));
+    /// assert_eq!(world.contact_pairs(), vec![(a, b)]);
+    ///
+    /// world.set_transform(c, QTransform::from_translation(qvec2!(1.0, 1.0)));
+    /// assert_eq!(world.contact_pairs(), vec![(a, b), (a, c), (b, c)]);
+    /// assert_eq!(world.intersections_with(c), vec![a, b]);
+    /// ```
+    pub fn contact_pairs(&self) -> Vec<(QShapeHandle, QShapeHandle)> {
+        let mut rst: Vec<(QShapeHandle, QShapeHandle)> = self
+            .broadphase
+            .query_pairs()
+            .into_iter()
+            .map(|(proxy_a, proxy_b)| {
+                let (a, b) = (self.handle_of(proxy_a), self.handle_of(proxy_b));
+                if a < b { (a, b) } else { (b, a) }
+            })
+            .filter(|(a, b)| self.is_pair_colliding(*a, *b))
+            .collect();
+        rst.sort_unstable();
+        rst
+    }
+
+    /// Cast a ray, return the first shape hit within `max_t` and the hit distance.
+    pub fn raycast(&self, ray: &QRay, max_t: Q64) -> Option<(QShapeHandle, Q64)> {
+        self.raycast_with_filter(ray, max_t, &QCollisionFilter::ALL)
+    }
+
+    /// Cast a ray, only shapes that can interact with `filter` are hit.
+    pub fn raycast_with_filter(&self, ray: &QRay, max_t: Q64, filter: &QCollisionFilter) -> Option<(QShapeHandle, Q64)> {
+        self.broadphase
+            .raycast_first(ray, max_t, |_, handle| self.hit_object(ray, *handle, filter))
+            .map(|(proxy, t)| (self.handle_of(proxy), t))
+    }
+
+    /// Cast a ray, return every shape hit within `max_t` sorted by hit distance.
+    pub fn raycast_all(&self, ray: &QRay, max_t: Q64) -> Vec<(QShapeHandle, Q64)> {
+        self.raycast_all_with_filter(ray, max_t, &QCollisionFilter::ALL)
+    }
+
+    /// Cast a ray, return every shape that can interact with `filter` hit within `max_t`.
+    pub fn raycast_all_with_filter(&self, ray: &QRay, max_t: Q64, filter: &QCollisionFilter) -> Vec<(QShapeHandle, Q64)> {
+        let mut rst: Vec<(QShapeHandle, Q64)> = self
+            .broadphase
+            .raycast_all(ray, max_t, |_, handle| self.hit_object(ray, *handle, filter))
+            .into_iter()
+            .map(|(proxy, t)| (self.handle_of(proxy), t))
+            .collect();
+        rst.sort_by(|a, b| (a.1, a.0).partial_cmp(&(b.1, b.0)).unwrap_or(core::cmp::Ordering::Equal));
+        rst
+    }
+
+    /// Get all shapes containing `point`.
+    pub fn point_query(&self, point: &QPoint) -> Vec<QShapeHandle> {
+        self.point_query_with_filter(point, &QCollisionFilter::ALL)
+    }
+
+    /// Get all shapes containing `point` that can interact with `filter`.
+    pub fn point_query_with_filter(&self, point: &QPoint, filter: &QCollisionFilter) -> Vec<QShapeHandle> {
+        let mut rst: Vec<QShapeHandle> = self
+            .broadphase
+            .query_point(point)
+            .into_iter()
+            .map(|proxy| self.handle_of(proxy))
+            .filter(|handle| {
+                self.object(*handle).is_some_and(|object| filter.can_interact(&object.filter) && object.shape.is_point_inside(point))
+            })
+            .collect();
+        rst.sort_unstable();
+        rst
+    }
+
+    /// Get all shapes whose geometry intersects `region`.
+    pub fn query_region(&self, region: &QBbox) -> Vec<QShapeHandle> {
+        self.query_shape(region, region)
+    }
+
+    /// Get all shapes whose geometry intersects `circle`.
+    pub fn query_circle(&self, circle: &QCircle) -> Vec<QShapeHandle> {
+        self.query_shape(&circle.get_bbox(), circle)
+    }
+
+    fn query_shape(&self, bbox: &QBbox, region: &impl QShapeCommon) -> Vec<QShapeHandle> {
+        let mut rst: Vec<QShapeHandle> = self
+            .broadphase
+            .query_bbox(bbox)
+            .into_iter()
+            .map(|proxy| self.handle_of(proxy))
+            .filter(|handle| self.object(*handle).is_some_and(|object| object.shape.is_collide(region)))
+            .collect();
+        rst.sort_unstable();
+        rst
+    }
+
+    fn object(&self, handle: QShapeHandle) -> Option<&QCollisionObject> {
+        let slot = self.slots.get(handle.index as usize)?;
+        if slot.generation != handle.generation { return None; }
+        slot.object.as_ref()
+    }
+
+    fn hit_object(&self, ray: &QRay, handle: QShapeHandle, filter: &QCollisionFilter) -> Option<Q64> {
+        let object = self.object(handle)?;
+        if !filter.can_interact(&object.filter) { return None; }
+        ray.intersect_shape(&object.shape)
+    }
+
+    fn handle_of(&self, proxy: usize) -> QShapeHandle {
+        *self.broadphase.data(proxy).expect("[QCollisionWorld::handle_of] Proxy must exist.")
+    }
+
+    /// Layer filters first, then the pair filter callback, then the narrowphase.
+    fn is_pair_colliding(&self, a: QShapeHandle, b: QShapeHandle) -> bool {
+        // Same order as `contact_pairs`, so both report the same pairs.
+        let (a, b) = if a < b { (a, b) } else { (b, a) };
+        let (Some(object_a), Some(object_b)) = (self.object(a), self.object(b)) else { return false; };
+        if !object_a.filter.can_interact(&object_b.filter) { return false; }
+        if let Some(pair_filter) = &self.pair_filter {
+            if !pair_filter(a, b) { return false; }
+        }
+        object_a.shape.is_collide(&object_b.shape)
+    }
+}
@@ -1,5 +1,6 @@
 use qmath::prelude::*;
 use qmath::vec2::QVec2;
+use qmath::dir::QDir;
 use qgeometry::prelude::*;
 use qgeometry::algorithm::*;
 
@@ -280,6 +281,65 @@ fn test_get_minkowski_difference_triangle() {
     assert_eq!(diff_points.len(), 6);
 }
 
+#[test]
+fn test_get_minkowski_sum() {
+    let square = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(1.0, 0.0),
+        qvec2!(1.0, 1.0),
+        qvec2!(0.0, 1.0),
+    ]);
+    // Clockwise on purpose
+    let triangle = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(1.0, 2.0),
+        qvec2!(2.0, 0.0),
+    ]);
+
+    let sum = get_minkowski_sum(&square, &triangle);
+    let expected = QPolygon::new_from_parts(andrew_graham_scan(&vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(3.0, 0.0),
+        qvec2!(3.0, 1.0),
+        qvec2!(2.0, 3.0),
+        qvec2!(1.0, 3.0),
+        qvec2!(0.0, 1.0),
+    ]));
+    assert_eq!(sum.points().len(), 6);
+    for point in expected.points() {
+        assert!(sum.points().contains(point));
+    }
+}
+
+#[test]
+fn test_get_minkowski_difference_concave() {
+    // Arrow shape, the difference must match the one of its hull.
+    let arrow = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(2.0, 1.0),
+        qvec2!(0.0, 2.0),
+        qvec2!(1.0, 1.0),
+    ]);
+    let hull = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(2.0, 1.0),
+        qvec2!(0.0, 2.0),
+    ]);
+    let square = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(1.0, 0.0),
+        qvec2!(1.0, 1.0),
+        qvec2!(0.0, 1.0),
+    ]);
+
+    let diff = get_minkowski_difference(&arrow, &square);
+    let hull_diff = get_minkowski_difference(&hull, &square);
+    assert_eq!(diff.points().len(), hull_diff.points().len());
+    for point in hull_diff.points() {
+        assert!(diff.points().contains(point));
+    }
+}
+
 #[test]
 fn test_epa_simple() {
     // Test EPA with two overlapping squares
@@ -322,4 +382,1272 @@ fn test_epa_no_collision() {
     let separation_vector = epa(&square1, &square2);
     // Should return None since the shapes don't overlap
     assert!(separation_vector.is_none());
-}
\ No newline at end of file
+}
+#[test]
+fn test_gjk_distance() {
+    let square1 = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(1.0, 0.0),
+        qvec2!(1.0, 1.0),
+        qvec2!(0.0, 1.0),
+    ]);
+
+    let square2 = QPolygon::new_from_parts(vec![
+        qvec2!(3.0, 0.5),
+        qvec2!(4.0, 0.5),
+        qvec2!(4.0, 1.5),
+        qvec2!(3.0, 1.5),
+    ]);
+
+    assert_eq!(gjk_distance(&square1, &square2), q64!(2));
+    assert_eq!(gjk_distance(&square1, &square1), Q64::ZERO);
+
+    // Curved shapes are queried through their support maps, not a sampled hull.
+    let circle = QCircle::new(QPoint::new(qvec2!(0.5, 4.0)), Q64::ONE);
+    assert!((gjk_distance(&square1, &circle) - q64!(2)).abs() < q64!(0.001));
+}
+
+#[test]
+fn test_distance_to() {
+    let bbox = QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE);
+    let point = QPoint::new(qvec2!(1.0, 4.0));
+    let line = QLine::new_from_parts(qvec2!(3.0, -1.0), qvec2!(3.0, 2.0));
+
+    assert_eq!(bbox.distance_to(&point), q64!(3));
+    assert_eq!(point.distance_to(&bbox), q64!(3));
+    assert_eq!(bbox.distance_to(&line), q64!(2));
+    assert_eq!(line.distance_to(&bbox), q64!(2));
+    assert_eq!(bbox.distance_to(&bbox.translated(qvec2!(0.5, 0.5))), Q64::ZERO);
+}
+
+#[test]
+fn test_contains() {
+    // U shape, the notch is outside
+    let u_shape = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(3.0, 0.0),
+        qvec2!(3.0, 3.0),
+        qvec2!(2.0, 3.0),
+        qvec2!(2.0, 1.0),
+        qvec2!(1.0, 1.0),
+        qvec2!(1.0, 3.0),
+        qvec2!(0.0, 3.0),
+    ]);
+
+    let inside = QBbox::new_from_parts(qvec2!(0.25, 0.25), qvec2!(0.75, 2.5));
+    let bridging = QBbox::new_from_parts(qvec2!(0.25, 2.0), qvec2!(2.75, 2.5));
+    let overlapping = QBbox::new_from_parts(qvec2!(2.5, 2.5), qvec2!(4.0, 4.0));
+
+    assert!(u_shape.contains(&inside));
+    // Every vertex is inside but the edges cross the notch.
+    assert!(!u_shape.contains(&bridging));
+    assert!(!u_shape.contains(&overlapping));
+    assert!(u_shape.contains(&QCircle::new(QPoint::new(qvec2!(1.5, 0.5)), q64!(0.5))));
+    assert!(!u_shape.contains(&QCircle::new(QPoint::new(qvec2!(1.5, 0.5)), q64!(0.75))));
+}
+
+#[test]
+fn test_sat_matches_gjk() {
+    let square = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(2.0, 0.0),
+        qvec2!(2.0, 2.0),
+        qvec2!(0.0, 2.0),
+    ]);
+    let triangle = QPolygon::new_from_parts(vec![
+        qvec2!(1.5, 1.0),
+        qvec2!(4.0, 1.0),
+        qvec2!(3.0, 3.0),
+    ]);
+    let far_triangle = triangle.translated(qvec2!(1.0, 0.0));
+
+    assert_eq!(sat_collide(&square, &triangle), gjk(&square, &triangle));
+    assert_eq!(sat_collide(&square, &far_triangle), gjk(&square, &far_triangle));
+    assert!(sat_collide(&square, &triangle));
+    assert!(!sat_collide(&square, &far_triangle));
+}
+
+#[test]
+fn test_sat_mtv() {
+    let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 2.0));
+    let other = QBbox::new_from_parts(qvec2!(1.5, 0.5), qvec2!(3.5, 1.5));
+    assert_eq!(sat_mtv(&bbox, &other), Some(qvec2!(0.5, 0.0)));
+    assert_eq!(sat_mtv(&bbox, &other.translated(qvec2!(2.0, 0.0))), None);
+
+    let circle = QCircle::new(QPoint::new(qvec2!(3.0, 1.0)), Q64::ONE);
+    assert!(sat_collide(&bbox, &circle));
+    assert!(!sat_collide(&bbox, &circle.translated(qvec2!(1.0, 1.0))));
+    assert_eq!(sat_mtv(&bbox, &circle), Some(QVec2::ZERO));
+}
+
+#[test]
+fn test_gjk_support_maps() {
+    let circle = QCircle::new(QPoint::ZERO, Q64::ONE);
+    let capsule = QCapsule::new_from_parts(qvec2!(1.5, -2.0), qvec2!(1.5, 2.0), q64!(0.25));
+    let ellipse = QEllipse::new(QPoint::new(qvec2!(-2.5, 0.0)), qvec2!(2.0, 0.5));
+
+    // The polygon approximation of the circle would miss the capsule by its chord sagitta.
+    assert!(!gjk(&circle, &capsule));
+    assert!(gjk(&circle, &capsule.translated(qvec2!(-0.3, 0.0))));
+    assert!(gjk(&circle, &ellipse));
+    assert!(!gjk(&capsule, &ellipse));
+}
+
+#[test]
+fn test_shape_collisions_use_exact_supports() {
+    // A wedge 0.99 from the center, between two of the 16 circle vertices the hull only
+    // reaches 0.98 there.
+    let circle = QCircle::new(QPoint::ZERO, Q64::ONE);
+    let wedge = QPolygon::new_from_parts(vec![
+        qvec2!(1.166067, -0.787646),
+        qvec2!(2.932547, 0.583319),
+        qvec2!(0.775887, 1.173924),
+    ]);
+    assert!(gjk(&circle, &wedge));
+    assert!(circle.is_collide(&wedge));
+    assert!(wedge.is_collide(&circle));
+    assert!(QShape::from(circle).is_collide(&QShape::from(wedge.clone())));
+    assert!(wedge.try_get_seperation_vector(&circle).is_some());
+}
+
+#[test]
+fn test_epa_circles() {
+    let circle_a = QCircle::new(QPoint::ZERO, Q64::ONE);
+    let circle_b = QCircle::new(QPoint::new(qvec2!(1.5, 0.0)), Q64::ONE);
+
+    let separation_vector = epa(&circle_a, &circle_b).unwrap();
+    assert!(separation_vector.x > q64!(0.45) && separation_vector.x < q64!(0.55));
+    assert!(separation_vector.y.abs() < q64!(0.05));
+}
+
+#[test]
+fn test_intersection_area_and_iou() {
+    let bbox_a = QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 2.0));
+    let bbox_b = QBbox::new_from_parts(QVec2::ONE, qvec2!(3.0, 3.0));
+
+    assert_eq!(intersection_area(&bbox_a, &bbox_b), Q64::ONE);
+    assert_eq!(iou(&bbox_a, &bbox_b), q64!(1) / q64!(7));
+    assert_eq!(iou(&bbox_a, &bbox_a), Q64::ONE);
+    assert_eq!(intersection_area(&bbox_a, &bbox_b.translated(qvec2!(5.0, 0.0))), Q64::ZERO);
+
+    // Concave L shape against a convex square
+    let l_shape = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(2.0, 0.0),
+        qvec2!(2.0, 1.0),
+        qvec2!(1.0, 1.0),
+        qvec2!(1.0, 2.0),
+        qvec2!(0.0, 2.0),
+    ]);
+    assert_eq!(intersection_area(&l_shape, &bbox_b), Q64::ZERO);
+    assert_eq!(intersection_area(&bbox_a, &l_shape), q64!(3));
+
+    // Two concave shapes, the notch of the second one must not count.
+    let flipped = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(2.0, 0.0),
+        qvec2!(2.0, 2.0),
+        qvec2!(1.0, 2.0),
+        qvec2!(1.0, 1.0),
+        qvec2!(0.0, 1.0),
+    ]);
+    assert_eq!(intersection_area(&l_shape, &flipped), q64!(2));
+    assert_eq!(iou(&l_shape, &flipped), q64!(2) / q64!(4));
+    assert!(iou(&l_shape, &l_shape) <= Q64::ONE);
+
+    // The reflex vertex of the chevron must not be clipped as an ear.
+    let chevron = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 1.0), qvec2!(4.0, 0.0), qvec2!(2.0, 3.0)]);
+    let triangles = chevron.ear_clipping_triangulation();
+    assert_eq!(triangles.len(), 6);
+    let triangle_area = triangles.chunks_exact(3).fold(Q64::ZERO, |acc, t| {
+        let [a, b, c] = [t[0], t[1], t[2]].map(|i| chevron.points()[i].pos());
+        assert!(b.saturating_sub(a).cross(c.saturating_sub(a)) < Q64::ZERO);
+        acc + QPolygon::new_from_parts(vec![a, b, c]).area()
+    });
+    assert_eq!(triangle_area, q64!(4));
+    let wide_l = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(4.0, 0.0),
+        qvec2!(4.0, 1.0),
+        qvec2!(1.0, 1.0),
+        qvec2!(1.0, 3.0),
+        qvec2!(0.0, 3.0),
+    ]);
+    assert!((intersection_area(&wide_l, &chevron) - q64!(17) / q64!(12)).abs() < q64!(0.001));
+}
+
+#[test]
+fn test_hausdorff_distance() {
+    let square = QBbox::new_from_parts(QVec2::ZERO, qvec2!(4.0, 4.0));
+    let notched = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(4.0, 0.0),
+        qvec2!(4.0, 4.0),
+        qvec2!(2.0, 3.0),
+        qvec2!(0.0, 4.0),
+    ]);
+
+    // The farthest point of the square is the middle of its top edge, above the notch.
+    assert!((directed_hausdorff_distance(&square, &notched) - q64!(0.894427)).abs() < q64!(0.0001));
+    assert_eq!(directed_hausdorff_distance(&notched, &square), Q64::ONE);
+    assert_eq!(hausdorff_distance(&square, &notched), Q64::ONE);
+    assert_eq!(hausdorff_distance(&square, &square), Q64::ZERO);
+}
+
+#[test]
+fn test_frechet_distance() {
+    let path = QPolyline::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(1.0, 0.0),
+        qvec2!(2.0, 0.0),
+    ]);
+    let shifted = path.translated(qvec2!(0.0, 1.0));
+    let reversed = QPolyline::new_from_parts(vec![
+        qvec2!(2.0, 0.0),
+        qvec2!(1.0, 0.0),
+        qvec2!(0.0, 0.0),
+    ]);
+
+    assert_eq!(frechet_distance(&path, &path), Q64::ZERO);
+    assert_eq!(frechet_distance(&path, &shifted), Q64::ONE);
+    // Same vertices but walked backwards
+    assert_eq!(frechet_distance(&path, &reversed), q64!(2));
+    assert_eq!(hausdorff_distance(&path, &reversed), Q64::ZERO);
+}
+
+#[test]
+fn test_lerp_polygons_endpoints() {
+    // Interpolating a square with a shifted copy of itself
+    let square1 = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(1.0, 0.0),
+        qvec2!(1.0, 1.0),
+        qvec2!(0.0, 1.0),
+    ]);
+
+    let square2 = QPolygon::new_from_parts(vec![
+        qvec2!(3.0, 1.0),
+        qvec2!(3.0, 0.0),
+        qvec2!(2.0, 0.0),
+        qvec2!(2.0, 1.0),
+    ]);
+
+    let start = lerp_polygons(&square1, &square2, Q64::ZERO);
+    assert_eq!(start.points(), square1.points());
+
+    let middle = lerp_polygons(&square1, &square2, Q64::HALF);
+    let positions: Vec<QVec2> = middle.points().iter().map(|p| p.pos()).collect();
+    // No swirling, every vertex just moves by half the offset
+    assert!(positions.contains(&qvec2!(1.0, 0.0)));
+    assert!(positions.contains(&qvec2!(2.0, 0.0)));
+    assert!(positions.contains(&qvec2!(2.0, 1.0)));
+    assert!(positions.contains(&qvec2!(1.0, 1.0)));
+}
+
+#[test]
+fn test_collide_pairs() {
+    let shapes: Vec<QShape> = vec![
+        QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(1.0, 1.0)).into(),
+        QCircle::new(QPoint::new(qvec2!(1.5, 0.5)), Q64::ONE).into(),
+        QPolygon::new_from_parts(vec![qvec2!(5.0, 5.0), qvec2!(6.0, 5.0), qvec2!(5.5, 6.0)]).into(),
+    ];
+
+    assert_eq!(collide_pairs(&shapes, &[(0, 1), (0, 2), (2, 1)]), vec![true, false, false]);
+    assert_eq!(collide_all_pairs(&shapes), vec![(0, 1)]);
+}
+
+#[test]
+fn test_points_iter_matches_points() {
+    let circle = QCircle::new(QPoint::new(qvec2!(1.0, 2.0)), q64!(3));
+    let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 1.0));
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0)]);
+    let shape = QShape::QCircle(circle);
+    let transformed = QTransformedShape::new(polygon.clone(), QTransform::from_translation(qvec2!(1.0, 1.0)));
+
+    assert_eq!(circle.points_iter().collect::<Vec<_>>(), circle.points());
+    assert_eq!(bbox.points_iter().collect::<Vec<_>>(), bbox.points());
+    assert_eq!(shape.points_iter().collect::<Vec<_>>(), shape.points());
+    assert_eq!(transformed.points_iter().collect::<Vec<_>>(), transformed.points());
+    assert_eq!(polygon.points_slice(), Some(&polygon.points()[..]));
+    assert_eq!(bbox.points_slice(), None);
+
+    let edges: Vec<QLine> = polygon.edges_iter().collect();
+    assert_eq!(edges.len(), 3);
+    assert_eq!(edges[2], QLine::new_from_parts(qvec2!(4.0, 3.0), qvec2!(0.0, 0.0)));
+    let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(0.0, 0.0), qvec2!(1.0, 0.0)]);
+    assert_eq!(polyline.edges_iter().count(), 1);
+}
+
+#[test]
+fn test_polygon_bbox_cache_follows_mutation() {
+    let mut polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(0.0, 2.0)]);
+    let before = polygon.get_bbox();
+    assert!(before.right_top().x() < q64!(3));
+
+    polygon.transform(&QTransform::from_translation(qvec2!(10.0, 0.0)));
+    assert!(polygon.get_bbox().left_bottom().x() > q64!(9));
+
+    polygon.points_mut()[1] = QPoint::new(qvec2!(20.0, 0.0));
+    assert!(polygon.get_bbox().right_top().x() >= q64!(20));
+    assert_eq!(polygon, QPolygon::new(polygon.points().clone()));
+}
+
+#[test]
+fn test_polygon_triangulation_cache_is_rebuilt_lazily() {
+    let mut polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)])
+        .with_cached_triangulation();
+    let version = polygon.triangulation_version();
+
+    polygon.set_vertex(2, QPoint::new(qvec2!(4.0, 4.0)));
+    assert_ne!(polygon.triangulation_version(), version);
+    assert_eq!(polygon.ear_clipping_triangulation().len(), 6);
+    assert!(polygon.cached_convex_hull().unwrap().contains(&QPoint::new(qvec2!(4.0, 4.0))));
+
+    let version = polygon.triangulation_version();
+    polygon.transform(&QTransform::from_translation(qvec2!(10.0, 0.0)));
+    assert_ne!(polygon.triangulation_version(), version);
+    assert!(polygon.cached_convex_hull().unwrap().contains(&QPoint::new(qvec2!(14.0, 4.0))));
+    assert_eq!(polygon.cached_triangulation().unwrap().len(), 6);
+    assert_eq!(polygon.convex_hull().points().len(), 4);
+}
+
+#[test]
+fn test_precomputed_normals_match_sat() {
+    let clockwise = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(0.0, 2.0), qvec2!(2.0, 2.0), qvec2!(2.0, 0.0)]);
+    let cached = clockwise.clone().with_precomputed_normals();
+    let normals = cached.precomputed_normals().unwrap();
+    assert_eq!(normals.len(), 4);
+    assert_eq!(normals[0].to_vec(), qvec2!(-1.0, 0.0));
+
+    let other = QCircle::new(QPoint::new(qvec2!(2.5, 1.0)), Q64::ONE);
+    assert_eq!(sat_collide(&cached, &other), sat_collide(&clockwise, &other));
+    assert_eq!(sat_mtv(&cached, &other), sat_mtv(&clockwise, &other));
+
+    let mut moved = cached.clone();
+    moved.points_mut().reverse();
+    assert_eq!(moved.precomputed_normals().unwrap()[0].to_vec(), qvec2!(1.0, 0.0));
+
+    let notched = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 4.0), qvec2!(2.0, 1.0), qvec2!(0.0, 4.0)]);
+    let notched_cached = notched.clone().with_precomputed_normals();
+    for probe in [qvec2!(2.0, 3.0), qvec2!(5.0, 5.0), qvec2!(-1.0, 2.0), qvec2!(2.0, -3.0), qvec2!(1.0, 1.0)] {
+        let probe = QPoint::new(probe);
+        assert_eq!(notched_cached.closest_point(&probe), notched.closest_point(&probe));
+    }
+}
+
+#[test]
+fn test_contains_points_matches_single_queries() {
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 5.0), qvec2!(4.0, 2.0), qvec2!(3.0, 5.0), qvec2!(2.0, 2.0), qvec2!(0.0, 5.0),
+    ]);
+    let mut queries = vec![];
+    for i in -2..16 {
+        for j in -2..14 {
+            queries.push(QPoint::new(QVec2::new(q64!(i) * Q64::HALF, q64!(j) * Q64::HALF)));
+        }
+    }
+    let expected: Vec<bool> = queries.iter().map(|point| polygon.is_point_inside(point)).collect();
+    assert_eq!(polygon.contains_points(&queries), expected);
+    assert!(expected.iter().any(|&inside| inside) && expected.iter().any(|&inside| !inside));
+}
+
+#[test]
+fn test_orient2d_is_exact_at_large_coordinates() {
+    use core::cmp::Ordering;
+    let big = Q64::MAX.saturating_div(q64!(2));
+    let a = QVec2::new(-big, -big);
+    let b = QVec2::new(big, big);
+    // One raw step below the diagonal: the saturating cross product can't see it.
+    let c = QVec2::new(big, big.saturating_sub(Q64::EPS));
+    assert_eq!(orient2d(a, b, QVec2::ZERO), Ordering::Equal);
+    assert_eq!(orient2d(a, b, c), Ordering::Less);
+    assert_eq!(orient2d(b, a, c), Ordering::Greater);
+
+    let line = QLine::new_from_parts(a, b);
+    assert!(line.is_point_on_line(&QPoint::new(QVec2::ZERO)));
+    assert!(!line.is_point_on_line(&QPoint::new(c)));
+
+    let hull = andrew_graham_scan(&vec![a, QVec2::ZERO, b, QVec2::new(big, -big)]);
+    assert_eq!(hull.len(), 3);
+}
+
+#[test]
+fn test_tolerance_is_honored_consistently() {
+    let loose = QTolerance::new(q64!(0.1));
+    let line = QLine::new_from_parts(qvec2!(0.0, 0.0), qvec2!(2.0, 0.0));
+    let near = QPoint::new(qvec2!(1.0, 0.05));
+    assert!(!line.is_point_on_line(&near));
+    assert!(line.is_point_on_line_with_tolerance(&near, loose));
+    assert!(!line.is_point_on_line_with_tolerance(&QPoint::new(qvec2!(2.2, 0.0)), loose));
+
+    let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)]);
+    let outside = QPoint::new(qvec2!(1.0, -0.05));
+    assert!(!square.is_point_inside(&outside));
+    assert!(!square.is_point_inside_with_tolerance(&outside, QTolerance::EXACT));
+    assert!(square.is_point_inside_with_tolerance(&outside, loose));
+
+    let points = vec![qvec2!(0.0, 0.0), qvec2!(1.0, -0.05), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)];
+    assert_eq!(andrew_graham_scan(&points).len(), 5);
+    assert_eq!(andrew_graham_scan_with_tolerance(&points, loose).len(), 4);
+}
+
+#[test]
+fn test_snap_rounding_keeps_edges_from_crossing() {
+    let line = QLine::new_from_parts(qvec2!(0.1, 0.1), qvec2!(0.3, 0.2));
+    assert_eq!(line.snap_to_grid(Q64::ONE), None);
+
+    // Plain snapping moves the notch vertex below the bottom edge's rerouted path.
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(6.2, 0.0), qvec2!(6.2, 3.0), qvec2!(3.1, 0.3), qvec2!(0.0, 3.0),
+    ]);
+    let snapped = polygon.snap_rounded(Q64::ONE).unwrap();
+    let edges: Vec<QLine> = snapped.edges_iter().collect();
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            assert!(!edges[i].is_crossing(&edges[j]));
+        }
+    }
+    assert!(snapped.points().iter().all(|point| point.snap_to_grid(Q64::ONE) == *point));
+
+    let sliver = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(5.0, 0.1), qvec2!(0.0, 0.2)]);
+    assert_eq!(sliver.snap_rounded(Q64::ONE), None);
+    assert_eq!(sliver.snap_to_grid(Q64::ONE).points().len(), 2);
+}
+
+#[test]
+fn test_new_cleaned_removes_repeated_and_collinear_vertices() {
+    let points = QPoint::from_f64_slice(&[
+        [0.0, 0.0], [2.0, 0.0], [2.0, 0.0], [4.0, 0.0], [4.0, 4.0], [4.0, 5.0], [4.0, 4.0], [0.0, 4.0], [0.0, 0.0],
+    ]);
+    let polygon = QPolygon::new_cleaned(points, QTolerance::EXACT).unwrap();
+    assert_eq!(polygon.points().len(), 4);
+    assert_eq!(polygon.ear_clipping_triangulation().len(), 6);
+
+    let noisy = QPoint::from_f64_slice(&[[0.0, 0.0], [2.0, 0.001], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]);
+    assert_eq!(QPolygon::new_cleaned(noisy.clone(), QTolerance::EXACT).unwrap().points().len(), 5);
+    assert_eq!(QPolygon::new_cleaned(noisy, QTolerance::new(q64!(0.01))).unwrap().points().len(), 4);
+
+    assert_eq!(QPolygon::new_cleaned(QPoint::from_f64_slice(&[[1.0, 1.0], [1.0, 1.0], [1.0, 1.0]]), QTolerance::EXACT), None);
+}
+
+#[test]
+fn test_bbox_from_points_and_enclosing() {
+    assert_eq!(QBbox::from_points([QPoint::new(qvec2!(2.0, 3.0))]).unwrap().get_centroid().pos(), qvec2!(2.0, 3.0));
+    let flat = QBbox::from_points([QPoint::new(qvec2!(0.0, 1.0)), QPoint::new(qvec2!(4.0, 1.0))]).unwrap();
+    assert_eq!(flat.width(), q64!(4));
+    assert!(flat.height() > Q64::ZERO);
+
+    let shapes: Vec<QShape> = vec![
+        QCircle::new(QPoint::new(qvec2!(5.0, 5.0)), Q64::ONE).into(),
+        QBbox::new_from_parts(qvec2!(-3.0, 0.0), qvec2!(0.0, 1.0)).into(),
+    ];
+    assert_eq!(QBbox::enclosing(&shapes), Some(QBbox::new_from_parts(qvec2!(-3.0, 0.0), qvec2!(6.0, 6.0))));
+    let no_shapes: [QShape; 0] = [];
+    assert_eq!(QBbox::enclosing(&no_shapes), None);
+}
+
+#[test]
+fn test_fat_and_swept_bboxes() {
+    let circle = QCircle::new(QPoint::new(qvec2!(1.0, 1.0)), Q64::ONE);
+    assert_eq!(circle.get_fat_bbox(Q64::HALF), QBbox::new_from_parts(qvec2!(-0.5, -0.5), qvec2!(2.5, 2.5)));
+    let swept = circle.get_swept_bbox(qvec2!(-3.0, 0.0));
+    assert_eq!(swept, QBbox::new_from_parts(qvec2!(-3.0, 0.0), qvec2!(2.0, 2.0)));
+    assert!(swept.is_bbox_inside(&circle.translated(qvec2!(-1.5, 0.0)).get_bbox()));
+}
+
+#[test]
+fn test_line_parametric_api() {
+    let line = QLine::new_from_parts(qvec2!(-2.0, 1.0), qvec2!(6.0, -3.0));
+    assert_eq!(line.point_at(Q64::ZERO), line.start());
+    assert_eq!(line.point_at(Q64::ONE), line.end());
+    assert_eq!(line.point_at(q64!(2)).pos(), qvec2!(14.0, -7.0));
+    assert_eq!(line.midpoint(), line.get_centroid());
+
+    let pieces = line.subdivide(3);
+    assert_eq!(pieces.len(), 3);
+    assert_eq!(pieces[0].start(), line.start());
+    assert_eq!(pieces[2].end(), line.end());
+    assert!(pieces.windows(2).all(|w| w[0].end() == w[1].start()));
+}
+
+#[test]
+fn test_line_relation_predicates() {
+    let a = QLine::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 2.0));
+    let parallel = QLine::new_from_parts(qvec2!(0.0, 1.0), qvec2!(-2.0, 0.0));
+    let collinear = QLine::new_from_parts(qvec2!(6.0, 3.0), qvec2!(8.0, 4.0));
+    assert!(a.is_parallel_to(&parallel) && !a.is_collinear_with(&parallel));
+    assert!(a.is_parallel_to(&collinear) && a.is_collinear_with(&collinear));
+    assert!(!a.intersects(&collinear) && !a.intersects(&parallel));
+    assert!(a.intersects(&QLine::new_from_parts(qvec2!(4.0, 2.0), qvec2!(6.0, 3.0))));
+    assert!(a.intersects(&QLine::new_from_parts(qvec2!(0.0, 2.0), qvec2!(4.0, 0.0))));
+
+    // Directions whose cross product saturates are still compared exactly.
+    let big = Q64::MAX.saturating_div(q64!(2));
+    let long = QLine::new_from_parts(QVec2::new(-big, -big), QVec2::new(big, big));
+    let tilted = QLine::new_from_parts(QVec2::new(-big, -big), QVec2::new(big, big - Q64::EPS));
+    assert!(long.is_parallel_to(&QLine::new_from_parts(QVec2::ZERO, QVec2::ONE)));
+    assert!(!long.is_parallel_to(&tilted));
+}
+
+#[test]
+fn test_segment_distance_and_capsule_overlap() {
+    let a = QLine::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 0.0));
+    let parallel = QLine::new_from_parts(qvec2!(1.0, 2.0), qvec2!(3.0, 2.0));
+    let collinear = QLine::new_from_parts(qvec2!(6.0, 0.0), qvec2!(8.0, 0.0));
+    let skew = QLine::new_from_parts(qvec2!(5.0, -3.0), qvec2!(5.0, 3.0));
+    assert_eq!(a.distance_to_segment(&parallel), q64!(2));
+    assert_eq!(a.distance_to_segment(&collinear), q64!(2));
+    assert_eq!(a.distance_to_segment(&skew), Q64::ONE);
+    assert_eq!(skew.distance_to_segment(&a), Q64::ONE);
+
+    let capsule = QCapsule::new(a, Q64::HALF);
+    assert!(capsule.is_overlapping(&QCapsule::new(skew, Q64::HALF)));
+    assert!(!capsule.is_overlapping(&QCapsule::new(parallel, Q64::ONE)));
+}
+
+#[test]
+fn test_polygon_edges_skip_repeated_vertices() {
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0), qvec2!(0.0, 0.0),
+    ]);
+    let edges: Vec<QLine> = polygon.edges().collect();
+    assert_eq!(edges.len(), 4);
+    assert!(edges.windows(2).all(|w| w[0].end() == w[1].start()));
+    assert!(polygon.is_point_inside(&QPoint::new(qvec2!(1.0, 1.0))));
+    assert!(!polygon.is_point_inside(&QPoint::new(qvec2!(3.0, 1.0))));
+    assert_eq!(polygon.get_nearest_lines_index_to_point(&QPoint::new(qvec2!(1.0, -1.0))), vec![0, 1]);
+}
+
+#[test]
+fn test_polygon_vertex_editing_refreshes_caches() {
+    let mut polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)])
+        .with_precomputed_normals();
+    polygon.insert_vertex(2, QPoint::new(qvec2!(4.0, 1.0)));
+    assert!(polygon.is_point_inside(&QPoint::new(qvec2!(3.0, 1.0))));
+    assert_eq!(polygon.get_bbox().right_top().x(), q64!(4));
+    assert_eq!(polygon.precomputed_normals().unwrap().len(), 5);
+
+    assert_eq!(polygon.set_vertex(2, QPoint::new(qvec2!(3.0, 1.0))).pos(), qvec2!(4.0, 1.0));
+    assert_eq!(polygon.remove_vertex(2).pos(), qvec2!(3.0, 1.0));
+    assert_eq!(polygon.get_bbox().right_top().x(), q64!(2));
+    assert_eq!(polygon.precomputed_normals().unwrap().len(), 4);
+    assert!(polygon.is_simple());
+
+    let bow_tie = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 2.0), qvec2!(2.0, 0.0), qvec2!(0.0, 2.0)]);
+    assert!(!bow_tie.is_simple());
+    let spike = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(4.0, 0.0), qvec2!(3.0, 0.0), qvec2!(0.0, 2.0)]);
+    assert!(!spike.is_simple());
+}
+
+#[test]
+fn test_polygon_resampling_is_evenly_spaced() {
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(0.5, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 3.0), qvec2!(0.0, 3.0),
+    ]);
+    let resampled = polygon.resample(Q64::ONE);
+    assert_eq!(resampled.points().len(), 18);
+    assert_eq!(resampled.points()[0], polygon.points()[0]);
+    for edge in resampled.edges_iter() {
+        assert!((edge.vector().length() - Q64::ONE).abs() < q64!(0.001));
+        assert!(polygon.is_point_inside(&edge.start()));
+    }
+    assert_eq!(polygon.resample_n(3).points().len(), 3);
+}
+
+#[test]
+fn test_interior_angles_sum_and_reflex_vertices() {
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 5.0), qvec2!(4.0, 2.0), qvec2!(3.0, 5.0), qvec2!(2.0, 2.0), qvec2!(0.0, 5.0),
+    ]);
+    let angles = polygon.interior_angles();
+    let sum = angles.iter().fold(Q64::ZERO, |acc, angle| acc + *angle);
+    assert!((sum - Q64::PI * q64!(5)).abs() < q64!(0.001));
+    assert_eq!(polygon.reflex_vertices(), vec![3, 5]);
+
+    let mut clockwise = polygon.clone();
+    clockwise.points_mut().reverse();
+    assert!(!clockwise.is_ccw());
+    assert_eq!(clockwise.reflex_vertices(), vec![1, 3]);
+    assert!((clockwise.interior_angles()[0] - angles[6]).abs() < q64!(0.0001));
+
+    for (y, x) in [(1.0, 2.0), (-3.0, 0.5), (0.2, -4.0), (-1.0, -1.0)] {
+        let expected = (y as f64).atan2(x);
+        assert!((atan2(q64!(y), q64!(x)).to_num::<f64>() - expected).abs() < 0.0001);
+    }
+}
+
+#[test]
+fn test_regular_polygon_and_star_constructors() {
+    let center = QPoint::new(qvec2!(3.0, -2.0));
+    let hexagon = QPolygon::regular(center, q64!(2), 6, Q64::PI / q64!(6));
+    assert_eq!(hexagon.points().len(), 6);
+    assert!(hexagon.is_ccw() && hexagon.is_simple());
+    assert!(hexagon.reflex_vertices().is_empty());
+    assert!(hexagon.points().iter().all(|point| (point.distance(&center) - q64!(2)).abs() < q64!(0.001)));
+
+    let star = QPolygon::star(center, q64!(3), Q64::ONE, 5);
+    assert_eq!(star.points().len(), 10);
+    assert!(star.is_ccw() && star.is_simple());
+    assert_eq!(star.reflex_vertices(), vec![1, 3, 5, 7, 9]);
+    assert!((star.points()[0].pos() - qvec2!(3.0, 1.0)).length() < q64!(0.001));
+}
+
+#[test]
+fn test_circle_from_three_points() {
+    let points = [QPoint::new(qvec2!(1.0, 7.0)), QPoint::new(qvec2!(-4.0, 2.0)), QPoint::new(qvec2!(6.0, 2.0))];
+    let circle = QCircle::from_three_points(&points[0], &points[1], &points[2]).unwrap();
+    assert_eq!(circle.center().pos(), qvec2!(1.0, 2.0));
+    assert_eq!(circle.radius(), q64!(5));
+    assert_eq!(QCircle::from_three_points(&points[2], &points[1], &points[0]), Some(circle));
+    assert!(QCircle::from_three_points(&points[0], &points[0], &points[1]).is_none());
+}
+
+#[test]
+fn test_circle_tangents() {
+    let circle = QCircle::new(QPoint::new(qvec2!(1.0, 1.0)), q64!(2));
+    let point = QPoint::new(qvec2!(-3.0, 4.0));
+    let tangents = circle.tangents_from_point(&point);
+    assert_eq!(tangents.len(), 2);
+    for tangent in &tangents {
+        assert_eq!(tangent.start(), point);
+        let radius = tangent.end().pos() - circle.center().pos();
+        assert!((radius.length() - q64!(2)).abs() < q64!(0.001));
+        assert!(radius.dot(tangent.vector()).abs() < q64!(0.001));
+    }
+
+    let other = QCircle::new(QPoint::new(qvec2!(6.0, 2.0)), Q64::ONE);
+    let common = circle.common_tangents(&other);
+    assert_eq!(common.len(), 4);
+    for tangent in &common {
+        assert!((tangent.start().distance(&circle.center()) - q64!(2)).abs() < q64!(0.001));
+        assert!(tangent.get_distance_from_point(&circle.center()) > q64!(1.999));
+        assert!(tangent.get_distance_from_point(&other.center()) > q64!(0.999));
+    }
+
+    let touching = QCircle::new(QPoint::new(qvec2!(4.0, 1.0)), Q64::ONE);
+    assert_eq!(circle.common_tangents(&touching).len(), 2);
+    assert!(circle.common_tangents(&QCircle::new(circle.center(), Q64::ONE)).is_empty());
+}
+
+#[test]
+fn test_circle_circle_intersection() {
+    let circle = QCircle::new(QPoint::new(qvec2!(1.0, 2.0)), q64!(3));
+    let other = QCircle::new(QPoint::new(qvec2!(3.0, 5.0)), q64!(2));
+    let intersection = circle.intersect_circle(&other);
+    let points = intersection.points();
+    assert_eq!(points.len(), 2);
+    for point in points {
+        assert!((point.distance(&circle.center()) - q64!(3)).abs() < q64!(0.001));
+        assert!((point.distance(&other.center()) - q64!(2)).abs() < q64!(0.001));
+    }
+
+    let inside_tangent = QCircle::new(QPoint::new(qvec2!(2.0, 2.0)), q64!(2));
+    assert_eq!(circle.intersect_circle(&inside_tangent).points(), &[QPoint::new(qvec2!(4.0, 2.0))]);
+    assert_eq!(circle.intersect_circle(&QCircle::new(QPoint::new(qvec2!(10.0, 2.0)), Q64::ONE)), QCircleIntersection::Disjoint);
+    assert_eq!(circle.intersect_circle(&QCircle::new(circle.center(), Q64::ONE)), QCircleIntersection::Disjoint);
+    assert_eq!(circle.intersect_circle(&circle), QCircleIntersection::Coincident);
+}
+
+#[test]
+fn test_polygon_second_moments() {
+    // Right triangle with legs 3 and 6: Ixx = b h³ / 36, Iyy = h b³ / 36, Ixy = -b² h² / 72 about the centroid.
+    let triangle = QPolygon::new_from_parts(vec![qvec2!(2.0, 1.0), qvec2!(5.0, 1.0), qvec2!(2.0, 7.0)]);
+    assert_eq!(triangle.area(), q64!(9));
+    assert_eq!(triangle.area_centroid().pos(), qvec2!(3.0, 3.0));
+    let (ixx, iyy, ixy) = triangle.second_moments();
+    assert!((ixx - q64!(18)).abs() < q64!(0.001));
+    assert!((iyy - q64!(4.5)).abs() < q64!(0.001));
+    assert!((ixy + q64!(4.5)).abs() < q64!(0.001));
+
+    let mut clockwise = triangle.clone();
+    clockwise.points_mut().reverse();
+    assert_eq!(clockwise.area(), q64!(9));
+    assert_eq!(clockwise.second_moments(), (ixx, iyy, ixy));
+    assert_eq!(triangle.moment_of_inertia(q64!(2)), (ixx + iyy) * q64!(2));
+}
+
+#[test]
+fn test_pca_obb_of_rotated_rectangle() {
+    let rect = QPolygon::new_from_parts(vec![qvec2!(-4.0, -1.0), qvec2!(4.0, -1.0), qvec2!(4.0, 1.0), qvec2!(-4.0, 1.0)]);
+    let angle = q64!(0.5);
+    let points: Vec<QVec2> = rect.resample_n(40).points().iter().map(|point| point.rotated_around(&QPoint::ZERO, angle).pos() + qvec2!(10.0, 5.0)).collect();
+    let obb = pca_obb(&points);
+    let (sin, cos) = angle.sin_cos();
+    assert!(obb.axis().cross(QVec2::new(cos, sin)).abs() < q64!(0.01));
+    assert!((obb.center().pos() - qvec2!(10.0, 5.0)).length() < q64!(0.01));
+    assert!((obb.half_extents() - qvec2!(4.0, 1.0)).length() < q64!(0.01));
+    let inflated = QObb::new(obb.center(), QDir::new_from_vec(obb.axis()), obb.half_extents() + QVec2::splat(q64!(0.001)));
+    assert!(points.iter().all(|p| inflated.is_point_inside(&QPoint::new(*p))));
+    assert_eq!(obb.get_polygon().points().len(), 4);
+}
+
+#[test]
+fn test_point_cloud_queries() {
+    let mut cloud = QPointCloud::new_from_parts(vec![qvec2!(1.0, 1.0), qvec2!(3.0, 1.0), qvec2!(3.0, 1.0), qvec2!(5.0, 1.0)]);
+    assert!(cloud.convex_hull().is_none());
+    assert_eq!(cloud.nearest(&QPoint::new(qvec2!(3.0, 2.0))), Some(1));
+    cloud.push(QPoint::new(qvec2!(3.0, 4.0)));
+    assert_eq!(cloud.len(), 5);
+    assert_eq!(cloud.get_bbox(), Some(QBbox::new_from_parts(qvec2!(1.0, 1.0), qvec2!(5.0, 4.0))));
+    assert_eq!(cloud.convex_hull().unwrap().points().len(), 3);
+    assert_eq!(cloud.within_radius(&QPoint::new(qvec2!(3.0, 2.0)), q64!(2)), cloud.to_kd_tree().within_radius(&QPoint::new(qvec2!(3.0, 2.0)), q64!(2)));
+    assert_eq!(cloud.to_polyline().points().len(), 5);
+    assert!(QPointCloud::default().get_centroid().is_none());
+}
+
+#[test]
+fn test_closest_pair_matches_brute_force() {
+    use qgeometry::generate::{ QRng, QSplitMix64 };
+    let mut rng = QSplitMix64::new(11);
+    for n in [2, 3, 4, 7, 50, 300] {
+        let points: Vec<QVec2> = (0..n).map(|_| QVec2::new(rng.next_in_range(q64!(-100)..=q64!(100)), rng.next_in_range(q64!(-100)..=q64!(100)))).collect();
+        let mut expected = Q64::MAX;
+        for i in 0..n {
+            for j in i + 1..n {
+                expected = expected.min(points[i].distance_squared(points[j]));
+            }
+        }
+        let (i, j, distance) = closest_pair(&points);
+        assert!(i < j);
+        assert_eq!(points[i].distance_squared(points[j]), expected);
+        assert_eq!(distance, points[i].distance(points[j]));
+    }
+
+    let duplicated = [qvec2!(1.0, 1.0), qvec2!(4.0, 4.0), qvec2!(1.0, 1.0)];
+    assert_eq!(closest_pair(&duplicated), (0, 2, Q64::ZERO));
+}
+
+#[test]
+fn test_largest_empty_circle() {
+    use qgeometry::generate::{ QRng, QSplitMix64 };
+    let mut rng = QSplitMix64::new(23);
+    let points: Vec<QVec2> = (0..60).map(|_| QVec2::new(rng.next_in_range(q64!(0)..=q64!(50)), rng.next_in_range(q64!(0)..=q64!(20)))).collect();
+    let bounds = QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(50.0, 20.0));
+    let circle = largest_empty_circle(&points, &bounds);
+    assert!(bounds.is_point_inside(&circle.center()));
+    assert!(points.iter().all(|p| p.distance(circle.center().pos()) > circle.radius() - q64!(0.001)));
+    for x in 0..=50 {
+        for y in 0..=20 {
+            let probe = qvec2!(x, y);
+            let clearance = points.iter().map(|p| p.distance(probe)).min().unwrap();
+            assert!(clearance <= circle.radius() + q64!(0.001));
+        }
+    }
+
+    let on_a_line = largest_empty_circle(&[qvec2!(1.0, 1.0), qvec2!(3.0, 1.0)], &QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 2.0)));
+    assert_eq!(on_a_line.radius(), QVec2::new(q64!(1), q64!(1)).length());
+}
+
+#[test]
+fn test_largest_inscribed_rect_stays_inside() {
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(8.0, 1.0), qvec2!(9.0, 6.0), qvec2!(5.0, 4.0), qvec2!(2.0, 7.0), qvec2!(-1.0, 3.0),
+    ]);
+    let coarse = polygon.largest_inscribed_rect(4).unwrap();
+    let fine = polygon.largest_inscribed_rect(32).unwrap();
+    assert!(fine.width() * fine.height() >= coarse.width() * coarse.height());
+    assert!(fine.width() * fine.height() > q64!(15));
+    for corner in fine.corners() {
+        assert!(polygon.is_point_inside(&corner));
+    }
+    assert!(QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 1.0)]).largest_inscribed_rect(8).is_none());
+}
+
+#[test]
+fn test_icp_recovers_rigid_motion() {
+    let target: Vec<QVec2> = QPolygon::star(QPoint::new(qvec2!(2.0, 1.0)), q64!(10), q64!(4), 5)
+        .resample_n(80)
+        .points()
+        .iter()
+        .map(|point| point.pos())
+        .collect();
+    let motion = QTransform::from_translation(qvec2!(0.2, -0.1)) * QTransform::from_rotation_around(qvec2!(2.0, 1.0), q64!(0.02));
+    let source: Vec<QVec2> = target.iter().map(|p| motion.transform_point(&QPoint::new(*p)).pos()).collect();
+    let transform = icp(&source, &target, QIcpOptions::new(50, q64!(3), Q64::EPS));
+    for (s, t) in source.iter().zip(&target) {
+        assert!((transform.transform_point(&QPoint::new(*s)).pos() - *t).length() < q64!(0.01));
+    }
+    assert_eq!(icp(&[], &target, QIcpOptions::default()), QTransform::IDENTITY);
+}
+
+#[test]
+fn test_estimate_similarity_and_rigid_fit() {
+    let motion = QTransform::from_scale_rotation_translation(QVec2::splat(q64!(1.5)), q64!(0.7), qvec2!(-3.0, 2.0));
+    let points = [qvec2!(0.0, 0.0), qvec2!(4.0, 1.0), qvec2!(2.0, 5.0), qvec2!(-1.0, 3.0), qvec2!(6.0, -2.0)];
+    let pairs: Vec<(QVec2, QVec2)> = points.iter().map(|p| (*p, motion.transform_point(&QPoint::new(*p)).pos())).collect();
+    let similarity = estimate_similarity(&pairs);
+    for (source, target) in &pairs {
+        assert!((similarity.transform_point(&QPoint::new(*source)).pos() - *target).length() < q64!(0.001));
+    }
+
+    let rigid = estimate_rigid(&pairs);
+    assert!((rigid.determinant() - Q64::ONE).abs() < q64!(0.001));
+    let (sin, cos) = q64!(0.7).sin_cos();
+    assert!((rigid.x_axis() - QVec2::new(cos, sin)).length() < q64!(0.001));
+    assert_eq!(estimate_similarity(&[]), QTransform::IDENTITY);
+}
+
+#[test]
+fn test_contains_point_convex_matches_linear_scan() {
+    use qgeometry::generate::{ random_convex_polygon, QSplitMix64 };
+    let mut rng = QSplitMix64::new(8);
+    for n in [3, 4, 9, 40] {
+        let polygon = random_convex_polygon(&mut rng, n, q64!(2)..=q64!(5));
+        let mut clockwise = polygon.clone();
+        clockwise.points_mut().reverse();
+        for x in -12..=12 {
+            for y in -12..=12 {
+                let point = QPoint::new(qvec2!(x, y).saturating_mul_num(Q64::HALF));
+                assert_eq!(polygon.contains_point_convex(&point), polygon.is_point_inside(&point));
+                assert_eq!(clockwise.contains_point_convex(&point), polygon.is_point_inside(&point));
+            }
+        }
+        assert!(polygon.points().iter().all(|vertex| polygon.contains_point_convex(vertex)));
+    }
+}
+
+#[test]
+fn test_dilate_erode_topology() {
+    // Two squares joined by a neck one unit wide.
+    let dumbbell = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 1.5), qvec2!(6.0, 1.5), qvec2!(6.0, 0.0), qvec2!(10.0, 0.0),
+        qvec2!(10.0, 4.0), qvec2!(6.0, 4.0), qvec2!(6.0, 2.5), qvec2!(4.0, 2.5), qvec2!(4.0, 4.0), qvec2!(0.0, 4.0),
+    ]);
+    let eroded = dumbbell.erode(Q64::ONE);
+    assert_eq!(eroded.len(), 2);
+    assert!(eroded.iter().all(|part| part.is_ccw()));
+    let centers = [QPoint::new(qvec2!(2.0, 2.0)), QPoint::new(qvec2!(8.0, 2.0))];
+    assert!(centers.iter().all(|center| eroded.iter().filter(|part| part.is_point_inside(center)).count() == 1));
+    assert!(eroded.iter().all(|part| !part.is_point_inside(&QPoint::new(qvec2!(5.0, 2.0)))));
+    assert_eq!(dumbbell.erode(Q64::ZERO), vec![dumbbell.clone()]);
+
+    // A ring with a gap one unit wide closes into a ring with a hole.
+    let c_shape = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 2.5), qvec2!(5.0, 2.5), qvec2!(5.0, 1.0), qvec2!(1.0, 1.0),
+        qvec2!(1.0, 5.0), qvec2!(5.0, 5.0), qvec2!(5.0, 3.5), qvec2!(6.0, 3.5), qvec2!(6.0, 6.0), qvec2!(0.0, 6.0),
+    ]);
+    let dilated = c_shape.dilate(Q64::ONE);
+    assert_eq!(dilated.len(), 1);
+    let (outer, holes) = &dilated[0];
+    assert_eq!(holes.len(), 1);
+    let hole = &holes[0];
+    assert!(outer.is_ccw() && !hole.is_ccw());
+    assert!(outer.is_point_inside(&QPoint::new(qvec2!(6.5, 3.0))));
+    assert!(!outer.is_point_inside(&QPoint::new(qvec2!(7.5, 3.0))));
+    assert!(hole.is_point_inside(&QPoint::new(qvec2!(3.0, 3.0))));
+    assert!(!hole.is_point_inside(&QPoint::new(qvec2!(5.5, 3.0))));
+    assert!(c_shape.erode(q64!(2)).is_empty());
+
+    // Exact at any scale, even for a radius far below the polygon size.
+    let large = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1000.0, 0.0), qvec2!(1000.0, 1000.0), qvec2!(0.0, 1000.0)]);
+    let (outer, holes) = &large.dilate(Q64::ONE)[0];
+    assert!(holes.is_empty());
+    assert!(outer.is_point_inside(&QPoint::new(qvec2!(1000.9, 500.0))));
+    assert!(!outer.is_point_inside(&QPoint::new(qvec2!(1001.1, 500.0))));
+    let eroded = large.erode(Q64::ONE);
+    assert!(eroded[0].is_point_inside(&QPoint::new(qvec2!(1.1, 500.0))));
+    assert!(!eroded[0].is_point_inside(&QPoint::new(qvec2!(0.9, 500.0))));
+}
+
+#[test]
+fn test_ray_hit_matches_intersect_shape() {
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 4.0), qvec2!(3.0, 1.0), qvec2!(0.0, 4.0)]);
+    let bbox = QBbox::new_from_parts(qvec2!(-1.0, -1.0), qvec2!(1.0, 1.0));
+    let circle = QCircle::new(QPoint::new(qvec2!(1.0, 2.0)), q64!(2));
+    for i in 0..16 {
+        let angle = Q64::TAU.saturating_mul(q64!(i)).saturating_div(q64!(16));
+        let (sin, cos) = angle.sin_cos();
+        let ray = QRay::new(QPoint::new(qvec2!(3.0, 6.0)), QDir::new_from_vec(QVec2::new(cos, sin)));
+        let hit = ray.cast_polygon(&polygon);
+        assert_eq!(hit.map(|hit| hit.t()), ray.intersect_shape(&polygon));
+        if let Some(hit) = hit {
+            assert!(hit.normal().to_vec().dot(ray.vector()) <= Q64::ZERO);
+            let index = hit.edge_index().unwrap();
+            let edge = QLine::new(polygon.points()[index], polygon.points()[(index + 1) % 5]);
+            assert!(edge.is_point_on_line_with_tolerance(&hit.point(), QTolerance::new(q64!(0.001))));
+        }
+
+        let outside = QRay::new(QPoint::new(qvec2!(-3.0, 0.5)), ray.dir());
+        let (cast, slab) = (outside.cast_bbox(&bbox).map(|hit| hit.t()), outside.intersect_bbox(&bbox));
+        assert_eq!(cast.is_some(), slab.is_some());
+        assert!(cast.zip(slab).is_none_or(|(cast, slab)| cast.saturating_sub(slab).abs() < q64!(0.001)));
+        if let Some(hit) = outside.cast_circle(&circle) {
+            let distance = hit.point().pos().distance(circle.center().pos());
+            assert!(distance.saturating_sub(circle.radius()).abs() < q64!(0.001));
+            assert!(hit.normal().to_vec().dot(outside.vector()) <= Q64::ZERO);
+        }
+    }
+    let inside = QRay::new(QPoint::new(qvec2!(1.0, 1.0)), QDir::new_from_vec(QVec2::X));
+    let hit = inside.cast_polygon(&polygon).unwrap();
+    assert_eq!((hit.t(), hit.point(), hit.edge_index()), (Q64::ZERO, inside.origin(), None));
+    assert_eq!(hit.normal().to_vec(), -QVec2::X);
+}
+
+#[test]
+fn test_ray_trace_bounces_inside_rooms() {
+    let room = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(8.0, 0.0), qvec2!(8.0, 8.0), qvec2!(0.0, 8.0)]);
+    let ray = QRay::new(QPoint::new(qvec2!(1.0, 2.0)), QDir::new_from_vec(qvec2!(1.0, 1.0)));
+    let path = ray.trace(&[QShape::QPolygon(room.clone())], 6, Q64::MAX);
+    assert_eq!(path.points().len(), 8);
+    let tolerance = QTolerance::new(q64!(0.001));
+    for point in &path.points()[1..] {
+        assert!(room.edges_iter().any(|edge| edge.is_point_on_line_with_tolerance(point, tolerance)));
+    }
+    // Every leg keeps the diagonal direction up to its signs.
+    for pair in path.points().windows(2) {
+        let leg = pair[1].pos().saturating_sub(pair[0].pos());
+        assert!(leg.x.abs().saturating_sub(leg.y.abs()).abs() < q64!(0.001));
+    }
+
+    let circle = QShape::QCircle(QCircle::new(QPoint::ZERO, q64!(4)));
+    let mirror = QShape::QLine(QLine::new_from_parts(qvec2!(-1.0, -1.0), qvec2!(1.0, -1.0)));
+    let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(qvec2!(1.0, 2.0)));
+    let path = ray.trace(&[circle, mirror], 10, q64!(30));
+    let length = path.points().windows(2).fold(Q64::ZERO, |acc, pair| acc + pair[0].pos().distance(pair[1].pos()));
+    assert!(length <= q64!(30.01));
+    assert!(path.points().len() >= 4);
+    for point in &path.points()[1..path.points().len() - 1] {
+        let on_circle = point.pos().length().saturating_sub(q64!(4)).abs() < q64!(0.01);
+        let on_mirror = point.y().saturating_add(Q64::ONE).abs() < q64!(0.01);
+        assert!(on_circle || on_mirror);
+    }
+}
+
+#[test]
+fn test_shadow_polygons_cover_blocked_points() {
+    let occluder = QPolygon::new_from_parts(vec![
+        qvec2!(2.0, 1.0), qvec2!(6.0, 1.0), qvec2!(6.0, 5.0), qvec2!(4.0, 2.5), qvec2!(2.0, 5.0),
+    ]);
+    let light = QPoint::new(qvec2!(0.3, -2.7));
+    let range = q64!(20);
+    let shadows = shadow_polygons(light, &occluder, range);
+    assert!(!shadows.is_empty());
+    assert!(shadows.iter().all(|shadow| shadow.is_ccw()));
+    for x in -4..24 {
+        for y in -4..24 {
+            let point = QPoint::new(QVec2::new(q64!(x) + q64!(0.37), q64!(y) + q64!(0.61)));
+            if point.pos().distance(light.pos()) > range || occluder.is_point_inside(&point) {
+                continue;
+            }
+            let sight = QLine::new(light, point);
+            let is_blocked = occluder.edges_iter().any(|edge| edge.is_crossing(&sight));
+            let is_shadowed = shadows.iter().any(|shadow| shadow.is_point_inside(&point));
+            assert_eq!(is_shadowed, is_blocked, "point {point:?}");
+        }
+    }
+    let mut clockwise = occluder.clone();
+    clockwise.points_mut().reverse();
+    assert_eq!(shadow_polygons(light, &clockwise, range).len(), shadows.len());
+    assert!(shadow_polygons(QPoint::new(qvec2!(3.0, 2.0)), &occluder, range).is_empty());
+
+    // Both ends of the wall are out of range but its middle is not.
+    let wall = QPolygon::new_from_parts(vec![qvec2!(4.0, -10.0), qvec2!(5.0, -10.0), qvec2!(5.0, 10.0), qvec2!(4.0, 10.0)]);
+    let shadows = shadow_polygons(QPoint::ZERO, &wall, q64!(6));
+    assert_eq!(shadows.len(), 1);
+    assert!(shadows[0].is_point_inside(&QPoint::new(qvec2!(5.9, 0.0))));
+    assert!(shadows[0].is_point_inside(&QPoint::new(qvec2!(4.5, 3.0))));
+    assert!(!shadows[0].is_point_inside(&QPoint::new(qvec2!(3.0, 0.0))));
+}
+
+#[test]
+fn test_field_of_view_matches_line_of_sight() {
+    let occluders = [
+        QPolygon::new_from_parts(vec![qvec2!(3.0, -2.0), qvec2!(5.0, -2.0), qvec2!(5.0, 3.0), qvec2!(4.0, 0.5), qvec2!(3.0, 3.0)]),
+        QPolygon::new_from_parts(vec![qvec2!(-4.0, 4.0), qvec2!(-1.0, 5.0), qvec2!(-3.0, 6.0)]),
+        QPolygon::new_from_parts(vec![qvec2!(-6.0, -3.0), qvec2!(-5.0, -3.0), qvec2!(-5.0, -1.0), qvec2!(-6.0, -1.0)]),
+    ];
+    let origin = QPoint::new(qvec2!(0.2, 0.1));
+    let radius = q64!(9);
+    for (angle, dir) in [(q64!(2.5), qvec2!(1.0, 0.5)), (Q64::TAU, QVec2::X)] {
+        let dir = QDir::new_from_vec(dir);
+        let view = field_of_view(origin, dir, angle, radius, &occluders);
+        assert_eq!(view.points()[0] == origin, angle < Q64::TAU);
+        for x in -10..=10 {
+            for y in -10..=10 {
+                let point = QPoint::new(QVec2::new(q64!(x) + q64!(0.13), q64!(y) + q64!(0.29)));
+                let offset = point.pos().saturating_sub(origin.pos());
+                let distance = offset.length();
+                let off_axis = atan2(dir.to_vec().cross(offset), dir.to_vec().dot(offset)).abs();
+                let half = angle * Q64::HALF;
+                if (distance - radius).abs() < q64!(0.1) || (angle < Q64::TAU && (off_axis - half).abs() < q64!(0.01)) {
+                    continue;
+                }
+                let sight = QLine::new(origin, point);
+                let is_blocked = occluders.iter().any(|occluder| occluder.edges_iter().any(|edge| edge.is_crossing(&sight)));
+                let is_visible = distance < radius && off_axis < half && !is_blocked;
+                assert_eq!(view.is_point_inside(&point), is_visible, "point {point:?} angle {angle:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_clip_to_viewport() {
+    let view = QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(10.0, 6.0));
+    let zigzag = QPolyline::new_from_parts(vec![qvec2!(-2.0, 1.0), qvec2!(3.0, 1.0), qvec2!(3.0, 8.0), qvec2!(5.0, 8.0), qvec2!(5.0, 2.0), qvec2!(12.0, 2.0)]);
+    let cover = QPolygon::new_from_parts(vec![qvec2!(-1.0, -1.0), qvec2!(11.0, -1.0), qvec2!(11.0, 7.0), qvec2!(-1.0, 7.0)]);
+    let notched = QPolygon::new_from_parts(vec![qvec2!(8.0, 1.0), qvec2!(14.0, 1.0), qvec2!(14.0, 5.0), qvec2!(8.0, 5.0), qvec2!(12.0, 3.0)]);
+    let shapes = vec![
+        QShape::QPolyline(zigzag),
+        QShape::QPolygon(cover),
+        QShape::QPolygon(notched),
+        QShape::QBbox(QBbox::new_from_parts(qvec2!(9.0, 5.0), qvec2!(12.0, 9.0))),
+        QShape::QLine(QLine::new_from_parts(qvec2!(-3.0, -3.0), qvec2!(-1.0, 20.0))),
+        QShape::QCircle(QCircle::new(QPoint::new(qvec2!(10.0, 6.0)), Q64::ONE)),
+        // Only touches the right border.
+        QShape::QBbox(QBbox::new_from_parts(qvec2!(10.0, 0.0), qvec2!(12.0, 5.0))),
+    ];
+    let visible = clip_to_viewport(&shapes, &view);
+    assert_eq!(visible.len(), 6);
+    assert!(visible.iter().all(|shape| matches!(shape, QShape::QCircle(_)) || view.is_bbox_inside(&shape.get_bbox())));
+
+    // The polyline leaves the view through the top and comes back.
+    let (QShape::QPolyline(first), QShape::QPolyline(second)) = (&visible[0], &visible[1]) else {
+        panic!("expected two polylines, got {visible:?}");
+    };
+    assert_eq!(first.points(), &vec![QPoint::new(qvec2!(0.0, 1.0)), QPoint::new(qvec2!(3.0, 1.0)), QPoint::new(qvec2!(3.0, 6.0))]);
+    assert_eq!(second.points(), &vec![QPoint::new(qvec2!(5.0, 6.0)), QPoint::new(qvec2!(5.0, 2.0)), QPoint::new(qvec2!(10.0, 2.0))]);
+
+    let QShape::QPolygon(covered) = &visible[2] else { panic!() };
+    assert!((covered.area() - q64!(60)).abs() < q64!(0.001));
+    // Only the two thin wedges beside the notch are visible, joined along the border.
+    let QShape::QPolygon(notch) = &visible[3] else { panic!() };
+    assert!((notch.area() - q64!(2)).abs() < q64!(0.001));
+    assert_eq!(visible[4], QShape::QBbox(QBbox::new_from_parts(qvec2!(9.0, 5.0), qvec2!(10.0, 6.0))));
+    assert_eq!(visible[5], shapes[5]);
+}
+
+#[test]
+fn test_clip_segments_matches_sampling() {
+    let bbox = QBbox::new_from_parts(qvec2!(-3.0, -2.0), qvec2!(4.0, 5.0));
+    let ends = [qvec2!(-6.0, -4.5), qvec2!(-1.5, 7.0), qvec2!(0.5, 0.5), qvec2!(6.0, 1.0), qvec2!(2.0, -5.0), qvec2!(4.0, 5.0), qvec2!(-3.0, 3.0)];
+    let lines: Vec<QLine> = ends
+        .iter()
+        .flat_map(|a| ends.iter().filter(move |b| *b != a).map(move |b| QLine::new_from_parts(*a, *b)))
+        .collect();
+    let clipped = clip_segments(&lines, &bbox);
+    assert_eq!(clipped.len(), lines.len());
+    for (line, clipped) in lines.iter().zip(&clipped) {
+        let inside_samples: Vec<QPoint> = (1..64)
+            .map(|i| QPoint::new(line.start().pos() + line.vector().saturating_mul_num(q64!(i) / q64!(64))))
+            .filter(|point| bbox.inflated(-q64!(0.01)).is_point_inside(point))
+            .collect();
+        match clipped {
+            None => assert!(inside_samples.is_empty(), "{line:?} was rejected"),
+            Some(part) => {
+                assert!(bbox.is_point_inside(&part.start()) && bbox.is_point_inside(&part.end()));
+                let tolerance = QTolerance::new(q64!(0.001));
+                assert!(line.is_point_on_line_with_tolerance(&part.start(), tolerance));
+                assert!(line.is_point_on_line_with_tolerance(&part.end(), tolerance));
+                assert!(part.vector().dot(line.vector()) > Q64::ZERO);
+                assert!(inside_samples.iter().all(|point| part.is_point_on_line_with_tolerance(point, tolerance)));
+            }
+        }
+    }
+    assert!(clipped.contains(&None));
+    // Segments inside are passed through untouched.
+    let inner = lines.iter().position(|line| *line == QLine::new_from_parts(qvec2!(0.5, 0.5), qvec2!(-3.0, 3.0))).unwrap();
+    assert_eq!(clipped[inner], Some(lines[inner]));
+    // Passing outside a corner leaves nothing, not a piece of the border.
+    let corner = QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 4.0));
+    assert_eq!(clip_segments(&[QLine::new_from_parts(qvec2!(-2.0, 3.0), qvec2!(5.0, 7.0))], &corner), vec![None]);
+}
+
+#[test]
+fn test_slice_cuts_every_crossing() {
+    let u_shape = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(6.0, 0.0), qvec2!(6.0, 6.0), qvec2!(4.0, 6.0),
+        qvec2!(4.0, 2.0), qvec2!(2.0, 2.0), qvec2!(2.0, 6.0), qvec2!(0.0, 6.0),
+    ]);
+    // The stretch across the gap between the arms is outside and cuts nothing.
+    let parts = u_shape.slice(&QPolyline::new_from_parts(vec![qvec2!(-1.0, 4.0), qvec2!(7.0, 4.0)]));
+    assert_eq!(parts.len(), 3);
+    let mut areas: Vec<Q64> = parts.iter().map(|part| part.area()).collect();
+    areas.sort();
+    assert_eq!(areas, vec![q64!(4), q64!(4), q64!(20)]);
+    assert!(parts.iter().all(|part| part.is_ccw()));
+
+    // A zigzag through a clockwise square cuts it into three strips, the dangling end cuts nothing.
+    let square = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(0.0, 4.0), qvec2!(4.0, 4.0), qvec2!(4.0, 0.0)]);
+    let zigzag = QPolyline::new_from_parts(vec![
+        qvec2!(1.0, -1.0), qvec2!(1.0, 5.0), qvec2!(2.0, 5.0), qvec2!(2.0, -1.0), qvec2!(3.0, -1.0), qvec2!(3.0, 2.0),
+    ]);
+    let parts = square.slice(&zigzag);
+    assert_eq!(parts.len(), 3);
+    let total = parts.iter().fold(Q64::ZERO, |acc, part| acc + part.area());
+    assert_eq!(total, q64!(16));
+    for probe in [qvec2!(0.5, 2.0), qvec2!(1.5, 2.0), qvec2!(3.0, 3.0)] {
+        assert_eq!(parts.iter().filter(|part| part.is_point_inside(&QPoint::new(probe))).count(), 1);
+    }
+}
+
+#[test]
+fn test_fracture_partitions_by_nearest_seed() {
+    let l_shape = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(8.0, 0.0), qvec2!(8.0, 2.0), qvec2!(2.0, 2.0), qvec2!(2.0, 8.0), qvec2!(0.0, 8.0),
+    ]);
+    let seeds = [qvec2!(1.0, 1.0), qvec2!(7.0, 0.5), qvec2!(0.5, 7.0), qvec2!(4.0, 4.0)];
+    let parts = fracture(&l_shape, &seeds);
+    // The seed in the notch gets a part of both arms.
+    assert_eq!(parts.len(), 5);
+    assert!(parts.iter().all(|part| part.is_ccw()));
+    let total = parts.iter().fold(Q64::ZERO, |acc, part| acc + part.area());
+    assert!((total - l_shape.area()).abs() < q64!(0.001));
+
+    // Cells are convex, so a part is in a cell if all its vertices are.
+    for part in &parts {
+        assert!(seeds.iter().any(|owner| part.points().iter().all(|point| {
+            let distance = point.pos().distance(*owner);
+            seeds.iter().all(|seed| point.pos().distance(*seed) >= distance - q64!(0.001))
+        })));
+    }
+    for x in 0..16 {
+        for y in 0..16 {
+            let probe = QPoint::new(qvec2!(0.2371, 0.1289) + QVec2::new(q64!(x), q64!(y)).saturating_mul_num(Q64::HALF));
+            let covering = parts.iter().filter(|part| part.is_point_inside(&probe)).count();
+            assert_eq!(covering, usize::from(l_shape.is_point_inside(&probe)), "{probe:?}");
+        }
+    }
+}
+
+#[test]
+fn test_hull_of_touches_circles_at_tangents() {
+    let shapes = [
+        QShape::from(QCircle::new(QPoint::new(qvec2!(-3.0, 0.0)), q64!(2))),
+        QShape::from(QCircle::new(QPoint::new(qvec2!(5.0, 1.0)), Q64::ONE)),
+        QShape::from(QBbox::new_from_parts(qvec2!(0.0, 3.0), qvec2!(2.0, 5.0))),
+        QShape::from(QLine::new_from_parts(qvec2!(1.0, -4.0), qvec2!(2.0, -1.0))),
+    ];
+    let hull = hull_of(&shapes);
+    let points = hull.points();
+    let n = points.len();
+    assert!(n >= 3);
+    // Strictly convex and counter-clockwise.
+    for i in 0..n {
+        assert_eq!(orient2d(points[i].pos(), points[(i + 1) % n].pos(), points[(i + 2) % n].pos()), core::cmp::Ordering::Greater);
+    }
+    // Every boundary point of every shape is inside, and every hull vertex is on a shape.
+    let tolerance = QTolerance::new(q64!(0.001));
+    for shape in &shapes {
+        for k in 0..100 {
+            let point = shape.boundary_point(q64!(k) / q64!(100));
+            assert!(hull.is_point_inside_with_tolerance(&point, tolerance), "{point:?}");
+        }
+    }
+    // Hull vertices stay within the circumscribed bound of r * (1 / cos(π / 32) - 1).
+    for point in points {
+        assert!(shapes.iter().any(|shape| shape.closest_point(point).distance(point) <= q64!(0.01)), "{point:?}");
+    }
+    // The edge from the bbox corner runs along its exact tangent to the left circle.
+    let QShape::QCircle(left) = &shapes[0] else { panic!() };
+    let touches: Vec<QPoint> = left.tangents_from_point(&QPoint::new(qvec2!(0.0, 5.0))).iter().map(|line| line.end()).collect();
+    assert!(touches.iter().any(|touch| hull.edges_iter().any(|edge| edge.is_point_on_line_with_tolerance(touch, tolerance))));
+}
+
+#[test]
+fn test_hull_options_are_order_independent() {
+    let mut points = vec![
+        qvec2!(0.0, 0.0), qvec2!(3.0, 0.0), qvec2!(1.0, 0.0), qvec2!(3.0, 3.0), qvec2!(0.0, 3.0),
+        qvec2!(0.0, 1.0), qvec2!(1.0, 1.0), qvec2!(3.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 0.0),
+    ];
+    let options = [
+        QHullOptions::DEFAULT,
+        QHullOptions::new(true, true, false),
+        QHullOptions::new(false, false, true),
+        QHullOptions::new(true, false, true),
+    ];
+    let expected: Vec<Vec<QVec2>> = options.iter().map(|options| andrew_graham_scan_with_options(&points, *options)).collect();
+    assert_eq!(expected[0], vec![qvec2!(0.0, 0.0), qvec2!(3.0, 0.0), qvec2!(3.0, 3.0), qvec2!(0.0, 3.0)]);
+    assert_eq!(expected[1].len(), 5);
+    assert_eq!(expected[1][0], expected[1][4]);
+    assert_eq!(expected[2], vec![qvec2!(0.0, 0.0), qvec2!(0.0, 1.0), qvec2!(0.0, 3.0), qvec2!(3.0, 3.0), qvec2!(3.0, 0.0), qvec2!(1.0, 0.0)]);
+    for _ in 0..points.len() {
+        points.rotate_left(3);
+        points.reverse();
+        for (options, expected) in options.iter().zip(&expected) {
+            assert_eq!(&andrew_graham_scan_with_options(&points, *options), expected);
+        }
+    }
+    // A line keeps its inner points only when asked to, small inputs are sorted too.
+    let line = vec![qvec2!(2.0, 2.0), qvec2!(0.0, 0.0), qvec2!(1.0, 1.0)];
+    assert_eq!(andrew_graham_scan_with_options(&line, QHullOptions::DEFAULT), vec![qvec2!(0.0, 0.0), qvec2!(2.0, 2.0)]);
+    assert_eq!(andrew_graham_scan_with_options(&line, QHullOptions::new(false, true, true)), vec![qvec2!(0.0, 0.0), qvec2!(1.0, 1.0), qvec2!(2.0, 2.0)]);
+    assert_eq!(andrew_graham_scan(&vec![qvec2!(1.0, 0.0), qvec2!(0.0, 5.0)]), vec![qvec2!(0.0, 5.0), qvec2!(1.0, 0.0)]);
+}
+
+#[test]
+fn test_gjk_diagnostics_agree_with_gjk() {
+    let circle = QCircle::new(QPoint::ZERO, q64!(2));
+    for i in 0..24 {
+        let (sin, cos) = (q64!(i) / q64!(24) * Q64::TAU).sin_cos();
+        for distance in [q64!(1), q64!(3.9), q64!(4.1), q64!(7)] {
+            let other = QCircle::new(QPoint::new(QVec2::new(cos, sin).saturating_mul_num(distance)), q64!(2));
+            let (result, iterations) = gjk_with_diagnostics(&circle, &other, GJK_MAX_ITERATIONS);
+            assert!(iterations <= GJK_MAX_ITERATIONS);
+            // The radii add up to 4, closer centers overlap.
+            if distance < q64!(4) {
+                assert_eq!(result, QGjkResult::Intersecting);
+                assert!(gjk(&circle, &other));
+            } else {
+                let QGjkResult::Separated { distance: gap } = result else {
+                    panic!("circles {distance} apart should be separated, got {result:?}");
+                };
+                assert!(gap > Q64::ZERO && gap <= distance - q64!(4) + q64!(0.001));
+                assert!(!gjk(&circle, &other));
+            }
+            // Capped runs stop early and report where they got to.
+            if iterations > 1 {
+                let (capped, capped_iterations) = gjk_with_diagnostics(&circle, &other, 1);
+                assert_eq!(capped_iterations, 1);
+                assert!(matches!(capped, QGjkResult::MaxIterations { last_simplex } if !last_simplex.is_empty()));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_concave_compound_collides_per_child() {
+    // An L made of two boxes, the notch at (1..2, 1..2) is empty.
+    let mut l_shape = QCompound::new(QVec2::ZERO, vec![]);
+    l_shape.add_child(QVec2::ZERO, QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(2.0, 1.0)));
+    l_shape.add_child(QVec2::ZERO, QBbox::new_from_parts(qvec2!(0.0, 1.0), qvec2!(1.0, 2.0)));
+    let in_notch = QCircle::new(QPoint::new(qvec2!(1.6, 1.6)), q64!(0.3));
+    assert!(!l_shape.is_collide(&in_notch));
+    assert!(!in_notch.is_collide(&l_shape));
+    assert_eq!(in_notch.try_get_seperation_vector(&l_shape), None);
+
+    let overlapping = QCircle::new(QPoint::new(qvec2!(1.5, 1.2)), q64!(0.3));
+    assert!(l_shape.is_collide(&overlapping) && overlapping.is_collide(&l_shape));
+    let vector = l_shape.try_get_seperation_vector(&overlapping).unwrap();
+    assert!(vector.y > Q64::ZERO);
+    assert_eq!(overlapping.try_get_seperation_vector(&l_shape), Some(-vector));
+}
+
+#[test]
+fn test_transformed_shape_uses_base_support() {
+    let circle = QCircle::new(QPoint::ZERO, Q64::ONE);
+    let transform = QTransform::from_translation(qvec2!(5.0, 0.0)) * QTransform::from_scale(qvec2!(2.0, 1.0));
+    let ellipse = QTransformedShape::new(circle, transform);
+
+    let right = ellipse.support(QDir::new_from_vec(QVec2::X)).pos();
+    assert!((right.x - q64!(7)).abs() < q64!(0.001) && right.y.abs() < q64!(0.001));
+    let bbox = ellipse.get_bbox();
+    assert!((bbox.left_bottom().x() - q64!(3)).abs() < q64!(0.001));
+    assert!((bbox.right_top().y() - Q64::ONE).abs() < q64!(0.001));
+    assert_eq!(ellipse.get_shape_type(), QShapeType::QPolygon);
+
+    let probe = QCircle::new(QPoint::new(qvec2!(7.5, 0.0)), q64!(0.6));
+    assert!(ellipse.is_collide(&probe));
+    assert!(!ellipse.is_collide(&QCircle::new(QPoint::new(qvec2!(5.0, 1.5)), q64!(0.4))));
+
+    let moved = QTransformedShape::new(circle, QTransform::from_translation(qvec2!(5.0, 0.0)));
+    let closest = moved.closest_point(&QPoint::new(qvec2!(8.0, 0.0))).pos();
+    assert!((closest.x - q64!(6)).abs() < q64!(0.001));
+}
+
+#[test]
+#[should_panic(expected = "[QPolygon::scaled] factor")]
+fn test_polygon_rejects_mirroring_scale() {
+    let triangle = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(0.0, 1.0)]);
+    triangle.scaled(-Q64::ONE);
+}
@@ -0,0 +1,37 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+
+#[test]
+fn test_float_round_trip() {
+    let line = QLine::from_f64([0.0, 0.5], [2.0, -1.25]);
+    assert_eq!(line.start().pos(), qvec2!(0.0, 0.5));
+    assert_eq!(line.to_f64(), [[0.0, 0.5], [2.0, -1.25]]);
+
+    let bbox = QBbox::from_f32([-1.0, -1.0], [3.0, 2.0]);
+    assert_eq!(bbox.width(), q64!(4));
+    assert_eq!(bbox.to_f32(), [[-1.0, -1.0], [3.0, 2.0]]);
+
+    let circle = QCircle::from_f64([1.0, 1.0], 0.5);
+    assert_eq!(circle.radius(), Q64::HALF);
+    assert_eq!(circle.to_f64(), ([1.0, 1.0], 0.5));
+
+    let coordinates = [[0.0, 0.0], [4.0, 0.0], [4.0, 3.0]];
+    let polygon = QPolygon::from_f64(&coordinates);
+    assert_eq!(polygon.points()[2].pos(), qvec2!(4.0, 3.0));
+    assert_eq!(polygon.to_f64(), coordinates.to_vec());
+    assert_eq!(QPoint::to_f32_vec(polygon.points()), vec![[0.0f32, 0.0], [4.0, 0.0], [4.0, 3.0]]);
+}
+
+#[test]
+fn test_float_rounds_to_nearest() {
+    let eps = Q64::EPS.to_num::<f64>();
+    let point = QPoint::from_f64([eps * 0.75, -eps * 0.25]);
+    assert_eq!(point.pos(), QVec2::new(Q64::EPS, Q64::ZERO));
+}
+
+#[test]
+#[should_panic]
+fn test_float_rejects_nan() {
+    QPoint::from_f64([f64::NAN, 0.0]);
+}
@@ -0,0 +1,88 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::algorithm::*;
+use qgeometry::generate::*;
+use qgeometry::cluster::*;
+
+#[test]
+fn test_random_convex_polygons_are_convex_and_reproducible() {
+    let mut rng = QSplitMix64::new(42);
+    for n in 3..40 {
+        let polygon = random_convex_polygon(&mut rng, n, q64!(1)..=q64!(4));
+        let ring: Vec<_> = polygon.points().iter().map(|point| point.pos()).collect();
+        assert!(ring.len() >= 3 && ring.len() <= n);
+        assert_eq!(andrew_graham_scan(&ring).len(), ring.len());
+        let farthest = ring.iter().map(|p| p.length()).max().unwrap();
+        assert!(farthest >= q64!(0.99) && farthest <= q64!(4.01));
+    }
+
+    let a = random_convex_polygon(&mut QSplitMix64::new(3), 16, q64!(1)..=q64!(1));
+    let b = random_convex_polygon(&mut QSplitMix64::new(3), 16, q64!(1)..=q64!(1));
+    assert_eq!(a, b);
+    assert!(gjk(&a, &a.translated(qvec2!(0.5, 0.0))));
+}
+
+#[test]
+fn test_sample_interior_stays_inside_and_spreads_by_area() {
+    let mut rng = QSplitMix64::new(9);
+    let circle = QCircle::new(QPoint::new(qvec2!(3.0, -1.0)), q64!(2));
+    let bbox = QBbox::new_from_parts(qvec2!(-1.0, -1.0), qvec2!(1.0, 3.0));
+    let line = QLine::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 2.0));
+    for _ in 0..200 {
+        assert!(circle.is_point_inside(&circle.sample_interior(&mut rng)));
+        assert!(bbox.is_point_inside(&bbox.sample_interior(&mut rng)));
+        assert!(line.is_point_on_line_with_tolerance(&line.sample_interior(&mut rng), QTolerance::new(q64!(0.001))));
+    }
+
+    // Each column holds 3/7 of the area, the bridge between them the rest.
+    let shape = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(1.0, 0.5), qvec2!(3.0, 0.5), qvec2!(3.0, 0.0), qvec2!(4.0, 0.0),
+        qvec2!(4.0, 3.0), qvec2!(3.0, 3.0), qvec2!(3.0, 1.0), qvec2!(1.0, 1.0), qvec2!(1.0, 3.0), qvec2!(0.0, 3.0),
+    ]);
+    let samples: Vec<QPoint> = (0..2000).map(|_| shape.sample_interior(&mut rng)).collect();
+    assert!(samples.iter().all(|point| shape.is_point_inside(point)));
+    let left = samples.iter().filter(|point| point.x() < Q64::ONE).count();
+    assert!(left > 760 && left < 960, "left samples: {left}");
+}
+
+#[test]
+fn test_poisson_disk_keeps_min_distance_inside_shape() {
+    let shape = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(12.0, 0.0), qvec2!(12.0, 12.0), qvec2!(6.0, 3.0), qvec2!(0.0, 12.0),
+    ]);
+    let min_distance = Q64::ONE;
+    let points = poisson_disk(&shape, min_distance, &mut QSplitMix64::new(11));
+    assert!(points.len() > 30);
+    assert!(points.iter().all(|point| shape.is_point_inside(point)));
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            assert!(points[i].distance(&points[j]) >= min_distance - q64!(0.001));
+        }
+    }
+    assert_eq!(points, poisson_disk(&shape, min_distance, &mut QSplitMix64::new(11)));
+}
+
+#[test]
+fn test_kmeans_separates_blobs_reproducibly() {
+    let mut rng = QSplitMix64::new(5);
+    let centers = [qvec2!(-20.0, 0.0), qvec2!(20.0, 0.0), qvec2!(0.0, 30.0)];
+    let mut points = vec![];
+    for center in centers {
+        let blob = QCircle::new(QPoint::new(center), q64!(3));
+        points.extend((0..30).map(|_| blob.sample_interior(&mut rng)));
+    }
+    let (assignments, centroids) = kmeans(&points, 3, 50, &mut QSplitMix64::new(1));
+    assert_eq!(centroids.len(), 3);
+    for blob in 0..3 {
+        let cluster = assignments[blob * 30];
+        assert!(assignments[blob * 30..(blob + 1) * 30].iter().all(|&a| a == cluster));
+        assert!(centroids[cluster].pos().distance(centers[blob]) < q64!(3));
+    }
+    assert_eq!(kmeans(&points, 3, 50, &mut QSplitMix64::new(1)), (assignments, centroids));
+
+    let duplicates = vec![QPoint::new(QVec2::ONE); 4];
+    let (assignments, centroids) = kmeans(&duplicates, 3, 10, &mut rng);
+    assert_eq!(assignments, vec![0; 4]);
+    assert_eq!(centroids, vec![QPoint::new(QVec2::ONE)]);
+}
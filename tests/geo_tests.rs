@@ -0,0 +1,36 @@
+#![cfg(feature = "geo-types")]
+
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::geo::QGeoTypesError;
+
+#[test]
+fn test_geo_types_round_trip() {
+    let point = QPoint::new(qvec2!(1.5, -2.0));
+    let geo_point: geo_types::Point<f64> = point.into();
+    assert_eq!(geo_point, geo_types::Point::new(1.5, -2.0));
+    assert_eq!(QPoint::try_from(geo_point), Ok(point));
+
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0)]);
+    let geo_polygon = geo_types::Polygon::from(&polygon);
+    assert_eq!(geo_polygon.exterior().0.len(), 4);
+    assert_eq!(QPolygon::try_from(&geo_polygon), Ok(polygon));
+
+    let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(2.0, 1.0));
+    assert_eq!(QBbox::try_from(geo_types::Rect::from(bbox)), Ok(bbox));
+}
+
+#[test]
+fn test_geo_types_errors() {
+    assert!(matches!(QPoint::try_from(geo_types::Point::new(f64::NAN, 0.0)), Err(QGeoTypesError::OutOfRange(_))));
+    assert!(matches!(QPoint::try_from(geo_types::Point::new(1e300, 0.0)), Err(QGeoTypesError::OutOfRange(_))));
+
+    let exterior = geo_types::LineString::from(vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)]);
+    let hole = geo_types::LineString::from(vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0)]);
+    let with_hole = geo_types::Polygon::new(exterior, vec![hole]);
+    assert_eq!(QPolygon::try_from(&with_hole), Err(QGeoTypesError::PolygonHasHoles));
+
+    let flat = geo_types::Rect::new(geo_types::coord! { x: 0.0, y: 1.0 }, geo_types::coord! { x: 2.0, y: 1.0 });
+    assert_eq!(QBbox::try_from(flat), Err(QGeoTypesError::EmptyRect));
+}
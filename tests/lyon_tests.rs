@@ -0,0 +1,47 @@
+#![cfg(feature = "lyon")]
+
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::lyon::{ fill_mesh, shapes_from_path, stroke_mesh };
+use lyon::math::point;
+use lyon::path::Path;
+
+#[test]
+fn test_lyon_path_round_trip() {
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0)]);
+    let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 1.0), qvec2!(2.0, 0.0)]);
+
+    assert_eq!(shapes_from_path(&Path::from(&polygon), 0.01).unwrap(), vec![QShape::QPolygon(polygon)]);
+    assert_eq!(shapes_from_path(&Path::from(&polyline), 0.01).unwrap(), vec![QShape::QPolyline(polyline)]);
+}
+
+#[test]
+fn test_lyon_fill_with_hole_and_curve() {
+    let mut builder = Path::builder();
+    builder.begin(point(0.0, 0.0));
+    builder.line_to(point(4.0, 0.0));
+    builder.quadratic_bezier_to(point(6.0, 2.0), point(4.0, 4.0));
+    builder.line_to(point(0.0, 4.0));
+    builder.end(true);
+    builder.begin(point(1.0, 1.0));
+    builder.line_to(point(1.0, 2.0));
+    builder.line_to(point(2.0, 2.0));
+    builder.line_to(point(2.0, 1.0));
+    builder.end(true);
+    let path = builder.build();
+
+    let mesh = fill_mesh(&path, 0.05).unwrap();
+    let hole_center = QPoint::new(qvec2!(1.5, 1.5));
+    for [a, b, c] in mesh.indices() {
+        let triangle = QPolygon::new_from_parts(vec![mesh.positions()[*a as usize], mesh.positions()[*b as usize], mesh.positions()[*c as usize]]);
+        assert!(!triangle.is_point_inside(&hole_center));
+    }
+    assert!(mesh.positions().iter().any(|p| p.x > q64!(4.5)));
+
+    let shapes = shapes_from_path(&path, 0.05).unwrap();
+    assert_eq!(shapes.len(), 2);
+    assert!(shapes[0].points().len() > 4);
+
+    assert!(!stroke_mesh(&path, 0.5, 0.05).unwrap().is_empty());
+}
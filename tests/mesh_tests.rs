@@ -0,0 +1,76 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+
+fn is_ccw(positions: &[QVec2], [a, b, c]: [u32; 3]) -> bool {
+    let (pa, pb, pc) = (positions[a as usize], positions[b as usize], positions[c as usize]);
+    (pb - pa).cross(pc - pa) > Q64::ZERO
+}
+
+#[test]
+fn test_mesh_polygon_is_ccw() {
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 4.0), qvec2!(2.0, 1.0), qvec2!(0.0, 4.0)]);
+    let mesh = polygon.to_mesh(0);
+
+    assert_eq!(mesh.positions().len(), 5);
+    assert_eq!(mesh.indices().len(), 3);
+    assert!(mesh.indices().iter().all(|t| is_ccw(mesh.positions(), *t)));
+    assert!(mesh.uvs().is_none());
+}
+
+#[test]
+fn test_mesh_compound_and_uvs() {
+    let mut compound = QCompound::new(QVec2::ZERO, vec![]);
+    compound.add_child(QVec2::ZERO, QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE));
+    compound.add_child(qvec2!(3.0, 0.0), QCircle::new(QPoint::ZERO, Q64::ONE));
+    let mesh = compound.to_mesh(8).with_planar_uvs(Q64::HALF);
+
+    assert_eq!(mesh.positions().len(), 4 + 8);
+    assert_eq!(mesh.indices().len(), 2 + 6);
+    assert!(mesh.indices()[2..].iter().all(|t| t.iter().all(|&i| i >= 4)));
+    assert_eq!(mesh.uvs().unwrap()[2], qvec2!(0.5, 0.5));
+}
+
+#[test]
+fn test_mesh_without_area() {
+    let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(1.0, 1.0)]);
+    let mesh = polyline.to_mesh(0);
+    assert_eq!(mesh.positions().len(), 3);
+    assert!(mesh.is_empty());
+}
+
+#[test]
+fn test_mesh_resolution_of_curved_shapes() {
+    let ellipse = QEllipse::new(QPoint::ZERO, qvec2!(2.0, 1.0));
+    let capsule = QCapsule::new_from_parts(QVec2::ZERO, qvec2!(0.0, 3.0), Q64::HALF);
+    let spline = QSpline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 2.0), qvec2!(3.0, 2.0), qvec2!(4.0, 0.0)]);
+    for resolution in [4, 12, 40] {
+        let mesh = ellipse.to_mesh(resolution);
+        assert_eq!(mesh.positions().len(), resolution);
+        assert!(mesh.indices().iter().all(|t| is_ccw(mesh.positions(), *t)));
+
+        let mesh = capsule.to_mesh(resolution);
+        assert_eq!(mesh.positions().len(), resolution + 2);
+        assert!(mesh.indices().iter().all(|t| is_ccw(mesh.positions(), *t)));
+        assert!(mesh.positions().iter().all(|p| capsule.is_point_inside(&QPoint::new(p.saturating_mul_num(q64!(0.999))))));
+
+        assert_eq!(spline.to_mesh(resolution).positions().len(), 3 * resolution + 1);
+    }
+}
+
+#[test]
+fn test_bbox_uvs_match_positions() {
+    let bbox = QBbox::new_from_parts(qvec2!(2.0, 2.0), qvec2!(6.0, 4.0));
+    let mesh = bbox.to_mesh(0).with_bbox_uvs();
+    assert_eq!(mesh.uvs().unwrap().len(), mesh.positions().len());
+
+    let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0)]);
+    assert!(polyline.to_mesh(0).with_bbox_uvs().uvs().is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_spline_rejects_tension_out_of_range() {
+    let mut spline = QSpline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 2.0), qvec2!(3.0, 2.0)]);
+    spline.set_tension(q64!(2));
+}
@@ -0,0 +1,93 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::nav::*;
+
+fn get_comb() -> QPolygon {
+    // Three teeth hanging from a bar along the top.
+    QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(1.0, 4.0), qvec2!(3.0, 4.0), qvec2!(3.0, 0.0), qvec2!(4.0, 0.0),
+        qvec2!(4.0, 4.0), qvec2!(6.0, 4.0), qvec2!(6.0, 0.0), qvec2!(7.0, 0.0), qvec2!(7.0, 5.0), qvec2!(0.0, 5.0),
+    ])
+}
+
+#[test]
+fn test_navmesh_find_path_through_comb() {
+    let comb = get_comb();
+    let navmesh = QNavMesh::from_polygon(&comb);
+    assert_eq!(navmesh.len(), 10);
+    // Every inner edge is shared by exactly two triangles.
+    let links: usize = (0..navmesh.len()).map(|i| navmesh.neighbors(i).iter().flatten().count()).sum();
+    assert_eq!(links, 2 * (navmesh.len() - 1));
+
+    let (start, goal) = (QPoint::new(qvec2!(0.5, 0.5)), QPoint::new(qvec2!(6.5, 0.5)));
+    let corridor = navmesh.find_path(&start, &goal).unwrap();
+    assert_eq!(corridor.triangles()[0], navmesh.locate(&start).unwrap());
+    assert_eq!(*corridor.triangles().last().unwrap(), navmesh.locate(&goal).unwrap());
+    for (pair, (left, right)) in corridor.triangles().windows(2).zip(corridor.portals()) {
+        assert!(navmesh.neighbors(pair[0]).contains(&Some(pair[1])));
+        // The next triangle lies ahead, the portal's left end is left of the walk.
+        let ahead = navmesh.triangles()[pair[1]].iter().fold(QVec2::ZERO, |acc, p| acc + *p);
+        assert_eq!(qgeometry::algorithm::orient2d(right.pos(), left.pos(), ahead.saturating_mul_num(Q64::ONE / q64!(3))), core::cmp::Ordering::Less);
+    }
+    let points = corridor.points();
+    assert_eq!(points.first(), Some(&start));
+    assert_eq!(points.last(), Some(&goal));
+    assert!(points.iter().all(|point| comb.is_point_inside(point)));
+    // The walk has to climb over the bar.
+    assert!(points.iter().any(|point| point.y() > q64!(4)));
+
+    let same = navmesh.find_path(&start, &QPoint::new(qvec2!(0.52, 0.5))).unwrap();
+    assert!(same.portals().is_empty());
+    assert_eq!(same.points().len(), 2);
+    assert!(navmesh.find_path(&start, &QPoint::new(qvec2!(2.0, 1.0))).is_none());
+    assert!(QNavMesh::default().find_path(&start, &goal).is_none());
+}
+
+#[test]
+fn test_smooth_path_stays_inside_and_shortens() {
+    let comb = get_comb();
+    let navmesh = QNavMesh::from_polygon(&comb);
+    let corridor = navmesh.find_path(&QPoint::new(qvec2!(0.5, 0.5)), &QPoint::new(qvec2!(6.5, 0.5))).unwrap();
+    let length = |path: &[QPoint]| path.windows(2).fold(Q64::ZERO, |acc, pair| acc + pair[0].pos().distance(pair[1].pos()));
+    for radius in [Q64::ZERO, q64!(0.25)] {
+        let path = smooth_path(&corridor, radius);
+        assert_eq!(path.first(), Some(&corridor.start()));
+        assert_eq!(path.last(), Some(&corridor.goal()));
+        assert!(length(&path) <= length(&corridor.points()));
+        for pair in path.windows(2) {
+            let leg = QLine::new(pair[0], pair[1]);
+            assert!(comb.edges_iter().all(|edge| !edge.is_crossing(&leg)));
+            assert!(comb.is_point_inside(&QPoint::new(pair[0].pos().midpoint(pair[1].pos()))));
+        }
+        for point in &path[1..path.len() - 1] {
+            assert!(comb.points().iter().all(|corner| corner.pos().distance(point.pos()) + q64!(0.001) >= radius));
+        }
+    }
+    // Without clearance the path wraps tightly over the two inner teeth.
+    let tight = smooth_path(&corridor, Q64::ZERO);
+    assert!(tight.contains(&QPoint::new(qvec2!(1.0, 4.0))));
+    assert!(tight.contains(&QPoint::new(qvec2!(6.0, 4.0))));
+
+    // The gaps between the teeth are the obstacles.
+    let gaps = [
+        QShape::QBbox(QBbox::new_from_parts(qvec2!(1.0, 0.0), qvec2!(3.0, 4.0))),
+        QShape::QBbox(QBbox::new_from_parts(qvec2!(4.0, 0.0), qvec2!(6.0, 4.0))),
+    ];
+    let radius = q64!(0.25);
+    let points = corridor.points();
+    let shortcut = shortcut_path(&points, &gaps, radius);
+    assert_eq!(shortcut.first(), Some(&corridor.start()));
+    assert_eq!(shortcut.last(), Some(&corridor.goal()));
+    assert!(shortcut.len() > 2 && shortcut.len() <= points.len());
+    // Skipping legs are clear, blocked legs of the input are kept as they are.
+    for pair in shortcut.windows(2) {
+        let leg = QLine::new(pair[0], pair[1]);
+        let is_input_leg = points.windows(2).any(|input| input == pair);
+        assert!(is_input_leg || gaps.iter().all(|gap| gap.edges_iter().all(|edge| leg.distance_to_segment(&edge) >= radius)));
+    }
+    let open = [QShape::QCircle(QCircle::new(QPoint::new(qvec2!(3.5, 8.0)), Q64::ONE))];
+    assert_eq!(shortcut_path(&points, &open, Q64::ZERO), vec![corridor.start(), corridor.goal()]);
+    assert_eq!(shortcut_path(&tight, &gaps, Q64::ZERO), tight);
+    assert!(shortcut_path(&[], &gaps, Q64::ZERO).is_empty());
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "parry2d")]
+
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::parry::{ from_parry_cuboid, to_parry_cuboid, QParryError };
+use parry2d::math::{ Isometry, Point, Vector };
+use parry2d::shape::{ ConvexPolygon, Cuboid, Segment };
+
+#[test]
+fn test_parry_round_trip() {
+    let line = QLine::new_from_parts(qvec2!(0.0, 0.5), qvec2!(2.0, -1.0));
+    assert_eq!(QLine::try_from(&Segment::from(&line)), Ok(line));
+
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0), qvec2!(0.0, 3.0)]);
+    let convex = ConvexPolygon::try_from(&polygon).unwrap();
+    assert_eq!(convex.points().len(), 4);
+    assert_eq!(QPolygon::try_from(&convex).unwrap().points().len(), 4);
+
+    let bbox = QBbox::new_from_parts(qvec2!(-1.0, 0.0), qvec2!(3.0, 2.0));
+    let (position, cuboid) = to_parry_cuboid(&bbox);
+    assert_eq!(cuboid.half_extents, Vector::new(2.0, 1.0));
+    assert_eq!(from_parry_cuboid(&position, &cuboid), Ok(bbox));
+}
+
+#[test]
+fn test_parry_errors() {
+    let concave = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(2.0, 1.0), qvec2!(4.0, 4.0), qvec2!(0.0, 4.0)]);
+    assert_eq!(ConvexPolygon::try_from(&concave).err(), Some(QParryError::NotConvex));
+
+    let segment = Segment::new(Point::new(1.0, 1.0), Point::new(1.0, 1.0));
+    assert_eq!(QLine::try_from(&segment), Err(QParryError::Degenerate));
+
+    let rotated = Isometry::new(Vector::new(0.0, 0.0), 0.5);
+    assert_eq!(from_parry_cuboid(&rotated, &Cuboid::new(Vector::new(1.0, 1.0))), Err(QParryError::Rotated));
+    assert_eq!(from_parry_cuboid(&Isometry::identity(), &Cuboid::new(Vector::new(0.0, 1.0))), Err(QParryError::Degenerate));
+}
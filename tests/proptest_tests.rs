@@ -0,0 +1,38 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use qmath::prelude::*;
+use qgeometry::prelude::*;
+use qgeometry::algorithm::gjk;
+use qgeometry::arbitrary::strategy;
+
+proptest! {
+    #[test]
+    fn test_strategy_polygon_is_simple(polygon in strategy::polygon(12)) {
+        let points = polygon.points();
+        let n = points.len();
+        prop_assert!(n >= 3);
+        for i in 0..n {
+            for j in i + 2..n {
+                if i == 0 && j == n - 1 {
+                    continue;
+                }
+                let a = QLine::new(points[i], points[(i + 1) % n]);
+                let b = QLine::new(points[j], points[(j + 1) % n]);
+                prop_assert!(!a.is_crossing(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_strategy_gjk_is_symmetric(a in strategy::polygon(8), b in strategy::circle()) {
+        prop_assert_eq!(gjk(&a, &b), gjk(&b, &a));
+    }
+
+    #[test]
+    fn test_strategy_shapes_are_valid(line in strategy::line(), circle in strategy::circle(), bbox in strategy::bbox()) {
+        prop_assert!(line.start() != line.end());
+        prop_assert!(circle.radius() > Q64::ZERO);
+        prop_assert!(bbox.width() > Q64::ZERO && bbox.height() > Q64::ZERO);
+    }
+}
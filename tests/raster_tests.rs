@@ -0,0 +1,112 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::raster::*;
+use qgeometry::sdf::QSdfGrid;
+
+fn cell_center(col: i64, row: i64) -> QPoint {
+    QPoint::new(QVec2::new(q64!(col) + Q64::HALF, q64!(row) + Q64::HALF))
+}
+
+#[test]
+fn test_cells_covered_is_conservative() {
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.2, 0.2), qvec2!(8.7, 0.2), qvec2!(8.7, 6.4), qvec2!(4.5, 1.6), qvec2!(0.2, 6.4),
+    ]);
+    let cells: Vec<(i64, i64)> = cells_covered(&polygon, Q64::ONE).collect();
+    for col in -1..10 {
+        for row in -1..8 {
+            if polygon.is_point_inside(&cell_center(col, row)) {
+                assert!(cells.contains(&(col, row)), "missing cell ({col}, {row})");
+            }
+        }
+    }
+    // The notch between the two arms stays empty.
+    assert!(!cells.contains(&(4, 5)));
+    let mut sorted = cells.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), cells.len());
+
+    let circle = QCircle::new(QPoint::new(qvec2!(0.5, 0.5)), q64!(2));
+    let cells: Vec<(i64, i64)> = cells_covered(&circle, Q64::ONE).collect();
+    assert!(cells.contains(&(2, 0)) && cells.contains(&(-2, 0)));
+    assert!(!cells.contains(&(2, 2)) && !cells.contains(&(-2, -2)));
+    assert_eq!(cells_covered(&QPoint::new(qvec2!(-0.5, 3.5)), Q64::ONE).collect::<Vec<_>>(), vec![(-1, 3)]);
+}
+
+#[test]
+fn test_scanlines_match_point_queries() {
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0), qvec2!(9.0, 0.0), qvec2!(9.0, 7.0), qvec2!(6.0, 2.0), qvec2!(4.5, 6.0), qvec2!(3.0, 2.0), qvec2!(0.0, 7.0),
+    ]);
+    let row_height = Q64::HALF;
+    let spans: Vec<(Q64, Q64, Q64)> = polygon.scanlines(row_height).collect();
+    assert_eq!(spans.iter().filter(|span| span.0 == q64!(0.25)).count(), 1);
+    assert_eq!(spans.iter().filter(|span| span.0 == q64!(4.25)).count(), 3);
+    for i in 0..40 {
+        for j in 0..16 {
+            let point = QPoint::new(QVec2::new(q64!(i) * q64!(0.25) + q64!(0.01), q64!(j) * row_height + q64!(0.25)));
+            let in_span = spans.iter().any(|&(y, x_start, x_end)| y == point.y() && x_start <= point.x() && point.x() <= x_end);
+            assert_eq!(in_span, polygon.is_point_inside(&point), "{point:?}");
+        }
+    }
+}
+
+fn ring_area(polygon: &QPolygon) -> Q64 {
+    let points = polygon.points();
+    let n = points.len();
+    (0..n).fold(Q64::ZERO, |acc, i| acc + points[i].pos().cross(points[(i + 1) % n].pos())) * Q64::HALF
+}
+
+#[test]
+fn test_marching_squares_recovers_baked_shapes() {
+    use qgeometry::sdf::{ self, QSdfSubtraction };
+
+    let bbox = QBbox::new_from_parts(qvec2!(-5.0, -5.0), qvec2!(5.0, 5.0));
+    let disk = QCircle::new(QPoint::ZERO, q64!(4));
+    let polygons = marching_squares(&sdf::bake(&disk, &bbox, q64!(0.25)), Q64::ZERO, QTolerance::EXACT);
+    assert_eq!(polygons.len(), 1);
+    assert!((ring_area(&polygons[0]) - q64!(50.265)).abs() < q64!(0.5));
+    assert!(polygons[0].points().iter().all(|point| (point.pos().length() - q64!(4)).abs() < q64!(0.05)));
+
+    let simplified = marching_squares(&sdf::bake(&disk, &bbox, q64!(0.25)), Q64::ZERO, QTolerance::new(q64!(0.05)));
+    assert!(simplified[0].points().len() < polygons[0].points().len());
+
+    let ring = QSdfSubtraction::new(disk, QCircle::new(QPoint::ZERO, q64!(2)));
+    let mut polygons = marching_squares(&sdf::bake(&ring, &bbox, q64!(0.25)), Q64::ZERO, QTolerance::EXACT);
+    polygons.sort_by_key(|polygon| ring_area(polygon));
+    assert_eq!(polygons.len(), 2);
+    assert!(ring_area(&polygons[0]) < Q64::ZERO && ring_area(&polygons[1]) > Q64::ZERO);
+
+    // Regions touching the grid border are closed along it.
+    let everything = marching_squares(&QSdfGrid::new(QVec2::ZERO, Q64::ONE, 3, 3, vec![-Q64::ONE; 9]), Q64::ZERO, QTolerance::EXACT);
+    assert_eq!(everything.len(), 1);
+    assert_eq!(ring_area(&everything[0]), q64!(4));
+}
+
+#[test]
+fn test_ray_grid_traversal_reaches_target() {
+    let origin = QPoint::new(qvec2!(0.3, 0.7));
+    for target in [qvec2!(7.9, 3.2), qvec2!(-5.6, 2.3), qvec2!(-2.2, -6.1), qvec2!(4.1, -0.45), qvec2!(0.6, 5.4)] {
+        let target = QPoint::new(target);
+        let ray = QRay::new_towards(origin, target);
+        let end = (target.x().floor().to_num::<i64>(), target.y().floor().to_num::<i64>());
+        let mut cells: Vec<(i64, i64)> = ray_grid_traversal(&ray, Q64::ONE).take(64).take_while(|cell| *cell != end).collect();
+        cells.push(end);
+        let (start_col, start_row) = cells[0];
+        assert_eq!(cells.len() as i64, (end.0 - start_col).abs() + (end.1 - start_row).abs() + 1);
+        for pair in cells.windows(2) {
+            assert_eq!((pair[1].0 - pair[0].0).abs() + (pair[1].1 - pair[0].1).abs(), 1);
+        }
+        let path = QLine::new(origin, target);
+        for (col, row) in cells {
+            let center = cell_center(col, row);
+            assert!(path.get_distance_from_point(&center) < q64!(0.71));
+        }
+    }
+
+    let ray = QRay::new(QPoint::new(qvec2!(-1.0, 3.0)), qmath::dir::QDir::new_from_vec(-QVec2::Y));
+    let cells: Vec<(i64, i64)> = ray_grid_traversal(&ray, q64!(2)).take(3).collect();
+    assert_eq!(cells, vec![(-1, 1), (-1, 0), (-1, -1)]);
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "std")]
+
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::algorithm::andrew_graham_scan;
+use qgeometry::scalar::{ gjk_distance, QScalarBbox, QScalarCircle, QScalarPolygon, QScalarShape, QScalarVec2 };
+
+const L_SHAPE: [[f64; 2]; 6] = [[0.0, 0.0], [4.0, 0.0], [4.0, 2.0], [2.0, 2.0], [2.0, 4.0], [0.0, 4.0]];
+
+fn float_l_shape() -> QScalarPolygon<f64> {
+    QScalarPolygon::new(L_SHAPE.iter().map(|pos| QScalarVec2::new(pos[0], pos[1])).collect())
+}
+
+#[test]
+fn test_scalar_polygon_agrees_on_f64_and_q64() {
+    let polygon = QPolygon::from_f64(&L_SHAPE);
+    let float = float_l_shape();
+    let fixed: QScalarPolygon<Q64> = QScalarPolygon::from_qpolygon(&polygon);
+
+    assert_eq!(float.area(), 12.0);
+    assert_eq!(fixed.area(), polygon.area());
+    assert_eq!(float.get_centroid(), QScalarVec2::new(2.0, 2.0));
+    assert_eq!(fixed.get_centroid().to_qvec2(), polygon.get_centroid().pos());
+    assert_eq!(float.get_bbox().max(), QScalarVec2::new(4.0, 4.0));
+    assert_eq!(fixed.get_bbox().to_qbbox(), QBbox::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 4.0)));
+
+    for pos in [[1.0, 1.0], [3.0, 3.0], [3.0, 1.0], [2.0, 3.0], [4.0, 4.0], [-1.0, 0.0], [1.0, 4.0]] {
+        let point = QPoint::from_f64(pos);
+        let inside = polygon.is_point_inside(&point);
+        assert_eq!(float.is_point_inside(QScalarVec2::new(pos[0], pos[1])), inside, "{pos:?}");
+        assert_eq!(fixed.is_point_inside(QScalarVec2::from_qvec2(point.pos())), inside, "{pos:?}");
+    }
+
+    let positions: Vec<QVec2> = polygon.points().iter().map(|point| point.pos()).collect();
+    let hull = andrew_graham_scan(&positions);
+    assert_eq!(fixed.convex_hull().to_qpolygon().points().iter().map(|point| point.pos()).collect::<Vec<_>>(), hull);
+    assert_eq!(float.convex_hull().to_qpolygon(), fixed.convex_hull().to_qpolygon());
+}
+
+#[test]
+fn test_scalar_gjk_agrees_on_f64_and_q64() {
+    let polygon = QPolygon::from_f64(&L_SHAPE);
+    let float = float_l_shape();
+    let fixed: QScalarPolygon<Q64> = QScalarPolygon::from_qpolygon(&polygon);
+
+    let apart = QCircle::from_f64([7.0, 1.0], 1.0);
+    let float_apart = QScalarCircle::new(QScalarVec2::new(7.0, 1.0), 1.0);
+    assert!(!float.is_collide(&float_apart));
+    assert!((float.distance_to(&float_apart) - 2.0).abs() < 1e-9);
+    let fixed_apart = QScalarCircle::from_qcircle(&apart);
+    assert!(!fixed.is_collide(&fixed_apart));
+    assert!((fixed.distance_to(&fixed_apart) - polygon.distance_to(&apart)).abs() <= q64!(0.001));
+    assert!((fixed.distance_to(&fixed_apart) - q64!(2)).abs() <= q64!(0.001));
+
+    let overlapping = QCircle::from_f64([4.5, 1.0], 1.0);
+    assert!(polygon.is_collide(&overlapping));
+    assert!(float.is_collide(&QScalarCircle::new(QScalarVec2::new(4.5, 1.0), 1.0)));
+    assert!(fixed.is_collide(&QScalarCircle::from_qcircle(&overlapping)));
+    assert_eq!(fixed.distance_to(&QScalarCircle::from_qcircle(&overlapping)), Q64::ZERO);
+
+    // `f32` runs the same code, with its own tolerance.
+    let narrow: QScalarBbox<f32> = QScalarBbox::new(QScalarVec2::new(0.0, 0.0), QScalarVec2::new(1.0, 1.0));
+    let far = QScalarCircle::new(QScalarVec2::new(4.0, 5.0), 1.0f32);
+    assert!((gjk_distance(&narrow, &far) - 4.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_scalar_round_trip() {
+    let polygon = QPolygon::from_f64(&L_SHAPE);
+    assert_eq!(QScalarPolygon::<f64>::from_qpolygon(&polygon).to_qpolygon(), polygon);
+    assert_eq!(float_l_shape().to_qpolygon(), polygon);
+
+    let circle = QCircle::from_f64([1.0, 1.0], 0.5);
+    let float_circle = QScalarCircle::<f64>::from_qcircle(&circle);
+    assert_eq!(float_circle.radius(), 0.5);
+    assert_eq!(float_circle.to_qcircle(), circle);
+
+    let bbox = QBbox::from_f32([-1.0, -1.0], [3.0, 2.0]);
+    assert_eq!(QScalarBbox::<f32>::from_qbbox(&bbox).to_qbbox(), bbox);
+}
+
+#[test]
+#[should_panic(expected = "[QScalarCircle::new] radius")]
+fn test_scalar_circle_rejects_non_positive_radius() {
+    QScalarCircle::new(QScalarVec2::new(0.0, 0.0), 0.0);
+}
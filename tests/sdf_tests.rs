@@ -0,0 +1,59 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::sdf::{ self, QSdf };
+
+#[test]
+fn test_signed_distance_sign() {
+    let polygon = QPolygon::new_from_parts(vec![
+        qvec2!(0.0, 0.0),
+        qvec2!(4.0, 0.0),
+        qvec2!(4.0, 4.0),
+        qvec2!(0.0, 4.0),
+    ]);
+
+    assert_eq!(polygon.signed_distance(&QPoint::new(qvec2!(1.0, 2.0))), q64!(-1));
+    assert_eq!(polygon.signed_distance(&QPoint::new(qvec2!(2.0, 7.0))), q64!(3));
+    assert_eq!(polygon.signed_distance(&QPoint::new(qvec2!(4.0, 2.0))), Q64::ZERO);
+}
+
+#[test]
+fn test_signed_distance_combinators() {
+    let a = QCircle::new(QPoint::ZERO, q64!(2));
+    let b = QCircle::new(QPoint::new(qvec2!(3.0, 0.0)), q64!(2));
+    let inside_a_only = QPoint::new(qvec2!(-1.5, 0.0));
+    let inside_both = QPoint::new(qvec2!(1.5, 0.0));
+
+    let union = a.sdf_union(b);
+    let intersection = a.sdf_intersection(b);
+    let subtraction = a.sdf_subtraction(b);
+
+    assert!(union.signed_distance(&inside_a_only) < Q64::ZERO);
+    assert!(intersection.signed_distance(&inside_a_only) > Q64::ZERO);
+    assert!(intersection.signed_distance(&inside_both) < Q64::ZERO);
+    assert!(subtraction.signed_distance(&inside_a_only) < Q64::ZERO);
+    assert!(subtraction.signed_distance(&inside_both) > Q64::ZERO);
+}
+
+#[test]
+fn test_bake_matches_samples() {
+    let bbox = QBbox::new_from_parts(QVec2::ZERO, qvec2!(4.0, 4.0));
+    let grid = sdf::bake(&bbox, &QBbox::new_from_parts(qvec2!(-2.0, -2.0), qvec2!(6.0, 6.0)), Q64::ONE);
+
+    assert_eq!(grid.columns(), 9);
+    assert_eq!(grid.rows(), 9);
+    assert_eq!(grid.sample(&QPoint::new(qvec2!(2.0, 1.0))), q64!(-1));
+    assert_eq!(grid.sample(&QPoint::new(qvec2!(2.0, 0.5))), q64!(-0.5));
+    // Outside the grid the border samples are used.
+    assert_eq!(grid.sample(&QPoint::new(qvec2!(2.0, 100.0))), q64!(2));
+}
+
+#[test]
+fn test_bake_normal_points_outward() {
+    let circle = QCircle::new(QPoint::ZERO, q64!(2));
+    let grid = sdf::bake(&circle, &QBbox::new_from_parts(qvec2!(-4.0, -4.0), qvec2!(4.0, 4.0)), q64!(0.5));
+
+    let gradient = grid.gradient(&QPoint::new(qvec2!(2.0, 0.0)));
+    assert!(gradient.x > Q64::ZERO);
+    assert!(grid.normal(&QPoint::new(qvec2!(0.0, 3.0))).is_some());
+}
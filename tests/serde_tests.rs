@@ -0,0 +1,46 @@
+#![cfg(feature = "serde")]
+
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+
+#[test]
+fn test_shape_round_trip() {
+    let shapes = vec![
+        QShape::from(QPoint::new(qvec2!(1.0, 2.0))),
+        QShape::from(QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE)),
+        QShape::from(QCircle::new(QPoint::ZERO, q64!(0.5))),
+        QShape::from(QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 0.0), qvec2!(0.0, 1.0)])),
+    ];
+
+    let json = serde_json::to_string(&shapes).unwrap();
+    let decoded: Vec<QShape> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, shapes);
+}
+
+#[test]
+fn test_triangulation_round_trip() {
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(2.0, 2.0), qvec2!(0.0, 2.0)]);
+    let triangles = polygon.ear_clipping_triangulation();
+
+    let json = serde_json::to_string(&(polygon.clone(), triangles.clone())).unwrap();
+    let decoded: (QPolygon, Vec<usize>) = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, (polygon, triangles));
+}
+
+#[test]
+fn test_deserialized_polygon_queries_match() {
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0), qvec2!(0.0, 3.0)]);
+    let decoded: QPolygon = serde_json::from_str(&serde_json::to_string(&polygon).unwrap()).unwrap();
+    assert_eq!(decoded.scanlines(Q64::ONE).collect::<Vec<_>>(), polygon.scanlines(Q64::ONE).collect::<Vec<_>>());
+    assert_eq!(decoded.largest_inscribed_rect(4), polygon.largest_inscribed_rect(4));
+    assert!(decoded.largest_inscribed_rect(4).is_some());
+}
+
+#[test]
+fn test_polygon_version_round_trip() {
+    let mut polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(2.0, 0.0), qvec2!(0.0, 2.0)]);
+    polygon.set_vertex(1, QPoint::new(qvec2!(3.0, 0.0)));
+    let decoded: QPolygon = serde_json::from_str(&serde_json::to_string(&polygon).unwrap()).unwrap();
+    assert_eq!(decoded.triangulation_version(), polygon.triangulation_version());
+}
@@ -0,0 +1,233 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::spatial::*;
+
+#[test]
+fn test_spatial_hash_remove() {
+    let mut grid = QSpatialHash::new(q64!(2));
+    grid.insert(1, QBbox::new_from_parts(QVec2::ZERO, qvec2!(5.0, 5.0)));
+    grid.insert(2, QBbox::new_from_parts(qvec2!(1.0, 1.0), qvec2!(2.0, 2.0)));
+    assert_eq!(grid.len(), 2);
+
+    assert!(grid.remove(&1).is_some());
+    assert!(grid.remove(&1).is_none());
+    assert_eq!(grid.query_bbox(&QBbox::new_from_parts(QVec2::ZERO, qvec2!(5.0, 5.0))), vec![2]);
+}
+
+#[test]
+fn test_spatial_hash_negative_coordinates() {
+    // Cells on the negative side of the origin must not alias the positive ones
+    let mut grid = QSpatialHash::new(Q64::ONE);
+    grid.insert(1, QBbox::new_from_parts(qvec2!(-0.8, -0.8), qvec2!(-0.2, -0.2)));
+    grid.insert(2, QBbox::new_from_parts(qvec2!(0.2, 0.2), qvec2!(0.8, 0.8)));
+
+    assert_eq!(grid.query_point(&QPoint::new(qvec2!(-0.5, -0.5))), vec![1]);
+    assert_eq!(grid.query_point(&QPoint::new(qvec2!(0.5, 0.5))), vec![2]);
+}
+
+#[test]
+fn test_dynamic_tree_fat_bbox_absorbs_small_moves() {
+    let mut tree = QDynamicTree::new(Q64::HALF);
+    let proxy = tree.insert(QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE), 7);
+    assert_eq!(tree.fat_bbox(proxy), QBbox::new_from_parts(qvec2!(-0.5, -0.5), qvec2!(1.5, 1.5)));
+
+    // Still inside the fat bbox, nothing to do
+    assert!(!tree.update(proxy, QBbox::new_from_parts(qvec2!(0.25, 0.25), qvec2!(1.25, 1.25))));
+    // Leaves the fat bbox, the leaf is reinserted
+    assert!(tree.update(proxy, QBbox::new_from_parts(qvec2!(3.0, 3.0), qvec2!(4.0, 4.0))));
+    assert_eq!(tree.query_point(&QPoint::new(qvec2!(3.5, 3.5))), vec![proxy]);
+    assert_eq!(tree.data(proxy), Some(&7));
+}
+
+#[test]
+fn test_dynamic_tree_insert_remove_many() {
+    let mut tree = QDynamicTree::new(Q64::ZERO);
+    let mut proxies = vec![];
+    for i in 0..32 {
+        let min = qvec2!(i, 0);
+        proxies.push(tree.insert(QBbox::new_from_parts(min, min + QVec2::splat(Q64::HALF)), i));
+    }
+    assert_eq!(tree.len(), 32);
+    assert!(tree.height() > 1);
+
+    for proxy in proxies.iter().step_by(2) {
+        tree.remove(*proxy);
+    }
+    assert_eq!(tree.len(), 16);
+    let hits = tree.query_bbox(&QBbox::new_from_parts(qvec2!(-1.0, -1.0), qvec2!(40.0, 1.0)));
+    assert_eq!(hits.len(), 16);
+    assert!(hits.iter().all(|proxy| tree.data(*proxy).unwrap() % 2 == 1));
+}
+
+#[test]
+fn test_dynamic_tree_query_ray() {
+    let mut tree = QDynamicTree::new(Q64::ZERO);
+    let far = tree.insert(QBbox::new_from_parts(qvec2!(8.0, -1.0), qvec2!(9.0, 1.0)), ());
+    let near = tree.insert(QBbox::new_from_parts(qvec2!(2.0, -1.0), qvec2!(3.0, 1.0)), ());
+    tree.insert(QBbox::new_from_parts(qvec2!(2.0, 5.0), qvec2!(3.0, 6.0)), ());
+
+    let ray = QRay::new(QPoint::ZERO, qmath::dir::QDir::new_from_vec(QVec2::X));
+    assert_eq!(tree.query_ray(&ray, Q64::MAX), vec![near, far]);
+    assert_eq!(tree.query_ray(&ray, q64!(5)), vec![near]);
+}
+
+#[test]
+fn test_rtree_empty_and_single() {
+    let empty: QRTree<()> = QRTree::new(vec![]);
+    assert!(empty.is_empty());
+    assert_eq!(empty.nearest(&QPoint::ZERO), None);
+    assert!(empty.query_point(&QPoint::ZERO).is_empty());
+
+    let single = QRTree::new(vec![(QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE), "a")]);
+    assert_eq!(single.query_point(&QPoint::new(QVec2::splat(Q64::HALF))), vec![0]);
+    assert_eq!(single.nearest(&QPoint::new(qvec2!(-5.0, -5.0))), Some(0));
+}
+
+#[test]
+fn test_rtree_matches_linear_scan() {
+    let items: Vec<(QBbox, usize)> = (0..200)
+        .map(|i| {
+            let min = qvec2!((i * 7) % 50, (i * 13) % 50);
+            (QBbox::new_from_parts(min, min + QVec2::ONE), i)
+        })
+        .collect();
+    let tree = QRTree::new_with_capacity(items.clone(), 8);
+
+    let region = QBbox::new_from_parts(qvec2!(10.0, 10.0), qvec2!(20.0, 25.0));
+    let mut hits = tree.query_bbox(&region);
+    hits.sort();
+    let expected: Vec<usize> = items.iter().filter(|(bbox, _)| bbox.is_overlap(&region)).map(|(_, i)| *i).collect();
+    assert_eq!(hits, expected);
+}
+
+#[test]
+fn test_kd_tree_matches_linear_scan() {
+    let points: Vec<QPoint> = (0..100)
+        .map(|i| QPoint::new(qvec2!((i * 37) % 23, (i * 11) % 19)))
+        .collect();
+    let tree = QKdTree::new(points.clone());
+    let query = QPoint::new(qvec2!(7.3, 9.6));
+
+    let mut expected: Vec<(Q64, usize)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.pos().distance_squared(query.pos()), i))
+        .collect();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let expected: Vec<usize> = expected.iter().take(5).map(|(_, i)| *i).collect();
+    assert_eq!(tree.k_nearest(&query, 5), expected);
+
+    let radius = q64!(3);
+    let expected: Vec<usize> = (0..points.len())
+        .filter(|i| points[*i].pos().distance_squared(query.pos()) <= radius.saturating_mul(radius))
+        .collect();
+    assert_eq!(tree.within_radius(&query, radius), expected);
+}
+
+#[test]
+fn test_trapezoidal_map_matches_point_in_polygon() {
+    let outer = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(12.0, 1.0), qvec2!(11.0, 9.0), qvec2!(6.0, 5.0), qvec2!(1.0, 10.0)]);
+    let hole = QPolygon::new_from_parts(vec![qvec2!(2.0, 2.0), qvec2!(5.0, 2.0), qvec2!(5.0, 4.0), qvec2!(2.0, 5.0)]);
+    let island = QPolygon::new_from_parts(vec![qvec2!(3.0, 3.0), qvec2!(4.0, 3.0), qvec2!(3.0, 4.0)]);
+    let neighbour = QPolygon::new_from_parts(vec![qvec2!(12.0, 1.0), qvec2!(16.0, 2.0), qvec2!(11.0, 9.0)]);
+    let polygons = [outer, hole, island, neighbour];
+    let map = QTrapezoidalMap::new(&polygons);
+    assert_eq!(map.len(), 15);
+    // The shared edge is stored once.
+    assert!(map.trapezoid_count() <= 3 * 14 + 1);
+    for x in -2..=34 {
+        for y in -2..=22 {
+            let point = QPoint::new(qvec2!(x, y).saturating_mul_num(Q64::HALF));
+            let expected = (0..polygons.len()).rev().find(|&i| polygons[i].is_point_inside(&point));
+            let located = map.locate(&point);
+            // On a shared or nested boundary either side is a valid answer.
+            if located != expected {
+                assert!(polygons[located.unwrap()].is_point_inside(&point) && polygons[expected.unwrap()].is_point_inside(&point));
+            }
+        }
+    }
+
+    let lines = QTrapezoidalMap::from_segments(&[
+        QLine::new_from_parts(qvec2!(0.0, 0.0), qvec2!(4.0, 0.0)),
+        QLine::new_from_parts(qvec2!(1.0, 2.0), qvec2!(3.0, 1.0)),
+    ]);
+    assert_eq!(lines.segment_below(&QPoint::new(qvec2!(2.0, 3.0))), Some(1));
+    assert_eq!(lines.segment_below(&QPoint::new(qvec2!(2.0, 1.0))), Some(0));
+    assert_eq!(lines.segment_below(&QPoint::new(qvec2!(2.0, -1.0))), None);
+    assert_eq!(lines.locate(&QPoint::new(qvec2!(2.0, 1.0))), None);
+}
+
+#[test]
+fn test_trapezoidal_map_over_tiled_zones() {
+    // A grid of tiles sharing edges and corners, nested in a larger zone.
+    let mut polygons = vec![QPolygon::new_from_parts(vec![qvec2!(-1.0, -1.0), qvec2!(9.0, -1.0), qvec2!(9.0, 9.0), qvec2!(-1.0, 9.0)])];
+    for x in 0..8 {
+        for y in 0..8 {
+            let corner = qvec2!(x, y);
+            let tile = [QVec2::ZERO, QVec2::X, QVec2::ONE, QVec2::Y].map(|offset| corner.saturating_add(offset));
+            polygons.push(QPolygon::new_from_parts(tile.to_vec()));
+        }
+    }
+    let map = QTrapezoidalMap::new(&polygons);
+    assert_eq!(map.len(), 4 * 65);
+    assert!(map.trapezoid_count() <= 3 * map.len() + 1);
+    for x in -6..=22 {
+        for y in -6..=22 {
+            let point = QPoint::new(qvec2!(x, y).saturating_mul_num(Q64::HALF));
+            let expected = (0..polygons.len()).rev().find(|&i| polygons[i].is_point_inside(&point));
+            let located = map.locate(&point);
+            if located != expected {
+                assert!(polygons[located.unwrap()].is_point_inside(&point) && polygons[expected.unwrap()].is_point_inside(&point));
+                assert_ne!(located, Some(0));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bsp_tree_classification_and_ordering() {
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(8.0, 0.0), qvec2!(8.0, 6.0), qvec2!(4.0, 2.0), qvec2!(0.0, 6.0)]);
+    let mut clockwise = polygon.clone();
+    clockwise.points_mut().reverse();
+    let tree = QBspTree::from_polygons(&[clockwise]);
+    assert!(tree.len() >= 5);
+    assert!(tree.height() >= 2);
+    for x in -2..=18 {
+        for y in -2..=14 {
+            let point = QPoint::new(qvec2!(x, y).saturating_mul_num(Q64::HALF));
+            let on_boundary = polygon.edges_iter().any(|edge| edge.is_point_on_line(&point));
+            let expected = if on_boundary {
+                QBspSide::On
+            } else if polygon.is_point_inside(&point) {
+                QBspSide::Back
+            } else {
+                QBspSide::Front
+            };
+            assert_eq!(tree.classify_point(&point), expected);
+        }
+    }
+
+    let (outside, inside) = tree.clip_segment(&QLine::new_from_parts(qvec2!(-1.0, 4.0), qvec2!(9.0, 4.0)));
+    let length = |lines: &Vec<QLine>| lines.iter().fold(Q64::ZERO, |acc, line| acc + line.vector().length());
+    assert_eq!(inside.len(), 2);
+    assert_eq!(length(&inside), q64!(4));
+    assert_eq!(length(&outside), q64!(6));
+
+    let viewpoint = QPoint::new(qvec2!(4.0, 1.0));
+    let ordered = tree.front_to_back(&viewpoint);
+    assert_eq!(ordered.len(), tree.len());
+    assert!(QBspTree::new(vec![]).front_to_back(&viewpoint).is_empty());
+
+    let walls = vec![
+        QLine::new_from_parts(qvec2!(0.0, 2.0), qvec2!(4.0, 2.0)),
+        QLine::new_from_parts(qvec2!(0.0, 1.0), qvec2!(4.0, 1.0)),
+        QLine::new_from_parts(qvec2!(0.0, 3.0), qvec2!(4.0, 3.0)),
+    ];
+    let tree = QBspTree::new(walls.clone());
+    assert_eq!(tree.height(), 2);
+    assert_eq!(tree.front_to_back(&QPoint::new(qvec2!(2.0, 0.0))), vec![walls[1], walls[0], walls[2]]);
+    assert_eq!(tree.front_to_back(&QPoint::new(qvec2!(2.0, 5.0))), vec![walls[2], walls[0], walls[1]]);
+    assert_eq!(tree.front_to_back(&QPoint::new(qvec2!(2.0, 1.5))), vec![walls[1], walls[0], walls[2]]);
+    assert_eq!(QBspTree::default().classify_point(&viewpoint), QBspSide::Front);
+}
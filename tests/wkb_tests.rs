@@ -0,0 +1,48 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::wkb::{ QByteOrder, QFromWkb, QToWkb, QWkbError };
+
+#[test]
+fn test_wkb_point_layout() {
+    let point = QPoint::new(qvec2!(1.0, 2.0));
+    let bytes = point.to_wkb(QByteOrder::LittleEndian);
+
+    assert_eq!(bytes.len(), 21);
+    assert_eq!(bytes[0], 1);
+    assert_eq!(&bytes[1..5], &1u32.to_le_bytes());
+    assert_eq!(&bytes[5..13], &1.0f64.to_le_bytes());
+    assert_eq!(&bytes[13..21], &2.0f64.to_le_bytes());
+}
+
+#[test]
+fn test_wkb_round_trip_both_byte_orders() {
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0)]);
+    let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(-1.5, 1.0), qvec2!(2.0, 0.25)]);
+    let mut compound = QCompound::new(QVec2::ZERO, vec![]);
+    compound.add_child(QVec2::ZERO, polygon.clone());
+
+    for byte_order in [QByteOrder::BigEndian, QByteOrder::LittleEndian] {
+        assert_eq!(QPolygon::from_wkb(&polygon.to_wkb(byte_order)).unwrap(), polygon);
+        assert_eq!(QPolyline::from_wkb(&polyline.to_wkb(byte_order)).unwrap(), polyline);
+        assert_eq!(QCompound::from_wkb(&compound.to_wkb(byte_order)).unwrap(), compound);
+    }
+}
+
+#[test]
+fn test_wkb_errors() {
+    let bytes = QPoint::new(QVec2::ONE).to_wkb(QByteOrder::BigEndian);
+    assert_eq!(QPoint::from_wkb(&bytes[..bytes.len() - 1]), Err(QWkbError::UnexpectedEnd));
+    assert_eq!(QPolygon::from_wkb(&bytes), Err(QWkbError::Unsupported("expected POLYGON".to_string())));
+
+    let mut nan = bytes.clone();
+    nan[5..13].copy_from_slice(&f64::NAN.to_be_bytes());
+    assert!(matches!(QPoint::from_wkb(&nan), Err(QWkbError::OutOfRange(_))));
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(QPoint::from_wkb(&trailing), Err(QWkbError::TrailingBytes(1)));
+
+    let nested: Vec<u8> = (0..100_000).flat_map(|_| [1, 6, 0, 0, 0, 1, 0, 0, 0]).collect();
+    assert_eq!(QCompound::from_wkb(&nested), Err(QWkbError::Unsupported("MULTIPOLYGON member is not a polygon".to_string())));
+}
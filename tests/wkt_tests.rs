@@ -0,0 +1,39 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qgeometry::prelude::*;
+use qgeometry::wkt::{ QToWkt, QWktError };
+
+#[test]
+fn test_wkt_round_trip() {
+    let point = QPoint::new(qvec2!(1.0, -2.5));
+    assert_eq!(point.to_wkt().parse::<QPoint>().unwrap(), point);
+
+    let polyline = QPolyline::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(1.0, 1.0), qvec2!(2.0, 0.0)]);
+    assert_eq!(polyline.to_wkt(), "LINESTRING (0 0, 1 1, 2 0)");
+    assert_eq!(polyline.to_wkt().parse::<QPolyline>().unwrap(), polyline);
+
+    let polygon = QPolygon::new_from_parts(vec![qvec2!(0.0, 0.0), qvec2!(4.0, 0.0), qvec2!(4.0, 3.0)]);
+    assert_eq!(polygon.to_wkt().parse::<QPolygon>().unwrap(), polygon);
+}
+
+#[test]
+fn test_wkt_shape_and_multipolygon() {
+    let shape: QShape = "linestring(0 0,3 4)".parse().unwrap();
+    assert_eq!(shape, QShape::QLine(QLine::new_from_parts(QVec2::ZERO, qvec2!(3.0, 4.0))));
+
+    let compound: QCompound = "MULTIPOLYGON (((0 0, 1 0, 0 1, 0 0)), ((5 5, 6 5, 5 6, 5 5)))".parse().unwrap();
+    assert_eq!(compound.children().len(), 2);
+    assert!(compound.is_point_inside(&QPoint::new(qvec2!(5.25, 5.25))));
+    assert_eq!(compound.to_wkt(), "MULTIPOLYGON (((0 0, 1 0, 0 1, 0 0)), ((5 5, 6 5, 5 6, 5 5)))");
+}
+
+#[test]
+fn test_wkt_errors() {
+    assert_eq!("POINT (1)".parse::<QPoint>(), Err(QWktError::InvalidNumber(")".to_string())));
+    assert_eq!("POINT (1 2".parse::<QPoint>(), Err(QWktError::UnexpectedEnd));
+    assert_eq!("POINT (nan 2)".parse::<QPoint>(), Err(QWktError::OutOfRange("nan".to_string())));
+    assert_eq!("POINT (1 inf)".parse::<QPoint>(), Err(QWktError::OutOfRange("inf".to_string())));
+    assert_eq!("POINT (1e300 2)".parse::<QPoint>(), Err(QWktError::OutOfRange("1e300".to_string())));
+    assert!(matches!("CIRCLE (1 2)".parse::<QShape>(), Err(QWktError::Unsupported(_))));
+    assert!(matches!("POLYGON ((0 0, 1 0, 0 1, 0 0), (0.1 0.1, 0.2 0.1, 0.1 0.2, 0.1 0.1))".parse::<QPolygon>(), Err(QWktError::Unsupported(_))));
+}
@@ -0,0 +1,94 @@
+use qmath::prelude::*;
+use qmath::vec2::QVec2;
+use qmath::dir::QDir;
+use qgeometry::prelude::*;
+use qgeometry::world::*;
+
+fn unit_square() -> QBbox {
+    QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE)
+}
+
+#[test]
+fn test_world_stale_handle() {
+    let mut world = QCollisionWorld::new(Q64::ZERO);
+    let a = world.insert(unit_square(), QTransform::IDENTITY);
+    assert!(world.remove(a).is_some());
+    assert!(!world.contains(a));
+
+    // The slot is reused but the old handle stays invalid
+    let b = world.insert(unit_square(), QTransform::IDENTITY);
+    assert_eq!(a.index(), b.index());
+    assert_ne!(a, b);
+    assert!(world.remove(a).is_none());
+    assert!(!world.set_transform(a, QTransform::IDENTITY));
+    assert_eq!(world.len(), 1);
+}
+
+#[test]
+fn test_world_raycast_and_point_query() {
+    let mut world = QCollisionWorld::new(Q64::HALF);
+    let near = world.insert(unit_square(), QTransform::from_translation(qvec2!(2.0, -0.5)));
+    let far = world.insert(QCircle::new(QPoint::ZERO, Q64::ONE), QTransform::from_translation(qvec2!(8.0, 0.0)));
+
+    let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::X));
+    assert_eq!(world.raycast(&ray, Q64::MAX), Some((near, q64!(2))));
+
+    world.remove(near);
+    let (hit, t) = world.raycast(&ray, Q64::MAX).unwrap();
+    assert_eq!(hit, far);
+    assert!(t > q64!(6) && t < q64!(8));
+    assert_eq!(world.raycast(&ray, q64!(5)), None);
+
+    assert_eq!(world.point_query(&QPoint::new(qvec2!(8.0, 0.5))), vec![far]);
+    assert!(world.point_query(&QPoint::new(qvec2!(2.5, 0.0))).is_empty());
+}
+
+#[test]
+fn test_world_filters() {
+    const TERRAIN: u32 = 1 << 0;
+    const SENSOR: u32 = 1 << 1;
+    const PROJECTILE: u32 = 1 << 2;
+
+    let mut world = QCollisionWorld::new(Q64::ZERO);
+    let terrain = world.insert_with_filter(unit_square(), QTransform::IDENTITY, QCollisionFilter::new(TERRAIN, PROJECTILE));
+    let sensor = world.insert_with_filter(unit_square(), QTransform::IDENTITY, QCollisionFilter::new(SENSOR, PROJECTILE));
+    let projectile = world.insert_with_filter(unit_square(), QTransform::IDENTITY, QCollisionFilter::new(PROJECTILE, TERRAIN | SENSOR));
+
+    // Terrain and sensor ignore each other
+    assert_eq!(world.contact_pairs(), vec![(terrain, projectile), (sensor, projectile)]);
+
+    // The pair filter callback removes the projectile-sensor pair
+    world.set_pair_filter(Some(std::sync::Arc::new(move |a: QShapeHandle, b: QShapeHandle| a != sensor && b != sensor)));
+    assert_eq!(world.intersections_with(projectile), vec![terrain]);
+
+    let probe = QCollisionFilter::new(u32::MAX, TERRAIN);
+    assert_eq!(world.point_query_with_filter(&QPoint::new(QVec2::splat(Q64::HALF)), &probe), vec![terrain]);
+}
+
+#[test]
+fn test_world_raycast_all() {
+    let mut world = QCollisionWorld::new(Q64::ZERO);
+    let shapes: Vec<QShapeHandle> = (0..5)
+        .map(|i| world.insert(unit_square(), QTransform::from_translation(qvec2!(10 - 2 * i, -0.5))))
+        .collect();
+    world.insert(unit_square(), QTransform::from_translation(qvec2!(3.0, 5.0)));
+
+    let ray = QRay::new(QPoint::ZERO, QDir::new_from_vec(QVec2::X));
+    let hits = world.raycast_all(&ray, Q64::MAX);
+    let hit_handles: Vec<QShapeHandle> = hits.iter().map(|(handle, _)| *handle).collect();
+    assert_eq!(hit_handles, shapes.iter().rev().copied().collect::<Vec<_>>());
+    assert_eq!(hits[0].1, q64!(2));
+    assert_eq!(world.raycast(&ray, Q64::MAX), Some(hits[0]));
+}
+
+#[test]
+fn test_world_region_queries() {
+    let mut world = QCollisionWorld::new(Q64::ZERO);
+    let diagonal = world.insert(QLine::new_from_parts(qvec2!(0.0, 4.0), qvec2!(4.0, 0.0)), QTransform::IDENTITY);
+    let square = world.insert(unit_square(), QTransform::from_translation(qvec2!(5.0, 5.0)));
+
+    // Inside the bbox of the line but away from the line itself
+    assert!(world.query_region(&QBbox::new_from_parts(QVec2::ZERO, QVec2::ONE)).is_empty());
+    assert_eq!(world.query_region(&QBbox::new_from_parts(QVec2::ZERO, qvec2!(6.0, 6.0))), vec![diagonal, square]);
+    assert_eq!(world.query_circle(&QCircle::new(QPoint::new(qvec2!(2.0, 2.0)), Q64::HALF)), vec![diagonal]);
+}